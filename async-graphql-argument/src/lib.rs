@@ -0,0 +1,346 @@
+//! Resolves the arguments of a field in an async-graphql extension, for the checks based on
+//! the argument values.
+//!
+//! ```ignore
+//! // in `Extension::parse_query`
+//! let variables = async_graphql_argument::operation_variables(&document, operation_name, variables);
+//! // in `Extension::resolve`
+//! let arguments =
+//!     async_graphql_argument::resolve_arguments(&ctx.schema_env.registry, &info, &variables);
+//! let id = arguments.get(&["input", "id"]);
+//! ```
+
+use async_graphql::{
+    Name, Value, Variables,
+    extensions::ResolveInfo,
+    indexmap::IndexMap,
+    parser::types::{DocumentOperations, ExecutableDocument, Selection},
+    registry::{MetaInputValue, MetaType, MetaTypeName, Registry},
+};
+
+/// The arguments of a field with the variables resolved and the default values applied.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Arguments(IndexMap<Name, Value>);
+
+impl Arguments {
+    /// Returns the value at the path of argument names and input object field names
+    /// (e.g. `["input", "id"]`).
+    pub fn get(&self, path: &[&str]) -> Option<&Value> {
+        let (name, rest) = path.split_first()?;
+        let mut value = self.0.get(*name)?;
+        for name in rest {
+            match value {
+                Value::Object(object) => value = object.get(*name)?,
+                _ => return None,
+            }
+        }
+        Some(value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Name, &Value)> {
+        self.0.iter()
+    }
+}
+
+impl From<IndexMap<Name, Value>> for Arguments {
+    fn from(arguments: IndexMap<Name, Value>) -> Self {
+        Self(arguments)
+    }
+}
+
+/// Returns the variables of the operation with the default values of the variable
+/// definitions applied.
+pub fn operation_variables(
+    document: &ExecutableDocument,
+    operation_name: Option<&str>,
+    variables: &Variables,
+) -> Variables {
+    let operation = match (&document.operations, operation_name) {
+        (DocumentOperations::Single(operation), _) => Some(operation),
+        (DocumentOperations::Multiple(operations), Some(operation_name)) => {
+            operations.get(operation_name)
+        }
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            operations.values().next()
+        }
+        (DocumentOperations::Multiple(_), None) => None,
+    };
+    let mut variables = variables.clone();
+    for definition in operation
+        .into_iter()
+        .flat_map(|it| &it.node.variable_definitions)
+    {
+        let definition = &definition.node;
+        if let Some(default_value) = definition.default_value() {
+            variables
+                .entry(definition.name.node.clone())
+                .or_insert_with(|| default_value.clone());
+        }
+    }
+    variables
+}
+
+/// Returns the arguments of the field, coerced by the types of the schema.
+///
+/// - variables are replaced by their values (`null` if not provided)
+/// - default values of the arguments and the input object fields are applied
+/// - a single value is wrapped in a list where a list is expected
+pub fn resolve_arguments(
+    registry: &Registry,
+    info: &ResolveInfo<'_>,
+    variables: &Variables,
+) -> Arguments {
+    let mut arguments = info
+        .field
+        .arguments
+        .iter()
+        .map(|(name, value)| {
+            let value = value
+                .node
+                .clone()
+                .into_const_with(|name| {
+                    Ok::<_, std::convert::Infallible>(
+                        variables.get(&name).cloned().unwrap_or(Value::Null),
+                    )
+                })
+                .unwrap_or_else(|e| match e {});
+            (name.node.clone(), value)
+        })
+        .collect::<IndexMap<Name, Value>>();
+
+    let Some(meta_field) = registry
+        .types
+        .get(info.parent_type)
+        .and_then(|it| it.field_by_name(info.name))
+    else {
+        return Arguments(arguments);
+    };
+    let mut coerced = IndexMap::new();
+    for (name, meta_input_value) in &meta_field.args {
+        let name = Name::new(name);
+        if let Some(value) = take_or_default(&mut arguments, &name, meta_input_value) {
+            coerced.insert(name, coerce(registry, &meta_input_value.ty, value));
+        }
+    }
+    // unknown arguments are rejected by the validation, but keep them just in case
+    coerced.extend(arguments);
+    Arguments(coerced)
+}
+
+fn coerce(registry: &Registry, type_name: &str, value: Value) -> Value {
+    match MetaTypeName::create(type_name) {
+        MetaTypeName::NonNull(type_name) => coerce(registry, type_name, value),
+        MetaTypeName::List(type_name) => match value {
+            Value::Null => Value::Null,
+            Value::List(values) => Value::List(
+                values
+                    .into_iter()
+                    .map(|it| coerce(registry, type_name, it))
+                    .collect(),
+            ),
+            value => Value::List(vec![coerce(registry, type_name, value)]),
+        },
+        MetaTypeName::Named(type_name) => match (registry.types.get(type_name), value) {
+            (Some(MetaType::InputObject { input_fields, .. }), Value::Object(mut object)) => {
+                let mut coerced = IndexMap::new();
+                for (name, meta_input_value) in input_fields {
+                    let name = Name::new(name);
+                    if let Some(value) = take_or_default(&mut object, &name, meta_input_value) {
+                        coerced.insert(name, coerce(registry, &meta_input_value.ty, value));
+                    }
+                }
+                coerced.extend(object);
+                Value::Object(coerced)
+            }
+            (_, value) => value,
+        },
+    }
+}
+
+/// Removes the provided value, or returns the default value if not provided.
+fn take_or_default(
+    values: &mut IndexMap<Name, Value>,
+    name: &Name,
+    meta_input_value: &MetaInputValue,
+) -> Option<Value> {
+    values.shift_remove(name).or_else(|| {
+        meta_input_value
+            .default_value
+            .as_deref()
+            .and_then(parse_value)
+    })
+}
+
+/// Parses the default value in the registry (GraphQL input value syntax).
+fn parse_value(s: &str) -> Option<Value> {
+    let document = async_graphql::parser::parse_query(format!("{{ f(v: {s}) }}")).ok()?;
+    let DocumentOperations::Single(operation) = document.operations else {
+        return None;
+    };
+    let selection = operation.node.selection_set.node.items.into_iter().next()?;
+    let Selection::Field(field) = selection.node else {
+        return None;
+    };
+    let (_, value) = field.node.arguments.into_iter().next()?;
+    value.node.into_const()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_impls() {
+        fn assert_impl<T: Send + Sync>() {}
+
+        assert_impl::<Arguments>();
+    }
+
+    #[test]
+    fn test_arguments_get() {
+        let arguments = Arguments(IndexMap::from([(
+            Name::new("input"),
+            Value::from_json(serde_json::json!({ "id": "abc", "ids": ["def"] })).unwrap(),
+        )]));
+        assert_eq!(
+            arguments.get(&["input", "id"]),
+            Some(&Value::String("abc".to_owned()))
+        );
+        assert_eq!(
+            arguments.get(&["input", "ids"]),
+            Some(&Value::List(vec![Value::String("def".to_owned())]))
+        );
+        assert_eq!(arguments.get(&["input", "id", "x"]), None);
+        assert_eq!(arguments.get(&["input", "unknown"]), None);
+        assert_eq!(arguments.get(&[]), None);
+    }
+
+    #[test]
+    fn test_operation_variables() -> anyhow::Result<()> {
+        let document = async_graphql::parser::parse_query(
+            r#"query a($id: String = "abc", $n: Int) { f } query b($id: String = "def") { f }"#,
+        )?;
+        let variables = Variables::from_json(serde_json::json!({ "n": 1 }));
+        assert_eq!(
+            operation_variables(&document, Some("a"), &variables).into_value(),
+            Value::from_json(serde_json::json!({ "id": "abc", "n": 1 }))?
+        );
+        assert_eq!(
+            operation_variables(
+                &document,
+                Some("b"),
+                &Variables::from_json(serde_json::json!({ "id": "ghi" }))
+            )
+            .into_value(),
+            Value::from_json(serde_json::json!({ "id": "ghi" }))?
+        );
+        assert_eq!(
+            operation_variables(&document, None, &variables).into_value(),
+            Value::from_json(serde_json::json!({ "n": 1 }))?
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resolve_arguments() -> anyhow::Result<()> {
+        struct Query;
+
+        #[derive(async_graphql::InputObject)]
+        struct Input {
+            #[graphql(default = 10)]
+            limit: i32,
+            ids: Vec<String>,
+        }
+
+        #[async_graphql::Object]
+        impl Query {
+            async fn items(&self, input: Input, #[graphql(default)] offset: i32) -> i32 {
+                input.limit + offset
+            }
+        }
+
+        /// Records the arguments of the fields.
+        struct Recorder {
+            recorded: std::sync::Arc<std::sync::Mutex<Vec<Arguments>>>,
+            variables: std::sync::OnceLock<Variables>,
+        }
+
+        #[async_trait::async_trait]
+        impl async_graphql::extensions::Extension for Recorder {
+            async fn parse_query(
+                &self,
+                ctx: &async_graphql::extensions::ExtensionContext<'_>,
+                query: &str,
+                variables: &Variables,
+                next: async_graphql::extensions::NextParseQuery<'_>,
+            ) -> async_graphql::ServerResult<ExecutableDocument> {
+                let document = next.run(ctx, query, variables).await?;
+                let _ = self
+                    .variables
+                    .set(operation_variables(&document, None, variables));
+                Ok(document)
+            }
+
+            async fn resolve(
+                &self,
+                ctx: &async_graphql::extensions::ExtensionContext<'_>,
+                info: ResolveInfo<'_>,
+                next: async_graphql::extensions::NextResolve<'_>,
+            ) -> async_graphql::ServerResult<Option<Value>> {
+                let variables = self.variables.get().expect("the query is parsed");
+                let arguments = resolve_arguments(&ctx.schema_env.registry, &info, variables);
+                self.recorded.lock().unwrap().push(arguments);
+                next.run(ctx, info).await
+            }
+        }
+
+        struct RecorderFactory(std::sync::Arc<std::sync::Mutex<Vec<Arguments>>>);
+
+        impl async_graphql::extensions::ExtensionFactory for RecorderFactory {
+            fn create(&self) -> std::sync::Arc<dyn async_graphql::extensions::Extension> {
+                std::sync::Arc::new(Recorder {
+                    recorded: self.0.clone(),
+                    variables: std::sync::OnceLock::new(),
+                })
+            }
+        }
+
+        let recorded = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let schema = async_graphql::Schema::build(
+            Query,
+            async_graphql::EmptyMutation,
+            async_graphql::EmptySubscription,
+        )
+        .extension(RecorderFactory(recorded.clone()))
+        .finish();
+        let request =
+            async_graphql::Request::new("query($id: String!) { items(input: { ids: $id }) }")
+                .variables(Variables::from_json(serde_json::json!({ "id": "abc" })));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let arguments = recorded.lock().unwrap().clone();
+        assert_eq!(
+            arguments,
+            [Arguments(IndexMap::from([
+                (
+                    Name::new("input"),
+                    Value::from_json(serde_json::json!({ "limit": 10, "ids": ["abc"] }))?
+                ),
+                (Name::new("offset"), Value::from(0)),
+            ]))]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value("1"), Some(Value::from(1)));
+        assert_eq!(
+            parse_value(r#"{ id: "abc", ids: [] }"#),
+            Some(Value::from_json(serde_json::json!({ "id": "abc", "ids": [] })).unwrap())
+        );
+        assert_eq!(parse_value("$v"), None);
+        assert_eq!(parse_value("{"), None);
+    }
+}
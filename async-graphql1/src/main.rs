@@ -1,52 +1,361 @@
-use axum::{extract::State, response::Html};
+mod persisted_query;
+mod schema;
 
-struct Query;
+use axum::{
+    extract::{ws::WebSocketUpgrade, RawQuery, State},
+    http::StatusCode,
+    response::{Html, IntoResponse as _, Response},
+};
+use futures_util::{SinkExt as _, StreamExt as _};
 
-#[async_graphql::Object]
-impl Query {
-    async fn hello(&self) -> String {
-        "world".to_string()
+/// Executes the request in the query string (e.g. the hash of a persisted query), or returns
+/// GraphiQL without the query string.
+///
+/// The mutations and the subscriptions are rejected with `405 Method Not Allowed` (CSRF).
+async fn graphql_get(
+    State(AppState { schema, .. }): State<AppState>,
+    RawQuery(query): RawQuery,
+) -> Response {
+    let Some(query) = query else {
+        return Html(
+            async_graphql::http::GraphiQLSource::build()
+                .endpoint("/graphql")
+                .subscription_endpoint("/graphql/ws")
+                .finish(),
+        )
+        .into_response();
+    };
+    let request = match async_graphql::http::parse_query_string(&query) {
+        Ok(request) => request.data(schema::GetRequest),
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let response = schema.execute(request).await;
+    let method_not_allowed = response.errors.iter().any(|it| {
+        it.extensions
+            .as_ref()
+            .and_then(|it| it.get("code"))
+            .is_some_and(|it| *it == async_graphql::Value::from(schema::METHOD_NOT_ALLOWED))
+    });
+    let response = async_graphql_axum::GraphQLResponse::from(response);
+    if method_not_allowed {
+        (
+            StatusCode::METHOD_NOT_ALLOWED,
+            [(axum::http::header::ALLOW, "POST")],
+            response,
+        )
+            .into_response()
+    } else {
+        response.into_response()
     }
 }
 
-async fn graphql_get() -> Html<String> {
-    Html(
-        async_graphql::http::GraphiQLSource::build()
-            .endpoint("/graphql")
-            .finish(),
-    )
-}
-
 async fn graphql_post(
-    State(AppState(schema)): State<AppState>,
+    State(AppState { schema, .. }): State<AppState>,
     request: async_graphql_axum::GraphQLRequest,
 ) -> async_graphql_axum::GraphQLResponse {
     let request = request.into_inner();
     async_graphql_axum::GraphQLResponse::from(schema.execute(request).await)
 }
 
+/// Serves the subscriptions over the `graphql-transport-ws` and `graphql-ws` protocols.
+///
+/// The `connection_init` payload must have the token (`{ "token": "..." }`). The connections are
+/// closed with `1001 Going Away` when the server shuts down.
+async fn graphql_ws(
+    State(AppState {
+        schema,
+        token,
+        shutdown,
+    }): State<AppState>,
+    protocol: async_graphql_axum::GraphQLProtocol,
+    websocket: WebSocketUpgrade,
+) -> Response {
+    websocket
+        .protocols(async_graphql::http::ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |socket| async move {
+            let (mut sink, stream) = socket.split();
+            // the live streams end when the input ends
+            let stream = stream.take_until(Box::pin(wait_for_shutdown(shutdown.clone())));
+            async_graphql_axum::GraphQLWebSocket::new_with_pair(
+                &mut sink, stream, schema, protocol,
+            )
+            .on_connection_init(move |payload| on_connection_init(token, payload))
+            .serve()
+            .await;
+            if *shutdown.borrow() {
+                let _ = sink
+                    .send(axum::extract::ws::Message::Close(Some(
+                        axum::extract::ws::CloseFrame {
+                            code: axum::extract::ws::close_code::AWAY,
+                            reason: "server is shutting down".into(),
+                        },
+                    )))
+                    .await;
+            }
+        })
+}
+
+async fn on_connection_init(
+    token: std::sync::Arc<str>,
+    payload: serde_json::Value,
+) -> async_graphql::Result<async_graphql::Data> {
+    match payload.get("token").and_then(serde_json::Value::as_str) {
+        Some(t) if is_valid_token(t, &token) => {
+            let mut data = async_graphql::Data::default();
+            data.insert(schema::Authenticated);
+            Ok(data)
+        }
+        _ => Err(async_graphql::Error::new("invalid token")),
+    }
+}
+
+/// Compares the digests in constant time not to leak the token (or its length) by the timing.
+fn is_valid_token(token: &str, expected: &str) -> bool {
+    use sha2::{Digest as _, Sha256};
+    use subtle::ConstantTimeEq as _;
+
+    Sha256::digest(token.as_bytes())
+        .ct_eq(&Sha256::digest(expected.as_bytes()))
+        .into()
+}
+
+async fn wait_for_shutdown(mut shutdown: tokio::sync::watch::Receiver<bool>) {
+    // an error means the sender is dropped, i.e. the server is stopped
+    let _ = shutdown.wait_for(|it| *it).await;
+}
+
 #[derive(Clone)]
-struct AppState(
-    async_graphql::Schema<Query, async_graphql::EmptyMutation, async_graphql::EmptySubscription>,
-);
+struct AppState {
+    schema: schema::AppSchema,
+    token: std::sync::Arc<str>,
+    shutdown: tokio::sync::watch::Receiver<bool>,
+}
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let app = axum::Router::new()
+fn app(state: AppState) -> axum::Router {
+    axum::Router::new()
         .route(
             "/graphql",
             axum::routing::get(graphql_get).post(graphql_post),
         )
-        .with_state(AppState(
-            async_graphql::Schema::build(
-                Query,
-                async_graphql::EmptyMutation,
-                async_graphql::EmptySubscription,
+        .route("/graphql/ws", axum::routing::get(graphql_ws))
+        .with_state(state)
+}
+
+/// Accepts only the queries in the JSON array file `GRAPHQL_ALLOW_LIST` if set, otherwise
+/// stores the queries sent by the clients (APQ).
+fn persisted_queries(
+) -> anyhow::Result<persisted_query::PersistedQueries<persisted_query::InMemoryStorage>> {
+    Ok(match std::env::var_os("GRAPHQL_ALLOW_LIST") {
+        None => persisted_query::PersistedQueries::new(
+            persisted_query::InMemoryStorage::default(),
+            persisted_query::Mode::Automatic,
+        ),
+        Some(path) => {
+            let queries: Vec<String> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            persisted_query::PersistedQueries::new(
+                persisted_query::InMemoryStorage::with_queries(queries),
+                persisted_query::Mode::AllowList,
             )
-            .finish(),
-        ));
+        }
+    })
+}
+
+/// The token the WebSocket clients must send. There is no default not to accept a known token.
+fn graphql_token() -> anyhow::Result<std::sync::Arc<str>> {
+    match std::env::var("GRAPHQL_TOKEN") {
+        Ok(token) if !token.is_empty() => Ok(token.into()),
+        _ => anyhow::bail!("GRAPHQL_TOKEN must be set"),
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let (shutdown_sender, shutdown) = tokio::sync::watch::channel(false);
+    let app = app(AppState {
+        schema: schema::build(schema::Storage::default(), persisted_queries()?),
+        token: graphql_token()?,
+        shutdown: shutdown.clone(),
+    });
     let socket_addr = std::net::SocketAddr::from(([127, 0, 0, 1], 3000));
     let listener = tokio::net::TcpListener::bind(socket_addr).await?;
-    axum::serve(listener, app.into_make_service()).await?;
+    tokio::spawn(async move {
+        let _ = tokio::signal::ctrl_c().await;
+        let _ = shutdown_sender.send(true);
+    });
+    axum::serve(listener, app.into_make_service())
+        .with_graceful_shutdown(wait_for_shutdown(shutdown))
+        .await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> schema::AppSchema {
+        schema::build(
+            schema::Storage::default(),
+            persisted_query::PersistedQueries::new(
+                persisted_query::InMemoryStorage::default(),
+                persisted_query::Mode::Automatic,
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_on_connection_init() {
+        let token: std::sync::Arc<str> = "secret".into();
+        let data = on_connection_init(token.clone(), serde_json::json!({ "token": "secret" }))
+            .await
+            .expect("the token is valid");
+        assert!(data.contains_key(&std::any::TypeId::of::<schema::Authenticated>()));
+
+        for payload in [
+            serde_json::json!({ "token": "invalid" }),
+            serde_json::json!({ "token": "secret1" }),
+            serde_json::json!({ "token": 1 }),
+            serde_json::json!({}),
+        ] {
+            let err = on_connection_init(token.clone(), payload.clone())
+                .await
+                .err();
+            assert_eq!(
+                err.map(|it| it.message).as_deref(),
+                Some("invalid token"),
+                "{payload}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_websocket() {
+        let schema = schema();
+        let (shutdown_sender, shutdown) = tokio::sync::watch::channel(false);
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<String>();
+        // the same input as `graphql_ws`
+        let input = Box::pin(futures_util::stream::unfold(
+            receiver,
+            |mut receiver| async move { receiver.recv().await.map(|it| (it, receiver)) },
+        ))
+        .take_until(Box::pin(wait_for_shutdown(shutdown)));
+        let mut output = async_graphql::http::WebSocket::new(
+            schema.clone(),
+            input,
+            async_graphql::http::WebSocketProtocols::GraphQLWS,
+        )
+        .on_connection_init(|payload| on_connection_init("secret".into(), payload));
+        let send = |message: serde_json::Value| sender.send(message.to_string()).unwrap();
+        let text = |message: Option<async_graphql::http::WsMessage>| -> serde_json::Value {
+            match message {
+                Some(async_graphql::http::WsMessage::Text(text)) => {
+                    serde_json::from_str(&text).unwrap()
+                }
+                message => panic!("unexpected message: {:?}", message),
+            }
+        };
+
+        send(serde_json::json!({ "type": "connection_init", "payload": { "token": "secret" } }));
+        assert_eq!(
+            text(output.next().await),
+            serde_json::json!({ "type": "connection_ack" })
+        );
+        send(serde_json::json!({
+            "type": "subscribe",
+            "id": "1",
+            "payload": { "query": "subscription { messages }" },
+        }));
+        // the messages are posted until the subscription starts
+        let message = loop {
+            schema
+                .execute(r#"mutation { postMessage(text: "hello") }"#)
+                .await;
+            let next = tokio::time::timeout(std::time::Duration::from_millis(10), output.next());
+            if let Ok(message) = next.await {
+                break text(message);
+            }
+        };
+        assert_eq!(
+            message,
+            serde_json::json!({
+                "type": "next",
+                "id": "1",
+                "payload": { "data": { "messages": "hello" } },
+            })
+        );
+
+        // the live stream ends on shutdown
+        shutdown_sender.send(true).unwrap();
+        let next = tokio::time::timeout(std::time::Duration::from_secs(1), output.next()).await;
+        assert!(matches!(next, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn test_websocket_invalid_token() {
+        let (_shutdown_sender, shutdown) = tokio::sync::watch::channel(false);
+        let init =
+            serde_json::json!({ "type": "connection_init", "payload": { "token": "invalid" } });
+        let input = futures_util::stream::iter([init.to_string()])
+            .chain(futures_util::stream::pending())
+            .take_until(Box::pin(wait_for_shutdown(shutdown)));
+        let mut output = async_graphql::http::WebSocket::new(
+            schema(),
+            input,
+            async_graphql::http::WebSocketProtocols::GraphQLWS,
+        )
+        .on_connection_init(|payload| on_connection_init("secret".into(), payload));
+        assert!(matches!(
+            output.next().await,
+            Some(async_graphql::http::WsMessage::Close(_, reason)) if reason == "invalid token"
+        ));
+        assert!(output.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_graphql_get() -> anyhow::Result<()> {
+        let (_, shutdown) = tokio::sync::watch::channel(false);
+        let app = app(AppState {
+            schema: schema(),
+            token: "secret".into(),
+            shutdown,
+        });
+        let get = |query: &str| {
+            axum::http::Request::builder()
+                .uri(format!(
+                    "/graphql?{}",
+                    serde_urlencoded::to_string([("query", query)]).unwrap()
+                ))
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        let response = tower::ServiceExt::oneshot(app.clone(), get("{ hello }")).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // mutations over GET are refused (CSRF)
+        let response = tower::ServiceExt::oneshot(
+            app.clone(),
+            get(r#"mutation { postMessage(text: "hello") }"#),
+        )
+        .await?;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[axum::http::header::ALLOW], "POST");
+        let response = tower::ServiceExt::oneshot(app, get("{ messages }")).await?;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body)?,
+            serde_json::json!({ "data": { "messages": [] } })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_graphql_token() {
+        // the only test that changes the environment variable
+        std::env::remove_var("GRAPHQL_TOKEN");
+        assert!(graphql_token().is_err());
+        std::env::set_var("GRAPHQL_TOKEN", "");
+        assert!(graphql_token().is_err());
+        std::env::set_var("GRAPHQL_TOKEN", "secret");
+        assert_eq!(graphql_token().ok().as_deref(), Some("secret"));
+    }
+}
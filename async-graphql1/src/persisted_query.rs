@@ -0,0 +1,370 @@
+//! Persisted queries and automatic persisted queries (APQ).
+//!
+//! The client sends `extensions.persistedQuery` (`{ "version": 1, "sha256Hash": "..." }`)
+//! with or without the query. The query is stored by the hash once it passes the validation, so
+//! that the client can send only the hash afterwards.
+//!
+//! <https://www.apollographql.com/docs/apollo-server/performance/apq>
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use sha2::{Digest as _, Sha256};
+
+#[derive(serde::Deserialize)]
+struct PersistedQuery {
+    version: i32,
+    #[serde(rename = "sha256Hash")]
+    sha256_hash: String,
+}
+
+/// The storage of the queries keyed by the SHA-256 hash (lowercase hex) of the query.
+#[async_graphql::async_trait::async_trait]
+pub trait PersistedQueryStorage: Send + Sync + 'static {
+    async fn get(&self, hash: &str) -> Option<String>;
+
+    async fn set(&self, hash: String, query: String);
+}
+
+/// The storage that keeps the queries in memory until the process exits.
+///
+/// The least recently used query is evicted when the storage is full.
+pub struct InMemoryStorage {
+    capacity: usize,
+    queries: Mutex<Queries>,
+}
+
+#[derive(Default)]
+struct Queries {
+    // hash -> (query, last used)
+    queries: HashMap<String, (String, u64)>,
+    // last used -> hash
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl Queries {
+    fn touch(&mut self, hash: &str) -> Option<&String> {
+        self.clock += 1;
+        let (query, last_used) = self.queries.get_mut(hash)?;
+        let hash = self.recency.remove(last_used)?;
+        *last_used = self.clock;
+        self.recency.insert(self.clock, hash);
+        Some(query)
+    }
+}
+
+impl Default for InMemoryStorage {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl InMemoryStorage {
+    pub const DEFAULT_CAPACITY: usize = 1_000;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            queries: Mutex::new(Queries::default()),
+        }
+    }
+
+    /// Creates the storage with the queries (e.g. the allow-list extracted from the clients).
+    pub fn with_queries<I, S>(queries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let queries = queries.into_iter().map(Into::into).collect::<Vec<String>>();
+        let mut storage = Self::new(queries.len());
+        for query in queries {
+            storage.insert(sha256_hash(&query), query);
+        }
+        storage
+    }
+
+    fn insert(&mut self, hash: String, query: String) {
+        let queries = self.queries.get_mut().unwrap_or_else(|e| e.into_inner());
+        insert(queries, self.capacity, hash, query);
+    }
+}
+
+fn insert(queries: &mut Queries, capacity: usize, hash: String, query: String) {
+    if queries.touch(&hash).is_some() {
+        return;
+    }
+    if queries.queries.len() >= capacity {
+        let Some((_, evicted)) = queries.recency.pop_first() else {
+            return;
+        };
+        queries.queries.remove(&evicted);
+    }
+    queries.recency.insert(queries.clock, hash.clone());
+    queries.queries.insert(hash, (query, queries.clock));
+}
+
+#[async_graphql::async_trait::async_trait]
+impl PersistedQueryStorage for InMemoryStorage {
+    async fn get(&self, hash: &str) -> Option<String> {
+        self.queries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .touch(hash)
+            .cloned()
+    }
+
+    async fn set(&self, hash: String, query: String) {
+        let mut queries = self.queries.lock().unwrap_or_else(|e| e.into_inner());
+        insert(&mut queries, self.capacity, hash, query);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Stores the queries sent by the clients (APQ). Queries without the hash are also accepted.
+    Automatic,
+    /// Accepts only the queries in the storage. The storage is never updated.
+    AllowList,
+}
+
+pub struct PersistedQueries<S> {
+    storage: Arc<S>,
+    mode: Mode,
+}
+
+impl<S: PersistedQueryStorage> PersistedQueries<S> {
+    pub fn new(storage: S, mode: Mode) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            mode,
+        }
+    }
+}
+
+impl<S: PersistedQueryStorage> async_graphql::extensions::ExtensionFactory for PersistedQueries<S> {
+    fn create(&self) -> Arc<dyn async_graphql::extensions::Extension> {
+        Arc::new(PersistedQueriesExtension {
+            storage: self.storage.clone(),
+            mode: self.mode,
+            pending: Mutex::new(None),
+        })
+    }
+}
+
+struct PersistedQueriesExtension<S> {
+    storage: Arc<S>,
+    mode: Mode,
+    // the hash and the query to store after the validation (APQ)
+    pending: Mutex<Option<(String, String)>>,
+}
+
+impl<S: PersistedQueryStorage> PersistedQueriesExtension<S> {
+    /// Returns the query to execute.
+    async fn query(
+        &self,
+        query: String,
+        persisted_query: Option<PersistedQuery>,
+    ) -> async_graphql::ServerResult<String> {
+        let Some(persisted_query) = persisted_query else {
+            return match self.mode {
+                Mode::Automatic => Ok(query),
+                Mode::AllowList => match self.storage.get(&sha256_hash(&query)).await {
+                    Some(_) => Ok(query),
+                    None => Err(error("PersistedQueryNotAllowed")),
+                },
+            };
+        };
+        if persisted_query.version != 1 {
+            return Err(error(format!(
+                "unsupported persisted query version: {}",
+                persisted_query.version
+            )));
+        }
+
+        if query.is_empty() {
+            return self
+                .storage
+                .get(&persisted_query.sha256_hash)
+                .await
+                .ok_or_else(|| error("PersistedQueryNotFound"));
+        }
+        if sha256_hash(&query) != persisted_query.sha256_hash {
+            return Err(error("provided sha does not match query"));
+        }
+        match self.mode {
+            Mode::Automatic => {
+                *self.pending.lock().unwrap_or_else(|e| e.into_inner()) =
+                    Some((persisted_query.sha256_hash, query.clone()));
+                Ok(query)
+            }
+            Mode::AllowList => match self.storage.get(&persisted_query.sha256_hash).await {
+                Some(_) => Ok(query),
+                None => Err(error("PersistedQueryNotAllowed")),
+            },
+        }
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl<S: PersistedQueryStorage> async_graphql::extensions::Extension
+    for PersistedQueriesExtension<S>
+{
+    async fn prepare_request(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        mut request: async_graphql::Request,
+        next: async_graphql::extensions::NextPrepareRequest<'_>,
+    ) -> async_graphql::ServerResult<async_graphql::Request> {
+        let persisted_query = match request.extensions.remove("persistedQuery") {
+            None => None,
+            Some(value) => Some(
+                async_graphql::from_value::<PersistedQuery>(value)
+                    .map_err(|_| error("invalid persistedQuery extension"))?,
+            ),
+        };
+        let query = std::mem::take(&mut request.query);
+        request.query = self.query(query, persisted_query).await?;
+        next.run(ctx, request).await
+    }
+
+    async fn validation(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        next: async_graphql::extensions::NextValidation<'_>,
+    ) -> Result<async_graphql::ValidationResult, Vec<async_graphql::ServerError>> {
+        let result = next.run(ctx).await?;
+        let pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some((hash, query)) = pending {
+            self.storage.set(hash, query).await;
+        }
+        Ok(result)
+    }
+}
+
+fn error(message: impl Into<String>) -> async_graphql::ServerError {
+    async_graphql::ServerError::new(message, None)
+}
+
+fn sha256_hash(query: &str) -> String {
+    format!("{:x}", Sha256::digest(query.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        async fn hello(&self) -> String {
+            "world".to_string()
+        }
+    }
+
+    fn schema(
+        storage: InMemoryStorage,
+        mode: Mode,
+    ) -> async_graphql::Schema<Query, async_graphql::EmptyMutation, async_graphql::EmptySubscription>
+    {
+        async_graphql::Schema::build(
+            Query,
+            async_graphql::EmptyMutation,
+            async_graphql::EmptySubscription,
+        )
+        .extension(PersistedQueries::new(storage, mode))
+        .finish()
+    }
+
+    fn request(query: &str, hash: &str) -> async_graphql::Request {
+        let mut request = async_graphql::Request::new(query);
+        request.extensions.insert(
+            "persistedQuery".to_owned(),
+            async_graphql::value!({ "version": 1, "sha256Hash": hash }),
+        );
+        request
+    }
+
+    fn errors(response: &async_graphql::Response) -> Vec<&str> {
+        response
+            .errors
+            .iter()
+            .map(|it| it.message.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_automatic() {
+        let schema = schema(InMemoryStorage::default(), Mode::Automatic);
+        let query = "{ hello }";
+        let hash = sha256_hash(query);
+
+        let response = schema.execute(request("", &hash)).await;
+        assert_eq!(errors(&response), ["PersistedQueryNotFound"]);
+
+        let response = schema.execute(request(query, "0000")).await;
+        assert_eq!(errors(&response), ["provided sha does not match query"]);
+
+        let response = schema.execute(request(query, &hash)).await;
+        assert_eq!(response.data, async_graphql::value!({ "hello": "world" }));
+        let response = schema.execute(request("", &hash)).await;
+        assert_eq!(response.data, async_graphql::value!({ "hello": "world" }));
+
+        // the queries without the hash are accepted
+        let response = schema.execute("{ __typename }").await;
+        assert_eq!(
+            response.data,
+            async_graphql::value!({ "__typename": "Query" })
+        );
+
+        // the invalid queries are not stored
+        for invalid in ["{ hello", "{ unknown }"] {
+            let hash = sha256_hash(invalid);
+            let response = schema.execute(request(invalid, &hash)).await;
+            assert!(!response.errors.is_empty());
+            let response = schema.execute(request("", &hash)).await;
+            assert_eq!(errors(&response), ["PersistedQueryNotFound"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_storage_capacity() {
+        let storage = InMemoryStorage::new(2);
+        storage.set("a".to_owned(), "A".to_owned()).await;
+        storage.set("b".to_owned(), "B".to_owned()).await;
+        // "a" is used more recently than "b"
+        assert_eq!(storage.get("a").await, Some("A".to_owned()));
+        storage.set("c".to_owned(), "C".to_owned()).await;
+        assert_eq!(storage.get("a").await, Some("A".to_owned()));
+        assert_eq!(storage.get("b").await, None);
+        assert_eq!(storage.get("c").await, Some("C".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn test_allow_list() {
+        let query = "{ hello }";
+        let hash = sha256_hash(query);
+        let schema = schema(InMemoryStorage::with_queries([query]), Mode::AllowList);
+
+        let response = schema.execute(request("", &hash)).await;
+        assert_eq!(response.data, async_graphql::value!({ "hello": "world" }));
+        let response = schema.execute(query).await;
+        assert_eq!(response.data, async_graphql::value!({ "hello": "world" }));
+
+        let other = "{ __typename }";
+        let response = schema.execute(other).await;
+        assert_eq!(errors(&response), ["PersistedQueryNotAllowed"]);
+        let response = schema.execute(request(other, &sha256_hash(other))).await;
+        assert_eq!(errors(&response), ["PersistedQueryNotAllowed"]);
+        // not stored
+        let response = schema.execute(request("", &sha256_hash(other))).await;
+        assert_eq!(errors(&response), ["PersistedQueryNotFound"]);
+    }
+}
@@ -0,0 +1,249 @@
+use futures_util::Stream;
+
+pub type AppSchema = async_graphql::Schema<Query, Mutation, Subscription>;
+
+pub fn build(
+    storage: Storage,
+    persisted_queries: crate::persisted_query::PersistedQueries<
+        crate::persisted_query::InMemoryStorage,
+    >,
+) -> AppSchema {
+    async_graphql::Schema::build(Query, Mutation, Subscription)
+        .data(storage)
+        .extension(persisted_queries)
+        .extension(GetRequestGuard)
+        .finish()
+}
+
+/// Marks the request sent with GET, whose operations must be queries (no side effects, CSRF).
+#[derive(Clone, Copy, Debug)]
+pub struct GetRequest;
+
+/// The `extensions.code` of the error for the mutations and the subscriptions over GET.
+pub const METHOD_NOT_ALLOWED: &str = "METHOD_NOT_ALLOWED";
+
+/// Rejects the documents with the mutations or the subscriptions in the requests with
+/// `GetRequest`. The persisted queries are checked after they are resolved.
+pub struct GetRequestGuard;
+
+impl async_graphql::extensions::ExtensionFactory for GetRequestGuard {
+    fn create(&self) -> std::sync::Arc<dyn async_graphql::extensions::Extension> {
+        std::sync::Arc::new(GetRequestGuard)
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl async_graphql::extensions::Extension for GetRequestGuard {
+    async fn parse_query(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        query: &str,
+        variables: &async_graphql::Variables,
+        next: async_graphql::extensions::NextParseQuery<'_>,
+    ) -> async_graphql::ServerResult<async_graphql::parser::types::ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let is_get = ctx.data_opt::<GetRequest>().is_some();
+        let has_side_effects = document
+            .operations
+            .iter()
+            .any(|(_, it)| it.node.ty != async_graphql::parser::types::OperationType::Query);
+        if is_get && has_side_effects {
+            let mut error = async_graphql::ServerError::new(
+                "only queries are allowed over GET; use POST",
+                None,
+            );
+            error
+                .extensions
+                .get_or_insert_with(Default::default)
+                .set("code", METHOD_NOT_ALLOWED);
+            return Err(error);
+        }
+        Ok(document)
+    }
+}
+
+/// Marks the WebSocket connection authenticated by the `connection_init` payload.
+#[derive(Clone, Copy, Debug)]
+pub struct Authenticated;
+
+/// The posted messages and the channel to notify the subscribers of them.
+pub struct Storage {
+    messages: std::sync::Mutex<Vec<String>>,
+    sender: tokio::sync::broadcast::Sender<String>,
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        let (sender, _) = tokio::sync::broadcast::channel(16);
+        Self {
+            messages: std::sync::Mutex::new(vec![]),
+            sender,
+        }
+    }
+}
+
+pub struct Query;
+
+#[async_graphql::Object]
+impl Query {
+    async fn hello(&self) -> String {
+        "world".to_string()
+    }
+
+    async fn messages(&self, ctx: &async_graphql::Context<'_>) -> Vec<String> {
+        let storage = ctx.data_unchecked::<Storage>();
+        storage.messages.lock().unwrap().clone()
+    }
+}
+
+pub struct Mutation;
+
+#[async_graphql::Object]
+impl Mutation {
+    async fn post_message(&self, ctx: &async_graphql::Context<'_>, text: String) -> String {
+        let storage = ctx.data_unchecked::<Storage>();
+        storage.messages.lock().unwrap().push(text.clone());
+        // no subscribers is not an error
+        let _ = storage.sender.send(text.clone());
+        text
+    }
+}
+
+pub struct Subscription;
+
+#[async_graphql::Subscription]
+impl Subscription {
+    /// The messages posted after the subscription starts.
+    async fn messages(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::Result<impl Stream<Item = String>> {
+        // only the authenticated connections can subscribe
+        ctx.data::<Authenticated>()?;
+        let receiver = ctx.data_unchecked::<Storage>().sender.subscribe();
+        Ok(futures_util::stream::unfold(
+            receiver,
+            |mut receiver| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(message) => return Some((message, receiver)),
+                        // skip the messages the slow subscriber missed
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> AppSchema {
+        build(
+            Storage::default(),
+            crate::persisted_query::PersistedQueries::new(
+                crate::persisted_query::InMemoryStorage::default(),
+                crate::persisted_query::Mode::Automatic,
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_post_message() {
+        let schema = schema();
+        let response = schema
+            .execute(r#"mutation { postMessage(text: "hello") }"#)
+            .await;
+        assert_eq!(
+            response.data,
+            async_graphql::value!({ "postMessage": "hello" })
+        );
+        let response = schema.execute("{ messages }").await;
+        assert_eq!(
+            response.data,
+            async_graphql::value!({ "messages": ["hello"] })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscription() {
+        use futures_util::StreamExt as _;
+
+        let schema = schema();
+        // unauthenticated
+        let mut stream = schema.execute_stream("subscription { messages }");
+        let response = stream.next().await.expect("an error response");
+        assert_eq!(
+            response
+                .errors
+                .iter()
+                .map(|it| it.message.as_str())
+                .collect::<Vec<_>>(),
+            ["Data `async_graphql1::schema::Authenticated` does not exist."]
+        );
+
+        let mut stream = schema.execute_stream(
+            async_graphql::Request::new("subscription { messages }").data(Authenticated),
+        );
+        // the stream subscribes when it is polled first, so the messages are posted until one
+        // is received
+        let next = tokio::spawn(async move { stream.next().await });
+        let mut posted = vec![];
+        while !next.is_finished() {
+            let text = format!("m{}", posted.len());
+            schema
+                .execute(format!(r#"mutation {{ postMessage(text: "{}") }}"#, text))
+                .await;
+            posted.push(async_graphql::value!({ "messages": text }));
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let response = next.await.unwrap().expect("a message");
+        assert!(posted.contains(&response.data), "{:?}", response.data);
+    }
+
+    #[tokio::test]
+    async fn test_get_request_guard() {
+        let schema = schema();
+        let response = schema
+            .execute(async_graphql::Request::new("{ hello }").data(GetRequest))
+            .await;
+        assert_eq!(response.data, async_graphql::value!({ "hello": "world" }));
+
+        for query in [
+            r#"mutation { postMessage(text: "hello") }"#,
+            "subscription { messages }",
+            // any operation of the document
+            r#"query A { hello } mutation B { postMessage(text: "hello") }"#,
+        ] {
+            let response = schema
+                .execute(async_graphql::Request::new(query).data(GetRequest))
+                .await;
+            let codes = response
+                .errors
+                .iter()
+                .map(|it| {
+                    it.extensions
+                        .as_ref()
+                        .and_then(|it| it.get("code"))
+                        .cloned()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                codes,
+                [Some(async_graphql::Value::from(METHOD_NOT_ALLOWED))],
+                "{query}"
+            );
+        }
+        let response = schema.execute("{ messages }").await;
+        assert_eq!(response.data, async_graphql::value!({ "messages": [] }));
+
+        // POST
+        let response = schema
+            .execute(r#"mutation { postMessage(text: "hello") }"#)
+            .await;
+        assert!(response.errors.is_empty());
+    }
+}
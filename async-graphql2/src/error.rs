@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use futures_util::FutureExt as _;
+
+/// The message of the errors whose details are not shown to the clients.
+pub const MASKED_MESSAGE: &str = "internal error";
+
+/// The code of the error in `extensions.code`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCode {
+    BadUserInput,
+    NotFound,
+    Unavailable,
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BadUserInput => "BAD_USER_INPUT",
+            Self::NotFound => "NOT_FOUND",
+            Self::Unavailable => "UNAVAILABLE",
+            Self::Internal => "INTERNAL",
+        }
+    }
+
+    /// Whether the same request may succeed later.
+    pub fn retryable(&self) -> bool {
+        matches!(self, Self::Unavailable)
+    }
+}
+
+/// The error of the resolvers.
+///
+/// This is not `Display`, because `async_graphql::Error` converts any `Display` into the error
+/// without the extensions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppError {
+    code: ErrorCode,
+    message: String,
+    retryable: bool,
+}
+
+impl AppError {
+    /// The message is shown to the clients.
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            retryable: code.retryable(),
+        }
+    }
+
+    /// The message is masked in the production mode (see `ErrorMode`).
+    pub fn internal(error: impl std::fmt::Display) -> Self {
+        Self::new(ErrorCode::Internal, error.to_string())
+    }
+
+    pub fn retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    fn extensions(&self) -> async_graphql::ErrorExtensionValues {
+        let mut extensions = async_graphql::ErrorExtensionValues::default();
+        extensions.set("code", self.code.as_str());
+        extensions.set("retryable", self.retryable);
+        extensions
+    }
+}
+
+impl From<AppError> for async_graphql::Error {
+    fn from(error: AppError) -> Self {
+        let extensions = error.extensions();
+        let mut e = async_graphql::Error::new(error.message);
+        e.extensions = Some(extensions);
+        e
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorMode {
+    /// Shows the messages of the internal errors.
+    Development,
+    /// Logs the messages of the internal errors and shows `MASKED_MESSAGE` instead.
+    ///
+    /// The internal errors are the errors with `INTERNAL` and the resolver errors without the
+    /// code. The request errors (e.g. the parse errors) are shown as is.
+    Production,
+}
+
+/// Converts the panics of the resolvers into `INTERNAL` errors and masks the internal errors in
+/// the production mode.
+///
+/// Only the tests build the schema with it (`main` is empty).
+pub struct ErrorMasking {
+    mode: ErrorMode,
+}
+
+impl ErrorMasking {
+    pub fn new(mode: ErrorMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl async_graphql::extensions::ExtensionFactory for ErrorMasking {
+    fn create(&self) -> Arc<dyn async_graphql::extensions::Extension> {
+        Arc::new(ErrorMaskingExtension { mode: self.mode })
+    }
+}
+
+struct ErrorMaskingExtension {
+    mode: ErrorMode,
+}
+
+#[async_graphql::async_trait::async_trait]
+impl async_graphql::extensions::Extension for ErrorMaskingExtension {
+    async fn request(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        next: async_graphql::extensions::NextRequest<'_>,
+    ) -> async_graphql::Response {
+        let mut response = next.run(ctx).await;
+        if self.mode == ErrorMode::Production {
+            for error in response.errors.iter_mut().filter(|it| is_internal(it)) {
+                if error.message != MASKED_MESSAGE {
+                    tracing::error!(path = ?error.path, "{}", error.message);
+                }
+                mask(error);
+            }
+        }
+        response
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        info: async_graphql::extensions::ResolveInfo<'_>,
+        next: async_graphql::extensions::NextResolve<'_>,
+    ) -> async_graphql::ServerResult<Option<async_graphql::Value>> {
+        let pos = info.field.name.pos;
+        let path = info.path_node.to_string_vec();
+        match std::panic::AssertUnwindSafe(next.run(ctx, info))
+            .catch_unwind()
+            .await
+        {
+            Ok(result) => result,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("unknown panic");
+                tracing::error!(?path, "resolver panicked: {message}");
+                let mut error = async_graphql::ServerError::new(MASKED_MESSAGE, Some(pos));
+                error.path = path
+                    .into_iter()
+                    .map(async_graphql::PathSegment::Field)
+                    .collect();
+                mask(&mut error);
+                Err(error)
+            }
+        }
+    }
+}
+
+fn is_internal(error: &async_graphql::ServerError) -> bool {
+    match error.extensions.as_ref().and_then(|it| it.get("code")) {
+        Some(code) => *code == async_graphql::Value::from(ErrorCode::Internal.as_str()),
+        None => !error.path.is_empty(),
+    }
+}
+
+fn mask(error: &mut async_graphql::ServerError) {
+    error.message = MASKED_MESSAGE.to_owned();
+    error.source = None;
+    error.extensions = Some(AppError::new(ErrorCode::Internal, MASKED_MESSAGE).extensions());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_error() {
+        let error = async_graphql::Error::from(AppError::new(ErrorCode::Unavailable, "message"));
+        assert_eq!(error.message, "message");
+        assert_eq!(
+            serde_json::to_value(error.extensions).ok(),
+            Some(serde_json::json!({ "code": "UNAVAILABLE", "retryable": true }))
+        );
+
+        let error = AppError::new(ErrorCode::NotFound, "message").retryable(true);
+        assert!(error.retryable);
+        assert!(!AppError::internal("message").retryable);
+    }
+}
@@ -0,0 +1,50 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// The data source of `Context4OutputItem.context5` (e.g. a database).
+#[derive(Default)]
+pub struct Backend {
+    calls: AtomicUsize,
+}
+
+impl Backend {
+    /// Returns the number of the calls to the backend.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    async fn find_context5(&self, ids: &[u32]) -> HashMap<u32, String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        ids.iter().map(|id| (*id, "context5".to_owned())).collect()
+    }
+}
+
+pub struct Context5Loader {
+    backend: Arc<Backend>,
+}
+
+impl async_graphql::dataloader::Loader<u32> for Context5Loader {
+    type Value = String;
+    type Error = std::convert::Infallible;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Self::Value>, Self::Error> {
+        Ok(self.backend.find_context5(keys).await)
+    }
+}
+
+/// Returns the loader for a request. The loaded values are cached until the request ends.
+pub fn data_loader(
+    backend: Arc<Backend>,
+) -> async_graphql::dataloader::DataLoader<Context5Loader, async_graphql::dataloader::HashMapCache>
+{
+    async_graphql::dataloader::DataLoader::with_cache(
+        Context5Loader { backend },
+        tokio::spawn,
+        async_graphql::dataloader::HashMapCache::default(),
+    )
+}
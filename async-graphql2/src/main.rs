@@ -1,3 +1,9 @@
+// the schema is built in the tests only (`main` is empty)
+#[cfg_attr(not(test), allow(dead_code))]
+mod error;
+#[cfg_attr(not(test), allow(dead_code))]
+mod loader;
+
 struct Query;
 
 #[async_graphql::Object]
@@ -84,11 +90,18 @@ impl Query {
             format!("{:?}", context.path_node),
             "Some(QueryPathNode { parent: None, segment: Name(\"context4\") })"
         );
-        vec![Context4OutputItem, Context4OutputItem]
+        vec![Context4OutputItem { id: 1 }, Context4OutputItem { id: 2 }]
+    }
+
+    async fn error(&self) -> Result<String, error::AppError> {
+        Err(error::AppError::new(
+            error::ErrorCode::Unavailable,
+            "An error occurred",
+        ))
     }
 
-    async fn error(&self) -> Result<String, String> {
-        Err("An error occurred".to_owned())
+    async fn error_internal(&self) -> Result<String, error::AppError> {
+        Err(error::AppError::internal("connection refused"))
     }
 
     async fn error_panic(&self) -> &str {
@@ -150,11 +163,17 @@ impl Context2Output {
     }
 }
 
-struct Context4OutputItem;
+struct Context4OutputItem {
+    id: u32,
+}
 
 #[async_graphql::Object]
 impl Context4OutputItem {
-    async fn context5(&self, context: &async_graphql::Context<'_>) -> &str {
+    /// Loaded in a batch with the other items (see `loader::data_loader`).
+    async fn context5(
+        &self,
+        context: &async_graphql::Context<'_>,
+    ) -> async_graphql::Result<String> {
         let field: async_graphql::SelectionField<'_> = context.field();
         assert_eq!(field.alias(), None);
         assert_eq!(field.arguments(), Ok(vec![]));
@@ -191,7 +210,14 @@ impl Context4OutputItem {
             async_graphql::QueryPathSegment::Name(n) => format!("name:{n}"),
         });
         assert_eq!(name, Some("name:context5".to_owned()));
-        "context5"
+        let loader = context.data::<async_graphql::dataloader::DataLoader<
+            loader::Context5Loader,
+            async_graphql::dataloader::HashMapCache,
+        >>()?;
+        loader
+            .load_one(self.id)
+            .await?
+            .ok_or_else(|| async_graphql::Error::new("context5 not found"))
     }
 }
 
@@ -220,7 +246,7 @@ mod tests {
     async fn test_error() -> anyhow::Result<()> {
         let schema = build_schema();
         let request = "{ error }";
-        let expected_response_data = r#"{"data":null,"errors":[{"message":"An error occurred","locations":[{"line":1,"column":3}],"path":["error"]}]}"#;
+        let expected_response_data = r#"{"data":null,"errors":[{"message":"An error occurred","locations":[{"line":1,"column":3}],"path":["error"],"extensions":{"code":"UNAVAILABLE","retryable":true}}]}"#;
         let response = schema.execute(request).await;
         assert_eq!(
             serde_json::Value::from_str(&serde_json::to_string(&response)?)?,
@@ -230,11 +256,56 @@ mod tests {
     }
 
     #[tokio::test]
-    #[should_panic(expected = "This is a panic")]
-    async fn test_error_panic() {
+    async fn test_error_panic() -> anyhow::Result<()> {
         let schema = build_schema();
         let request = "{ errorPanic }";
-        schema.execute(request).await;
+        let expected_response_data = r#"{"data":null,"errors":[{"message":"internal error","locations":[{"line":1,"column":3}],"path":["errorPanic"],"extensions":{"code":"INTERNAL","retryable":false}}]}"#;
+        let response = schema.execute(request).await;
+        assert_eq!(
+            serde_json::Value::from_str(&serde_json::to_string(&response)?)?,
+            serde_json::Value::from_str(expected_response_data)?
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_masking() -> anyhow::Result<()> {
+        let request = "{ errorInternal }";
+        let response = build_schema().execute(request).await;
+        assert_eq!(
+            serde_json::to_value(&response.errors)?,
+            serde_json::json!([{
+                "message": "connection refused",
+                "locations": [{ "line": 1, "column": 3 }],
+                "path": ["errorInternal"],
+                "extensions": { "code": "INTERNAL", "retryable": false },
+            }])
+        );
+
+        let schema = build_schema_with_mode(error::ErrorMode::Production);
+        let response = schema.execute(request).await;
+        assert_eq!(
+            serde_json::to_value(&response.errors)?,
+            serde_json::json!([{
+                "message": "internal error",
+                "locations": [{ "line": 1, "column": 3 }],
+                "path": ["errorInternal"],
+                "extensions": { "code": "INTERNAL", "retryable": false },
+            }])
+        );
+        // the errors for the clients are shown as is
+        let response = schema.execute("{ error context1(input: 1) }").await;
+        assert_eq!(
+            response
+                .errors
+                .iter()
+                .map(|it| it.message.as_str())
+                .collect::<Vec<_>>(),
+            [r#"Unknown argument "input" on field "context1" of type "Query"."#]
+        );
+        let response = schema.execute("{ error }").await;
+        assert_eq!(response.errors[0].message, "An error occurred");
+        Ok(())
     }
 
     #[tokio::test]
@@ -279,7 +350,9 @@ mod tests {
     #[tokio::test]
     async fn test_context4() -> anyhow::Result<()> {
         let schema = build_schema();
-        let request = r#"{ context4 { context5 } }"#;
+        let backend = std::sync::Arc::new(loader::Backend::default());
+        let request = async_graphql::Request::new(r#"{ context4 { context5 } }"#)
+            .data(loader::data_loader(backend.clone()));
         let expected_response_data =
             r#"{"data":{"context4":[{"context5":"context5"},{"context5":"context5"}]}}"#;
         let response = schema.execute(request).await;
@@ -287,17 +360,46 @@ mod tests {
             serde_json::Value::from_str(&serde_json::to_string(&response)?)?,
             serde_json::Value::from_str(expected_response_data)?
         );
+        // context5 of the items are loaded at once
+        assert_eq!(backend.calls(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_context4_cache() -> anyhow::Result<()> {
+        let schema = build_schema();
+        let backend = std::sync::Arc::new(loader::Backend::default());
+        let data_loader = loader::data_loader(backend.clone());
+        assert_eq!(data_loader.load_one(1).await?, Some("context5".to_owned()));
+        // cached
+        assert_eq!(data_loader.load_one(1).await?, Some("context5".to_owned()));
+        assert_eq!(backend.calls(), 1);
+
+        // the cache is per request (per loader)
+        let request = async_graphql::Request::new(r#"{ context4 { context5 } }"#)
+            .data(loader::data_loader(backend.clone()));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(backend.calls(), 2);
         Ok(())
     }
 
     fn build_schema()
     -> async_graphql::Schema<Query, async_graphql::EmptyMutation, async_graphql::EmptySubscription>
+    {
+        build_schema_with_mode(error::ErrorMode::Development)
+    }
+
+    fn build_schema_with_mode(
+        mode: error::ErrorMode,
+    ) -> async_graphql::Schema<Query, async_graphql::EmptyMutation, async_graphql::EmptySubscription>
     {
         async_graphql::Schema::build(
             Query,
             async_graphql::EmptyMutation,
             async_graphql::EmptySubscription,
         )
+        .extension(error::ErrorMasking::new(mode))
         .finish()
     }
 }
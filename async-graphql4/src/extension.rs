@@ -4,7 +4,9 @@ use std::collections::BTreeSet;
 pub use async_graphql;
 pub use async_trait::async_trait;
 
+/// Creates the error of the field with the code in `extensions.code` (e.g. `FORBIDDEN`).
 pub fn error_from_info(
+    code: &str,
     message: impl Into<String>,
     info: &async_graphql::extensions::ResolveInfo<'_>,
 ) -> async_graphql::ServerError {
@@ -14,20 +16,26 @@ pub fn error_from_info(
         .into_iter()
         .map(|it| async_graphql::PathSegment::Field(it))
         .collect::<Vec<async_graphql::PathSegment>>();
+    let mut extensions = async_graphql::ErrorExtensionValues::default();
+    extensions.set("code", code);
     async_graphql::ServerError {
         message: message.into(),
         source: None,
         locations: vec![info.field.name.pos],
         path,
-        extensions: None,
+        extensions: Some(extensions),
     }
 }
 
+/// The field arguments with the variables replaced by their values and the default values
+/// applied.
+pub use async_graphql_argument::Arguments;
+
 #[async_trait]
 pub trait Rbac {
-    type Permission: Ord + Send;
+    type Permission: Ord + Send + Sync;
     type Role: Ord + Send;
-    type User: Send;
+    type User: Send + Sync;
 
     async fn get_permissions(
         &self,
@@ -42,6 +50,20 @@ pub trait Rbac {
         info: &async_graphql::extensions::ResolveInfo<'_>,
     ) -> async_graphql::ServerResult<Self::Permission>;
 
+    /// Returns the permission required to resolve the field for the user with the arguments,
+    /// or `None` if the field can be resolved without any permission (attribute-based).
+    ///
+    /// The default implementation ignores the user and the arguments.
+    async fn get_required_permission_with_arguments(
+        &self,
+        context: &async_graphql::extensions::ExtensionContext<'_>,
+        info: &async_graphql::extensions::ResolveInfo<'_>,
+        _user: &Self::User,
+        _arguments: &Arguments,
+    ) -> async_graphql::ServerResult<Option<Self::Permission>> {
+        self.get_required_permission(context, info).await.map(Some)
+    }
+
     async fn get_roles(
         &self,
         context: &async_graphql::extensions::ExtensionContext<'_>,
@@ -56,18 +78,69 @@ pub trait Rbac {
     ) -> async_graphql::ServerResult<Self::User>;
 }
 
-pub struct RbacExtension<T> {
-    inner: T,
+/// The user and the permissions of the user's roles.
+struct Grant<T: Rbac> {
+    user: T::User,
+    permissions: BTreeSet<T::Permission>,
+}
+
+/// The extension created for each request.
+///
+/// The user, the roles and the permissions are resolved on the first field and reused for the
+/// rest of the request. A failed resolution is not cached, so the error is reported on each field.
+pub struct RbacExtension<T: Rbac> {
+    inner: std::sync::Arc<T>,
+    grant: tokio::sync::OnceCell<Grant<T>>,
+    operation_name: std::sync::OnceLock<Option<String>>,
+    variables: std::sync::OnceLock<async_graphql::Variables>,
+}
+
+impl<T: Rbac + Send + Sync + 'static> RbacExtension<T> {
+    async fn grant(
+        &self,
+        context: &async_graphql::extensions::ExtensionContext<'_>,
+        info: &async_graphql::extensions::ResolveInfo<'_>,
+    ) -> async_graphql::ServerResult<Grant<T>> {
+        let user = self.inner.get_user(context, info).await?;
+        let roles = self.inner.get_roles(context, info, &user).await?;
+        let mut permissions = BTreeSet::new();
+        for role in roles {
+            let ps = self.inner.get_permissions(context, info, &role).await?;
+            permissions.extend(ps);
+        }
+        Ok(Grant { user, permissions })
+    }
 }
 
 #[async_trait]
 impl<T: Rbac + Send + Sync + 'static> async_graphql::extensions::Extension for RbacExtension<T> {
-    async fn request(
+    async fn prepare_request(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        request: async_graphql::Request,
+        next: async_graphql::extensions::NextPrepareRequest<'_>,
+    ) -> async_graphql::ServerResult<async_graphql::Request> {
+        let _ = self.operation_name.set(request.operation_name.clone());
+        next.run(ctx, request).await
+    }
+
+    async fn parse_query(
         &self,
         ctx: &async_graphql::extensions::ExtensionContext<'_>,
-        next: async_graphql::extensions::NextRequest<'_>,
-    ) -> async_graphql::Response {
-        next.run(ctx).await
+        query: &str,
+        variables: &async_graphql::Variables,
+        next: async_graphql::extensions::NextParseQuery<'_>,
+    ) -> async_graphql::ServerResult<async_graphql::parser::types::ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let operation_name = self.operation_name.get().and_then(|it| it.as_deref());
+        let _ = self
+            .variables
+            .set(async_graphql_argument::operation_variables(
+                &document,
+                operation_name,
+                variables,
+            ));
+        Ok(document)
     }
 
     async fn resolve(
@@ -76,38 +149,55 @@ impl<T: Rbac + Send + Sync + 'static> async_graphql::extensions::Extension for R
         info: async_graphql::extensions::ResolveInfo<'_>,
         next: async_graphql::extensions::NextResolve<'_>,
     ) -> async_graphql::ServerResult<Option<async_graphql::Value>> {
-        let user = self.inner.get_user(context, &info).await?;
-        let roles = self.inner.get_roles(context, &info, &user).await?;
-        let mut permissions = BTreeSet::new();
-        for role in roles {
-            let ps = self.inner.get_permissions(context, &info, &role).await?;
-            permissions.extend(ps);
-        }
-        let required_permission = self.inner.get_required_permission(context, &info).await?;
-        if permissions.contains(&required_permission) {
-            next.run(context, info).await
-        } else {
-            Err(error_from_info("required permission not granted", &info))
+        let grant = self
+            .grant
+            .get_or_try_init(|| self.grant(context, &info))
+            .await?;
+        let empty = async_graphql::Variables::default();
+        let variables = self.variables.get().unwrap_or(&empty);
+        let arguments = async_graphql_argument::resolve_arguments(
+            &context.schema_env.registry,
+            &info,
+            variables,
+        );
+        let required_permission = self
+            .inner
+            .get_required_permission_with_arguments(context, &info, &grant.user, &arguments)
+            .await?;
+        match required_permission {
+            Some(required_permission) if !grant.permissions.contains(&required_permission) => Err(
+                error_from_info("FORBIDDEN", "required permission not granted", &info),
+            ),
+            _ => next.run(context, info).await,
         }
     }
 }
 
 pub struct RbacExtensionFactory<T> {
-    inner: std::sync::Arc<RbacExtension<T>>,
+    inner: std::sync::Arc<T>,
 }
 
 impl<T: Rbac + Send + Sync + 'static> RbacExtensionFactory<T> {
     pub fn new(inner: T) -> Self {
         Self {
-            inner: std::sync::Arc::new(RbacExtension::<T> { inner }),
+            inner: std::sync::Arc::new(inner),
         }
     }
+
+    pub fn from_arc(inner: std::sync::Arc<T>) -> Self {
+        Self { inner }
+    }
 }
 
 impl<T: Rbac + Send + Sync + 'static> async_graphql::extensions::ExtensionFactory
     for RbacExtensionFactory<T>
 {
     fn create(&self) -> std::sync::Arc<dyn async_graphql::extensions::Extension> {
-        self.inner.clone()
+        std::sync::Arc::new(RbacExtension {
+            inner: self.inner.clone(),
+            grant: tokio::sync::OnceCell::new(),
+            operation_name: std::sync::OnceLock::new(),
+            variables: std::sync::OnceLock::new(),
+        })
     }
 }
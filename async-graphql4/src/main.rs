@@ -6,7 +6,9 @@ struct MyData {
     user: String,
 }
 
-struct MyRbac;
+struct MyRbac {
+    policy: std::sync::Arc<rbac::PolicyStore>,
+}
 
 #[extension::async_trait]
 impl extension::Rbac for MyRbac {
@@ -20,8 +22,9 @@ impl extension::Rbac for MyRbac {
         info: &async_graphql::extensions::ResolveInfo<'_>,
         role: &Self::Role,
     ) -> async_graphql::ServerResult<std::collections::BTreeSet<Self::Permission>> {
-        match crate::rbac::ROLE_TO_PERMISSIONS.get(&role).cloned() {
+        match self.policy.policy().permissions(role).cloned() {
             None => Err(extension::error_from_info(
+                "INTERNAL",
                 "role permissions not found",
                 info,
             )),
@@ -34,21 +37,47 @@ impl extension::Rbac for MyRbac {
         _context: &async_graphql::extensions::ExtensionContext<'_>,
         info: &async_graphql::extensions::ResolveInfo<'_>,
     ) -> async_graphql::ServerResult<Self::Permission> {
-        let operation = format!("{}::{}", info.parent_type, info.name);
-        match <Self::Permission as std::str::FromStr>::from_str(operation.as_str()) {
-            Err(_) => Err(extension::error_from_info("unknown permission", info)),
+        match Self::Permission::new(info.parent_type, info.name) {
+            Err(_) => Err(extension::error_from_info(
+                "INTERNAL",
+                "unknown permission",
+                info,
+            )),
             Ok(required_permission) => Ok(required_permission),
         }
     }
 
+    async fn get_required_permission_with_arguments(
+        &self,
+        context: &async_graphql::extensions::ExtensionContext<'_>,
+        info: &async_graphql::extensions::ResolveInfo<'_>,
+        user: &Self::User,
+        arguments: &extension::Arguments,
+    ) -> async_graphql::ServerResult<Option<Self::Permission>> {
+        // the user can access their own parent2 (`input.id`) without the permissions.
+        // the children still require the permissions (their own `input.id` says nothing about
+        // the parent)
+        if let ("QueryRoot", "parent2") = (info.parent_type, info.name)
+            && let Some(async_graphql::Value::String(id)) = arguments.get(&["input", "id"])
+            && *id == user.to_string()
+        {
+            return Ok(None);
+        }
+        self.get_required_permission(context, info).await.map(Some)
+    }
+
     async fn get_roles(
         &self,
         _context: &async_graphql::extensions::ExtensionContext<'_>,
         info: &async_graphql::extensions::ResolveInfo<'_>,
         user: &Self::User,
     ) -> async_graphql::ServerResult<std::collections::BTreeSet<Self::Role>> {
-        match crate::rbac::USER_TO_ROLES.get(user).cloned() {
-            None => Err(extension::error_from_info("user roles not found", info)),
+        match self.policy.policy().roles(user).cloned() {
+            None => Err(extension::error_from_info(
+                "FORBIDDEN",
+                "user roles not found",
+                info,
+            )),
             Some(roles) => Ok(roles),
         }
     }
@@ -60,13 +89,50 @@ impl extension::Rbac for MyRbac {
     ) -> async_graphql::ServerResult<Self::User> {
         let data = context.data_unchecked::<MyData>();
         match <Self::User as std::str::FromStr>::from_str(data.user.as_str()) {
-            Err(_) => Err(extension::error_from_info("user not found", info)),
+            Err(_) => Err(extension::error_from_info(
+                "UNAUTHENTICATED",
+                "user not found",
+                info,
+            )),
             Ok(user) => Ok(user),
         }
     }
 }
 
-fn main() {}
+/// `permissions` prints the permissions required by the schema.
+/// `check <policy>` reports the fields no role can reach and the permissions matching no field.
+fn main() -> anyhow::Result<()> {
+    let schema = async_graphql::Schema::build(
+        schema::QueryRoot,
+        async_graphql::EmptyMutation,
+        async_graphql::EmptySubscription,
+    )
+    .finish();
+    let schema_permissions = rbac::SchemaPermissions::new(&schema)?;
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        ["permissions"] => {
+            for permission in schema_permissions.permissions() {
+                println!("{permission}");
+            }
+        }
+        ["check", path] => {
+            let policy = rbac::Policy::read(std::path::Path::new(path))?;
+            let report = schema_permissions.coverage(&policy);
+            print!("{report}");
+            if !report.is_ok() {
+                std::process::exit(1);
+            }
+        }
+        _ => anyhow::bail!("usage: async-graphql4 (permissions | check <policy>)"),
+    }
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
@@ -74,6 +140,37 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_policy_coverage() -> anyhow::Result<()> {
+        let schema = async_graphql::Schema::build(
+            schema::QueryRoot,
+            async_graphql::EmptyMutation,
+            async_graphql::EmptySubscription,
+        )
+        .finish();
+        let schema_permissions = rbac::SchemaPermissions::new(&schema)?;
+        assert_eq!(
+            schema_permissions
+                .permissions()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            [
+                "Parent1Output::child1",
+                "Parent2Output::child2",
+                "QueryRoot::parent1",
+                "QueryRoot::parent2",
+            ]
+        );
+        let policy = rbac::Policy::read(std::path::Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/policy.toml"
+        )))?;
+        let report = schema_permissions.coverage(&policy);
+        assert!(report.is_ok(), "{report}");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_extension() -> anyhow::Result<()> {
         let request: async_graphql::Request =
@@ -97,7 +194,8 @@ mod tests {
                         }
                     ],
                     "message": "user roles not found",
-                    "path": ["parent2"]
+                    "path": ["parent2"],
+                    "extensions": { "code": "FORBIDDEN" }
                 }
             ]
         })
@@ -120,7 +218,8 @@ mod tests {
                         }
                     ],
                     "message": "required permission not granted",
-                    "path": ["parent2"]
+                    "path": ["parent2"],
+                    "extensions": { "code": "FORBIDDEN" }
                 }
             ]
         })
@@ -155,6 +254,145 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_owner() -> anyhow::Result<()> {
+        let query = "query($parent: String!, $child: String!) { parent2(input: { id: $parent }) { child2(input: { id: $child }) } }";
+        let request = |parent: &str, child: &str| {
+            async_graphql::Request::new(query)
+                .variables(async_graphql::Variables::from_json(
+                    serde_json::json!({ "parent": parent, "child": child }),
+                ))
+                .data(MyData {
+                    user: "user123".to_owned(),
+                })
+        };
+        let forbidden = |column: usize, path: &[&str]| {
+            serde_json::json!({
+                "data": null,
+                "errors": [
+                    {
+                        "locations": [{ "column": column, "line": 1 }],
+                        "message": "required permission not granted",
+                        "path": path,
+                        "extensions": { "code": "FORBIDDEN" }
+                    }
+                ]
+            })
+            .to_string()
+        };
+
+        // the own parent2 is resolved, but child2 requires the permission
+        test_graphql_request(
+            request("user123", "user123"),
+            &forbidden(78, &["parent2", "child2"]),
+        )
+        .await?;
+        test_graphql_request(
+            request("user123", "admin123"),
+            &forbidden(78, &["parent2", "child2"]),
+        )
+        .await?;
+        // the own id in child2 does not grant the other's parent2
+        test_graphql_request(request("admin123", "user123"), &forbidden(44, &["parent2"])).await?;
+
+        // the default value of the variable definition is applied
+        let request = async_graphql::Request::new(
+            r#"query($id: String = "user123") { parent2(input: { id: $id }) { __typename } }"#,
+        )
+        .data(MyData {
+            user: "user123".to_owned(),
+        });
+        let expected_response = r#"{"data":{"parent2":{"__typename":"Parent2Output"}}}"#;
+        test_graphql_request(request, expected_response).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache() -> anyhow::Result<()> {
+        struct CountingRbac {
+            inner: MyRbac,
+            count: std::sync::atomic::AtomicUsize,
+        }
+
+        #[extension::async_trait]
+        impl extension::Rbac for CountingRbac {
+            type Permission = rbac::Permission;
+            type Role = rbac::Role;
+            type User = rbac::User;
+
+            async fn get_permissions(
+                &self,
+                context: &async_graphql::extensions::ExtensionContext<'_>,
+                info: &async_graphql::extensions::ResolveInfo<'_>,
+                role: &Self::Role,
+            ) -> async_graphql::ServerResult<std::collections::BTreeSet<Self::Permission>>
+            {
+                self.count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                self.inner.get_permissions(context, info, role).await
+            }
+
+            async fn get_required_permission(
+                &self,
+                context: &async_graphql::extensions::ExtensionContext<'_>,
+                info: &async_graphql::extensions::ResolveInfo<'_>,
+            ) -> async_graphql::ServerResult<Self::Permission> {
+                self.inner.get_required_permission(context, info).await
+            }
+
+            async fn get_roles(
+                &self,
+                context: &async_graphql::extensions::ExtensionContext<'_>,
+                info: &async_graphql::extensions::ResolveInfo<'_>,
+                user: &Self::User,
+            ) -> async_graphql::ServerResult<std::collections::BTreeSet<Self::Role>> {
+                self.count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                self.inner.get_roles(context, info, user).await
+            }
+
+            async fn get_user(
+                &self,
+                context: &async_graphql::extensions::ExtensionContext<'_>,
+                info: &async_graphql::extensions::ResolveInfo<'_>,
+            ) -> async_graphql::ServerResult<Self::User> {
+                self.count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                self.inner.get_user(context, info).await
+            }
+        }
+
+        let rbac = std::sync::Arc::new(CountingRbac {
+            inner: MyRbac {
+                policy: std::sync::Arc::new(rbac::PolicyStore::load(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/policy.toml"
+                ))?),
+            },
+            count: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let schema = async_graphql::Schema::build(
+            schema::QueryRoot,
+            async_graphql::EmptyMutation,
+            async_graphql::EmptySubscription,
+        )
+        .extension(extension::RbacExtensionFactory::from_arc(rbac.clone()))
+        .finish();
+        for _ in 0..2 {
+            let response = schema
+                .execute(
+                    async_graphql::Request::new(
+                        r#"{ parent1 { child1 } parent2 { child2(input: { id: "abc" }) } }"#,
+                    )
+                    .data(MyData {
+                        user: "admin123".to_owned(),
+                    }),
+                )
+                .await;
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+        }
+        // get_user, get_roles and get_permissions (for the only role) once per request
+        assert_eq!(rbac.count.load(std::sync::atomic::Ordering::SeqCst), 6);
+        Ok(())
+    }
+
     async fn test_graphql_request(
         request: impl Into<async_graphql::Request>,
         expected_response: &str,
@@ -164,7 +402,12 @@ mod tests {
             async_graphql::EmptyMutation,
             async_graphql::EmptySubscription,
         )
-        .extension(extension::RbacExtensionFactory::new(MyRbac))
+        .extension(extension::RbacExtensionFactory::new(MyRbac {
+            policy: std::sync::Arc::new(rbac::PolicyStore::load(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/policy.toml"
+            ))?),
+        }))
         .finish();
         let request: async_graphql::Request = request.into();
         let response = schema.execute(request).await;
@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use async_graphql::parser::types::{BaseType, Type, TypeKind, TypeSystemDefinition};
+
+use crate::rbac::{Permission, Policy, Role, permission::ParsePermissionError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaPermissionsError {
+    #[error("invalid field `{type_name}.{field_name}`")]
+    InvalidField {
+        type_name: String,
+        field_name: String,
+        #[source]
+        source: ParsePermissionError,
+    },
+    #[error("invalid SDL")]
+    InvalidSdl(#[source] async_graphql::parser::Error),
+}
+
+/// The `Type::field` permissions required by the fields of the object types in the schema.
+///
+/// Introspection types and fields (`__schema`, `__type`, ...) are not included.
+#[derive(Debug)]
+pub struct SchemaPermissions {
+    roots: BTreeSet<String>,
+    /// type name -> field name -> the object types the field can return
+    fields: BTreeMap<String, BTreeMap<String, BTreeSet<String>>>,
+}
+
+/// The result of checking a policy against the schema.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct CoverageReport {
+    /// The fields that no role can reach from the root types.
+    pub unreachable_fields: BTreeSet<Permission>,
+    /// The permissions of the roles that do not match any field in the schema.
+    pub unknown_permissions: BTreeMap<Role, BTreeSet<Permission>>,
+}
+
+impl CoverageReport {
+    pub fn is_ok(&self) -> bool {
+        self.unreachable_fields.is_empty() && self.unknown_permissions.is_empty()
+    }
+}
+
+impl std::fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for permission in &self.unreachable_fields {
+            writeln!(f, "unreachable field: {permission}")?;
+        }
+        for (role, permissions) in &self.unknown_permissions {
+            for permission in permissions {
+                writeln!(f, "unknown permission in role `{role}`: {permission}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SchemaPermissions {
+    pub fn new<Query, Mutation, Subscription>(
+        schema: &async_graphql::Schema<Query, Mutation, Subscription>,
+    ) -> Result<Self, SchemaPermissionsError>
+    where
+        Query: async_graphql::ObjectType + 'static,
+        Mutation: async_graphql::ObjectType + 'static,
+        Subscription: async_graphql::SubscriptionType + 'static,
+    {
+        Self::from_sdl(&schema.sdl())
+    }
+
+    pub fn from_sdl(sdl: &str) -> Result<Self, SchemaPermissionsError> {
+        let document =
+            async_graphql::parser::parse_schema(sdl).map_err(SchemaPermissionsError::InvalidSdl)?;
+
+        let mut roots = None;
+        // type name -> field name -> the named type of the field
+        let mut objects = BTreeMap::<String, BTreeMap<String, String>>::new();
+        // abstract (interface or union) type name -> object type names
+        let mut possible_types = BTreeMap::<String, BTreeSet<String>>::new();
+        for definition in document.definitions {
+            match definition {
+                TypeSystemDefinition::Schema(schema) => {
+                    let schema = schema.node;
+                    roots = Some(
+                        [schema.query, schema.mutation, schema.subscription]
+                            .into_iter()
+                            .flatten()
+                            .map(|it| it.node.to_string())
+                            .collect::<BTreeSet<String>>(),
+                    );
+                }
+                TypeSystemDefinition::Type(definition) => {
+                    let definition = definition.node;
+                    let type_name = definition.name.node.to_string();
+                    match definition.kind {
+                        TypeKind::Object(object) => {
+                            for interface in object.implements {
+                                possible_types
+                                    .entry(interface.node.to_string())
+                                    .or_default()
+                                    .insert(type_name.clone());
+                            }
+                            let fields = objects.entry(type_name.clone()).or_default();
+                            for field in object.fields {
+                                let field = field.node;
+                                let field_name = field.name.node.to_string();
+                                if field_name.starts_with("__") {
+                                    continue;
+                                }
+                                Permission::new(type_name.as_str(), field_name.as_str()).map_err(
+                                    |source| SchemaPermissionsError::InvalidField {
+                                        type_name: type_name.clone(),
+                                        field_name: field_name.clone(),
+                                        source,
+                                    },
+                                )?;
+                                fields.insert(field_name, named_type(&field.ty.node).to_owned());
+                            }
+                        }
+                        TypeKind::Union(union) => {
+                            possible_types
+                                .entry(type_name)
+                                .or_default()
+                                .extend(union.members.into_iter().map(|it| it.node.to_string()));
+                        }
+                        _ => {}
+                    }
+                }
+                TypeSystemDefinition::Directive(_) => {}
+            }
+        }
+
+        // the default root type names are used when the schema definition is omitted
+        let roots = roots.unwrap_or_else(|| {
+            ["Query", "Mutation", "Subscription"]
+                .into_iter()
+                .filter(|it| objects.contains_key(*it))
+                .map(str::to_owned)
+                .collect()
+        });
+        let fields = objects
+            .iter()
+            .map(|(type_name, fields)| {
+                let fields = fields
+                    .iter()
+                    .map(|(field_name, field_type)| {
+                        let object_types = if objects.contains_key(field_type) {
+                            BTreeSet::from([field_type.clone()])
+                        } else {
+                            possible_types.get(field_type).cloned().unwrap_or_default()
+                        };
+                        (field_name.clone(), object_types)
+                    })
+                    .collect();
+                (type_name.clone(), fields)
+            })
+            .collect();
+        Ok(Self { roots, fields })
+    }
+
+    /// Returns the permissions required by all fields in the schema.
+    pub fn permissions(&self) -> BTreeSet<Permission> {
+        self.fields
+            .iter()
+            .flat_map(|(type_name, fields)| {
+                fields
+                    .keys()
+                    .map(move |field_name| permission(type_name, field_name))
+            })
+            .collect()
+    }
+
+    /// Returns the fields that can be resolved with the permissions.
+    ///
+    /// A field is reachable only when it is granted and the fields on a path from a root type
+    /// to it are also granted.
+    pub fn reachable(&self, granted: &BTreeSet<Permission>) -> BTreeSet<Permission> {
+        let mut reachable = BTreeSet::new();
+        let mut visited = BTreeSet::new();
+        let mut stack = self.roots.iter().collect::<Vec<&String>>();
+        while let Some(type_name) = stack.pop() {
+            if !visited.insert(type_name) {
+                continue;
+            }
+            let Some(fields) = self.fields.get(type_name) else {
+                continue;
+            };
+            for (field_name, object_types) in fields {
+                let permission = permission(type_name, field_name);
+                if !granted.contains(&permission) {
+                    continue;
+                }
+                reachable.insert(permission);
+                stack.extend(object_types);
+            }
+        }
+        reachable
+    }
+
+    /// Checks that every field is reachable by at least one role and that every permission in
+    /// the policy matches a field.
+    pub fn coverage(&self, policy: &Policy) -> CoverageReport {
+        let permissions = self.permissions();
+        let mut unreachable_fields = permissions.clone();
+        let mut unknown_permissions = BTreeMap::new();
+        for (role, granted) in policy.role_permissions() {
+            for permission in self.reachable(granted) {
+                unreachable_fields.remove(&permission);
+            }
+            let unknown = granted
+                .difference(&permissions)
+                .cloned()
+                .collect::<BTreeSet<Permission>>();
+            if !unknown.is_empty() {
+                unknown_permissions.insert(role.clone(), unknown);
+            }
+        }
+        CoverageReport {
+            unreachable_fields,
+            unknown_permissions,
+        }
+    }
+}
+
+fn named_type(ty: &Type) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name.as_str(),
+        BaseType::List(ty) => named_type(ty),
+    }
+}
+
+fn permission(type_name: &str, field_name: &str) -> Permission {
+    // the names are validated in `SchemaPermissions::from_sdl`
+    Permission::new(type_name, field_name).expect("valid permission")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    const SDL: &str = r#"
+schema {
+    query: QueryRoot
+}
+
+interface Node {
+    id: String!
+}
+
+type Item implements Node {
+    id: String!
+    name: String!
+}
+
+union SearchResult = Item | User
+
+type User implements Node {
+    id: String!
+    items: [Item!]!
+}
+
+type QueryRoot {
+    node(id: String!): Node
+    search(q: String!): [SearchResult!]!
+}
+
+type Orphan {
+    value: Int!
+}
+"#;
+
+    fn permissions(s: &[&str]) -> anyhow::Result<BTreeSet<Permission>> {
+        Ok(s.iter()
+            .map(|it| Permission::from_str(it))
+            .collect::<Result<BTreeSet<Permission>, ParsePermissionError>>()?)
+    }
+
+    #[test]
+    fn test_impls() {
+        fn assert_impl<T: Send + Sync>() {}
+
+        assert_impl::<CoverageReport>();
+        assert_impl::<SchemaPermissions>();
+    }
+
+    #[test]
+    fn test_permissions() -> anyhow::Result<()> {
+        let schema_permissions = SchemaPermissions::from_sdl(SDL)?;
+        assert_eq!(
+            schema_permissions.permissions(),
+            permissions(&[
+                "Item::id",
+                "Item::name",
+                "Orphan::value",
+                "QueryRoot::node",
+                "QueryRoot::search",
+                "User::id",
+                "User::items",
+            ])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reachable() -> anyhow::Result<()> {
+        let schema_permissions = SchemaPermissions::from_sdl(SDL)?;
+        // `User::items` is granted but `User` is not reachable without `QueryRoot::search`
+        assert_eq!(
+            schema_permissions.reachable(&permissions(&[
+                "QueryRoot::node",
+                "Item::id",
+                "User::id",
+                "User::items",
+                "Orphan::value",
+            ])?),
+            permissions(&["QueryRoot::node", "Item::id", "User::id", "User::items",])?
+        );
+        assert_eq!(
+            schema_permissions.reachable(&permissions(&["User::items", "Item::name"])?),
+            BTreeSet::new()
+        );
+        assert_eq!(
+            schema_permissions.reachable(&permissions(&[
+                "QueryRoot::search",
+                "User::items",
+                "Item::name",
+            ])?),
+            permissions(&["QueryRoot::search", "User::items", "Item::name"])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_coverage() -> anyhow::Result<()> {
+        let schema_permissions = SchemaPermissions::from_sdl(SDL)?;
+        let policy = Policy::from_toml(
+            r#"
+[roles.reader]
+permissions = ["QueryRoot::node", "Item::id", "User::id", "Item::price"]
+
+[roles.searcher]
+permissions = ["QueryRoot::search", "User::items"]
+"#,
+        )?;
+        let report = schema_permissions.coverage(&policy);
+        assert_eq!(
+            report,
+            CoverageReport {
+                unreachable_fields: permissions(&["Item::name", "Orphan::value"])?,
+                unknown_permissions: BTreeMap::from([(
+                    Role::from_str("reader")?,
+                    permissions(&["Item::price"])?
+                )]),
+            }
+        );
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.to_string(),
+            [
+                "unreachable field: Item::name\n",
+                "unreachable field: Orphan::value\n",
+                "unknown permission in role `reader`: Item::price\n",
+            ]
+            .join("")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_sdl_error() {
+        assert!(matches!(
+            SchemaPermissions::from_sdl("type QueryRoot {"),
+            Err(SchemaPermissionsError::InvalidSdl(_))
+        ));
+    }
+}
@@ -1,40 +1,75 @@
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum Permission {
-    Parent1OutputChild1,
-    Parent2OutputChild2,
-    QueryRootParent1,
-    QueryRootParent2,
+#[derive(Debug, thiserror::Error)]
+pub enum ParsePermissionError {
+    #[error("permission `{0}` is not in the form `Type::field`")]
+    InvalidFormat(String),
+    #[error("`{0}` is not a valid GraphQL name")]
+    InvalidName(String),
+}
+
+/// The permission to resolve the field of the type (e.g. `QueryRoot::parent1`).
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Permission {
+    type_name: String,
+    field_name: String,
+}
+
+impl Permission {
+    pub fn new(
+        type_name: impl Into<String>,
+        field_name: impl Into<String>,
+    ) -> Result<Self, ParsePermissionError> {
+        let type_name = type_name.into();
+        let field_name = field_name.into();
+        for name in [&type_name, &field_name] {
+            if !is_name(name) {
+                return Err(ParsePermissionError::InvalidName(name.to_owned()));
+            }
+        }
+        Ok(Self {
+            type_name,
+            field_name,
+        })
+    }
+
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+/// <https://spec.graphql.org/October2021/#Name>
+fn is_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
 impl std::fmt::Display for Permission {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Permission::Parent1OutputChild1 => "Parent1Output::child1",
-            Permission::Parent2OutputChild2 => "Parent2Output::child2",
-            Permission::QueryRootParent1 => "Query::parent1",
-            Permission::QueryRootParent2 => "Query::parent2",
-        }
-        .fmt(f)
+        write!(f, "{}::{}", self.type_name, self.field_name)
     }
 }
 
 impl std::str::FromStr for Permission {
-    // FIXME
-    type Err = std::convert::Infallible;
+    type Err = ParsePermissionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Parent1Output::child1" => Ok(Permission::Parent1OutputChild1),
-            "Parent2Output::child2" => Ok(Permission::Parent2OutputChild2),
-            "QueryRoot::parent1" => Ok(Permission::QueryRootParent1),
-            "QueryRoot::parent2" => Ok(Permission::QueryRootParent2),
-            _ => todo!(),
+        match s.split_once("::") {
+            None => Err(ParsePermissionError::InvalidFormat(s.to_owned())),
+            Some((type_name, field_name)) => Self::new(type_name, field_name),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr as _;
+
     use super::*;
 
     #[test]
@@ -43,4 +78,30 @@ mod tests {
 
         assert_impl::<Permission>();
     }
+
+    #[test]
+    fn test_from_str() -> anyhow::Result<()> {
+        let permission = Permission::from_str("QueryRoot::parent1")?;
+        assert_eq!(permission.type_name(), "QueryRoot");
+        assert_eq!(permission.field_name(), "parent1");
+        assert_eq!(permission.to_string(), "QueryRoot::parent1");
+
+        assert!(matches!(
+            Permission::from_str("QueryRoot.parent1"),
+            Err(ParsePermissionError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            Permission::from_str("QueryRoot::"),
+            Err(ParsePermissionError::InvalidName(_))
+        ));
+        assert!(matches!(
+            Permission::from_str("Query-Root::parent1"),
+            Err(ParsePermissionError::InvalidName(_))
+        ));
+        assert!(matches!(
+            Permission::from_str("QueryRoot::parent1::child1"),
+            Err(ParsePermissionError::InvalidName(_))
+        ));
+        Ok(())
+    }
 }
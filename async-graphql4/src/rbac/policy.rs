@@ -0,0 +1,444 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr as _,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use crate::rbac::{
+    Permission, Role, User, permission::ParsePermissionError, role::ParseRoleError,
+    user::ParseUserError,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyError {
+    #[error("role inheritance cycle `{}`", path.join(" -> "))]
+    InheritanceCycle { path: Vec<String> },
+    #[error("invalid JSON policy")]
+    InvalidJson(#[source] serde_json::Error),
+    #[error("invalid permission in role `{role}`")]
+    InvalidPermission {
+        role: String,
+        #[source]
+        source: ParsePermissionError,
+    },
+    #[error("invalid role")]
+    InvalidRole(#[source] ParseRoleError),
+    #[error("invalid TOML policy")]
+    InvalidToml(#[source] toml::de::Error),
+    #[error("invalid user")]
+    InvalidUser(#[source] ParseUserError),
+    #[error("failed to read policy file {path}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("unknown role `{role}` referenced by `{referenced_by}`")]
+    UnknownRole { role: String, referenced_by: String },
+}
+
+/// The policy file format.
+///
+/// ```toml
+/// [roles.user]
+/// permissions = ["QueryRoot::parent1"]
+///
+/// [roles.admin]
+/// inherits = ["user"]
+/// permissions = ["QueryRoot::parent2"]
+///
+/// [users]
+/// admin123 = ["admin"]
+/// ```
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyFile {
+    #[serde(default)]
+    roles: BTreeMap<String, RoleDefinition>,
+    #[serde(default)]
+    users: BTreeMap<String, BTreeSet<String>>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoleDefinition {
+    #[serde(default)]
+    inherits: BTreeSet<String>,
+    #[serde(default)]
+    permissions: BTreeSet<String>,
+}
+
+/// The roles of the users and the permissions of the roles (including the inherited ones).
+#[derive(Debug, Eq, PartialEq)]
+pub struct Policy {
+    role_to_permissions: BTreeMap<Role, BTreeSet<Permission>>,
+    user_to_roles: BTreeMap<User, BTreeSet<Role>>,
+}
+
+impl Policy {
+    pub fn from_json(s: &str) -> Result<Self, PolicyError> {
+        Self::from_file(serde_json::from_str(s).map_err(PolicyError::InvalidJson)?)
+    }
+
+    pub fn from_toml(s: &str) -> Result<Self, PolicyError> {
+        Self::from_file(toml::from_str(s).map_err(PolicyError::InvalidToml)?)
+    }
+
+    /// Reads the policy file. The file is parsed as JSON if the extension is `.json`,
+    /// otherwise as TOML.
+    pub fn read(path: &Path) -> Result<Self, PolicyError> {
+        let s = std::fs::read_to_string(path).map_err(|source| PolicyError::Read {
+            path: path.to_owned(),
+            source,
+        })?;
+        match path.extension().and_then(|it| it.to_str()) {
+            Some("json") => Self::from_json(&s),
+            _ => Self::from_toml(&s),
+        }
+    }
+
+    pub fn permissions(&self, role: &Role) -> Option<&BTreeSet<Permission>> {
+        self.role_to_permissions.get(role)
+    }
+
+    pub fn roles(&self, user: &User) -> Option<&BTreeSet<Role>> {
+        self.user_to_roles.get(user)
+    }
+
+    /// Returns the roles and their permissions (including the inherited ones).
+    pub fn role_permissions(&self) -> impl Iterator<Item = (&Role, &BTreeSet<Permission>)> {
+        self.role_to_permissions.iter()
+    }
+
+    fn from_file(file: PolicyFile) -> Result<Self, PolicyError> {
+        let mut role_to_permissions = BTreeMap::new();
+        for role in file.roles.keys() {
+            let permissions = resolve_permissions(&file.roles, role, &mut vec![])?;
+            role_to_permissions.insert(
+                Role::from_str(role).map_err(PolicyError::InvalidRole)?,
+                permissions,
+            );
+        }
+
+        let mut user_to_roles = BTreeMap::new();
+        for (user, roles) in file.users {
+            let roles = roles
+                .into_iter()
+                .map(|role| {
+                    if !file.roles.contains_key(&role) {
+                        return Err(PolicyError::UnknownRole {
+                            role,
+                            referenced_by: user.clone(),
+                        });
+                    }
+                    Role::from_str(&role).map_err(PolicyError::InvalidRole)
+                })
+                .collect::<Result<BTreeSet<Role>, PolicyError>>()?;
+            user_to_roles.insert(
+                User::from_str(&user).map_err(PolicyError::InvalidUser)?,
+                roles,
+            );
+        }
+
+        Ok(Self {
+            role_to_permissions,
+            user_to_roles,
+        })
+    }
+}
+
+/// Returns the permissions of the role and the roles it inherits (transitively).
+fn resolve_permissions(
+    roles: &BTreeMap<String, RoleDefinition>,
+    role: &str,
+    visiting: &mut Vec<String>,
+) -> Result<BTreeSet<Permission>, PolicyError> {
+    if let Some(index) = visiting.iter().position(|it| it == role) {
+        let mut path = visiting[index..].to_vec();
+        path.push(role.to_owned());
+        return Err(PolicyError::InheritanceCycle { path });
+    }
+    let definition = roles.get(role).ok_or_else(|| PolicyError::UnknownRole {
+        role: role.to_owned(),
+        referenced_by: visiting.last().cloned().unwrap_or_default(),
+    })?;
+
+    let mut permissions = definition
+        .permissions
+        .iter()
+        .map(|it| Permission::from_str(it))
+        .collect::<Result<BTreeSet<Permission>, ParsePermissionError>>()
+        .map_err(|source| PolicyError::InvalidPermission {
+            role: role.to_owned(),
+            source,
+        })?;
+    visiting.push(role.to_owned());
+    for inherited in &definition.inherits {
+        permissions.extend(resolve_permissions(roles, inherited, visiting)?);
+    }
+    visiting.pop();
+    Ok(permissions)
+}
+
+/// The policy loaded from the file that can be reloaded without restarting the server.
+///
+/// A policy that fails to load never replaces the current one.
+pub struct PolicyStore {
+    path: PathBuf,
+    policy: RwLock<Arc<Policy>>,
+    modified: Mutex<Option<SystemTime>>,
+}
+
+impl PolicyStore {
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, PolicyError> {
+        let path = path.into();
+        let modified = modified(&path);
+        let policy = Policy::read(&path)?;
+        Ok(Self {
+            path,
+            policy: RwLock::new(Arc::new(policy)),
+            modified: Mutex::new(modified),
+        })
+    }
+
+    pub fn policy(&self) -> Arc<Policy> {
+        self.policy
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Reads the policy file again and replaces the current policy.
+    pub fn reload(&self) -> Result<(), PolicyError> {
+        let modified = modified(&self.path);
+        let policy = Policy::read(&self.path)?;
+        *self.policy.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(policy);
+        *self.modified.lock().unwrap_or_else(|e| e.into_inner()) = modified;
+        Ok(())
+    }
+
+    /// Reloads the policy whenever the modification time of the file changes.
+    ///
+    /// A failed reload is passed to `on_error` and is not retried until the file changes again.
+    /// The task stops when the store is dropped.
+    pub fn watch<F>(
+        self: &Arc<Self>,
+        interval: Duration,
+        on_error: F,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(PolicyError) + Send + 'static,
+    {
+        let store = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(store) = store.upgrade() else {
+                    break;
+                };
+                let modified = modified(&store.path);
+                if modified == *store.modified.lock().unwrap_or_else(|e| e.into_inner()) {
+                    continue;
+                }
+                if let Err(e) = store.reload() {
+                    *store.modified.lock().unwrap_or_else(|e| e.into_inner()) = modified;
+                    on_error(e);
+                }
+            }
+        })
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|it| it.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+[roles.user]
+permissions = ["QueryRoot::parent1"]
+
+[roles.admin]
+inherits = ["user"]
+permissions = ["QueryRoot::parent2"]
+
+[users]
+admin123 = ["admin"]
+user123 = ["user"]
+"#;
+
+    #[test]
+    fn test_impls() {
+        fn assert_impl<T: Send + Sync>() {}
+
+        assert_impl::<Policy>();
+        assert_impl::<PolicyStore>();
+    }
+
+    #[test]
+    fn test_from_toml() -> anyhow::Result<()> {
+        let policy = Policy::from_toml(POLICY)?;
+        assert_eq!(
+            policy.permissions(&Role::from_str("admin")?),
+            Some(&BTreeSet::from([
+                Permission::from_str("QueryRoot::parent1")?,
+                Permission::from_str("QueryRoot::parent2")?,
+            ]))
+        );
+        assert_eq!(
+            policy.permissions(&Role::from_str("user")?),
+            Some(&BTreeSet::from([Permission::from_str(
+                "QueryRoot::parent1"
+            )?]))
+        );
+        assert_eq!(policy.permissions(&Role::from_str("unknown")?), None);
+        assert_eq!(
+            policy.roles(&User::from_str("admin123")?),
+            Some(&BTreeSet::from([Role::from_str("admin")?]))
+        );
+        assert_eq!(policy.roles(&User::from_str("unknown")?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_json() -> anyhow::Result<()> {
+        let policy = Policy::from_json(
+            &serde_json::json!({
+                "roles": {
+                    "user": { "permissions": ["QueryRoot::parent1"] },
+                    "admin": { "inherits": ["user"], "permissions": ["QueryRoot::parent2"] },
+                },
+                "users": { "admin123": ["admin"], "user123": ["user"] },
+            })
+            .to_string(),
+        )?;
+        assert_eq!(policy, Policy::from_toml(POLICY)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Policy::from_toml("[roles.user]\npermission = []"),
+            Err(PolicyError::InvalidToml(_))
+        ));
+        assert!(matches!(
+            Policy::from_toml("[roles.user]\npermissions = [\"QueryRoot.parent1\"]"),
+            Err(PolicyError::InvalidPermission { role, .. }) if role == "user"
+        ));
+        assert!(matches!(
+            Policy::from_toml("[roles.admin]\ninherits = [\"user\"]"),
+            Err(PolicyError::UnknownRole { role, referenced_by }) if role == "user" && referenced_by == "admin"
+        ));
+        assert!(matches!(
+            Policy::from_toml("[roles.a]\ninherits = [\"b\"]\n[roles.b]\ninherits = [\"a\"]"),
+            Err(PolicyError::InheritanceCycle { path }) if path == ["a", "b", "a"]
+        ));
+        assert_eq!(
+            Policy::from_toml(
+                "[roles.a]\ninherits = [\"b\"]\n[roles.b]\ninherits = [\"c\"]\n[roles.c]\ninherits = [\"b\"]"
+            )
+            .unwrap_err()
+            .to_string(),
+            "role inheritance cycle `b -> c -> b`"
+        );
+        assert!(matches!(
+            Policy::from_toml("[roles.user]\n[users]\nuser123 = [\"admin\"]"),
+            Err(PolicyError::UnknownRole { role, referenced_by }) if role == "admin" && referenced_by == "user123"
+        ));
+        assert!(matches!(
+            Policy::from_toml("[roles.\"ad min\"]"),
+            Err(PolicyError::InvalidRole(_))
+        ));
+        assert!(matches!(
+            Policy::read(Path::new("/nonexistent/policy.toml")),
+            Err(PolicyError::Read { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_policy_store() -> anyhow::Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("async-graphql4-policy-{}.toml", std::process::id()));
+        std::fs::write(&path, POLICY)?;
+        let store = Arc::new(PolicyStore::load(&path)?);
+        let admin = Role::from_str("admin")?;
+        let parent1 = Permission::from_str("QueryRoot::parent1")?;
+        assert!(
+            store
+                .policy()
+                .permissions(&admin)
+                .unwrap()
+                .contains(&parent1)
+        );
+
+        // invalid policy keeps the current one
+        std::fs::write(&path, "[roles.admin]\ninherits = [\"unknown\"]")?;
+        assert!(store.reload().is_err());
+        assert!(
+            store
+                .policy()
+                .permissions(&admin)
+                .unwrap()
+                .contains(&parent1)
+        );
+
+        std::fs::write(&path, "[roles.admin]\npermissions = []")?;
+        store.reload()?;
+        assert!(
+            !store
+                .policy()
+                .permissions(&admin)
+                .unwrap()
+                .contains(&parent1)
+        );
+
+        // watch
+        let (error_sender, mut error_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let handle = store.watch(Duration::from_millis(10), move |e| {
+            let _ = error_sender.send(e);
+        });
+        let file = std::fs::File::options().write(true).open(&path)?;
+
+        // a failed reload is reported and keeps the current policy
+        std::fs::write(&path, "[roles.admin]\ninherits = [\"unknown\"]")?;
+        // ensure that the modification time changes
+        file.set_modified(SystemTime::now() + Duration::from_secs(1))?;
+        let e = tokio::time::timeout(Duration::from_secs(1), error_receiver.recv()).await?;
+        assert!(matches!(e, Some(PolicyError::UnknownRole { .. })));
+        assert!(
+            !store
+                .policy()
+                .permissions(&admin)
+                .unwrap()
+                .contains(&parent1)
+        );
+
+        std::fs::write(&path, POLICY)?;
+        file.set_modified(SystemTime::now() + Duration::from_secs(2))?;
+        let mut reloaded = false;
+        for _ in 0..100 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            if store
+                .policy()
+                .permissions(&admin)
+                .unwrap()
+                .contains(&parent1)
+            {
+                reloaded = true;
+                break;
+            }
+        }
+        assert!(reloaded);
+
+        drop(store);
+        tokio::time::timeout(Duration::from_secs(1), handle).await??;
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
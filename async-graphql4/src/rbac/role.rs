@@ -1,21 +1,35 @@
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum Role {
-    Admin,
-    User,
-}
+#[derive(Debug, thiserror::Error)]
+#[error("`{0}` is not a valid role name")]
+pub struct ParseRoleError(String);
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Role(String);
 
 impl std::fmt::Display for Role {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Role::Admin => "admin",
-            Role::User => "user",
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = ParseRoleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty()
+            || !s
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(ParseRoleError(s.to_owned()));
         }
-        .fmt(f)
+        Ok(Role(s.to_owned()))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr as _;
+
     use super::*;
 
     #[test]
@@ -24,4 +38,12 @@ mod tests {
 
         assert_impl::<Role>();
     }
+
+    #[test]
+    fn test_from_str() -> anyhow::Result<()> {
+        assert_eq!(Role::from_str("admin")?.to_string(), "admin");
+        assert!(Role::from_str("").is_err());
+        assert!(Role::from_str("ad min").is_err());
+        Ok(())
+    }
 }
@@ -1,3 +1,7 @@
+#[derive(Debug, thiserror::Error)]
+#[error("user must not be empty")]
+pub struct ParseUserError;
+
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct User(String);
 
@@ -8,16 +12,20 @@ impl std::fmt::Display for User {
 }
 
 impl std::str::FromStr for User {
-    // FIXME
-    type Err = std::convert::Infallible;
+    type Err = ParseUserError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseUserError);
+        }
         Ok(User(s.to_owned()))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr as _;
+
     use super::*;
 
     #[test]
@@ -26,4 +34,10 @@ mod tests {
 
         assert_impl::<User>();
     }
+
+    #[test]
+    fn test_from_str() {
+        assert!(User::from_str("admin123").is_ok());
+        assert!(User::from_str("").is_err());
+    }
 }
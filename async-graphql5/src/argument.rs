@@ -0,0 +1,104 @@
+pub use async_graphql_argument::{Arguments, operation_variables, resolve_arguments};
+
+/// The argument rejected by the policy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArgumentDenied {
+    message: String,
+    path: Vec<String>,
+}
+
+impl ArgumentDenied {
+    pub fn new(message: impl Into<String>, path: &[&str]) -> Self {
+        Self {
+            message: message.into(),
+            path: path.iter().map(|it| (*it).to_owned()).collect(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The path of the argument (e.g. `["input", "id"]`).
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{Name, Value, Variables, extensions::ResolveInfo, indexmap::IndexMap};
+
+    use super::*;
+
+    #[test]
+    fn test_impls() {
+        fn assert_impl<T: Send + Sync>() {}
+
+        assert_impl::<ArgumentDenied>();
+        assert_impl::<Arguments>();
+    }
+
+    #[tokio::test]
+    async fn test_resolve_arguments() -> anyhow::Result<()> {
+        struct Query;
+
+        #[derive(async_graphql::InputObject)]
+        struct Input {
+            #[graphql(default = 10)]
+            limit: i32,
+            ids: Vec<String>,
+        }
+
+        #[async_graphql::Object]
+        impl Query {
+            async fn items(&self, input: Input, #[graphql(default)] offset: i32) -> i32 {
+                input.limit + offset
+            }
+        }
+
+        struct RecordingPolicy(std::sync::Mutex<Vec<Arguments>>);
+
+        #[crate::extension::async_trait]
+        impl crate::extension::ArgumentPolicy for RecordingPolicy {
+            async fn check(
+                &self,
+                _context: &async_graphql::extensions::ExtensionContext<'_>,
+                _info: &ResolveInfo<'_>,
+                arguments: &Arguments,
+            ) -> Result<(), ArgumentDenied> {
+                self.0.lock().unwrap().push(arguments.clone());
+                Ok(())
+            }
+        }
+
+        let policy = std::sync::Arc::new(RecordingPolicy(std::sync::Mutex::new(vec![])));
+        let schema = async_graphql::Schema::build(
+            Query,
+            async_graphql::EmptyMutation,
+            async_graphql::EmptySubscription,
+        )
+        .extension(crate::extension::ArgumentExtensionFactory::from_arc(
+            policy.clone(),
+        ))
+        .finish();
+        let request =
+            async_graphql::Request::new("query($id: String!) { items(input: { ids: $id }) }")
+                .variables(Variables::from_json(serde_json::json!({ "id": "abc" })));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let arguments = policy.0.lock().unwrap().clone();
+        assert_eq!(
+            arguments,
+            [Arguments::from(IndexMap::from([
+                (
+                    Name::new("input"),
+                    Value::from_json(serde_json::json!({ "limit": 10, "ids": ["abc"] }))?
+                ),
+                (Name::new("offset"), Value::from(0)),
+            ]))]
+        );
+        Ok(())
+    }
+}
@@ -2,7 +2,9 @@
 pub use async_graphql;
 pub use async_trait::async_trait;
 
+/// Creates the error of the field with the code in `extensions.code` (e.g. `FORBIDDEN`).
 pub fn error_from_info(
+    code: &str,
     message: impl Into<String>,
     info: &async_graphql::extensions::ResolveInfo<'_>,
 ) -> async_graphql::ServerError {
@@ -12,19 +14,42 @@ pub fn error_from_info(
         .into_iter()
         .map(|it| async_graphql::PathSegment::Field(it))
         .collect::<Vec<async_graphql::PathSegment>>();
+    let mut extensions = async_graphql::ErrorExtensionValues::default();
+    extensions.set("code", code);
     async_graphql::ServerError {
         message: message.into(),
         source: None,
         locations: vec![info.field.name.pos],
         path,
-        extensions: None,
+        extensions: Some(extensions),
     }
 }
 
-pub struct MyExtension;
+/// Approves or denies the field based on the values of the arguments.
+#[async_trait]
+pub trait ArgumentPolicy {
+    async fn check(
+        &self,
+        context: &async_graphql::extensions::ExtensionContext<'_>,
+        info: &async_graphql::extensions::ResolveInfo<'_>,
+        arguments: &crate::argument::Arguments,
+    ) -> Result<(), crate::argument::ArgumentDenied>;
+}
+
+/// The extension created for each request.
+///
+/// The arguments are resolved with the variables of the request (including the default values
+/// of the variable definitions) before they are passed to the policy.
+pub struct ArgumentExtension<T> {
+    inner: std::sync::Arc<T>,
+    operation_name: std::sync::OnceLock<Option<String>>,
+    variables: std::sync::OnceLock<async_graphql::Variables>,
+}
 
 #[async_trait]
-impl async_graphql::extensions::Extension for MyExtension {
+impl<T: ArgumentPolicy + Send + Sync + 'static> async_graphql::extensions::Extension
+    for ArgumentExtension<T>
+{
     async fn request(
         &self,
         ctx: &async_graphql::extensions::ExtensionContext<'_>,
@@ -33,46 +58,91 @@ impl async_graphql::extensions::Extension for MyExtension {
         next.run(ctx).await
     }
 
+    async fn prepare_request(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        request: async_graphql::Request,
+        next: async_graphql::extensions::NextPrepareRequest<'_>,
+    ) -> async_graphql::ServerResult<async_graphql::Request> {
+        let _ = self.operation_name.set(request.operation_name.clone());
+        next.run(ctx, request).await
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        query: &str,
+        variables: &async_graphql::Variables,
+        next: async_graphql::extensions::NextParseQuery<'_>,
+    ) -> async_graphql::ServerResult<async_graphql::parser::types::ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let operation_name = self.operation_name.get().and_then(|it| it.as_deref());
+        let _ = self.variables.set(crate::argument::operation_variables(
+            &document,
+            operation_name,
+            variables,
+        ));
+        Ok(document)
+    }
+
     async fn resolve(
         &self,
         context: &async_graphql::extensions::ExtensionContext<'_>,
         info: async_graphql::extensions::ResolveInfo<'_>,
         next: async_graphql::extensions::NextResolve<'_>,
     ) -> async_graphql::ServerResult<Option<async_graphql::Value>> {
-        match info.parent_type {
-            "Parent2Output" => match info.name {
-                "child2" => {
-                    for (name, value) in &info.field.arguments {
-                        assert_eq!(name.node.as_str(), "input");
-                        match &value.node {
-                            async_graphql_value::Value::Variable(v) => {
-                                println!("variable = {}", v);
-                                todo!()
-                            }
-                            async_graphql_value::Value::Object(o) => {
-                                let v: &async_graphql_value::Value = o.get("id").unwrap();
-                                assert!(
-                                    v.to_string() == r#""abc""#
-                                        || v.to_string() == r#""def""#
-                                        || v.to_string() == r#"$id"#
-                                );
-                            }
-                            _ => unreachable!(),
-                        }
-                    }
-                    return next.run(context, info).await;
-                }
-                _ => return next.run(context, info).await,
-            },
-            _ => return next.run(context, info).await,
+        if info.is_for_introspection {
+            return next.run(context, info).await;
+        }
+        let empty = async_graphql::Variables::default();
+        let variables = self.variables.get().unwrap_or(&empty);
+        let arguments =
+            crate::argument::resolve_arguments(&context.schema_env.registry, &info, variables);
+        match self.inner.check(context, &info, &arguments).await {
+            Ok(()) => next.run(context, info).await,
+            Err(denied) => {
+                let mut error = error_from_info("FORBIDDEN", denied.message(), &info);
+                let extensions = error.extensions.get_or_insert_with(Default::default);
+                extensions.set(
+                    "argumentPath",
+                    async_graphql::Value::List(
+                        denied
+                            .path()
+                            .iter()
+                            .map(|it| async_graphql::Value::String(it.clone()))
+                            .collect(),
+                    ),
+                );
+                Err(error)
+            }
         }
     }
 }
 
-pub struct MyExtensionFactory;
+pub struct ArgumentExtensionFactory<T> {
+    inner: std::sync::Arc<T>,
+}
+
+impl<T: ArgumentPolicy + Send + Sync + 'static> ArgumentExtensionFactory<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner: std::sync::Arc::new(inner),
+        }
+    }
+
+    pub fn from_arc(inner: std::sync::Arc<T>) -> Self {
+        Self { inner }
+    }
+}
 
-impl async_graphql::extensions::ExtensionFactory for MyExtensionFactory {
+impl<T: ArgumentPolicy + Send + Sync + 'static> async_graphql::extensions::ExtensionFactory
+    for ArgumentExtensionFactory<T>
+{
     fn create(&self) -> std::sync::Arc<dyn async_graphql::extensions::Extension> {
-        std::sync::Arc::new(MyExtension)
+        std::sync::Arc::new(ArgumentExtension {
+            inner: self.inner.clone(),
+            operation_name: std::sync::OnceLock::new(),
+            variables: std::sync::OnceLock::new(),
+        })
     }
 }
@@ -1,3 +1,4 @@
+mod argument;
 mod extension;
 mod schema;
 
@@ -5,6 +6,28 @@ struct MyData {
     user: String,
 }
 
+struct MyPolicy;
+
+#[extension::async_trait]
+impl extension::ArgumentPolicy for MyPolicy {
+    async fn check(
+        &self,
+        _context: &async_graphql::extensions::ExtensionContext<'_>,
+        info: &async_graphql::extensions::ResolveInfo<'_>,
+        arguments: &argument::Arguments,
+    ) -> Result<(), argument::ArgumentDenied> {
+        if let ("Parent2Output", "child2") = (info.parent_type, info.name) {
+            let path = ["input", "id"];
+            match arguments.get(&path) {
+                Some(async_graphql::Value::String(id))
+                    if ["abc", "def", "ghi"].contains(&id.as_str()) => {}
+                _ => return Err(argument::ArgumentDenied::new("argument not allowed", &path)),
+            }
+        }
+        Ok(())
+    }
+}
+
 fn main() {}
 
 #[cfg(test)]
@@ -30,17 +53,78 @@ mod tests {
         Ok(())
     }
 
-    async fn test_graphql_request(
-        request: impl Into<async_graphql::Request>,
-        expected_response: &str,
-    ) -> anyhow::Result<()> {
-        let schema = async_graphql::Schema::build(
+    #[tokio::test]
+    async fn test_variables() -> anyhow::Result<()> {
+        let request = async_graphql::Request::new(
+            r#"query myQuery($input: Child2Input!) { parent2 { child2(input: $input) } }"#,
+        )
+        .variables(async_graphql::Variables::from_json(serde_json::json!({
+            "input": { "id": "ghi" },
+        })));
+        let response = schema().execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let request = async_graphql::Request::new(
+            r#"query myQuery($id: String! = "abc") { parent2 { child2(input: { id: $id }) } }"#,
+        );
+        let response = schema().execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let request = async_graphql::Request::new(
+            r#"query a($id: String! = "abc") { parent2 { child2(input: { id: $id }) } }
+query b($id: String! = "xyz") { parent2 { child2(input: { id: $id }) } }"#,
+        )
+        .operation_name("b");
+        let response = schema().execute(request).await;
+        assert_eq!(response.errors.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_denied() -> anyhow::Result<()> {
+        let request = async_graphql::Request::new(
+            r#"query myQuery($input: Child2Input!) { parent2 { child2(input: $input) } }"#,
+        )
+        .variables(async_graphql::Variables::from_json(serde_json::json!({
+            "input": { "id": "xyz" },
+        })));
+        let response = schema().execute(request).await;
+        assert_eq!(
+            serde_json::to_value(&response)?,
+            serde_json::json!({
+                "data": null,
+                "errors": [
+                    {
+                        "extensions": { "argumentPath": ["input", "id"], "code": "FORBIDDEN" },
+                        "locations": [{ "column": 49, "line": 1 }],
+                        "message": "argument not allowed",
+                        "path": ["parent2", "child2"]
+                    }
+                ]
+            })
+        );
+        Ok(())
+    }
+
+    fn schema() -> async_graphql::Schema<
+        schema::QueryRoot,
+        async_graphql::EmptyMutation,
+        async_graphql::EmptySubscription,
+    > {
+        async_graphql::Schema::build(
             schema::QueryRoot,
             async_graphql::EmptyMutation,
             async_graphql::EmptySubscription,
         )
-        .extension(extension::MyExtensionFactory)
-        .finish();
+        .extension(extension::ArgumentExtensionFactory::new(MyPolicy))
+        .finish()
+    }
+
+    async fn test_graphql_request(
+        request: impl Into<async_graphql::Request>,
+        expected_response: &str,
+    ) -> anyhow::Result<()> {
+        let schema = schema();
         let request: async_graphql::Request = request.into();
         let request = request.variables(async_graphql::Variables::from_json(serde_json::json!({
             "id": "ghi",
@@ -0,0 +1,261 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+/// The identity of the client. Put it in the request data (`Request::data`).
+///
+/// Requests without the identity share the limits of the anonymous client (`""`).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ClientId(String);
+
+impl ClientId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+/// The limits of the queries of a client.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostLimits {
+    /// The maximum complexity of a query.
+    pub max_complexity: usize,
+    /// The maximum depth of a query.
+    pub max_depth: usize,
+    /// The maximum cost (the sum of the complexities) the client can spend at once.
+    pub capacity: f64,
+    /// The cost restored per second.
+    pub refill_per_second: f64,
+}
+
+/// The token bucket of a client.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn new(limits: &CostLimits, now: Instant) -> Self {
+        Self {
+            tokens: limits.capacity,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, limits: &CostLimits, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limits.refill_per_second).min(limits.capacity);
+        self.updated_at = now;
+    }
+
+    /// Takes the cost from the bucket. Nothing is taken if the tokens are not enough.
+    fn take(&mut self, limits: &CostLimits, cost: f64, now: Instant) -> bool {
+        self.refill(limits, now);
+        if self.tokens < cost {
+            return false;
+        }
+        self.tokens -= cost;
+        true
+    }
+}
+
+/// The cost of the request reported in the response `extensions.cost`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Cost {
+    complexity: usize,
+    depth: usize,
+    remaining: f64,
+}
+
+struct State {
+    default_limits: CostLimits,
+    client_limits: HashMap<ClientId, CostLimits>,
+    buckets: Mutex<HashMap<ClientId, Bucket>>,
+}
+
+impl State {
+    fn limits(&self, client_id: &ClientId) -> &CostLimits {
+        self.client_limits
+            .get(client_id)
+            .unwrap_or(&self.default_limits)
+    }
+
+    /// Removes the full buckets, which are the same as the new ones. Only the clients spending
+    /// the cost keep their buckets.
+    fn remove_full_buckets(&self, buckets: &mut HashMap<ClientId, Bucket>, now: Instant) {
+        buckets.retain(|client_id, bucket| {
+            let limits = self.limits(client_id);
+            bucket.refill(limits, now);
+            bucket.tokens < limits.capacity
+        });
+    }
+}
+
+/// The extension created for each request.
+pub struct CostLimitExtension {
+    state: Arc<State>,
+    cost: Mutex<Option<Cost>>,
+}
+
+#[async_trait::async_trait]
+impl async_graphql::extensions::Extension for CostLimitExtension {
+    async fn request(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        next: async_graphql::extensions::NextRequest<'_>,
+    ) -> async_graphql::Response {
+        let mut response = next.run(ctx).await;
+        if let Some(cost) = *self.cost.lock().unwrap_or_else(|e| e.into_inner()) {
+            response.extensions.insert(
+                "cost".to_owned(),
+                async_graphql::value!({
+                    "complexity": cost.complexity,
+                    "depth": cost.depth,
+                    "remaining": cost.remaining.floor() as u64,
+                }),
+            );
+        }
+        response
+    }
+
+    async fn validation(
+        &self,
+        ctx: &async_graphql::extensions::ExtensionContext<'_>,
+        next: async_graphql::extensions::NextValidation<'_>,
+    ) -> Result<async_graphql::ValidationResult, Vec<async_graphql::ServerError>> {
+        let validation_result = next.run(ctx).await?;
+        let client_id = ctx.data_opt::<ClientId>().cloned().unwrap_or_default();
+        let limits = self.state.limits(&client_id);
+        let now = Instant::now();
+
+        let mut buckets = self.state.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        self.state.remove_full_buckets(&mut buckets, now);
+        let bucket = buckets
+            .entry(client_id)
+            .or_insert_with(|| Bucket::new(limits, now));
+        bucket.refill(limits, now);
+        let mut error = None;
+        if validation_result.complexity > limits.max_complexity {
+            error = Some(format!(
+                "query is too complex: {} > {}",
+                validation_result.complexity, limits.max_complexity
+            ));
+        } else if validation_result.depth > limits.max_depth {
+            error = Some(format!(
+                "query is nested too deep: {} > {}",
+                validation_result.depth, limits.max_depth
+            ));
+        } else if !bucket.take(limits, validation_result.complexity as f64, now) {
+            error = Some("query cost budget exceeded".to_owned());
+        }
+        // the rejected query is also reported with the remaining budget
+        *self.cost.lock().unwrap_or_else(|e| e.into_inner()) = Some(Cost {
+            complexity: validation_result.complexity,
+            depth: validation_result.depth,
+            remaining: bucket.tokens,
+        });
+
+        match error {
+            None => Ok(validation_result),
+            Some(message) => Err(vec![async_graphql::ServerError::new(message, None)]),
+        }
+    }
+}
+
+pub struct CostLimitExtensionFactory {
+    state: Arc<State>,
+}
+
+impl CostLimitExtensionFactory {
+    /// `client_limits` overrides the limits of the clients.
+    pub fn new(default_limits: CostLimits, client_limits: HashMap<ClientId, CostLimits>) -> Self {
+        Self {
+            state: Arc::new(State {
+                default_limits,
+                client_limits,
+                buckets: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+impl async_graphql::extensions::ExtensionFactory for CostLimitExtensionFactory {
+    fn create(&self) -> Arc<dyn async_graphql::extensions::Extension> {
+        Arc::new(CostLimitExtension {
+            state: self.state.clone(),
+            cost: Mutex::new(None),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const LIMITS: CostLimits = CostLimits {
+        max_complexity: 10,
+        max_depth: 3,
+        capacity: 10.0,
+        refill_per_second: 2.0,
+    };
+
+    #[test]
+    fn test_impls() {
+        fn assert_impl<T: Send + Sync>() {}
+
+        assert_impl::<ClientId>();
+        assert_impl::<CostLimitExtension>();
+        assert_impl::<CostLimitExtensionFactory>();
+    }
+
+    #[test]
+    fn test_bucket() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(&LIMITS, now);
+        assert!(bucket.take(&LIMITS, 6.0, now));
+        assert_eq!(bucket.tokens, 4.0);
+        assert!(!bucket.take(&LIMITS, 6.0, now));
+        assert_eq!(bucket.tokens, 4.0);
+
+        let now = now + Duration::from_secs(1);
+        assert!(bucket.take(&LIMITS, 6.0, now));
+        assert_eq!(bucket.tokens, 0.0);
+
+        // never exceeds the capacity
+        let now = now + Duration::from_secs(60);
+        bucket.refill(&LIMITS, now);
+        assert_eq!(bucket.tokens, 10.0);
+    }
+
+    #[test]
+    fn test_remove_full_buckets() {
+        let state = State {
+            default_limits: LIMITS,
+            client_limits: HashMap::new(),
+            buckets: Mutex::new(HashMap::new()),
+        };
+        let now = Instant::now();
+        let mut buckets = HashMap::new();
+        for (id, cost) in [("a", 6.0), ("b", 1.0)] {
+            let mut bucket = Bucket::new(&LIMITS, now);
+            assert!(bucket.take(&LIMITS, cost, now));
+            buckets.insert(ClientId::new(id), bucket);
+        }
+
+        let now = now + Duration::from_secs(1);
+        state.remove_full_buckets(&mut buckets, now);
+        assert_eq!(
+            buckets.keys().collect::<Vec<&ClientId>>(),
+            [&ClientId::new("a")]
+        );
+        assert_eq!(buckets[&ClientId::new("a")].tokens, 6.0);
+
+        let now = now + Duration::from_secs(60);
+        state.remove_full_buckets(&mut buckets, now);
+        assert!(buckets.is_empty());
+    }
+}
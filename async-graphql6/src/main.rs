@@ -1,3 +1,5 @@
+mod extension;
+
 fn main() {}
 
 #[cfg(test)]
@@ -106,6 +108,89 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_cost_limit_extension() -> anyhow::Result<()> {
+        let limits = crate::extension::CostLimits {
+            max_complexity: 10,
+            max_depth: 3,
+            capacity: 20.0,
+            refill_per_second: 0.0,
+        };
+        let schema = async_graphql::Schema::build(
+            MyObject,
+            async_graphql::EmptyMutation,
+            async_graphql::EmptySubscription,
+        )
+        .extension(crate::extension::CostLimitExtensionFactory::new(
+            limits,
+            std::collections::HashMap::from([(
+                crate::extension::ClientId::new("dashboard"),
+                crate::extension::CostLimits {
+                    max_depth: 2,
+                    capacity: 5.0,
+                    ..limits
+                },
+            )]),
+        ))
+        .finish();
+        async fn execute(
+            schema: &async_graphql::Schema<
+                MyObject,
+                async_graphql::EmptyMutation,
+                async_graphql::EmptySubscription,
+            >,
+            client_id: &str,
+            request: &str,
+        ) -> anyhow::Result<serde_json::Value> {
+            let request = async_graphql::Request::new(request)
+                .data(crate::extension::ClientId::new(client_id));
+            let response = schema.execute(request).await;
+            Ok(serde_json::to_value(&response)?)
+        }
+
+        assert_eq!(
+            execute(&schema, "app", "{ object { int, object { int } } }").await?,
+            serde_json::json!({
+                "data": { "object": { "int": 123, "object": { "int": 123 } } },
+                "extensions": { "cost": { "complexity": 4, "depth": 3, "remaining": 16 } }
+            })
+        );
+        // the depth limit of the client
+        assert_eq!(
+            execute(&schema, "dashboard", "{ object { int, object { int } } }").await?,
+            serde_json::json!({
+                "data": null,
+                "errors": [{ "message": "query is nested too deep: 3 > 2" }],
+                "extensions": { "cost": { "complexity": 4, "depth": 3, "remaining": 5 } }
+            })
+        );
+        assert_eq!(
+            execute(&schema, "dashboard", "{ object { int } }").await?,
+            serde_json::json!({
+                "data": { "object": { "int": 123 } },
+                "extensions": { "cost": { "complexity": 2, "depth": 2, "remaining": 3 } }
+            })
+        );
+        assert_eq!(
+            execute(&schema, "dashboard", "{ object { int } a: object { int } }").await?,
+            serde_json::json!({
+                "data": null,
+                "errors": [{ "message": "query cost budget exceeded" }],
+                "extensions": { "cost": { "complexity": 4, "depth": 2, "remaining": 3 } }
+            })
+        );
+        // the budget is per client
+        assert_eq!(
+            execute(&schema, "app", "{ object { complex } }").await?,
+            serde_json::json!({
+                "data": null,
+                "errors": [{ "message": "query is too complex: 11 > 10" }],
+                "extensions": { "cost": { "complexity": 11, "depth": 2, "remaining": 16 } }
+            })
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_my_extension() -> anyhow::Result<()> {
         struct MyExtensionFactory;
@@ -153,7 +238,7 @@ mod tests {
             let response: async_graphql::Response = schema.execute(request).await;
             assert_eq!(
                 serde_json::Value::from_str(&serde_json::to_string(&response)?)?,
-                serde_json::Value::from_str(&expected_response)?
+                serde_json::Value::from_str(expected_response)?
             );
             Ok(())
         }
@@ -0,0 +1,284 @@
+//! Correlation IDs and a `tracing` span per request.
+//!
+//! `RequestTraceLayer` propagates `X-Request-Id` and W3C `traceparent` (or assigns new ones),
+//! puts them into the request headers and extensions, runs the request in a `request` span with
+//! the method, the matched route, the status and the latency, and echoes them in the response.
+//!
+//! ```ignore
+//! let app = axum::Router::new()
+//!     .route("/users/{user_id}", axum::routing::get(handler))
+//!     .layer(axum_request_trace::RequestTraceLayer);
+//! // in the handler
+//! async fn handler(axum::Extension(request_id): axum::Extension<axum_request_trace::RequestId>) {}
+//! ```
+
+use tracing::Instrument as _;
+
+pub static X_REQUEST_ID: axum::http::HeaderName =
+    axum::http::HeaderName::from_static("x-request-id");
+pub static TRACEPARENT: axum::http::HeaderName = axum::http::HeaderName::from_static("traceparent");
+
+/// The `X-Request-Id` of the request. Defaults to the trace ID.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Accepts 1 to 128 visible ASCII characters.
+    fn parse(s: &str) -> Option<Self> {
+        (!s.is_empty() && s.len() <= 128 && s.bytes().all(|it| it.is_ascii_graphic()))
+            .then(|| Self(s.to_owned()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// The W3C `traceparent` (version `00`) of this server.
+///
+/// <https://www.w3.org/TR/trace-context/#traceparent-header>
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TraceParent {
+    pub trace_id: u128,
+    /// The span ID of this server.
+    pub parent_id: u64,
+    pub flags: u8,
+}
+
+impl TraceParent {
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.trim().split('-');
+        let (version, trace_id, parent_id, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        // the future versions may have more fields
+        if version.len() != 2 || version == "ff" || (version == "00" && parts.next().is_some()) {
+            return None;
+        }
+        fn hex(s: &str, len: usize) -> Option<&str> {
+            (s.len() == len && s.bytes().all(|it| matches!(it, b'0'..=b'9' | b'a'..=b'f')))
+                .then_some(s)
+        }
+        u8::from_str_radix(hex(version, 2)?, 16).ok()?;
+        let trace_id = u128::from_str_radix(hex(trace_id, 32)?, 16).ok()?;
+        let parent_id = u64::from_str_radix(hex(parent_id, 16)?, 16).ok()?;
+        let flags = u8::from_str_radix(hex(flags, 2)?, 16).ok()?;
+        (trace_id != 0 && parent_id != 0).then_some(Self {
+            trace_id,
+            parent_id,
+            flags,
+        })
+    }
+
+    fn root() -> Self {
+        Self {
+            trace_id: rand::random_range(1..=u128::MAX),
+            parent_id: rand::random_range(1..=u64::MAX),
+            flags: 0,
+        }
+    }
+
+    /// The traceparent of this server in the same trace.
+    fn child(&self) -> Self {
+        Self {
+            parent_id: rand::random_range(1..=u64::MAX),
+            ..*self
+        }
+    }
+
+    pub fn trace_id(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+}
+
+impl std::fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
+
+/// Apply with `Router::layer` to record the matched route.
+#[derive(Clone, Debug, Default)]
+pub struct RequestTraceLayer;
+
+impl<S> tower::Layer<S> for RequestTraceLayer {
+    type Service = RequestTrace<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestTrace { inner }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RequestTrace<S> {
+    inner: S,
+}
+
+impl<S> tower::Service<axum::http::Request<axum::body::Body>> for RequestTrace<S>
+where
+    S: tower::Service<
+            axum::http::Request<axum::body::Body>,
+            Response = axum::http::Response<axum::body::Body>,
+        > + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = futures_util::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: axum::http::Request<axum::body::Body>) -> Self::Future {
+        let started_at = std::time::Instant::now();
+        let headers = request.headers();
+        let trace_parent = headers
+            .get(&TRACEPARENT)
+            .and_then(|it| it.to_str().ok())
+            .and_then(TraceParent::parse)
+            .map_or_else(TraceParent::root, |it| it.child());
+        let request_id = headers
+            .get(&X_REQUEST_ID)
+            .and_then(|it| it.to_str().ok())
+            .and_then(RequestId::parse)
+            .unwrap_or_else(|| RequestId(trace_parent.trace_id()));
+        let ids = [
+            (X_REQUEST_ID.clone(), header_value(&request_id)),
+            (TRACEPARENT.clone(), header_value(&trace_parent)),
+        ];
+        for (name, value) in ids.clone() {
+            request.headers_mut().insert(name, value);
+        }
+
+        let route = request
+            .extensions()
+            .get::<axum::extract::MatchedPath>()
+            .map(|it| it.as_str());
+        let span = tracing::info_span!(
+            "request",
+            method = %request.method(),
+            route = route.map(tracing::field::display),
+            request_id = %request_id,
+            trace_id = %trace_parent.trace_id(),
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        );
+        request.extensions_mut().insert(request_id);
+        request.extensions_mut().insert(trace_parent);
+
+        let future = span.in_scope(|| self.inner.call(request));
+        Box::pin(async move {
+            let mut response = future.instrument(span.clone()).await?;
+            span.record("status", response.status().as_u16());
+            span.record("latency_ms", started_at.elapsed().as_millis() as u64);
+            tracing::info!(parent: &span, "finished");
+            for (name, value) in ids {
+                response.headers_mut().insert(name, value);
+            }
+            Ok(response)
+        })
+    }
+}
+
+fn header_value(value: &impl std::fmt::Display) -> axum::http::HeaderValue {
+    // RequestId and TraceParent are visible ASCII
+    axum::http::HeaderValue::from_str(&value.to_string()).expect("valid header value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id() {
+        assert_eq!(
+            RequestId::parse("abc-123").map(|it| it.0),
+            Some("abc-123".to_owned())
+        );
+        assert_eq!(RequestId::parse(""), None);
+        assert_eq!(RequestId::parse("a b"), None);
+        assert_eq!(RequestId::parse(&"a".repeat(129)), None);
+    }
+
+    #[test]
+    fn test_trace_parent() {
+        let s = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let trace_parent = TraceParent::parse(s);
+        assert_eq!(
+            trace_parent,
+            Some(TraceParent {
+                trace_id: 0x4bf92f3577b34da6a3ce929d0e0e4736,
+                parent_id: 0x00f067aa0ba902b7,
+                flags: 1,
+            })
+        );
+        assert_eq!(trace_parent.map(|it| it.to_string()).as_deref(), Some(s));
+
+        let child = trace_parent.map(|it| it.child());
+        assert_eq!(
+            child.map(|it| it.trace_id),
+            trace_parent.map(|it| it.trace_id)
+        );
+        assert_eq!(child.map(|it| it.flags), Some(1));
+
+        // the future version may have more fields
+        assert!(TraceParent::parse(&format!("01{}-00", &s[2..])).is_some());
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-00",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+        ] {
+            assert_eq!(TraceParent::parse(invalid), None, "{invalid}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_trace_assigned() {
+        async fn handler(header_map: axum::http::HeaderMap) -> String {
+            format!("{:?}", header_map.get("X-Request-Id"))
+        }
+
+        // with the other middlewares
+        let app = axum::Router::<()>::new()
+            .route("/", axum::routing::get(handler))
+            .layer(axum::middleware::map_request(
+                |request: axum::http::Request<axum::body::Body>| async { request },
+            ))
+            .layer(RequestTraceLayer);
+        let response = axum_test_client::TestClient::new(app)
+            .get("/")
+            // invalid
+            .header("X-Request-Id", "")
+            .header("traceparent", "00-0-0-0")
+            .await;
+        response.assert_status(axum::http::StatusCode::OK);
+        // the request ID is the new trace ID
+        let request_id = response.header("x-request-id").unwrap_or_default();
+        assert_eq!(request_id.len(), 32);
+        assert_eq!(
+            response
+                .header("traceparent")
+                .and_then(|it| it.split('-').nth(1)),
+            Some(request_id)
+        );
+        response.assert_text(&format!("Some({:?})", request_id));
+    }
+}
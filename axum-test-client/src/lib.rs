@@ -0,0 +1,469 @@
+//! An in-process HTTP client for testing `axum::Router`s.
+//!
+//! ```ignore
+//! let client = axum_test_client::TestClient::new(router());
+//! client
+//!     .post("/users")
+//!     .json(&serde_json::json!({ "name": "John Doe" }))
+//!     .await
+//!     .assert_status(axum::http::StatusCode::OK)
+//!     .assert_json(&serde_json::json!({ "name": "John Doe" }));
+//! ```
+//!
+//! The assertions panic with the response on failure.
+
+use std::{
+    collections::BTreeMap,
+    future::{Future, IntoFuture},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+/// Sends the requests to the router. The cookies set by the responses are sent with the following
+/// requests.
+#[derive(Clone)]
+pub struct TestClient {
+    router: axum::Router<()>,
+    cookies: Arc<Mutex<BTreeMap<String, String>>>,
+}
+
+impl TestClient {
+    pub fn new(router: axum::Router<()>) -> Self {
+        Self {
+            router,
+            cookies: Arc::default(),
+        }
+    }
+
+    pub fn request(&self, method: axum::http::Method, uri: &str) -> RequestBuilder<'_> {
+        RequestBuilder {
+            client: self,
+            method,
+            uri: uri.to_owned(),
+            query: None,
+            headers: axum::http::HeaderMap::new(),
+            body: axum::body::Body::empty(),
+        }
+    }
+
+    pub fn get(&self, uri: &str) -> RequestBuilder<'_> {
+        self.request(axum::http::Method::GET, uri)
+    }
+
+    pub fn post(&self, uri: &str) -> RequestBuilder<'_> {
+        self.request(axum::http::Method::POST, uri)
+    }
+
+    pub fn put(&self, uri: &str) -> RequestBuilder<'_> {
+        self.request(axum::http::Method::PUT, uri)
+    }
+
+    pub fn patch(&self, uri: &str) -> RequestBuilder<'_> {
+        self.request(axum::http::Method::PATCH, uri)
+    }
+
+    pub fn delete(&self, uri: &str) -> RequestBuilder<'_> {
+        self.request(axum::http::Method::DELETE, uri)
+    }
+
+    /// Returns the value of the cookie in the jar.
+    pub fn cookie(&self, name: &str) -> Option<String> {
+        self.cookies().get(name).cloned()
+    }
+
+    /// Sends the request as is (e.g. `axum::http::Request::builder()...`).
+    ///
+    /// The cookies in the jar are added unless the request has the `Cookie` header.
+    pub async fn send(&self, mut request: axum::extract::Request) -> TestResponse {
+        let cookie = self
+            .cookies()
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        if !cookie.is_empty() && !request.headers().contains_key(axum::http::header::COOKIE) {
+            request.headers_mut().insert(
+                axum::http::header::COOKIE,
+                axum::http::HeaderValue::from_str(&cookie).expect("cookies are valid"),
+            );
+        }
+
+        let response = tower::ServiceExt::oneshot(self.router.clone(), request)
+            .await
+            .unwrap_or_else(|e| match e {});
+        let (parts, body) = response.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .expect("failed to read the response body");
+
+        let mut cookies = self.cookies();
+        for set_cookie in parts.headers.get_all(axum::http::header::SET_COOKIE) {
+            let Some((name, value, expired)) = set_cookie.to_str().ok().and_then(parse_set_cookie)
+            else {
+                continue;
+            };
+            if expired {
+                cookies.remove(name);
+            } else {
+                cookies.insert(name.to_owned(), value.to_owned());
+            }
+        }
+        TestResponse {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        }
+    }
+
+    fn cookies(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, String>> {
+        self.cookies.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Returns the name, the value and whether the cookie is deleted (`Max-Age=0`).
+fn parse_set_cookie(set_cookie: &str) -> Option<(&str, &str, bool)> {
+    let mut attributes = set_cookie.split(';').map(str::trim);
+    let (name, value) = attributes.next()?.split_once('=')?;
+    let expired = attributes.any(|it| {
+        it.split_once('=').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("max-age") && value.parse::<i64>().is_ok_and(|it| it <= 0)
+        })
+    });
+    Some((name.trim(), value.trim(), expired))
+}
+
+/// The request to send with `.await` (or `send`).
+pub struct RequestBuilder<'a> {
+    client: &'a TestClient,
+    method: axum::http::Method,
+    uri: String,
+    query: Option<String>,
+    headers: axum::http::HeaderMap,
+    body: axum::body::Body,
+}
+
+impl RequestBuilder<'_> {
+    /// Panics if the header is invalid.
+    #[track_caller]
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: TryInto<axum::http::HeaderName, Error: std::fmt::Debug>,
+        V: TryInto<axum::http::HeaderValue, Error: std::fmt::Debug>,
+    {
+        self.headers.append(
+            name.try_into().expect("invalid header name"),
+            value.try_into().expect("invalid header value"),
+        );
+        self
+    }
+
+    /// Appends the query string (e.g. `&[("limit", "10")]`) to the URI.
+    #[track_caller]
+    pub fn query<T: serde::Serialize + ?Sized>(mut self, query: &T) -> Self {
+        self.query = Some(serde_urlencoded::to_string(query).expect("invalid query"));
+        self
+    }
+
+    /// Sets the body and `Content-Type: application/json`.
+    #[track_caller]
+    pub fn json<T: serde::Serialize + ?Sized>(self, json: &T) -> Self {
+        let body = serde_json::to_vec(json).expect("invalid JSON");
+        self.header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(body)
+    }
+
+    /// Sets the body and `Content-Type: text/plain; charset=utf-8`.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.header(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; charset=utf-8",
+        )
+        .body(text.into())
+    }
+
+    pub fn body(mut self, body: impl Into<axum::body::Body>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    pub async fn send(self) -> TestResponse {
+        let uri = match self.query {
+            None => self.uri,
+            Some(query) if self.uri.contains('?') => format!("{}&{}", self.uri, query),
+            Some(query) => format!("{}?{}", self.uri, query),
+        };
+        let mut request = axum::http::Request::builder()
+            .method(self.method)
+            .uri(uri)
+            .body(self.body)
+            .expect("invalid request");
+        *request.headers_mut() = self.headers;
+        self.client.send(request).await
+    }
+}
+
+impl<'a> IntoFuture for RequestBuilder<'a> {
+    type Output = TestResponse;
+    type IntoFuture = Pin<Box<dyn Future<Output = TestResponse> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
+
+/// The response whose body is read.
+#[derive(Clone, Debug)]
+pub struct TestResponse {
+    status: axum::http::StatusCode,
+    headers: axum::http::HeaderMap,
+    body: axum::body::Bytes,
+}
+
+impl TestResponse {
+    pub fn status(&self) -> axum::http::StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &axum::http::HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the header value if it is visible ASCII.
+    pub fn header(&self, name: impl axum::http::header::AsHeaderName) -> Option<&str> {
+        self.headers.get(name).and_then(|it| it.to_str().ok())
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Panics if the body is not UTF-8.
+    #[track_caller]
+    pub fn text(&self) -> &str {
+        std::str::from_utf8(&self.body).expect("the body is not UTF-8")
+    }
+
+    /// Panics if the body is not the JSON of `T`.
+    #[track_caller]
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> T {
+        serde_json::from_slice(&self.body)
+            .unwrap_or_else(|e| panic!("the body is not the JSON ({}): {:?}", e, self))
+    }
+
+    #[track_caller]
+    pub fn assert_status(&self, status: axum::http::StatusCode) -> &Self {
+        assert_eq!(self.status, status, "{:?}", self);
+        self
+    }
+
+    #[track_caller]
+    pub fn assert_header(&self, name: impl axum::http::header::AsHeaderName, value: &str) -> &Self {
+        assert_eq!(self.header(name), Some(value), "{:?}", self);
+        self
+    }
+
+    #[track_caller]
+    pub fn assert_text(&self, text: &str) -> &Self {
+        assert_eq!(self.text(), text, "{:?}", self);
+        self
+    }
+
+    /// Compares the body with the JSON (regardless of the formatting and the key order).
+    #[track_caller]
+    pub fn assert_json<T: serde::Serialize + ?Sized>(&self, json: &T) -> &Self {
+        let expected = serde_json::to_value(json).expect("invalid JSON");
+        assert_eq!(self.json::<serde_json::Value>(), expected, "{:?}", self);
+        self
+    }
+
+    /// Compares the status, the `Content-Type` and the body with
+    /// `<CARGO_MANIFEST_DIR>/snapshots/<name>.snap`.
+    ///
+    /// The snapshot is written only if `UPDATE_SNAPSHOTS=1` is set; a missing snapshot fails
+    /// the test (e.g. on CI) instead of being accepted.
+    #[track_caller]
+    pub fn assert_snapshot(&self, name: &str) -> &Self {
+        let path = PathBuf::from(
+            std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set"),
+        )
+        .join("snapshots")
+        .join(format!("{}.snap", name));
+        self.assert_snapshot_file(&path, std::env::var_os("UPDATE_SNAPSHOTS").is_some())
+    }
+
+    #[track_caller]
+    fn assert_snapshot_file(&self, path: &Path, update: bool) -> &Self {
+        let actual = self.snapshot();
+        if update {
+            std::fs::create_dir_all(path.parent().expect("path has the parent"))
+                .and_then(|_| std::fs::write(path, actual))
+                .unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+            return self;
+        }
+        match std::fs::read_to_string(path) {
+            Ok(expected) => {
+                assert_eq!(
+                    actual,
+                    expected,
+                    "snapshot {} does not match",
+                    path.display()
+                );
+            }
+            Err(e) => panic!(
+                "failed to read snapshot {} (run with UPDATE_SNAPSHOTS=1 to write it): {}\n{}",
+                path.display(),
+                e,
+                actual
+            ),
+        }
+        self
+    }
+
+    fn snapshot(&self) -> String {
+        let content_type = self
+            .header(axum::http::header::CONTENT_TYPE)
+            .unwrap_or_default();
+        // the JSON is pretty-printed for the readable diff
+        let body = match serde_json::from_slice::<serde_json::Value>(&self.body) {
+            Ok(json) if content_type.contains("json") => {
+                serde_json::to_string_pretty(&json).expect("JSON is serializable")
+            }
+            _ => String::from_utf8_lossy(&self.body).into_owned(),
+        };
+        format!(
+            "{}\ncontent-type: {}\n\n{}\n",
+            self.status, content_type, body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router() -> axum::Router<()> {
+        async fn echo(
+            axum::extract::Query(query): axum::extract::Query<BTreeMap<String, String>>,
+            headers: axum::http::HeaderMap,
+            body: String,
+        ) -> axum::Json<serde_json::Value> {
+            axum::Json(serde_json::json!({
+                "query": query,
+                "cookie": headers.get("cookie").and_then(|it| it.to_str().ok()),
+                "x-name": headers.get("x-name").and_then(|it| it.to_str().ok()),
+                "body": body,
+            }))
+        }
+
+        async fn login() -> impl axum::response::IntoResponse {
+            (
+                axum::response::AppendHeaders([
+                    (
+                        axum::http::header::SET_COOKIE,
+                        "session=abc; Path=/; HttpOnly",
+                    ),
+                    (axum::http::header::SET_COOKIE, "theme=dark"),
+                ]),
+                "logged in",
+            )
+        }
+
+        async fn logout() -> impl axum::response::IntoResponse {
+            (
+                [(axum::http::header::SET_COOKIE, "session=; Max-Age=0")],
+                "logged out",
+            )
+        }
+
+        axum::Router::new()
+            .route("/echo", axum::routing::post(echo))
+            .route("/login", axum::routing::post(login))
+            .route("/logout", axum::routing::post(logout))
+    }
+
+    #[tokio::test]
+    async fn test_request_builder() {
+        #[derive(serde::Serialize)]
+        struct Body {
+            name: &'static str,
+        }
+
+        let client = TestClient::new(router());
+        client
+            .post("/echo?a=1")
+            .query(&[("b", "2")])
+            .header("x-name", "bouzuya")
+            .json(&Body { name: "John Doe" })
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_header(axum::http::header::CONTENT_TYPE, "application/json")
+            .assert_json(&serde_json::json!({
+                "query": { "a": "1", "b": "2" },
+                "cookie": null,
+                "x-name": "bouzuya",
+                "body": r#"{"name":"John Doe"}"#,
+            }));
+
+        let response = client.post("/echo").text("hello").await;
+        assert_eq!(response.json::<serde_json::Value>()["body"], "hello");
+
+        let response = client.get("/unknown").await;
+        response
+            .assert_status(axum::http::StatusCode::NOT_FOUND)
+            .assert_text("");
+    }
+
+    #[tokio::test]
+    async fn test_cookies() {
+        let client = TestClient::new(router());
+        client.post("/login").await.assert_text("logged in");
+        assert_eq!(client.cookie("session").as_deref(), Some("abc"));
+        let response = client.post("/echo").await;
+        assert_eq!(
+            response.json::<serde_json::Value>()["cookie"],
+            "session=abc; theme=dark"
+        );
+
+        // the explicit header is sent as is
+        let response = client.post("/echo").header("cookie", "a=b").await;
+        assert_eq!(response.json::<serde_json::Value>()["cookie"], "a=b");
+
+        client.post("/logout").await;
+        assert_eq!(client.cookie("session"), None);
+        let response = client.post("/echo").await;
+        assert_eq!(response.json::<serde_json::Value>()["cookie"], "theme=dark");
+    }
+
+    #[test]
+    fn test_parse_set_cookie() {
+        assert_eq!(parse_set_cookie("a=b"), Some(("a", "b", false)));
+        assert_eq!(
+            parse_set_cookie("a=b; Path=/; Max-Age=60"),
+            Some(("a", "b", false))
+        );
+        assert_eq!(parse_set_cookie("a=; max-age=0"), Some(("a", "", true)));
+        assert_eq!(parse_set_cookie("invalid"), None);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot() {
+        let client = TestClient::new(router());
+        client
+            .post("/echo")
+            .json(&serde_json::json!({ "name": "John Doe" }))
+            .await
+            .assert_snapshot("echo");
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "run with UPDATE_SNAPSHOTS=1 to write it")]
+    async fn test_snapshot_missing() {
+        let client = TestClient::new(router());
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots/missing.snap");
+        client
+            .post("/echo")
+            .await
+            .assert_snapshot_file(&path, false);
+    }
+}
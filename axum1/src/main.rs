@@ -2,17 +2,17 @@
 // <https://docs.rs/axum/0.6.18/axum/index.html#routing>
 // <https://docs.rs/axum/0.6.18/axum/index.html#extractors>
 // <https://docs.rs/axum/0.6.18/axum/index.html#responses>
+mod storage;
+
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    response::{IntoResponse, Json, Response},
     routing::{get, post, put},
     Router,
 };
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::{collections::HashMap, sync::Arc};
+use storage::{Counter, InMemoryStorage, IncrementError, SqliteStorage, Storage};
 
 async fn root() -> &'static str {
     "Hello, World!"
@@ -110,25 +110,66 @@ async fn responses_json() -> Json<serde_json::Value> {
     Json(serde_json::json!({ "data": 42 }))
 }
 
-struct AppState {
-    counter: i32,
+type SharedState = Arc<dyn Storage>;
+
+fn etag(counter: &Counter) -> [(HeaderName, String); 1] {
+    [(header::ETAG, format!("\"{}\"", counter.version))]
 }
 
-type SharedState = Arc<Mutex<AppState>>;
+/// Returns the version in `If-Match: "<version>"`. `*` or no header matches any version.
+///
+/// The other values never match.
+fn if_match(headers: &HeaderMap) -> Option<i64> {
+    let value = headers
+        .get(header::IF_MATCH)?
+        .to_str()
+        .unwrap_or_default()
+        .trim();
+    if value == "*" {
+        return None;
+    }
+    Some(
+        value
+            .strip_prefix('"')
+            .and_then(|it| it.strip_suffix('"'))
+            .and_then(|it| it.parse().ok())
+            .unwrap_or(-1),
+    )
+}
 
-async fn shared_state_increment_count(State(state): State<SharedState>) -> Result<(), StatusCode> {
-    let mut state = state
-        .lock()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    state.counter += 1;
-    Ok(())
+// ```console
+// $ curl -D - -X PUT -H 'If-Match: "0"' 'http://localhost:3000/shared_state/count/increment'
+// HTTP/1.1 200 OK
+// content-type: text/plain; charset=utf-8
+// etag: "1"
+//
+// 1
+//
+// $ curl -D - -X PUT -H 'If-Match: "0"' 'http://localhost:3000/shared_state/count/increment'
+// HTTP/1.1 412 Precondition Failed
+// etag: "1"
+// ```
+async fn shared_state_increment_count(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> Response {
+    match state.increment(if_match(&headers)).await {
+        Ok(counter) => (etag(&counter), counter.value.to_string()).into_response(),
+        Err(IncrementError::Conflict(current)) => {
+            (StatusCode::PRECONDITION_FAILED, etag(&current)).into_response()
+        }
+        Err(IncrementError::Storage(_)) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
 }
 
-async fn shared_state_get_count(State(state): State<SharedState>) -> Result<String, StatusCode> {
-    let state = state
-        .lock()
+async fn shared_state_get_count(
+    State(state): State<SharedState>,
+) -> Result<([(HeaderName, String); 1], String), StatusCode> {
+    let counter = state
+        .get()
+        .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(state.counter.to_string())
+    Ok((etag(&counter), counter.value.to_string()))
 }
 
 fn build_app(state: SharedState) -> Router {
@@ -151,7 +192,11 @@ fn build_app(state: SharedState) -> Router {
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let state = Arc::new(Mutex::new(AppState { counter: 0 }));
+    // e.g. DATABASE_URL='sqlite://counter.db?mode=rwc'
+    let state: SharedState = match std::env::var("DATABASE_URL") {
+        Ok(url) => Arc::new(SqliteStorage::connect(&url).await?),
+        Err(_) => Arc::new(InMemoryStorage::default()),
+    };
     let app = build_app(state);
 
     let addr = "0.0.0.0:3000".parse()?;
@@ -159,3 +204,127 @@ async fn main() -> anyhow::Result<()> {
         .serve(app.into_make_service())
         .await?)
 }
+
+#[cfg(test)]
+mod tests {
+    use tower::ServiceExt as _;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_storage() -> anyhow::Result<()> {
+        test_storage(Arc::new(InMemoryStorage::default())).await
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_storage() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        test_storage(Arc::new(SqliteStorage::connect(&url).await?)).await?;
+
+        // the counter is kept after the reconnection
+        let storage = SqliteStorage::connect(&url).await?;
+        assert_eq!(storage.get().await?.value, 12);
+        Ok(())
+    }
+
+    /// The test suite for the storages.
+    async fn test_storage(state: SharedState) -> anyhow::Result<()> {
+        let (status, etag, body) = send(&state, get_count()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(etag.as_deref(), Some(r#""0""#));
+        assert_eq!(body, "0");
+
+        // without If-Match
+        let (status, etag, body) = send(&state, increment(None)).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(etag.as_deref(), Some(r#""1""#));
+        assert_eq!(body, "1");
+
+        // with If-Match
+        let (status, etag, body) = send(&state, increment(Some(r#""1""#))).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(etag.as_deref(), Some(r#""2""#));
+        assert_eq!(body, "2");
+        let (status, etag, body) = send(&state, increment(Some(r#""1""#))).await?;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        assert_eq!(etag.as_deref(), Some(r#""2""#));
+        assert_eq!(body, "");
+        let (status, _, _) = send(&state, increment(Some("2"))).await?;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        let (status, _, _) = send(&state, increment(Some("*"))).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        // the concurrent increments are not lost
+        let handles = (0..8)
+            .map(|_| {
+                let state = state.clone();
+                tokio::spawn(async move { state.increment(None).await })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.await??;
+        }
+        assert_eq!(
+            state.get().await?,
+            Counter {
+                value: 11,
+                version: 11
+            }
+        );
+
+        // only one of the increments with the same version succeeds
+        let handles = (0..8)
+            .map(|_| {
+                let state = state.clone();
+                tokio::spawn(async move { state.increment(Some(11)).await })
+            })
+            .collect::<Vec<_>>();
+        let mut succeeded = 0;
+        for handle in handles {
+            match handle.await? {
+                Ok(_) => succeeded += 1,
+                Err(IncrementError::Conflict(_)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        assert_eq!(succeeded, 1);
+        let (_, etag, body) = send(&state, get_count()).await?;
+        assert_eq!(etag.as_deref(), Some(r#""12""#));
+        assert_eq!(body, "12");
+        Ok(())
+    }
+
+    fn get_count() -> axum::http::Request<axum::body::Body> {
+        axum::http::Request::builder()
+            .uri("/shared_state/count")
+            .body(axum::body::Body::empty())
+            .unwrap()
+    }
+
+    fn increment(if_match: Option<&str>) -> axum::http::Request<axum::body::Body> {
+        let mut builder = axum::http::Request::builder()
+            .method(axum::http::Method::PUT)
+            .uri("/shared_state/count/increment");
+        if let Some(if_match) = if_match {
+            builder = builder.header(header::IF_MATCH, if_match);
+        }
+        builder.body(axum::body::Body::empty()).unwrap()
+    }
+
+    async fn send(
+        state: &SharedState,
+        request: axum::http::Request<axum::body::Body>,
+    ) -> anyhow::Result<(StatusCode, Option<String>, String)> {
+        let response = build_app(state.clone()).oneshot(request).await?;
+        let status = response.status();
+        let etag = response
+            .headers()
+            .get(header::ETAG)
+            .map(|it| it.to_str())
+            .transpose()?
+            .map(str::to_owned);
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        Ok((status, etag, String::from_utf8(body.to_vec())?))
+    }
+}
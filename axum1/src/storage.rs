@@ -0,0 +1,130 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+/// The counter and its version. The version is incremented on every update.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Counter {
+    pub value: i64,
+    pub version: i64,
+}
+
+#[derive(Debug)]
+pub enum IncrementError {
+    /// The version is not the expected one. Has the current counter.
+    Conflict(Counter),
+    Storage(anyhow::Error),
+}
+
+impl std::fmt::Display for IncrementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IncrementError::Conflict(current) => {
+                write!(f, "version conflict (current: {})", current.version)
+            }
+            IncrementError::Storage(e) => write!(f, "storage error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for IncrementError {}
+
+impl From<anyhow::Error> for IncrementError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Storage(e)
+    }
+}
+
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn get(&self) -> anyhow::Result<Counter>;
+
+    /// Increments the counter atomically if its version is `expected_version` (or any version
+    /// if `None`).
+    async fn increment(&self, expected_version: Option<i64>) -> Result<Counter, IncrementError>;
+}
+
+/// The storage that loses the counter on restart.
+#[derive(Debug, Default)]
+pub struct InMemoryStorage {
+    counter: Mutex<Counter>,
+}
+
+#[async_trait]
+impl Storage for InMemoryStorage {
+    async fn get(&self) -> anyhow::Result<Counter> {
+        let counter = self
+            .counter
+            .lock()
+            .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
+        Ok(*counter)
+    }
+
+    async fn increment(&self, expected_version: Option<i64>) -> Result<Counter, IncrementError> {
+        let mut counter = self
+            .counter
+            .lock()
+            .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
+        if expected_version.is_some_and(|it| it != counter.version) {
+            return Err(IncrementError::Conflict(*counter));
+        }
+        counter.value += 1;
+        counter.version += 1;
+        Ok(*counter)
+    }
+}
+
+/// The storage that can be shared by the processes.
+#[derive(Debug)]
+pub struct SqliteStorage {
+    pool: sqlx::SqlitePool,
+}
+
+impl SqliteStorage {
+    /// Connects to the database (e.g. `sqlite://counter.db?mode=rwc`) and creates the table.
+    pub async fn connect(url: &str) -> anyhow::Result<Self> {
+        let pool = sqlx::SqlitePool::connect(url).await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS counter (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                value INTEGER NOT NULL,
+                version INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query("INSERT OR IGNORE INTO counter (id, value, version) VALUES (1, 0, 0)")
+            .execute(&pool)
+            .await?;
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl Storage for SqliteStorage {
+    async fn get(&self) -> anyhow::Result<Counter> {
+        let (value, version) =
+            sqlx::query_as::<_, (i64, i64)>("SELECT value, version FROM counter WHERE id = 1")
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(Counter { value, version })
+    }
+
+    async fn increment(&self, expected_version: Option<i64>) -> Result<Counter, IncrementError> {
+        // a single statement to be atomic without a transaction
+        let updated = sqlx::query_as::<_, (i64, i64)>(
+            "UPDATE counter
+            SET value = value + 1, version = version + 1
+            WHERE id = 1 AND (?1 IS NULL OR version = ?1)
+            RETURNING value, version",
+        )
+        .bind(expected_version)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(anyhow::Error::from)?;
+        match updated {
+            Some((value, version)) => Ok(Counter { value, version }),
+            None => Err(IncrementError::Conflict(self.get().await?)),
+        }
+    }
+}
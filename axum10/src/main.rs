@@ -18,19 +18,11 @@ mod tests {
                     request
                 },
             ));
-        let response = send_request(
-            app,
-            axum::http::Request::builder()
-                .method("GET")
-                .uri("/")
-                .body(axum::body::Body::empty())?,
-        )
-        .await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(
-            response.into_body_string().await?,
-            "X-NAME: Some(\"bouzuya\")"
-        );
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("X-NAME: Some(\"bouzuya\")");
         Ok(())
     }
 
@@ -44,16 +36,11 @@ mod tests {
                     response
                 },
             ));
-        let response = send_request(
-            app,
-            axum::http::Request::builder()
-                .method("GET")
-                .uri("/")
-                .body(axum::body::Body::empty())?,
-        )
-        .await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "New Body");
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("New Body");
         Ok(())
     }
 
@@ -75,16 +62,11 @@ mod tests {
                     }
                 },
             ));
-        let response = send_request(
-            app,
-            axum::http::Request::builder()
-                .method("GET")
-                .uri("/")
-                .body(axum::body::Body::empty())?,
-        )
-        .await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "New Body");
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("New Body");
         Ok(())
     }
 
@@ -101,35 +83,46 @@ mod tests {
                             >::Ok(response)
                 },
             ));
-        let response = send_request(
-            app,
-            axum::http::Request::builder()
-                .method("GET")
-                .uri("/")
-                .body(axum::body::Body::empty())?,
-        )
-        .await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "New Body");
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("New Body");
         Ok(())
     }
 
-    async fn send_request(
-        router: axum::Router<()>,
-        request: axum::http::Request<axum::body::Body>,
-    ) -> anyhow::Result<axum::response::Response<axum::body::Body>> {
-        let response = tower::ServiceExt::oneshot(router, request).await?;
-        Ok(response)
-    }
-
-    trait ResponseExt {
-        async fn into_body_string(self) -> anyhow::Result<String>;
-    }
-
-    impl ResponseExt for axum::response::Response<axum::body::Body> {
-        async fn into_body_string(self) -> anyhow::Result<String> {
-            let bytes = axum::body::to_bytes(self.into_body(), usize::MAX).await?;
-            Ok(String::from_utf8(bytes.to_vec())?)
+    #[tokio::test]
+    async fn test_request_trace() -> anyhow::Result<()> {
+        async fn handler(header_map: axum::http::HeaderMap) -> String {
+            format!("X-NAME: {:?}", header_map.get("X-NAME"))
         }
+
+        // ServiceBuilder applies the layers from top to bottom (RequestTraceLayer is outermost)
+        let app = axum::Router::<()>::new()
+            .route("/", axum::routing::get(handler))
+            .layer(
+                tower::ServiceBuilder::new()
+                    .layer(axum_request_trace::RequestTraceLayer)
+                    .map_request(|mut request: axum::http::Request<axum::body::Body>| {
+                        let request_id = request
+                            .extensions()
+                            .get::<axum_request_trace::RequestId>()
+                            .map(|it| it.to_string())
+                            .unwrap_or_default();
+                        request.headers_mut().insert(
+                            "X-NAME",
+                            axum::http::HeaderValue::from_str(&request_id).unwrap(),
+                        );
+                        request
+                    }),
+            );
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .header("X-Request-Id", "req-1")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_header("x-request-id", "req-1")
+            .assert_text("X-NAME: Some(\"req-1\")");
+        Ok(())
     }
 }
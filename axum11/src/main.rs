@@ -1,4 +1,23 @@
-fn main() {}
+async fn handler(
+    axum::Extension(request_id): axum::Extension<axum_request_trace::RequestId>,
+) -> String {
+    tracing::info!("handling");
+    format!("request_id: {}", request_id.as_str())
+}
+
+fn router() -> axum::Router<()> {
+    axum::Router::new()
+        .route("/users/{user_id}", axum::routing::get(handler))
+        .layer(axum_request_trace::RequestTraceLayer)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt().init();
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
+    axum::serve(listener, router()).await?;
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
@@ -74,19 +93,11 @@ mod tests {
         let app = axum::Router::<()>::new()
             .route("/", axum::routing::get(handler))
             .layer(MyLayer::with_name("bouzuya"));
-        let response = send_request(
-            app,
-            axum::http::Request::builder()
-                .method("GET")
-                .uri("/")
-                .body(axum::body::Body::empty())?,
-        )
-        .await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(
-            response.into_body_string().await?,
-            "X-NAME: Some(\"bouzuya\")"
-        );
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("X-NAME: Some(\"bouzuya\")");
         Ok(())
     }
 
@@ -143,35 +154,83 @@ mod tests {
         let app = axum::Router::<()>::new()
             .route("/", axum::routing::get("Original Response"))
             .layer(MyLayer);
-        let response = send_request(
-            app,
-            axum::http::Request::builder()
-                .method("GET")
-                .uri("/")
-                .body(axum::body::Body::empty())?,
-        )
-        .await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "Updated Response Body");
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("Updated Response Body");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_trace() -> anyhow::Result<()> {
+        let logs = Logs::default();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::fmt()
+                .with_ansi(false)
+                .with_writer(logs.clone())
+                .finish(),
+        );
+
+        let response = axum_test_client::TestClient::new(super::router())
+            .get("/users/1")
+            .header("X-Request-Id", "req-1")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .await;
+        response
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_header("x-request-id", "req-1")
+            .assert_text("request_id: req-1");
+        let traceparent = response.header("traceparent").unwrap_or_default();
+        assert!(traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+        assert!(traceparent.ends_with("-01"));
+        assert_ne!(
+            traceparent,
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+
+        let logs = logs.to_string();
+        let span = r#"request{method=GET route=/users/{user_id} request_id=req-1 trace_id=4bf92f3577b34da6a3ce929d0e0e4736"#;
+        assert!(
+            logs.contains(&format!("{span}}}: axum11: handling")),
+            "{logs}"
+        );
+        assert!(
+            logs.contains(&format!("{span} status=200 latency_ms=")),
+            "{logs}"
+        );
         Ok(())
     }
 
-    async fn send_request(
-        router: axum::Router<()>,
-        request: axum::http::Request<axum::body::Body>,
-    ) -> anyhow::Result<axum::response::Response<axum::body::Body>> {
-        let response = tower::ServiceExt::oneshot(router, request).await?;
-        Ok(response)
+    /// The logs written by `tracing_subscriber`.
+    #[derive(Clone, Default)]
+    struct Logs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::fmt::Display for Logs {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).fmt(f)
+        }
     }
 
-    trait ResponseExt {
-        async fn into_body_string(self) -> anyhow::Result<String>;
+    impl std::io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
     }
 
-    impl ResponseExt for axum::response::Response<axum::body::Body> {
-        async fn into_body_string(self) -> anyhow::Result<String> {
-            let bytes = axum::body::to_bytes(self.into_body(), usize::MAX).await?;
-            Ok(String::from_utf8(bytes.to_vec())?)
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for Logs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
         }
     }
 }
@@ -1,39 +1,58 @@
+mod problem;
+
 async fn root() -> &'static str {
     "Hello, world!"
 }
 
+#[derive(serde::Deserialize)]
+struct ListUsersQuery {
+    limit: u32,
+}
+
+async fn list_users(
+    query: Result<axum::extract::Query<ListUsersQuery>, axum::extract::rejection::QueryRejection>,
+) -> Result<String, problem::Problem> {
+    let axum::extract::Query(query) = query?;
+    Ok(format!("Users (limit: {})", query.limit))
+}
+
 async fn get_user(
-    axum::extract::Path(user_id): axum::extract::Path<u32>,
-) -> Result<String, axum::http::StatusCode> {
+    path: Result<axum::extract::Path<u32>, axum::extract::rejection::PathRejection>,
+) -> Result<String, problem::Problem> {
+    let axum::extract::Path(user_id) = path?;
     if user_id == 0 {
-        Err(axum::http::StatusCode::NOT_FOUND)
+        Err(problem::Problem::new(axum::http::StatusCode::NOT_FOUND)
+            .with_type("/problems/user-not-found", "User not found")
+            .with_detail(format!("user {} not found", user_id)))
     } else {
         Ok(format!("User {}", user_id))
     }
 }
 
 async fn create_user(
-    axum::extract::Json(json): axum::extract::Json<serde_json::Value>,
-) -> axum::response::Json<serde_json::Value> {
+    json: Result<axum::extract::Json<serde_json::Value>, axum::extract::rejection::JsonRejection>,
+) -> Result<axum::response::Json<serde_json::Value>, problem::Problem> {
+    let axum::extract::Json(json) = json?;
     // ...
-    axum::response::Json(json)
+    Ok(axum::response::Json(json))
 }
 
-async fn fallback_handler() -> impl axum::response::IntoResponse {
-    (axum::http::StatusCode::OK, "fallback")
+async fn fallback_handler() -> problem::Problem {
+    problem::Problem::new(axum::http::StatusCode::NOT_FOUND)
 }
 
-async fn method_not_allowed_fallback() -> impl axum::response::IntoResponse {
-    (axum::http::StatusCode::OK, "method_not_allowed_fallback")
+async fn method_not_allowed_fallback() -> problem::Problem {
+    problem::Problem::new(axum::http::StatusCode::METHOD_NOT_ALLOWED)
 }
 
 fn router() -> axum::Router<()> {
     axum::Router::new()
         .route("/", axum::routing::get(root))
-        .route("/users", axum::routing::post(create_user))
+        .route("/users", axum::routing::get(list_users).post(create_user))
         .route("/users/{user_id}", axum::routing::get(get_user))
         .fallback(fallback_handler)
         .method_not_allowed_fallback(method_not_allowed_fallback)
+        .layer(axum::middleware::from_fn(problem::negotiate))
 }
 
 #[tokio::main]
@@ -48,127 +67,121 @@ mod tests {
     use super::*;
 
     #[tokio::test]
-    async fn test_root() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .uri("/")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(router, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "Hello, world!");
-        Ok(())
+    async fn test_root() {
+        axum_test_client::TestClient::new(router())
+            .get("/")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("Hello, world!");
     }
 
     #[tokio::test]
-    async fn test_create_user() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::POST)
-            .uri("/users")
-            .header(axum::http::header::CONTENT_TYPE, "application/json")
-            .body(axum::body::Body::from(r#"{"name":"John Doe"}"#))?;
-        let response = send_request(router, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, r#"{"name":"John Doe"}"#);
-        Ok(())
+    async fn test_create_user() {
+        axum_test_client::TestClient::new(router())
+            .post("/users")
+            .json(&serde_json::json!({ "name": "John Doe" }))
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text(r#"{"name":"John Doe"}"#);
     }
 
     #[tokio::test]
-    async fn test_get_user() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/users/1")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(router, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, r#"User 1"#);
-        Ok(())
+    async fn test_list_users() {
+        axum_test_client::TestClient::new(router())
+            .get("/users")
+            .query(&[("limit", 10)])
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("Users (limit: 10)");
     }
 
     #[tokio::test]
-    async fn test_get_user_not_found() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/users/0")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(router, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
-        assert_eq!(response.into_body_string().await?, "");
-        Ok(())
+    async fn test_get_user() {
+        axum_test_client::TestClient::new(router())
+            .get("/users/1")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("User 1");
     }
 
     #[tokio::test]
-    async fn test_path_not_found() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/unknown")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(router, request).await?;
-        // no fallback => HTTP 404
-        // assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
-        // assert_eq!(response.into_body_string().await?, "");
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "fallback");
-        Ok(())
+    async fn test_get_user_not_found() {
+        axum_test_client::TestClient::new(router())
+            .get("/users/0")
+            .await
+            .assert_snapshot("get_user_not_found");
     }
 
     #[tokio::test]
-    async fn test_method_not_allowed() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::POST)
-            .uri("/")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(router, request).await?;
-        // no fallback => HTTP 405
-        // assert_eq!(
-        //     response.status(),
-        //     axum::http::StatusCode::METHOD_NOT_ALLOWED
-        // );
-        // assert_eq!(response.into_body_string().await?, "");
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(
-            response.into_body_string().await?,
-            "method_not_allowed_fallback"
-        );
-        Ok(())
+    async fn test_get_user_not_found_text() {
+        axum_test_client::TestClient::new(router())
+            .get("/users/0")
+            .header(axum::http::header::ACCEPT, "text/plain")
+            .await
+            .assert_snapshot("get_user_not_found_text");
     }
 
     #[tokio::test]
-    async fn test_other_default_fallback() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::POST)
-            .uri("/unknown")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(router, request).await?;
-        // no fallback => HTTP 404
-        // assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
-        // assert_eq!(response.into_body_string().await?, "");
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "fallback");
-        Ok(())
+    async fn test_rejections() {
+        let client = axum_test_client::TestClient::new(router());
+        client
+            .get("/users/abc")
+            .await
+            .assert_status(axum::http::StatusCode::BAD_REQUEST)
+            .assert_json(&serde_json::json!({
+                "type": "about:blank",
+                "title": "Bad Request",
+                "status": 400,
+                "detail": "Invalid URL: Cannot parse `abc` to a `u32`",
+                "instance": "/users/abc",
+            }));
+
+        let response = client.get("/users").query(&[("limit", "x")]).await;
+        response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(response.json::<serde_json::Value>()["instance"], "/users");
+
+        client
+            .post("/users")
+            .header(axum::http::header::ACCEPT, "text/plain")
+            .body(r#"{"name":"John Doe"}"#)
+            .await
+            .assert_status(axum::http::StatusCode::UNSUPPORTED_MEDIA_TYPE)
+            .assert_text(
+                "415 Unsupported Media Type: Expected request with `Content-Type: application/json`",
+            );
     }
 
-    async fn send_request(
-        router: axum::Router<()>,
-        request: axum::http::Request<axum::body::Body>,
-    ) -> anyhow::Result<axum::response::Response<axum::body::Body>> {
-        let response = tower::ServiceExt::oneshot(router, request).await?;
-        Ok(response)
+    #[tokio::test]
+    async fn test_path_not_found() {
+        // the fallback => HTTP 404 with the problem
+        axum_test_client::TestClient::new(router())
+            .get("/unknown")
+            .await
+            .assert_snapshot("path_not_found");
     }
 
-    trait ResponseExt {
-        async fn into_body_string(self) -> anyhow::Result<String>;
+    #[tokio::test]
+    async fn test_method_not_allowed() {
+        axum_test_client::TestClient::new(router())
+            .post("/")
+            .await
+            // the method not allowed fallback => HTTP 405 with the problem and the allowed methods
+            .assert_snapshot("method_not_allowed")
+            .assert_header(axum::http::header::ALLOW, "GET,HEAD");
     }
 
-    impl ResponseExt for axum::response::Response<axum::body::Body> {
-        async fn into_body_string(self) -> anyhow::Result<String> {
-            let bytes = axum::body::to_bytes(self.into_body(), usize::MAX).await?;
-            Ok(String::from_utf8(bytes.to_vec())?)
-        }
+    #[tokio::test]
+    async fn test_other_default_fallback() {
+        axum_test_client::TestClient::new(router())
+            .post("/unknown")
+            .await
+            // the fallback (not the method not allowed fallback) => HTTP 404 with the problem
+            .assert_status(axum::http::StatusCode::NOT_FOUND)
+            .assert_json(&serde_json::json!({
+                "type": "about:blank",
+                "title": "Not Found",
+                "status": 404,
+                "instance": "/unknown",
+            }));
     }
 }
@@ -0,0 +1,318 @@
+//! The error responses (RFC 9457 problem details).
+//!
+//! `Problem` is rendered as `application/problem+json` or `text/plain` by `negotiate` depending
+//! on the `Accept` header of the request.
+
+use axum::response::IntoResponse as _;
+
+/// The problem details of an error response.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    type_: String,
+    title: String,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance: Option<String>,
+}
+
+impl Problem {
+    /// The problem of `about:blank` type, whose title is the reason phrase of the status.
+    pub fn new(status: axum::http::StatusCode) -> Self {
+        Self {
+            type_: "about:blank".to_owned(),
+            title: status.canonical_reason().unwrap_or_default().to_owned(),
+            status: status.as_u16(),
+            detail: None,
+            instance: None,
+        }
+    }
+
+    pub fn with_type(mut self, type_: impl Into<String>, title: impl Into<String>) -> Self {
+        self.type_ = type_.into();
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Defaults to the request path (see `negotiate`).
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    fn status(&self) -> axum::http::StatusCode {
+        axum::http::StatusCode::from_u16(self.status)
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn into_json_response(self) -> axum::response::Response {
+        let body = serde_json::to_vec(&self).expect("Problem is serializable");
+        let response = (
+            self.status(),
+            [(axum::http::header::CONTENT_TYPE, "application/problem+json")],
+            body,
+        )
+            .into_response();
+        self.attach(response)
+    }
+
+    fn into_text_response(self) -> axum::response::Response {
+        let body = match &self.detail {
+            None => format!("{} {}", self.status, self.title),
+            Some(detail) => format!("{} {}: {}", self.status, self.title, detail),
+        };
+        let response = (self.status(), body).into_response();
+        self.attach(response)
+    }
+
+    /// Keeps the problem in the response for `negotiate`.
+    fn attach(self, mut response: axum::response::Response) -> axum::response::Response {
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+impl axum::response::IntoResponse for Problem {
+    fn into_response(self) -> axum::response::Response {
+        self.into_json_response()
+    }
+}
+
+macro_rules! impl_from_rejection {
+    ($($rejection:ty),* $(,)?) => {
+        $(
+            impl From<$rejection> for Problem {
+                fn from(rejection: $rejection) -> Self {
+                    Problem::new(rejection.status()).with_detail(rejection.body_text())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_rejection!(
+    axum::extract::rejection::JsonRejection,
+    axum::extract::rejection::PathRejection,
+    axum::extract::rejection::QueryRejection,
+);
+
+/// The limit of the body read as the detail of the error response without `Problem`.
+const DETAIL_LIMIT: usize = 4 * 1024;
+
+/// Renders the error responses as the problems in the format the client accepts.
+///
+/// The error responses without `Problem` (e.g. the responses of the other middlewares) are
+/// converted with their text body as the detail. The headers of the error responses (e.g.
+/// `WWW-Authenticate`, `Allow` and `Retry-After`) are kept except the content headers.
+pub async fn negotiate(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let path = request.uri().path().to_owned();
+    let text = prefers_text(
+        request
+            .headers()
+            .get(axum::http::header::ACCEPT)
+            .and_then(|it| it.to_str().ok()),
+    );
+    let (mut parts, body) = next.run(request).await.into_parts();
+    let problem = match parts.extensions.remove::<Problem>() {
+        Some(problem) => problem,
+        None if parts.status.is_client_error() || parts.status.is_server_error() => {
+            let problem = Problem::new(parts.status);
+            match read_detail(&parts.headers, body).await {
+                Some(detail) => problem.with_detail(detail),
+                None => problem,
+            }
+        }
+        None => return axum::response::Response::from_parts(parts, body),
+    };
+    let problem = match problem.instance {
+        Some(_) => problem,
+        None => problem.with_instance(path),
+    };
+    let mut response = if text {
+        problem.into_text_response()
+    } else {
+        problem.into_json_response()
+    };
+    let content_headers = parts
+        .headers
+        .keys()
+        .filter(|it| it.as_str().starts_with("content-"))
+        .cloned()
+        .collect::<Vec<_>>();
+    for name in content_headers {
+        parts.headers.remove(name);
+    }
+    response.headers_mut().extend(parts.headers);
+    response
+}
+
+/// Reads the text body up to `DETAIL_LIMIT` bytes. The other bodies are ignored.
+async fn read_detail(headers: &axum::http::HeaderMap, body: axum::body::Body) -> Option<String> {
+    let is_text = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .is_none_or(|it| it.as_bytes().starts_with(b"text/"));
+    if !is_text {
+        return None;
+    }
+    let body = axum::body::to_bytes(body, DETAIL_LIMIT).await.ok()?;
+    match String::from_utf8_lossy(&body).trim() {
+        "" => None,
+        detail => Some(detail.to_owned()),
+    }
+}
+
+/// Returns true if `text/plain` is preferred to `application/problem+json`.
+///
+/// JSON is used when both are equally acceptable.
+fn prefers_text(accept: Option<&str>) -> bool {
+    let Some(accept) = accept else {
+        return false;
+    };
+    let mut json = 0.0_f32;
+    let mut text = 0.0_f32;
+    for media_range in accept.split(',') {
+        let mut params = media_range.split(';').map(str::trim);
+        let media_type = params.next().unwrap_or_default().to_ascii_lowercase();
+        let q = params
+            .filter_map(|it| it.strip_prefix("q="))
+            .find_map(|it| it.parse::<f32>().ok())
+            .unwrap_or(1.0);
+        match media_type.as_str() {
+            "application/problem+json" | "application/json" | "application/*" => json = json.max(q),
+            "text/plain" | "text/*" => text = text.max(q),
+            "*/*" => {
+                json = json.max(q);
+                text = text.max(q);
+            }
+            _ => {}
+        }
+    }
+    text > json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefers_text() {
+        assert!(!prefers_text(None));
+        assert!(!prefers_text(Some("*/*")));
+        assert!(!prefers_text(Some("application/problem+json")));
+        assert!(prefers_text(Some("text/plain")));
+        assert!(prefers_text(Some("text/html, text/*;q=0.9")));
+        assert!(prefers_text(Some("application/json;q=0.5, text/plain")));
+        assert!(!prefers_text(Some("application/json, text/plain;q=0.5")));
+        assert!(!prefers_text(Some("text/html")));
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_without_problem() {
+        let router = axum::Router::new()
+            .route(
+                "/unauthorized",
+                axum::routing::get(|| async {
+                    (
+                        axum::http::StatusCode::UNAUTHORIZED,
+                        [(axum::http::header::WWW_AUTHENTICATE, "Bearer")],
+                        "token required",
+                    )
+                }),
+            )
+            .route(
+                "/unavailable",
+                axum::routing::get(|| async {
+                    (
+                        axum::http::StatusCode::SERVICE_UNAVAILABLE,
+                        [
+                            (axum::http::header::CONTENT_TYPE, "application/octet-stream"),
+                            (axum::http::header::RETRY_AFTER, "120"),
+                        ],
+                        vec![0_u8; 16],
+                    )
+                }),
+            )
+            .route(
+                "/large",
+                axum::routing::get(|| async {
+                    (
+                        axum::http::StatusCode::BAD_REQUEST,
+                        "x".repeat(DETAIL_LIMIT + 1),
+                    )
+                }),
+            )
+            .layer(axum::middleware::from_fn(negotiate));
+        let client = axum_test_client::TestClient::new(router);
+
+        client
+            .get("/unauthorized")
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_header(axum::http::header::WWW_AUTHENTICATE, "Bearer")
+            .assert_header(axum::http::header::CONTENT_TYPE, "application/problem+json")
+            .assert_json(&serde_json::json!({
+                "type": "about:blank",
+                "title": "Unauthorized",
+                "status": 401,
+                "detail": "token required",
+                "instance": "/unauthorized",
+            }));
+
+        // the binary body is not the detail
+        client
+            .get("/unavailable")
+            .await
+            .assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE)
+            .assert_header(axum::http::header::RETRY_AFTER, "120")
+            .assert_header(axum::http::header::CONTENT_TYPE, "application/problem+json")
+            .assert_json(&serde_json::json!({
+                "type": "about:blank",
+                "title": "Service Unavailable",
+                "status": 503,
+                "instance": "/unavailable",
+            }));
+
+        // the body over the limit is not read
+        let response = client.get("/large").await;
+        response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(response.json::<serde_json::Value>().get("detail"), None);
+    }
+
+    #[test]
+    fn test_serialize() -> anyhow::Result<()> {
+        let problem = Problem::new(axum::http::StatusCode::NOT_FOUND);
+        assert_eq!(
+            serde_json::to_value(&problem)?,
+            serde_json::json!({ "type": "about:blank", "title": "Not Found", "status": 404 })
+        );
+
+        let problem = Problem::new(axum::http::StatusCode::CONFLICT)
+            .with_type(
+                "https://example.com/problems/duplicate-user",
+                "Duplicate user",
+            )
+            .with_detail("user 1 already exists")
+            .with_instance("/users/1");
+        assert_eq!(
+            serde_json::to_value(&problem)?,
+            serde_json::json!({
+                "type": "https://example.com/problems/duplicate-user",
+                "title": "Duplicate user",
+                "status": 409,
+                "detail": "user 1 already exists",
+                "instance": "/users/1",
+            })
+        );
+        Ok(())
+    }
+}
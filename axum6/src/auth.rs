@@ -0,0 +1,311 @@
+//! Bearer token authentication (RFC 6750).
+//!
+//! `AuthLayer` validates the `Authorization: Bearer <token>` header with a `TokenValidator` and
+//! inserts the `Principal` into the request extensions. The handlers get it with
+//! `axum::Extension<Principal>`.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// The authenticated subject and the scopes granted to the token.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Principal {
+    pub subject: String,
+    pub scopes: BTreeSet<String>,
+}
+
+impl Principal {
+    pub fn new<I, S>(subject: impl Into<String>, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            subject: subject.into(),
+            scopes: scopes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AuthError {
+    /// The request has no bearer token.
+    MissingToken,
+    /// The token is unknown, expired or malformed.
+    InvalidToken,
+    /// The token does not have the scopes.
+    InsufficientScope(Vec<String>),
+}
+
+impl axum::response::IntoResponse for AuthError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, challenge) = match self {
+            AuthError::MissingToken => (axum::http::StatusCode::UNAUTHORIZED, "Bearer".to_owned()),
+            AuthError::InvalidToken => (
+                axum::http::StatusCode::UNAUTHORIZED,
+                r#"Bearer error="invalid_token""#.to_owned(),
+            ),
+            AuthError::InsufficientScope(scopes) => (
+                axum::http::StatusCode::FORBIDDEN,
+                format!(
+                    r#"Bearer error="insufficient_scope", scope="{}""#,
+                    scopes.join(" ")
+                ),
+            ),
+        };
+        (status, [(axum::http::header::WWW_AUTHENTICATE, challenge)]).into_response()
+    }
+}
+
+pub trait TokenValidator: Send + Sync + 'static {
+    /// Returns the principal of the token, or `AuthError::InvalidToken`.
+    fn validate(&self, token: &str) -> impl Future<Output = Result<Principal, AuthError>> + Send;
+}
+
+/// Validates the tokens issued by this server (e.g. the session tokens in a database).
+#[derive(Clone, Debug, Default)]
+pub struct OpaqueTokenValidator {
+    tokens: HashMap<String, Principal>,
+}
+
+impl OpaqueTokenValidator {
+    pub fn with_token(mut self, token: impl Into<String>, principal: Principal) -> Self {
+        self.tokens.insert(token.into(), principal);
+        self
+    }
+}
+
+impl TokenValidator for OpaqueTokenValidator {
+    async fn validate(&self, token: &str) -> Result<Principal, AuthError> {
+        self.tokens
+            .get(token)
+            .cloned()
+            .ok_or(AuthError::InvalidToken)
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct Claims {
+    sub: String,
+    /// The space-separated scopes (RFC 8693).
+    #[serde(default)]
+    scope: String,
+}
+
+/// Validates the JWTs. The scopes are read from the `scope` claim.
+pub struct JwtValidator {
+    decoding_key: jsonwebtoken::DecodingKey,
+    validation: jsonwebtoken::Validation,
+}
+
+impl JwtValidator {
+    pub fn new(
+        decoding_key: jsonwebtoken::DecodingKey,
+        validation: jsonwebtoken::Validation,
+    ) -> Self {
+        Self {
+            decoding_key,
+            validation,
+        }
+    }
+}
+
+impl TokenValidator for JwtValidator {
+    async fn validate(&self, token: &str) -> Result<Principal, AuthError> {
+        let claims = jsonwebtoken::decode::<Claims>(token, &self.decoding_key, &self.validation)
+            .map_err(|_| AuthError::InvalidToken)?
+            .claims;
+        Ok(Principal::new(claims.sub, claims.scope.split_whitespace()))
+    }
+}
+
+/// Requires a valid bearer token with the scopes.
+pub struct AuthLayer<V> {
+    validator: Arc<V>,
+    scopes: Arc<[String]>,
+}
+
+impl<V> Clone for AuthLayer<V> {
+    fn clone(&self) -> Self {
+        Self {
+            validator: self.validator.clone(),
+            scopes: self.scopes.clone(),
+        }
+    }
+}
+
+impl<V: TokenValidator> AuthLayer<V> {
+    pub fn new(validator: V) -> Self {
+        Self::from_arc(Arc::new(validator))
+    }
+
+    /// Shares the validator with the other layers.
+    pub fn from_arc(validator: Arc<V>) -> Self {
+        Self {
+            validator,
+            scopes: Arc::new([]),
+        }
+    }
+
+    pub fn require_scope(self, scope: impl Into<String>) -> Self {
+        let mut scopes = self.scopes.to_vec();
+        scopes.push(scope.into());
+        Self {
+            validator: self.validator,
+            scopes: scopes.into(),
+        }
+    }
+}
+
+impl<V, S> tower::Layer<S> for AuthLayer<V> {
+    type Service = Auth<V, S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Auth {
+            inner,
+            validator: self.validator.clone(),
+            scopes: self.scopes.clone(),
+        }
+    }
+}
+
+/// The service created by `AuthLayer`.
+pub struct Auth<V, S> {
+    inner: S,
+    validator: Arc<V>,
+    scopes: Arc<[String]>,
+}
+
+impl<V, S: Clone> Clone for Auth<V, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            validator: self.validator.clone(),
+            scopes: self.scopes.clone(),
+        }
+    }
+}
+
+impl<V, S> tower::Service<axum::extract::Request> for Auth<V, S>
+where
+    V: TokenValidator,
+    S: tower::Service<axum::extract::Request, Response = axum::response::Response>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+{
+    type Response = axum::response::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: axum::extract::Request) -> Self::Future {
+        // the ready service is used for this request (see the tower::Service docs)
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let validator = self.validator.clone();
+        let scopes = self.scopes.clone();
+        Box::pin(async move {
+            match authenticate(&*validator, &scopes, request.headers()).await {
+                Ok(principal) => {
+                    request.extensions_mut().insert(principal);
+                    inner.call(request).await
+                }
+                Err(e) => Ok(axum::response::IntoResponse::into_response(e)),
+            }
+        })
+    }
+}
+
+async fn authenticate<V: TokenValidator>(
+    validator: &V,
+    scopes: &[String],
+    headers: &axum::http::HeaderMap,
+) -> Result<Principal, AuthError> {
+    let token = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|it| it.to_str().ok())
+        .and_then(|it| it.split_once(' '))
+        // the auth-scheme is case-insensitive (RFC 9110 11.1)
+        .and_then(|(scheme, token)| scheme.eq_ignore_ascii_case("Bearer").then_some(token))
+        .ok_or(AuthError::MissingToken)?;
+    let principal = validator.validate(token).await?;
+    if scopes.iter().all(|it| principal.scopes.contains(it)) {
+        Ok(principal)
+    } else {
+        Err(AuthError::InsufficientScope(scopes.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_impls() {
+        fn assert_impl<T: Send + Sync>() {}
+
+        assert_impl::<AuthLayer<OpaqueTokenValidator>>();
+        assert_impl::<AuthLayer<JwtValidator>>();
+    }
+
+    #[tokio::test]
+    async fn test_authenticate() {
+        let validator =
+            OpaqueTokenValidator::default().with_token("a", Principal::new("user1", ["read"]));
+        let scopes = ["read".to_owned()];
+        let mut headers = axum::http::HeaderMap::new();
+        assert_eq!(
+            authenticate(&validator, &scopes, &headers).await,
+            Err(AuthError::MissingToken)
+        );
+
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            axum::http::HeaderValue::from_static("Basic a"),
+        );
+        assert_eq!(
+            authenticate(&validator, &scopes, &headers).await,
+            Err(AuthError::MissingToken)
+        );
+
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            axum::http::HeaderValue::from_static("Bearer b"),
+        );
+        assert_eq!(
+            authenticate(&validator, &scopes, &headers).await,
+            Err(AuthError::InvalidToken)
+        );
+
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            axum::http::HeaderValue::from_static("Bearer a"),
+        );
+        assert_eq!(
+            authenticate(&validator, &scopes, &headers).await,
+            Ok(Principal::new("user1", ["read"]))
+        );
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            axum::http::HeaderValue::from_static("bearer a"),
+        );
+        assert_eq!(
+            authenticate(&validator, &scopes, &headers).await,
+            Ok(Principal::new("user1", ["read"]))
+        );
+        let scopes = ["read".to_owned(), "write".to_owned()];
+        assert_eq!(
+            authenticate(&validator, &scopes, &headers).await,
+            Err(AuthError::InsufficientScope(scopes.to_vec()))
+        );
+    }
+}
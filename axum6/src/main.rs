@@ -1,3 +1,5 @@
+mod auth;
+
 async fn root() -> &'static str {
     "Hello, world!"
 }
@@ -19,151 +21,69 @@ async fn create_user(
     axum::response::Json(json)
 }
 
+/// The secret of the JWTs accepted by `/private/d`.
+const JWT_SECRET: &[u8] = b"secret";
+
 fn private_router() -> axum::Router<()> {
+    let tokens = std::sync::Arc::new(
+        auth::OpaqueTokenValidator::default()
+            .with_token("a", auth::Principal::new("user-a", ["a"]))
+            .with_token("b", auth::Principal::new("user-b", ["b"])),
+    );
     axum::Router::new()
-        .merge(private_a())
-        .merge(private_b())
-        .merge(private_c())
+        .merge(private_a(&tokens))
+        .merge(private_b(&tokens))
+        .merge(private_c(&tokens))
         .merge(private_d())
-        .merge(private_e())
-}
-
-struct ExtractorA;
-
-impl axum::extract::FromRequestParts<()> for ExtractorA {
-    type Rejection = axum::http::StatusCode;
-
-    fn from_request_parts(
-        parts: &mut axum::http::request::Parts,
-        _state: &(),
-    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
-        async {
-            match parts.headers.get(axum::http::header::AUTHORIZATION) {
-                Some(header_value) => {
-                    // 本来 token は base64 encoded
-                    if header_value.as_bytes() == b"Bearer a" {
-                        Ok(ExtractorA)
-                    } else {
-                        Err(axum::http::StatusCode::FORBIDDEN)
-                    }
-                }
-                None => Err(axum::http::StatusCode::UNAUTHORIZED),
-            }
-        }
-    }
+        .merge(private_e(&tokens))
 }
 
-fn private_a() -> axum::Router<()> {
+fn private_a(tokens: &std::sync::Arc<auth::OpaqueTokenValidator>) -> axum::Router<()> {
     axum::Router::new()
         .route("/private/a", axum::routing::get(|| async { "/private/a" }))
-        .route_layer(axum::middleware::from_extractor::<ExtractorA>())
+        .route_layer(auth::AuthLayer::from_arc(tokens.clone()).require_scope("a"))
 }
 
-struct ExtractorB;
-
-impl axum::extract::FromRequestParts<()> for ExtractorB {
-    type Rejection = axum::http::StatusCode;
-
-    fn from_request_parts(
-        parts: &mut axum::http::request::Parts,
-        _state: &(),
-    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
-        async {
-            match parts.headers.get(axum::http::header::AUTHORIZATION) {
-                Some(header_value) => {
-                    // 本来 token は base64 encoded
-                    if header_value.as_bytes() == b"Bearer b" {
-                        Ok(ExtractorB)
-                    } else {
-                        Err(axum::http::StatusCode::FORBIDDEN)
-                    }
-                }
-                None => Err(axum::http::StatusCode::UNAUTHORIZED),
-            }
-        }
-    }
-}
-
-fn private_b() -> axum::Router<()> {
+fn private_b(tokens: &std::sync::Arc<auth::OpaqueTokenValidator>) -> axum::Router<()> {
     axum::Router::new()
         .route("/private/b", axum::routing::get(|| async { "/private/b" }))
-        .route_layer(axum::middleware::from_extractor::<ExtractorB>())
+        .route_layer(auth::AuthLayer::from_arc(tokens.clone()).require_scope("b"))
 }
 
-fn private_c() -> axum::Router<()> {
-    axum::Router::new().route("/private/c", axum::routing::get(private_c_handler))
-}
-
-async fn private_c_handler(ExtractorB: ExtractorB) -> impl axum::response::IntoResponse {
-    "/private/c"
-}
-
-trait Validator {
-    fn validate(&self, token: &str) -> bool;
+fn private_c(tokens: &std::sync::Arc<auth::OpaqueTokenValidator>) -> axum::Router<()> {
+    axum::Router::new()
+        .route("/private/c", axum::routing::get(private_c_handler))
+        .route_layer(auth::AuthLayer::from_arc(tokens.clone()).require_scope("b"))
 }
 
-struct ExtractorC;
-
-impl<S> axum::extract::FromRequestParts<S> for ExtractorC
-where
-    S: Validator + Send + Sync,
-{
-    type Rejection = axum::http::StatusCode;
-
-    fn from_request_parts(
-        parts: &mut axum::http::request::Parts,
-        state: &S,
-    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
-        async {
-            match parts.headers.get(axum::http::header::AUTHORIZATION) {
-                Some(header_value) => match header_value.as_bytes().strip_prefix(b"Bearer ") {
-                    None => Err(axum::http::StatusCode::FORBIDDEN),
-                    Some(token) => {
-                        let s = String::from_utf8(token.to_vec())
-                            .map_err(|_| axum::http::StatusCode::FORBIDDEN)?;
-                        if state.validate(&s) {
-                            Ok(ExtractorC)
-                        } else {
-                            Err(axum::http::StatusCode::FORBIDDEN)
-                        }
-                    }
-                },
-                None => Err(axum::http::StatusCode::UNAUTHORIZED),
-            }
-        }
-    }
+async fn private_c_handler(
+    axum::Extension(principal): axum::Extension<auth::Principal>,
+) -> impl axum::response::IntoResponse {
+    format!("/private/c {}", principal.subject)
 }
 
 fn private_d() -> axum::Router<()> {
+    let validator = auth::JwtValidator::new(
+        jsonwebtoken::DecodingKey::from_secret(JWT_SECRET),
+        jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256),
+    );
     axum::Router::new()
-        .route(
-            "/private/d",
-            axum::routing::get(private_d_handler::<StateD>),
-        )
-        .with_state(StateD)
+        .route("/private/d", axum::routing::get(private_d_handler))
+        .route_layer(auth::AuthLayer::new(validator).require_scope("d"))
 }
 
-async fn private_d_handler<S>(ExtractorC: ExtractorC) -> impl axum::response::IntoResponse {
+async fn private_d_handler() -> impl axum::response::IntoResponse {
     "/private/d"
 }
 
-#[derive(Clone)]
-struct StateD;
-
-impl Validator for StateD {
-    fn validate(&self, token: &str) -> bool {
-        token == "d"
-    }
-}
-
-fn private_e() -> axum::Router<()> {
+fn private_e(tokens: &std::sync::Arc<auth::OpaqueTokenValidator>) -> axum::Router<()> {
     axum::Router::new()
         .nest(
             "/private/e",
             axum::Router::new()
                 .route("/1", axum::routing::get(private_e_1_handler))
                 .route("/2", axum::routing::get(private_e_2_handler))
-                .route_layer(axum::middleware::from_extractor::<ExtractorB>()),
+                .route_layer(auth::AuthLayer::from_arc(tokens.clone()).require_scope("b")),
         )
         .route(
             "/private/e/3",
@@ -206,255 +126,234 @@ mod tests {
     use super::*;
 
     #[tokio::test]
-    async fn test_root() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .uri("/")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(router, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "Hello, world!");
-        Ok(())
+    async fn test_root() {
+        axum_test_client::TestClient::new(router())
+            .get("/")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("Hello, world!");
     }
 
     #[tokio::test]
-    async fn test_create_user() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::POST)
-            .uri("/users")
-            .header(axum::http::header::CONTENT_TYPE, "application/json")
-            .body(axum::body::Body::from(r#"{"name":"John Doe"}"#))?;
-        let response = send_request(router, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, r#"{"name":"John Doe"}"#);
-        Ok(())
+    async fn test_create_user() {
+        axum_test_client::TestClient::new(router())
+            .post("/users")
+            .json(&serde_json::json!({ "name": "John Doe" }))
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text(r#"{"name":"John Doe"}"#);
     }
 
     #[tokio::test]
-    async fn test_get_user() -> anyhow::Result<()> {
-        let router = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/users/1")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(router, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, r#"User 1"#);
-        Ok(())
+    async fn test_get_user() {
+        axum_test_client::TestClient::new(router())
+            .get("/users/1")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("User 1");
     }
 
     #[tokio::test]
-    async fn test_private_a() -> anyhow::Result<()> {
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
+    async fn test_private_a() {
+        let client = axum_test_client::TestClient::new(router());
+        client
             // no token
-            .uri("/private/a")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/a")
-            // invalid token
+            .get("/private/a")
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_header(axum::http::header::WWW_AUTHENTICATE, "Bearer")
+            .assert_text("");
+
+        client
+            .get("/private/a")
+            // unknown token
+            .header(axum::http::header::AUTHORIZATION, "Bearer c")
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_header(
+                axum::http::header::WWW_AUTHENTICATE,
+                r#"Bearer error="invalid_token""#,
+            )
+            .assert_text("");
+
+        client
+            .get("/private/a")
+            // the token without the scope
             .header(axum::http::header::AUTHORIZATION, "Bearer b")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::FORBIDDEN);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/a")
+            .await
+            .assert_status(axum::http::StatusCode::FORBIDDEN)
+            .assert_header(
+                axum::http::header::WWW_AUTHENTICATE,
+                r#"Bearer error="insufficient_scope", scope="a""#,
+            )
+            .assert_text("");
+
+        client
+            .get("/private/a")
             // valid token
             .header(axum::http::header::AUTHORIZATION, "Bearer a")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "/private/a");
-        Ok(())
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("/private/a");
     }
 
     #[tokio::test]
-    async fn test_private_b() -> anyhow::Result<()> {
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
+    async fn test_private_b() {
+        let client = axum_test_client::TestClient::new(router());
+        client
             // no token
-            .uri("/private/b")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/b")
+            .get("/private/b")
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_text("");
+
+        client
+            .get("/private/b")
             // invalid token
             .header(axum::http::header::AUTHORIZATION, "Bearer a")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::FORBIDDEN);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/b")
+            .await
+            .assert_status(axum::http::StatusCode::FORBIDDEN)
+            .assert_text("");
+
+        client
+            .get("/private/b")
             // valid token
             .header(axum::http::header::AUTHORIZATION, "Bearer b")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "/private/b");
-        Ok(())
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("/private/b");
     }
 
     #[tokio::test]
-    async fn test_private_c() -> anyhow::Result<()> {
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
+    async fn test_private_c() {
+        let client = axum_test_client::TestClient::new(router());
+        client
             // no token
-            .uri("/private/c")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/c")
+            .get("/private/c")
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_text("");
+
+        client
+            .get("/private/c")
             // invalid token
             .header(axum::http::header::AUTHORIZATION, "Bearer a")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::FORBIDDEN);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/c")
+            .await
+            .assert_status(axum::http::StatusCode::FORBIDDEN)
+            .assert_text("");
+
+        client
+            .get("/private/c")
             // valid token
             .header(axum::http::header::AUTHORIZATION, "Bearer b")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "/private/c");
-        Ok(())
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("/private/c user-b");
     }
 
     #[tokio::test]
     async fn test_private_d() -> anyhow::Result<()> {
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
+        let client = axum_test_client::TestClient::new(router());
+        client
             // no token
-            .uri("/private/d")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/d")
-            // invalid token
-            .header(axum::http::header::AUTHORIZATION, "Bearer c")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::FORBIDDEN);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/d")
-            // valid token
+            .get("/private/d")
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_text("");
+
+        client
+            .get("/private/d")
+            // not a JWT
             .header(axum::http::header::AUTHORIZATION, "Bearer d")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "/private/d");
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_header(
+                axum::http::header::WWW_AUTHENTICATE,
+                r#"Bearer error="invalid_token""#,
+            );
+
+        client
+            .get("/private/d")
+            // signed with the other secret
+            .header(
+                axum::http::header::AUTHORIZATION,
+                format!("Bearer {}", jwt(b"other", "d")?),
+            )
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED);
+
+        client
+            .get("/private/d")
+            // the token without the scope
+            .header(
+                axum::http::header::AUTHORIZATION,
+                format!("Bearer {}", jwt(JWT_SECRET, "a b")?),
+            )
+            .await
+            .assert_status(axum::http::StatusCode::FORBIDDEN)
+            .assert_header(
+                axum::http::header::WWW_AUTHENTICATE,
+                r#"Bearer error="insufficient_scope", scope="d""#,
+            );
+
+        client
+            .get("/private/d")
+            // valid token
+            .header(
+                axum::http::header::AUTHORIZATION,
+                format!("Bearer {}", jwt(JWT_SECRET, "a d")?),
+            )
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("/private/d");
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_private_e() -> anyhow::Result<()> {
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/e/1")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/e/1")
+    async fn test_private_e() {
+        let client = axum_test_client::TestClient::new(router());
+        client
+            .get("/private/e/1")
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_text("");
+
+        client
+            .get("/private/e/1")
             .header(axum::http::header::AUTHORIZATION, "Bearer b")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "/private/e/1");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/e/2")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
-        assert_eq!(response.into_body_string().await?, "");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/e/2")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("/private/e/1");
+
+        client
+            .get("/private/e/2")
+            .await
+            .assert_status(axum::http::StatusCode::UNAUTHORIZED)
+            .assert_text("");
+
+        client
+            .get("/private/e/2")
             .header(axum::http::header::AUTHORIZATION, "Bearer b")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "/private/e/2");
-
-        let app = router();
-        let request = axum::http::Request::builder()
-            .method(axum::http::Method::GET)
-            .uri("/private/e/3")
-            .body(axum::body::Body::empty())?;
-        let response = send_request(app, request).await?;
-        assert_eq!(response.status(), axum::http::StatusCode::OK);
-        assert_eq!(response.into_body_string().await?, "/private/e/3");
-        Ok(())
-    }
-
-    async fn send_request(
-        router: axum::Router<()>,
-        request: axum::http::Request<axum::body::Body>,
-    ) -> anyhow::Result<axum::response::Response<axum::body::Body>> {
-        let response = tower::ServiceExt::oneshot(router, request).await?;
-        Ok(response)
-    }
-
-    trait ResponseExt {
-        async fn into_body_string(self) -> anyhow::Result<String>;
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("/private/e/2");
+
+        client
+            .get("/private/e/3")
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_text("/private/e/3");
     }
 
-    impl ResponseExt for axum::response::Response<axum::body::Body> {
-        async fn into_body_string(self) -> anyhow::Result<String> {
-            let bytes = axum::body::to_bytes(self.into_body(), usize::MAX).await?;
-            Ok(String::from_utf8(bytes.to_vec())?)
-        }
+    fn jwt(secret: &[u8], scope: &str) -> anyhow::Result<String> {
+        let exp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs()
+            + 60;
+        Ok(jsonwebtoken::encode(
+            &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::HS256),
+            &serde_json::json!({ "sub": "user-d", "scope": scope, "exp": exp }),
+            &jsonwebtoken::EncodingKey::from_secret(secret),
+        )?)
     }
 }
@@ -1,7 +1,186 @@
-fn main() {}
+mod routes;
+
+#[derive(Clone, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
+struct User {
+    name: String,
+}
+
+#[derive(Clone, Default)]
+struct Users(std::sync::Arc<std::sync::Mutex<Vec<User>>>);
+
+async fn list_users(
+    axum::extract::State(users): axum::extract::State<Users>,
+) -> axum::Json<Vec<User>> {
+    axum::Json(users.0.lock().unwrap().clone())
+}
+
+async fn create_user(
+    axum::extract::State(users): axum::extract::State<Users>,
+    axum::Json(user): axum::Json<User>,
+) -> axum::Json<User> {
+    users.0.lock().unwrap().push(user.clone());
+    axum::Json(user)
+}
+
+async fn get_user(
+    axum::extract::State(users): axum::extract::State<Users>,
+    axum::extract::Path(user_id): axum::extract::Path<usize>,
+) -> Result<axum::Json<User>, axum::http::StatusCode> {
+    let users = users.0.lock().unwrap();
+    let user = users
+        .get(user_id)
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(axum::Json(user.clone()))
+}
+
+fn users() -> Result<routes::Routes<Users>, routes::RouteError> {
+    routes::Routes::new()
+        .route(
+            axum::http::Method::GET,
+            "/",
+            list_users,
+            routes::Operation::new()
+                .summary("List users")
+                .json_response::<Vec<User>>(axum::http::StatusCode::OK),
+        )?
+        .route(
+            axum::http::Method::POST,
+            "/",
+            create_user,
+            routes::Operation::new()
+                .summary("Create a user")
+                .json_body::<User>()
+                .json_response::<User>(axum::http::StatusCode::OK),
+        )?
+        .route(
+            axum::http::Method::GET,
+            "/{user_id}",
+            get_user,
+            routes::Operation::new()
+                .summary("Get a user")
+                .path_param::<usize>("user_id")
+                .json_response::<User>(axum::http::StatusCode::OK)
+                .response(axum::http::StatusCode::NOT_FOUND),
+        )
+}
+
+fn routes() -> Result<routes::Routes, routes::RouteError> {
+    let health = routes::Routes::new().route(
+        axum::http::Method::GET,
+        "/health",
+        || async { "OK" },
+        routes::Operation::new().summary("Health check"),
+    )?;
+    routes::Routes::new()
+        .merge(health)?
+        .nest("/users", users()?.with_state(Users::default()))
+}
+
+/// `axum7 openapi` prints the OpenAPI document.
+#[tokio::main]
+async fn main() {
+    let routes = routes().unwrap();
+    if std::env::args().nth(1).as_deref() == Some("openapi") {
+        let document = routes.openapi(env!("CARGO_PKG_NAME"), "0.0.0");
+        println!("{}", serde_json::to_string_pretty(&document).unwrap());
+        return;
+    }
+    for route in routes.routes() {
+        println!("{:<6} {}", route.method(), route.path());
+    }
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+    axum::serve(listener, routes.into_router()).await.unwrap();
+}
 
 #[cfg(test)]
 mod tests {
+    use super::routes;
+
+    #[tokio::test]
+    async fn test_routes() -> anyhow::Result<()> {
+        let routes = super::routes()?;
+        assert_eq!(
+            routes
+                .routes()
+                .iter()
+                .map(|it| it.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "GET /health",
+                "GET /users",
+                "POST /users",
+                "GET /users/{user_id}"
+            ]
+        );
+
+        let document = routes.openapi("axum7", "0.0.0");
+        assert_eq!(
+            document["paths"]["/users/{user_id}"]["get"]["parameters"][0]["schema"]["type"],
+            "integer"
+        );
+        assert_eq!(
+            document["paths"]["/users"]["post"]["requestBody"]["content"]["application/json"]["schema"],
+            serde_json::json!({ "$ref": "#/components/schemas/User" })
+        );
+
+        let client = axum_test_client::TestClient::new(routes.into_router());
+        client
+            .post("/users")
+            .json(&serde_json::json!({ "name": "John Doe" }))
+            .await
+            .assert_status(axum::http::StatusCode::OK)
+            .assert_json(&serde_json::json!({ "name": "John Doe" }));
+        client
+            .get("/users")
+            .await
+            .assert_json(&serde_json::json!([{ "name": "John Doe" }]));
+        client
+            .get("/users/0")
+            .await
+            .assert_json(&serde_json::json!({ "name": "John Doe" }));
+        client
+            .get("/users/1")
+            .await
+            .assert_status(axum::http::StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[test]
+    fn test_routes_duplicate() -> anyhow::Result<()> {
+        // `Routes` returns the errors instead of the panics of `test_merge_duplicate` and
+        // `test_nest_duplicate` and `test_wildcard`
+        fn router_a() -> Result<routes::Routes, routes::RouteError> {
+            routes::Routes::new().route(
+                axum::http::Method::GET,
+                "/a/1",
+                || async { "a_1" },
+                routes::Operation::new(),
+            )
+        }
+
+        let err = router_a()?.merge(router_a()?).err();
+        assert_eq!(
+            err.map(|it| it.to_string()).as_deref(),
+            Some("GET /a/1 conflicts with GET /a/1")
+        );
+
+        let nested = routes::Routes::new().route(
+            axum::http::Method::GET,
+            "/1",
+            || async { "nest a_1" },
+            routes::Operation::new(),
+        )?;
+        assert!(router_a()?.nest("/a", nested).is_err());
+
+        let err = routes::Routes::<()>::new()
+            .nest("/{*wildcard}", routes::Routes::new())
+            .err();
+        assert_eq!(
+            err,
+            Some(routes::RouteError::InvalidPrefix("/{*wildcard}".to_owned()))
+        );
+        Ok(())
+    }
 
     #[tokio::test]
     async fn test_merge() -> anyhow::Result<()> {
@@ -22,13 +201,11 @@ mod tests {
         }
 
         async fn f(path: &str, status: axum::http::StatusCode, body: &str) -> anyhow::Result<()> {
-            let router = router();
-            let request = axum::http::Request::builder()
-                .uri(path)
-                .body(axum::body::Body::empty())?;
-            let response = send_request(router, request).await?;
-            assert_eq!(response.status(), status);
-            assert_eq!(response.into_body_string().await?, body);
+            axum_test_client::TestClient::new(router())
+                .get(path)
+                .await
+                .assert_status(status)
+                .assert_text(body);
             Ok(())
         }
         f("/a/1", axum::http::StatusCode::OK, "a_1").await?;
@@ -60,13 +237,11 @@ mod tests {
         }
 
         async fn f(path: &str, status: axum::http::StatusCode, body: &str) -> anyhow::Result<()> {
-            let router = router();
-            let request = axum::http::Request::builder()
-                .uri(path)
-                .body(axum::body::Body::empty())?;
-            let response = send_request(router, request).await?;
-            assert_eq!(response.status(), status);
-            assert_eq!(response.into_body_string().await?, body);
+            axum_test_client::TestClient::new(router())
+                .get(path)
+                .await
+                .assert_status(status)
+                .assert_text(body);
             Ok(())
         }
         f("/a/1", axum::http::StatusCode::OK, "a_1").await?;
@@ -110,13 +285,11 @@ mod tests {
         }
 
         async fn f(path: &str, status: axum::http::StatusCode, body: &str) -> anyhow::Result<()> {
-            let router = router();
-            let request = axum::http::Request::builder()
-                .uri(path)
-                .body(axum::body::Body::empty())?;
-            let response = send_request(router, request).await?;
-            assert_eq!(response.status(), status);
-            assert_eq!(response.into_body_string().await?, body);
+            axum_test_client::TestClient::new(router())
+                .get(path)
+                .await
+                .assert_status(status)
+                .assert_text(body);
             Ok(())
         }
         f("/a/1", axum::http::StatusCode::OK, "a_1").await?;
@@ -164,13 +337,11 @@ mod tests {
         }
 
         async fn f(path: &str, status: axum::http::StatusCode, body: &str) -> anyhow::Result<()> {
-            let router = router();
-            let request = axum::http::Request::builder()
-                .uri(path)
-                .body(axum::body::Body::empty())?;
-            let response = send_request(router, request).await?;
-            assert_eq!(response.status(), status);
-            assert_eq!(response.into_body_string().await?, body);
+            axum_test_client::TestClient::new(router())
+                .get(path)
+                .await
+                .assert_status(status)
+                .assert_text(body);
             Ok(())
         }
         f("/a/1", axum::http::StatusCode::OK, "a_1").await?;
@@ -236,13 +407,11 @@ mod tests {
         }
 
         async fn f(path: &str, status: axum::http::StatusCode, body: &str) -> anyhow::Result<()> {
-            let router = router();
-            let request = axum::http::Request::builder()
-                .uri(path)
-                .body(axum::body::Body::empty())?;
-            let response = send_request(router, request).await?;
-            assert_eq!(response.status(), status);
-            assert_eq!(response.into_body_string().await?, body);
+            axum_test_client::TestClient::new(router())
+                .get(path)
+                .await
+                .assert_status(status)
+                .assert_text(body);
             Ok(())
         }
         f("/a/1", axum::http::StatusCode::OK, "a_1").await?;
@@ -308,13 +477,11 @@ mod tests {
         }
 
         async fn f(path: &str, status: axum::http::StatusCode, body: &str) -> anyhow::Result<()> {
-            let router = router();
-            let request = axum::http::Request::builder()
-                .uri(path)
-                .body(axum::body::Body::empty())?;
-            let response = send_request(router, request).await?;
-            assert_eq!(response.status(), status);
-            assert_eq!(response.into_body_string().await?, body);
+            axum_test_client::TestClient::new(router())
+                .get(path)
+                .await
+                .assert_status(status)
+                .assert_text(body);
             Ok(())
         }
         f("/a/1", axum::http::StatusCode::OK, "a_1").await?;
@@ -368,23 +535,4 @@ mod tests {
     fn test_wildcard() {
         let _ = axum::Router::<()>::new().nest("/{*wildcard}", axum::Router::new());
     }
-
-    async fn send_request(
-        router: axum::Router<()>,
-        request: axum::http::Request<axum::body::Body>,
-    ) -> anyhow::Result<axum::response::Response<axum::body::Body>> {
-        let response = tower::ServiceExt::oneshot(router, request).await?;
-        Ok(response)
-    }
-
-    trait ResponseExt {
-        async fn into_body_string(self) -> anyhow::Result<String>;
-    }
-
-    impl ResponseExt for axum::response::Response<axum::body::Body> {
-        async fn into_body_string(self) -> anyhow::Result<String> {
-            let bytes = axum::body::to_bytes(self.into_body(), usize::MAX).await?;
-            Ok(String::from_utf8(bytes.to_vec())?)
-        }
-    }
 }
@@ -0,0 +1,642 @@
+//! The route table built alongside `axum::Router`.
+//!
+//! `Routes` registers the routes to both the router and the table, so it can list every method
+//! and path, reject the routes that `axum` would panic on (conflicts, wildcard nesting, ...)
+//! before serving, and generate the OpenAPI 3.1 document.
+
+use std::collections::BTreeMap;
+
+type SchemaFn = fn(&mut schemars::SchemaGenerator) -> schemars::Schema;
+
+fn schema_for<T: schemars::JsonSchema>(
+    generator: &mut schemars::SchemaGenerator,
+) -> schemars::Schema {
+    generator.subschema_for::<T>()
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum RouteError {
+    /// The path is not accepted by `axum` (e.g. `users`, `/users/:id`, `/{*rest}/a`).
+    InvalidPath(String),
+    /// The nest prefix is not accepted by `axum` (e.g. `/`, `/{*rest}`).
+    InvalidPrefix(String),
+    UnsupportedMethod(axum::http::Method),
+    /// The route conflicts with the registered one (e.g. `GET /users/{id}`).
+    Conflict {
+        route: String,
+        registered: String,
+    },
+}
+
+impl std::fmt::Display for RouteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteError::InvalidPath(path) => write!(f, "invalid path: {}", path),
+            RouteError::InvalidPrefix(prefix) => write!(f, "invalid nest prefix: {}", prefix),
+            RouteError::UnsupportedMethod(method) => write!(f, "unsupported method: {}", method),
+            RouteError::Conflict { route, registered } => {
+                write!(f, "{} conflicts with {}", route, registered)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+/// The documentation of a route for the OpenAPI document.
+#[derive(Clone, Debug, Default)]
+pub struct Operation {
+    summary: Option<String>,
+    path_params: BTreeMap<String, SchemaFn>,
+    request_body: Option<SchemaFn>,
+    responses: BTreeMap<u16, Option<SchemaFn>>,
+}
+
+impl Operation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// The path parameters are strings unless specified.
+    pub fn path_param<T: schemars::JsonSchema>(mut self, name: impl Into<String>) -> Self {
+        self.path_params.insert(name.into(), schema_for::<T>);
+        self
+    }
+
+    pub fn json_body<T: schemars::JsonSchema>(mut self) -> Self {
+        self.request_body = Some(schema_for::<T>);
+        self
+    }
+
+    pub fn response(mut self, status: axum::http::StatusCode) -> Self {
+        self.responses.insert(status.as_u16(), None);
+        self
+    }
+
+    pub fn json_response<T: schemars::JsonSchema>(
+        mut self,
+        status: axum::http::StatusCode,
+    ) -> Self {
+        self.responses
+            .insert(status.as_u16(), Some(schema_for::<T>));
+        self
+    }
+}
+
+/// A registered method and path.
+#[derive(Clone, Debug)]
+pub struct Route {
+    method: axum::http::Method,
+    path: String,
+    operation: Operation,
+}
+
+impl Route {
+    pub fn method(&self) -> &axum::http::Method {
+        &self.method
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns true if `axum` would reject both routes: the same method and path, or the
+    /// different parameters at the same position (e.g. `/users/{id}` and `/users/{user_id}`,
+    /// `/a/{id}` and `/a/{*rest}`) regardless of the methods.
+    fn conflicts_with(&self, other: &Route) -> bool {
+        for (a, b) in self.path.split('/').zip(other.path.split('/')) {
+            match (parse_param(a), parse_param(b)) {
+                (Some(a), Some(b)) if a != b => return true,
+                (Some(_), Some(_)) => {}
+                (None, None) if a == b => {}
+                // a static segment can be next to a parameter
+                _ => return false,
+            }
+        }
+        self.method == other.method && self.path == other.path
+    }
+
+    fn params(&self) -> impl Iterator<Item = &str> {
+        self.path
+            .split('/')
+            .filter_map(|it| parse_param(it).map(|(name, _)| name))
+    }
+
+    /// The path in the OpenAPI style (`{*rest}` -> `{rest}`).
+    fn openapi_path(&self) -> String {
+        self.path.replace("{*", "{")
+    }
+}
+
+impl std::fmt::Display for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.path)
+    }
+}
+
+/// Returns the name and whether it is a wildcard if the segment is `{name}` or `{*name}`.
+fn parse_param(segment: &str) -> Option<(&str, bool)> {
+    let param = segment.strip_prefix('{')?.strip_suffix('}')?;
+    match param.strip_prefix('*') {
+        Some(name) => Some((name, true)),
+        None => Some((param, false)),
+    }
+}
+
+fn is_valid_path(path: &str) -> bool {
+    let Some(path) = path.strip_prefix('/') else {
+        return false;
+    };
+    let segments = path.split('/').collect::<Vec<_>>();
+    segments
+        .iter()
+        .enumerate()
+        .all(|(i, segment)| match parse_param(segment) {
+            Some((name, wildcard)) => {
+                !name.is_empty()
+                    && !name.contains(['{', '}', '*'])
+                    && (!wildcard || i == segments.len() - 1)
+            }
+            None => !segment.contains(['{', '}']) && !segment.starts_with([':', '*']),
+        })
+}
+
+/// `axum::Router` with its route table.
+pub struct Routes<S = ()> {
+    router: axum::Router<S>,
+    routes: Vec<Route>,
+}
+
+impl<S> Routes<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            router: axum::Router::new(),
+            routes: Vec::new(),
+        }
+    }
+
+    /// `Router::route(path, on(method, handler))` that returns an error instead of panicking.
+    pub fn route<H, T>(
+        mut self,
+        method: axum::http::Method,
+        path: &str,
+        handler: H,
+        operation: Operation,
+    ) -> Result<Self, RouteError>
+    where
+        H: axum::handler::Handler<T, S>,
+        T: 'static,
+    {
+        if !is_valid_path(path) {
+            return Err(RouteError::InvalidPath(path.to_owned()));
+        }
+        let filter = axum::routing::MethodFilter::try_from(method.clone())
+            .map_err(|_| RouteError::UnsupportedMethod(method.clone()))?;
+        let route = Route {
+            method,
+            path: path.to_owned(),
+            operation,
+        };
+        self.check(&route)?;
+        self.router = self.router.route(path, axum::routing::on(filter, handler));
+        self.routes.push(route);
+        Ok(self)
+    }
+
+    /// `Router::merge` that returns an error instead of panicking.
+    pub fn merge(mut self, other: Routes<S>) -> Result<Self, RouteError> {
+        for route in &other.routes {
+            self.check(route)?;
+        }
+        self.router = self.router.merge(other.router);
+        self.routes.extend(other.routes);
+        Ok(self)
+    }
+
+    /// `Router::nest` that returns an error instead of panicking.
+    pub fn nest(mut self, prefix: &str, other: Routes<S>) -> Result<Self, RouteError> {
+        if prefix == "/" || prefix.ends_with('/') || !is_valid_path(prefix) || prefix.contains("{*")
+        {
+            return Err(RouteError::InvalidPrefix(prefix.to_owned()));
+        }
+        let routes = other
+            .routes
+            .into_iter()
+            .map(|route| Route {
+                path: match route.path.as_str() {
+                    "/" => prefix.to_owned(),
+                    path => format!("{}{}", prefix, path),
+                },
+                ..route
+            })
+            .collect::<Vec<_>>();
+        for route in &routes {
+            self.check(route)?;
+        }
+        self.router = self.router.nest(prefix, other.router);
+        self.routes.extend(routes);
+        Ok(self)
+    }
+
+    pub fn with_state<S2>(self, state: S) -> Routes<S2> {
+        Routes {
+            router: self.router.with_state(state),
+            routes: self.routes,
+        }
+    }
+
+    /// The routes in the registration order.
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    pub fn into_router(self) -> axum::Router<S> {
+        self.router
+    }
+
+    /// Generates the OpenAPI 3.1 document. The JSON body schemas are in `components/schemas`.
+    pub fn openapi(&self, title: &str, version: &str) -> serde_json::Value {
+        let mut generator = schemars::generate::SchemaSettings::draft2020_12()
+            .with(|it| {
+                it.definitions_path = "/components/schemas".into();
+                it.meta_schema = None;
+            })
+            .into_generator();
+        let mut paths = serde_json::Map::new();
+        for route in &self.routes {
+            let operation = &route.operation;
+            let parameters = route
+                .params()
+                .map(|name| {
+                    let schema = match operation.path_params.get(name) {
+                        Some(schema_fn) => schema_fn(&mut generator),
+                        None => generator.subschema_for::<String>(),
+                    };
+                    serde_json::json!({
+                        "name": name,
+                        "in": "path",
+                        "required": true,
+                        "schema": schema,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let responses = if operation.responses.is_empty() {
+                BTreeMap::from([(200, None)])
+            } else {
+                operation.responses.clone()
+            };
+            let responses = responses
+                .into_iter()
+                .map(|(status, schema_fn)| {
+                    let description = axum::http::StatusCode::from_u16(status)
+                        .ok()
+                        .and_then(|it| it.canonical_reason())
+                        .unwrap_or_default();
+                    let mut response = serde_json::json!({ "description": description });
+                    if let Some(schema_fn) = schema_fn {
+                        response["content"] = json_content(schema_fn(&mut generator));
+                    }
+                    (status.to_string(), response)
+                })
+                .collect::<serde_json::Map<_, _>>();
+
+            let mut value = serde_json::json!({ "responses": responses });
+            if let Some(summary) = &operation.summary {
+                value["summary"] = serde_json::json!(summary);
+            }
+            if !parameters.is_empty() {
+                value["parameters"] = serde_json::json!(parameters);
+            }
+            if let Some(schema_fn) = operation.request_body {
+                value["requestBody"] = serde_json::json!({
+                    "required": true,
+                    "content": json_content(schema_fn(&mut generator)),
+                });
+            }
+            paths
+                .entry(route.openapi_path())
+                .or_insert_with(|| serde_json::json!({}))
+                [route.method.as_str().to_ascii_lowercase()] = value;
+        }
+
+        let mut document = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": { "title": title, "version": version },
+            "paths": paths,
+        });
+        let schemas = generator.take_definitions(true);
+        if !schemas.is_empty() {
+            document["components"] = serde_json::json!({ "schemas": schemas });
+        }
+        document
+    }
+
+    fn check(&self, route: &Route) -> Result<(), RouteError> {
+        match self.routes.iter().find(|it| route.conflicts_with(it)) {
+            Some(registered) => Err(RouteError::Conflict {
+                route: route.to_string(),
+                registered: registered.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<S> Default for Routes<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn json_content(schema: schemars::Schema) -> serde_json::Value {
+    serde_json::json!({ "application/json": { "schema": schema } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(method: axum::http::Method, path: &str) -> Route {
+        Route {
+            method,
+            path: path.to_owned(),
+            operation: Operation::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_valid_path() {
+        for valid in [
+            "/",
+            "/a",
+            "/a/",
+            "/a/{id}",
+            "/a/{id}/b",
+            "/{*rest}",
+            "/a/{*rest}",
+        ] {
+            assert!(is_valid_path(valid), "{valid}");
+        }
+        for invalid in [
+            "",
+            "a",
+            "/a/:id",
+            "/a/*rest",
+            "/{}",
+            "/{*}",
+            "/{*rest}/a",
+            "/a{id}",
+        ] {
+            assert!(!is_valid_path(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_conflicts_with() {
+        use axum::http::Method;
+        let get = route(Method::GET, "/users/{id}");
+        assert!(get.conflicts_with(&route(Method::GET, "/users/{id}")));
+        assert!(!get.conflicts_with(&route(Method::POST, "/users/{id}")));
+        // the different parameter names at the same position
+        assert!(get.conflicts_with(&route(Method::POST, "/users/{user_id}")));
+        assert!(get.conflicts_with(&route(Method::GET, "/users/{*id}")));
+        assert!(!get.conflicts_with(&route(Method::GET, "/users/me")));
+        assert!(!get.conflicts_with(&route(Method::GET, "/users/{id}/")));
+        assert!(get.conflicts_with(&route(Method::GET, "/users/{user_id}/posts")));
+        assert!(!route(Method::GET, "/{*rest}").conflicts_with(&route(Method::GET, "/a")));
+    }
+
+    #[test]
+    fn test_route_errors() {
+        fn routes() -> Routes {
+            Routes::new()
+                .route(
+                    axum::http::Method::GET,
+                    "/a/1",
+                    || async {},
+                    Operation::new(),
+                )
+                .unwrap()
+        }
+
+        let err = routes()
+            .route(
+                axum::http::Method::GET,
+                "/a/1",
+                || async {},
+                Operation::new(),
+            )
+            .err();
+        assert_eq!(
+            err.map(|it| it.to_string()).as_deref(),
+            Some("GET /a/1 conflicts with GET /a/1")
+        );
+        // merge and nest
+        let err = routes().merge(routes()).err();
+        assert_eq!(
+            err,
+            Some(RouteError::Conflict {
+                route: "GET /a/1".to_owned(),
+                registered: "GET /a/1".to_owned(),
+            })
+        );
+        let nested = Routes::new()
+            .route(axum::http::Method::GET, "/1", || async {}, Operation::new())
+            .unwrap();
+        assert!(matches!(
+            routes().nest("/a", nested),
+            Err(RouteError::Conflict { .. })
+        ));
+
+        assert!(matches!(
+            routes().route(
+                axum::http::Method::GET,
+                "/a/:id",
+                || async {},
+                Operation::new()
+            ),
+            Err(RouteError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            routes().route(
+                axum::http::Method::from_bytes(b"PURGE").unwrap(),
+                "/a/2",
+                || async {},
+                Operation::new()
+            ),
+            Err(RouteError::UnsupportedMethod(_))
+        ));
+        for prefix in ["/", "/a/", "a", "/{*wildcard}"] {
+            assert_eq!(
+                routes().nest(prefix, Routes::new()).err(),
+                Some(RouteError::InvalidPrefix(prefix.to_owned())),
+                "{prefix}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes() -> anyhow::Result<()> {
+        fn router_a() -> Result<Routes<&'static str>, RouteError> {
+            Routes::new()
+                .route(
+                    axum::http::Method::GET,
+                    "/",
+                    |axum::extract::State(s): axum::extract::State<&'static str>| async move { s },
+                    Operation::new(),
+                )?
+                .route(
+                    axum::http::Method::GET,
+                    "/{id}",
+                    |axum::extract::Path(id): axum::extract::Path<String>| async move { id },
+                    Operation::new(),
+                )
+        }
+
+        let routes = Routes::<()>::new()
+            .route(
+                axum::http::Method::GET,
+                "/",
+                || async { "root" },
+                Operation::new(),
+            )?
+            .route(
+                axum::http::Method::POST,
+                "/",
+                || async { "post" },
+                Operation::new(),
+            )?
+            .nest("/a", router_a()?.with_state("a"))?;
+        assert_eq!(
+            routes
+                .routes()
+                .iter()
+                .map(|it| it.to_string())
+                .collect::<Vec<_>>(),
+            ["GET /", "POST /", "GET /a", "GET /a/{id}"]
+        );
+
+        // the table matches the router
+        let client = axum_test_client::TestClient::new(routes.into_router());
+        client.get("/").await.assert_text("root");
+        client.post("/").await.assert_text("post");
+        client.get("/a").await.assert_text("a");
+        client.get("/a/1").await.assert_text("1");
+        client
+            .get("/b")
+            .await
+            .assert_status(axum::http::StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[test]
+    fn test_openapi() -> anyhow::Result<()> {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct User {
+            name: String,
+        }
+
+        let routes = Routes::<()>::new()
+            .route(
+                axum::http::Method::GET,
+                "/users/{user_id}",
+                || async {},
+                Operation::new()
+                    .summary("Get a user")
+                    .path_param::<u32>("user_id")
+                    .json_response::<User>(axum::http::StatusCode::OK)
+                    .response(axum::http::StatusCode::NOT_FOUND),
+            )?
+            .route(
+                axum::http::Method::PUT,
+                "/users/{user_id}",
+                || async {},
+                Operation::new().json_body::<User>(),
+            )?
+            .route(
+                axum::http::Method::GET,
+                "/files/{*path}",
+                || async {},
+                Operation::new(),
+            )?;
+        let user_id = serde_json::json!({
+            "name": "user_id",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "integer", "format": "uint32", "minimum": 0 },
+        });
+        let user = serde_json::json!({ "$ref": "#/components/schemas/User" });
+        assert_eq!(
+            routes.openapi("example", "1.0.0"),
+            serde_json::json!({
+                "openapi": "3.1.0",
+                "info": { "title": "example", "version": "1.0.0" },
+                "paths": {
+                    "/users/{user_id}": {
+                        "get": {
+                            "summary": "Get a user",
+                            "parameters": [user_id],
+                            "responses": {
+                                "200": {
+                                    "description": "OK",
+                                    "content": { "application/json": { "schema": user } },
+                                },
+                                "404": { "description": "Not Found" },
+                            },
+                        },
+                        "put": {
+                            "parameters": [
+                                {
+                                    "name": "user_id",
+                                    "in": "path",
+                                    "required": true,
+                                    "schema": { "type": "string" },
+                                },
+                            ],
+                            "requestBody": {
+                                "required": true,
+                                "content": { "application/json": { "schema": user } },
+                            },
+                            "responses": { "200": { "description": "OK" } },
+                        },
+                    },
+                    "/files/{path}": {
+                        "get": {
+                            "parameters": [
+                                {
+                                    "name": "path",
+                                    "in": "path",
+                                    "required": true,
+                                    "schema": { "type": "string" },
+                                },
+                            ],
+                            "responses": { "200": { "description": "OK" } },
+                        },
+                    },
+                },
+                "components": {
+                    "schemas": {
+                        "User": {
+                            "type": "object",
+                            "properties": { "name": { "type": "string" } },
+                            "required": ["name"],
+                        },
+                    },
+                },
+            })
+        );
+        Ok(())
+    }
+}
@@ -35,53 +35,59 @@ mod tests {
         let app = axum::Router::<()>::new()
             .route("/", axum::routing::get(handler))
             .layer(axum::middleware::from_fn(my_middleware_fn));
-        let response = send_request(
-            app,
-            axum::http::Request::builder()
-                .method("GET")
-                .uri("/")
-                .body(axum::body::Body::empty())?,
-        )
-        .await?;
-        assert_eq!(response.status(), StatusCode::OK);
-        assert_eq!(
-            response.into_body_string().await?,
-            "X-NAME: Some(\"bouzuya\")"
-        );
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .await
+            .assert_status(StatusCode::OK)
+            .assert_text("X-NAME: Some(\"bouzuya\")");
 
         let app = axum::Router::<()>::new()
             .route("/", axum::routing::get(handler))
             .layer(axum::middleware::from_fn(my_middleware_fn));
-        let response = send_request(
-            app,
-            axum::http::Request::builder()
-                .header("X-NAME", "dummy")
-                .method("GET")
-                .uri("/")
-                .body(axum::body::Body::empty())?,
-        )
-        .await?;
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-        assert_eq!(response.into_body_string().await?, "");
+        axum_test_client::TestClient::new(app)
+            .get("/")
+            .header("X-NAME", "dummy")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST)
+            .assert_text("");
         Ok(())
     }
 
-    async fn send_request(
-        router: axum::Router<()>,
-        request: axum::http::Request<axum::body::Body>,
-    ) -> anyhow::Result<axum::response::Response<axum::body::Body>> {
-        let response = tower::ServiceExt::oneshot(router, request).await?;
-        Ok(response)
-    }
+    #[tokio::test]
+    async fn test_request_trace() -> anyhow::Result<()> {
+        async fn my_middleware_fn(
+            // RequestTraceLayer (the outer layer) inserts the request ID
+            axum::Extension(request_id): axum::Extension<axum_request_trace::RequestId>,
+            request: axum::http::Request<axum::body::Body>,
+            next: axum::middleware::Next,
+        ) -> axum::response::Response<axum::body::Body> {
+            if request_id.as_str() == "blocked" {
+                return StatusCode::FORBIDDEN.into_response();
+            }
+            next.run(request).await
+        }
 
-    trait ResponseExt {
-        async fn into_body_string(self) -> anyhow::Result<String>;
-    }
+        let app = || {
+            axum::Router::<()>::new()
+                .route("/", axum::routing::get("OK"))
+                .layer(axum::middleware::from_fn(my_middleware_fn))
+                .layer(axum_request_trace::RequestTraceLayer)
+        };
+        axum_test_client::TestClient::new(app())
+            .get("/")
+            .header("X-Request-Id", "req-1")
+            .await
+            .assert_status(StatusCode::OK)
+            .assert_header("x-request-id", "req-1")
+            .assert_text("OK");
 
-    impl ResponseExt for axum::response::Response<axum::body::Body> {
-        async fn into_body_string(self) -> anyhow::Result<String> {
-            let bytes = axum::body::to_bytes(self.into_body(), usize::MAX).await?;
-            Ok(String::from_utf8(bytes.to_vec())?)
-        }
+        // the response of the middleware has the request ID too
+        axum_test_client::TestClient::new(app())
+            .get("/")
+            .header("X-Request-Id", "blocked")
+            .await
+            .assert_status(StatusCode::FORBIDDEN)
+            .assert_header("x-request-id", "blocked");
+        Ok(())
     }
 }
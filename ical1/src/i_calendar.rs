@@ -3,12 +3,34 @@ mod calendar_properties;
 mod component_properties;
 mod error;
 mod i_calendar_object;
+mod jcal;
+mod parser;
 mod property_parameters;
+mod recurrence;
+mod scheduling;
+mod structured;
 mod utils;
 mod value_type;
+mod xcal;
 
+pub use self::calendar_components::Alarm;
+pub use self::calendar_components::AlarmError;
 pub use self::calendar_components::Event;
 pub use self::calendar_components::EventError;
+pub use self::calendar_components::FreeBusy;
+pub use self::calendar_components::FreeBusyError;
+pub use self::calendar_components::IanaComponent;
+pub use self::calendar_components::IanaComponentError;
+pub use self::calendar_components::Journal;
+pub use self::calendar_components::JournalError;
+pub use self::calendar_components::NonStandardComponent;
+pub use self::calendar_components::NonStandardComponentError;
+pub use self::calendar_components::Observance;
+pub use self::calendar_components::ObservanceError;
+pub use self::calendar_components::TimeZone;
+pub use self::calendar_components::TimeZoneError;
+pub use self::calendar_components::Todo;
+pub use self::calendar_components::TodoError;
 pub use self::calendar_properties::CalendarScale;
 pub use self::calendar_properties::CalendarScaleError;
 pub use self::calendar_properties::Method;
@@ -17,22 +39,79 @@ pub use self::calendar_properties::ProductIdentifier;
 pub use self::calendar_properties::ProductIdentifierError;
 pub use self::calendar_properties::Version;
 pub use self::calendar_properties::VersionError;
+pub use self::component_properties::Action;
+pub use self::component_properties::ActionError;
+pub use self::component_properties::ActionValue;
+pub use self::component_properties::Attachment;
+pub use self::component_properties::AttachmentError;
+pub use self::component_properties::Attendee;
+pub use self::component_properties::AttendeeError;
 pub use self::component_properties::Categories;
 pub use self::component_properties::CategoriesError;
 pub use self::component_properties::Classification;
 pub use self::component_properties::ClassificationError;
+pub use self::component_properties::DateTimeCompleted;
+pub use self::component_properties::DateTimeCompletedError;
 pub use self::component_properties::DateTimeCreated;
 pub use self::component_properties::DateTimeCreatedError;
+pub use self::component_properties::DateTimeDue;
+pub use self::component_properties::DateTimeDueError;
 pub use self::component_properties::DateTimeEnd;
 pub use self::component_properties::DateTimeEndError;
 pub use self::component_properties::DateTimeStamp;
 pub use self::component_properties::DateTimeStampError;
 pub use self::component_properties::DateTimeStart;
 pub use self::component_properties::DateTimeStartError;
+pub use self::component_properties::Description;
+pub use self::component_properties::DescriptionError;
+pub use self::component_properties::DurationProperty;
+pub use self::component_properties::DurationPropertyError;
+pub use self::component_properties::ExceptionDateTimes;
+pub use self::component_properties::ExceptionDateTimesError;
+pub use self::component_properties::FreeBusyTime;
+pub use self::component_properties::FreeBusyTimeError;
+pub use self::component_properties::GeographicPosition;
+pub use self::component_properties::GeographicPositionError;
+pub use self::component_properties::IanaProperty;
+pub use self::component_properties::IanaPropertyError;
 pub use self::component_properties::LastModified;
 pub use self::component_properties::LastModifiedError;
+pub use self::component_properties::Location;
+pub use self::component_properties::LocationError;
+pub use self::component_properties::NonStandardProperty;
+pub use self::component_properties::NonStandardPropertyError;
+pub use self::component_properties::Organizer;
+pub use self::component_properties::OrganizerError;
+pub use self::component_properties::PercentComplete;
+pub use self::component_properties::PercentCompleteError;
+pub use self::component_properties::Priority;
+pub use self::component_properties::PriorityError;
+pub use self::component_properties::RecurrenceDateTimes;
+pub use self::component_properties::RecurrenceDateTimesError;
+pub use self::component_properties::RecurrenceRule;
+pub use self::component_properties::RecurrenceRuleError;
+pub use self::component_properties::RepeatCount;
+pub use self::component_properties::RepeatCountError;
+pub use self::component_properties::SequenceNumber;
+pub use self::component_properties::SequenceNumberError;
+pub use self::component_properties::Status;
+pub use self::component_properties::StatusError;
+pub use self::component_properties::StatusValue;
 pub use self::component_properties::Summary;
 pub use self::component_properties::SummaryError;
+pub use self::component_properties::TimeTransparency;
+pub use self::component_properties::TimeTransparencyError;
+pub use self::component_properties::TimeTransparencyValue;
+pub use self::component_properties::TimeZoneName;
+pub use self::component_properties::TimeZoneNameError;
+pub use self::component_properties::TimeZoneOffsetFrom;
+pub use self::component_properties::TimeZoneOffsetFromError;
+pub use self::component_properties::TimeZoneOffsetTo;
+pub use self::component_properties::TimeZoneOffsetToError;
+pub use self::component_properties::Trigger;
+pub use self::component_properties::TriggerError;
+pub use self::component_properties::UniformResourceLocator;
+pub use self::component_properties::UniformResourceLocatorError;
 pub use self::component_properties::UniqueIdentifier;
 pub use self::component_properties::UniqueIdentifierError;
 pub use self::error::Error;
@@ -40,5 +119,49 @@ pub use self::i_calendar_object::ICalendarObject;
 pub use self::i_calendar_object::ICalendarObjectError;
 pub use self::i_calendar_object::ICalendarStream;
 pub use self::i_calendar_object::ICalendarStreamError;
+pub use self::parser::ParseError;
+pub use self::property_parameters::AlarmTriggerRelationship;
+pub use self::property_parameters::AlarmTriggerRelationshipError;
+pub use self::property_parameters::AlternateTextRepresentation;
+pub use self::property_parameters::AlternateTextRepresentationError;
+pub use self::property_parameters::CalendarUserType;
+pub use self::property_parameters::CalendarUserTypeError;
+pub use self::property_parameters::CommonName;
+pub use self::property_parameters::CommonNameError;
+pub use self::property_parameters::FreeBusyTimeType;
+pub use self::property_parameters::FreeBusyTimeTypeError;
+pub use self::property_parameters::IanaToken;
+pub use self::property_parameters::IanaTokenError;
+pub use self::property_parameters::ParticipationRole;
+pub use self::property_parameters::ParticipationRoleError;
+pub use self::property_parameters::ParticipationStatus;
+pub use self::property_parameters::ParticipationStatusError;
+pub use self::property_parameters::RsvpExpectation;
+pub use self::property_parameters::RsvpExpectationError;
+pub use self::property_parameters::TimeZoneIdentifier;
+pub use self::property_parameters::TimeZoneIdentifierError;
+pub use self::property_parameters::XName;
+pub use self::property_parameters::XNameError;
+pub use self::recurrence::Occurrences;
+pub use self::scheduling::SchedulingError;
+pub use self::value_type::CalendarUserAddress;
+pub use self::value_type::CalendarUserAddressError;
+pub use self::value_type::DateTime;
+pub use self::value_type::DateTimeError;
+pub use self::value_type::Duration;
+pub use self::value_type::DurationError;
+pub use self::value_type::Float;
+pub use self::value_type::FloatError;
+pub use self::value_type::Frequency;
+pub use self::value_type::Period;
+pub use self::value_type::PeriodError;
+pub use self::value_type::Recur;
+pub use self::value_type::RecurError;
 pub use self::value_type::Text;
 pub use self::value_type::TextError;
+pub use self::value_type::Uri;
+pub use self::value_type::UriError;
+pub use self::value_type::UtcOffset;
+pub use self::value_type::UtcOffsetError;
+pub use self::value_type::Weekday;
+pub use self::value_type::WeekdayNum;
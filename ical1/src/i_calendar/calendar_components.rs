@@ -1,4 +1,28 @@
+mod alarm;
 mod event;
+mod free_busy;
+mod iana_component;
+mod journal;
+mod non_standard_component;
+mod time_zone;
+mod todo;
 
+pub use self::alarm::Alarm;
+pub use self::alarm::AlarmError;
 pub use self::event::Event;
 pub use self::event::EventError;
+pub use self::free_busy::FreeBusy;
+pub use self::free_busy::FreeBusyError;
+pub use self::iana_component::IanaComponent;
+pub use self::iana_component::IanaComponentError;
+pub use self::journal::Journal;
+pub use self::journal::JournalError;
+pub use self::non_standard_component::NonStandardComponent;
+pub use self::non_standard_component::NonStandardComponentError;
+pub use self::time_zone::Observance;
+pub use self::time_zone::ObservanceBuilder;
+pub use self::time_zone::ObservanceError;
+pub use self::time_zone::TimeZone;
+pub use self::time_zone::TimeZoneError;
+pub use self::todo::Todo;
+pub use self::todo::TodoError;
@@ -0,0 +1,447 @@
+use crate::i_calendar::component_properties::{
+    Action, ActionValue, Attachment, Attendee, Description, DurationProperty, IanaProperty,
+    NonStandardProperty, RepeatCount, Summary, Trigger,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("alarm")]
+pub struct AlarmError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("action required")]
+    ActionRequired,
+    #[error("attendee required")]
+    AttendeeRequired,
+    #[error("description required")]
+    DescriptionRequired,
+    #[error("duration and repeat must occur together")]
+    DurationAndRepeatPairRequired,
+    #[error("{0} not allowed for {1} action")]
+    PropertyNotAllowed(&'static str, &'static str),
+    #[error("summary required")]
+    SummaryRequired,
+    #[error("trigger required")]
+    TriggerRequired,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6.6>
+///
+/// alarmc     = "BEGIN" ":" "VALARM" CRLF
+///              (audioprop / dispprop / emailprop)
+///              "END" ":" "VALARM" CRLF
+///
+/// audioprop  = *(
+///              ; 'action' and 'trigger' are both REQUIRED,
+///              ; but MUST NOT occur more than once.
+///              action / trigger /
+///              ; 'duration' and 'repeat' are both OPTIONAL,
+///              ; and MUST NOT occur more than once each;
+///              ; but if one occurs, so MUST the other.
+///              duration / repeat /
+///              ; The following is OPTIONAL,
+///              ; but MUST NOT occur more than once.
+///              attach /
+///              ; The following is OPTIONAL,
+///              ; and MAY occur more than once.
+///              x-prop / iana-prop
+///              )
+///
+/// dispprop   = *(
+///              ; The following are REQUIRED,
+///              ; but MUST NOT occur more than once.
+///              action / description / trigger /
+///              duration / repeat /
+///              x-prop / iana-prop
+///              )
+///
+/// emailprop  = *(
+///              ; The following are all REQUIRED,
+///              ; but MUST NOT occur more than once.
+///              action / description / trigger / summary /
+///              ; The following is REQUIRED,
+///              ; and MAY occur more than once.
+///              attendee /
+///              duration / repeat /
+///              ; The following are OPTIONAL,
+///              ; and MAY occur more than once.
+///              attach / x-prop / iana-prop
+///              )
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Alarm {
+    action: Action,
+    trigger: Trigger,
+    description: Option<Description>,
+    summary: Option<Summary>,
+    duration: Option<DurationProperty>,
+    repeat: Option<RepeatCount>,
+    attach: Vec<Attachment>,
+    attendee: Vec<Attendee>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+}
+
+impl Alarm {
+    pub fn builder() -> AlarmBuilder {
+        AlarmBuilder::new()
+    }
+
+    fn from_builder(builder: AlarmBuilder) -> Result<Self, AlarmError> {
+        let action = builder.action.ok_or(ErrorInner::ActionRequired)?;
+        let action_name = action.value().as_str();
+        if builder.duration.is_some() != builder.repeat.is_some() {
+            return Err(ErrorInner::DurationAndRepeatPairRequired)?;
+        }
+        match action.value() {
+            ActionValue::Audio => {
+                if builder.description.is_some() {
+                    return Err(ErrorInner::PropertyNotAllowed("description", action_name))?;
+                }
+                if builder.summary.is_some() {
+                    return Err(ErrorInner::PropertyNotAllowed("summary", action_name))?;
+                }
+                if !builder.attendee.is_empty() {
+                    return Err(ErrorInner::PropertyNotAllowed("attendee", action_name))?;
+                }
+                if builder.attach.len() > 1 {
+                    return Err(ErrorInner::PropertyNotAllowed(
+                        "more than one attach",
+                        action_name,
+                    ))?;
+                }
+            }
+            ActionValue::Display => {
+                if builder.description.is_none() {
+                    return Err(ErrorInner::DescriptionRequired)?;
+                }
+                if builder.summary.is_some() {
+                    return Err(ErrorInner::PropertyNotAllowed("summary", action_name))?;
+                }
+                if !builder.attendee.is_empty() {
+                    return Err(ErrorInner::PropertyNotAllowed("attendee", action_name))?;
+                }
+                if !builder.attach.is_empty() {
+                    return Err(ErrorInner::PropertyNotAllowed("attach", action_name))?;
+                }
+            }
+            ActionValue::Email => {
+                if builder.description.is_none() {
+                    return Err(ErrorInner::DescriptionRequired)?;
+                }
+                if builder.summary.is_none() {
+                    return Err(ErrorInner::SummaryRequired)?;
+                }
+                if builder.attendee.is_empty() {
+                    return Err(ErrorInner::AttendeeRequired)?;
+                }
+            }
+        }
+        Ok(Self {
+            action,
+            trigger: builder.trigger.ok_or(ErrorInner::TriggerRequired)?,
+            description: builder.description,
+            summary: builder.summary,
+            duration: builder.duration,
+            repeat: builder.repeat,
+            attach: builder.attach,
+            attendee: builder.attendee,
+            x_prop: builder.x_prop,
+            iana_prop: builder.iana_prop,
+        })
+    }
+
+    pub fn action(&self) -> ActionValue {
+        self.action.value()
+    }
+
+    pub fn trigger(&self) -> &Trigger {
+        &self.trigger
+    }
+
+    pub(in crate::i_calendar) fn into_string(self) -> String {
+        let mut lines = vec![];
+        lines.push("BEGIN:VALARM\r\n".to_owned());
+        lines.push(self.action.to_escaped());
+        lines.push(self.trigger.to_escaped());
+        if let Some(duration) = self.duration {
+            lines.push(duration.to_escaped());
+        }
+        if let Some(repeat) = self.repeat {
+            lines.push(repeat.to_escaped());
+        }
+        if let Some(summary) = self.summary {
+            lines.push(summary.into_string());
+        }
+        if let Some(description) = self.description {
+            lines.push(description.to_escaped());
+        }
+        for attendee in self.attendee {
+            lines.push(attendee.to_escaped());
+        }
+        for attach in self.attach {
+            lines.push(attach.to_escaped());
+        }
+        for x_prop in self.x_prop {
+            lines.push(x_prop.to_escaped());
+        }
+        for iana_prop in self.iana_prop {
+            lines.push(iana_prop.to_escaped());
+        }
+        lines.push("END:VALARM\r\n".to_owned());
+        lines.join("")
+    }
+}
+
+pub struct AlarmBuilder {
+    action: Option<Action>,
+    trigger: Option<Trigger>,
+    description: Option<Description>,
+    summary: Option<Summary>,
+    duration: Option<DurationProperty>,
+    repeat: Option<RepeatCount>,
+    attach: Vec<Attachment>,
+    attendee: Vec<Attendee>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+}
+
+impl AlarmBuilder {
+    fn new() -> Self {
+        Self {
+            action: None,
+            trigger: None,
+            description: None,
+            summary: None,
+            duration: None,
+            repeat: None,
+            attach: Vec::new(),
+            attendee: Vec::new(),
+            x_prop: Vec::new(),
+            iana_prop: Vec::new(),
+        }
+    }
+
+    pub fn action(mut self, action: Action) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn add_attach(mut self, attach: Attachment) -> Self {
+        self.attach.push(attach);
+        self
+    }
+
+    pub fn add_attendee(mut self, attendee: Attendee) -> Self {
+        self.attendee.push(attendee);
+        self
+    }
+
+    pub fn add_iana_prop(mut self, iana_prop: IanaProperty) -> Self {
+        self.iana_prop.push(iana_prop);
+        self
+    }
+
+    pub fn add_x_prop(mut self, x_prop: NonStandardProperty) -> Self {
+        self.x_prop.push(x_prop);
+        self
+    }
+
+    pub fn build(self) -> Result<Alarm, AlarmError> {
+        Alarm::from_builder(self)
+    }
+
+    pub fn description(mut self, description: Description) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn duration(mut self, duration: DurationProperty) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn repeat(mut self, repeat: RepeatCount) -> Self {
+        self.repeat = Some(repeat);
+        self
+    }
+
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    pub fn trigger(mut self, trigger: Trigger) -> Self {
+        self.trigger = Some(trigger);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::{
+        property_parameters::AlarmTriggerRelationship,
+        value_type::{CalendarUserAddress, DateTime, Duration, Text, Uri},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Alarm>();
+
+        // audio
+        assert_eq!(
+            Alarm::builder()
+                .trigger(Trigger::absolute(DateTime::utc(1997, 3, 17, 13, 30, 0)?)?)
+                .repeat(RepeatCount::new(4)?)
+                .duration(DurationProperty::new(Duration::from_str("PT15M")?)?)
+                .action(Action::new(ActionValue::Audio)?)
+                .add_attach(Attachment::new(Uri::from_str(
+                    "ftp://example.com/pub/sounds/bell-01.aud"
+                )?)?)
+                .build()?
+                .into_string(),
+            [
+                "BEGIN:VALARM\r\n",
+                "ACTION:AUDIO\r\n",
+                "TRIGGER;VALUE=DATE-TIME:19970317T133000Z\r\n",
+                "DURATION:PT15M\r\n",
+                "REPEAT:4\r\n",
+                "ATTACH:ftp://example.com/pub/sounds/bell-01.aud\r\n",
+                "END:VALARM\r\n",
+            ]
+            .join("")
+        );
+
+        // display
+        assert_eq!(
+            Alarm::builder()
+                .trigger(Trigger::relative(Duration::from_str("-PT30M")?, None)?)
+                .action(Action::new(ActionValue::Display)?)
+                .description(Description::new(Text::from_unescaped(
+                    "Breakfast meeting with executive\nteam at 8:30 AM EST."
+                )?)?)
+                .build()?
+                .into_string(),
+            [
+                "BEGIN:VALARM\r\n",
+                "ACTION:DISPLAY\r\n",
+                "TRIGGER:-PT30M\r\n",
+                "DESCRIPTION:Breakfast meeting with executive\\nteam at 8:30 AM EST.\r\n",
+                "END:VALARM\r\n",
+            ]
+            .join("")
+        );
+
+        // email
+        assert_eq!(
+            Alarm::builder()
+                .trigger(Trigger::relative(
+                    Duration::from_str("-P2D")?,
+                    Some(AlarmTriggerRelationship::End)
+                )?)
+                .action(Action::new(ActionValue::Email)?)
+                .add_attendee(Attendee::new(CalendarUserAddress::from_str(
+                    "mailto:john_doe@example.com"
+                )?)?)
+                .summary(Summary::from_value("*** REMINDER: SEND AGENDA FOR WEEKLY STAFF MEETING ***")?)
+                .description(Description::new(Text::from_unescaped(
+                    "A draft agenda needs to be sent out to the attendees to the weekly managers meeting (MGR-LIST)."
+                )?)?)
+                .build()?
+                .into_string(),
+            [
+                "BEGIN:VALARM\r\n",
+                "ACTION:EMAIL\r\n",
+                "TRIGGER;RELATED=END:-P2D\r\n",
+                "SUMMARY:*** REMINDER: SEND AGENDA FOR WEEKLY STAFF MEETING ***\r\n",
+                "DESCRIPTION:A draft agenda needs to be sent out to the attendees to the weekly managers meeting (MGR-LIST).\r\n",
+                "ATTENDEE:mailto:john_doe@example.com\r\n",
+                "END:VALARM\r\n",
+            ]
+            .join("")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation() -> anyhow::Result<()> {
+        let display = || -> anyhow::Result<AlarmBuilder> {
+            Ok(Alarm::builder()
+                .trigger(Trigger::relative(Duration::from_str("-PT30M")?, None)?)
+                .action(Action::new(ActionValue::Display)?)
+                .description(Description::new(Text::from_unescaped("Reminder")?)?))
+        };
+        assert!(display()?.build().is_ok());
+
+        // action and trigger required
+        assert!(
+            Alarm::builder()
+                .action(Action::new(ActionValue::Audio)?)
+                .build()
+                .is_err()
+        );
+        assert!(
+            Alarm::builder()
+                .trigger(Trigger::relative(Duration::from_str("-PT30M")?, None)?)
+                .build()
+                .is_err()
+        );
+
+        // duration and repeat must occur together
+        assert!(display()?.repeat(RepeatCount::new(2)?).build().is_err());
+        assert!(
+            display()?
+                .duration(DurationProperty::new(Duration::from_str("PT5M")?)?)
+                .build()
+                .is_err()
+        );
+
+        // display requires description
+        assert!(
+            Alarm::builder()
+                .trigger(Trigger::relative(Duration::from_str("-PT30M")?, None)?)
+                .action(Action::new(ActionValue::Display)?)
+                .build()
+                .is_err()
+        );
+
+        // email requires summary and attendee
+        let email = || -> anyhow::Result<AlarmBuilder> {
+            Ok(Alarm::builder()
+                .trigger(Trigger::relative(Duration::from_str("-PT30M")?, None)?)
+                .action(Action::new(ActionValue::Email)?)
+                .description(Description::new(Text::from_unescaped("Reminder")?)?))
+        };
+        assert!(
+            email()?
+                .summary(Summary::from_value("Reminder")?)
+                .build()
+                .is_err()
+        );
+        assert!(
+            email()?
+                .add_attendee(Attendee::new(CalendarUserAddress::from_str(
+                    "mailto:john_doe@example.com"
+                )?)?)
+                .build()
+                .is_err()
+        );
+
+        // audio does not allow attendee
+        assert!(
+            Alarm::builder()
+                .trigger(Trigger::relative(Duration::from_str("-PT30M")?, None)?)
+                .action(Action::new(ActionValue::Audio)?)
+                .add_attendee(Attendee::new(CalendarUserAddress::from_str(
+                    "mailto:john_doe@example.com"
+                )?)?)
+                .build()
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -1,9 +1,15 @@
-use crate::{
-    DateTimeCreated,
-    i_calendar::component_properties::{
-        Categories, Classification, DateTimeEnd, DateTimeStamp, DateTimeStart, Summary,
+use crate::i_calendar::{
+    calendar_components::Alarm,
+    component_properties::{
+        Attendee, Categories, Classification, DateTimeCreated, DateTimeEnd, DateTimeStamp,
+        DateTimeStart, DurationProperty, ExceptionDateTimes, GeographicPosition, IanaProperty,
+        Location, NonStandardProperty, Organizer, Priority, RecurrenceDateTimes, RecurrenceRule,
+        SequenceNumber, Status, StatusValue, Summary, TimeTransparency, UniformResourceLocator,
         UniqueIdentifier,
     },
+    property_parameters::TimeZoneIdentifier,
+    recurrence::Occurrences,
+    value_type::DateTime,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -16,11 +22,26 @@ enum ErrorInner {
     DateTimeStampRequired,
     #[error("date-time start required")]
     DateTimeStartRequired,
+    #[error("date-time end and duration are mutually exclusive")]
+    DateTimeEndAndDuration,
+    #[error("invalid participation status for event: {0}")]
+    InvalidParticipationStatus(String),
+    #[error("invalid status for event: {0}")]
+    InvalidStatus(&'static str),
     #[error("unique identifier required")]
     UniqueIdentifierRequired,
+    #[error("until must have the same value type as date-time start")]
+    UntilFormMismatch,
 }
 
 /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6.1>
+///
+/// eventc     = "BEGIN" ":" "VEVENT" CRLF
+///              eventprop *alarmc
+///              "END" ":" "VEVENT" CRLF
+///
+/// description, last-mod, recurid, attach, comment, contact, rstatus, related and resources not
+/// supported
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Event {
     dtstamp: DateTimeStamp,
@@ -29,8 +50,24 @@ pub struct Event {
     class: Option<Classification>,
     created: Option<DateTimeCreated>,
     summary: Option<Summary>,
+    geo: Option<GeographicPosition>,
+    location: Option<Location>,
+    organizer: Option<Organizer>,
+    priority: Option<Priority>,
+    sequence: Option<SequenceNumber>,
+    status: Option<Status>,
+    transp: Option<TimeTransparency>,
+    url: Option<UniformResourceLocator>,
     dtend: Option<DateTimeEnd>,
+    duration: Option<DurationProperty>,
+    rrule: Option<RecurrenceRule>,
+    attendees: Vec<Attendee>,
     categories: Vec<Categories>,
+    exdate: Vec<ExceptionDateTimes>,
+    rdate: Vec<RecurrenceDateTimes>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+    alarms: Vec<Alarm>,
 }
 
 impl Event {
@@ -39,24 +76,148 @@ impl Event {
     }
 
     fn from_builder(builder: EventBuilder) -> Result<Self, EventError> {
+        let dtstart = builder.dtstart.ok_or(ErrorInner::DateTimeStartRequired)?;
+        if builder
+            .rrule
+            .as_ref()
+            .is_some_and(|rrule| !rrule.is_until_consistent_with(dtstart.value()))
+        {
+            return Err(ErrorInner::UntilFormMismatch)?;
+        }
+        // "DTEND" and "DURATION" MUST NOT occur in the same "eventprop".
+        if builder.dtend.is_some() && builder.duration.is_some() {
+            return Err(ErrorInner::DateTimeEndAndDuration)?;
+        }
+        if let Some(status) = &builder.status
+            && !status.value().is_for_event()
+        {
+            return Err(ErrorInner::InvalidStatus(status.value().as_str()))?;
+        }
+        if let Some(attendee) = builder
+            .attendees
+            .iter()
+            .find(|attendee| !attendee.partstat().is_for_event())
+        {
+            return Err(ErrorInner::InvalidParticipationStatus(
+                attendee.partstat().to_escaped(),
+            ))?;
+        }
         Ok(Self {
-            dtstamp: builder
-                .dtstamp
-                .ok_or_else(|| ErrorInner::DateTimeStampRequired)?,
-            uid: builder
-                .uid
-                .ok_or_else(|| ErrorInner::UniqueIdentifierRequired)?,
-            dtstart: builder
-                .dtstart
-                .ok_or_else(|| ErrorInner::DateTimeStartRequired)?,
+            dtstamp: builder.dtstamp.ok_or(ErrorInner::DateTimeStampRequired)?,
+            uid: builder.uid.ok_or(ErrorInner::UniqueIdentifierRequired)?,
+            dtstart,
             class: builder.class,
             created: builder.created,
             summary: builder.summary,
+            geo: builder.geo,
+            location: builder.location,
+            organizer: builder.organizer,
+            priority: builder.priority,
+            sequence: builder.sequence,
+            status: builder.status,
+            transp: builder.transp,
+            url: builder.url,
             dtend: builder.dtend,
+            duration: builder.duration,
+            rrule: builder.rrule,
+            attendees: builder.attendees,
             categories: builder.categories,
+            exdate: builder.exdate,
+            rdate: builder.rdate,
+            x_prop: builder.x_prop,
+            iana_prop: builder.iana_prop,
+            alarms: builder.alarms,
         })
     }
 
+    pub fn uid(&self) -> &UniqueIdentifier {
+        &self.uid
+    }
+
+    pub fn organizer(&self) -> Option<&Organizer> {
+        self.organizer.as_ref()
+    }
+
+    pub fn attendees(&self) -> &[Attendee] {
+        &self.attendees
+    }
+
+    pub fn sequence(&self) -> Option<&SequenceNumber> {
+        self.sequence.as_ref()
+    }
+
+    pub fn status(&self) -> Option<&Status> {
+        self.status.as_ref()
+    }
+
+    pub fn summary(&self) -> Option<&Summary> {
+        self.summary.as_ref()
+    }
+
+    /// Returns the occurrences of the recurrence set in `[start, end)`.
+    ///
+    /// The occurrences have the same form as the "DTSTART" property value. The date-times are
+    /// compared as wall-clock times (UTC offsets of the time zones are not applied).
+    pub fn occurrences(&self, start: &DateTime, end: &DateTime) -> Occurrences {
+        Occurrences::new(
+            self.dtstart.value(),
+            self.rrule.as_ref().map(RecurrenceRule::value),
+            self.rdate
+                .iter()
+                .flat_map(RecurrenceDateTimes::values)
+                .collect(),
+            self.exdate
+                .iter()
+                .flat_map(ExceptionDateTimes::values)
+                .collect(),
+            start,
+            end,
+        )
+    }
+
+    /// Returns the event with the attendee only and without alarms (e.g. for iTIP "REPLY").
+    pub(in crate::i_calendar) fn with_only_attendee(
+        self,
+        attendee: Attendee,
+        dtstamp: DateTimeStamp,
+    ) -> Self {
+        Self {
+            dtstamp,
+            attendees: vec![attendee],
+            alarms: vec![],
+            ..self
+        }
+    }
+
+    /// Returns the cancelled event without alarms (e.g. for iTIP "CANCEL").
+    pub(in crate::i_calendar) fn with_cancellation(
+        self,
+        sequence: SequenceNumber,
+        dtstamp: DateTimeStamp,
+    ) -> Self {
+        Self {
+            dtstamp,
+            sequence: Some(sequence),
+            status: Some(Status::new(StatusValue::Cancelled).expect("status")),
+            alarms: vec![],
+            ..self
+        }
+    }
+
+    /// Returns the time zone identifiers referenced by the date-time properties.
+    pub(in crate::i_calendar) fn time_zone_identifiers(&self) -> Vec<&TimeZoneIdentifier> {
+        [
+            Some(self.dtstart.value()),
+            self.dtend.as_ref().map(DateTimeEnd::value),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.exdate.iter().flat_map(ExceptionDateTimes::values))
+        .chain(self.rdate.iter().flat_map(RecurrenceDateTimes::values))
+        .filter_map(DateTime::time_zone)
+        .collect()
+    }
+
     pub(in crate::i_calendar) fn into_string(self) -> String {
         let mut lines = vec![];
         lines.push("BEGIN:VEVENT\r\n".to_owned());
@@ -66,6 +227,9 @@ impl Event {
         if let Some(dtend) = self.dtend {
             lines.push(dtend.into_string());
         }
+        if let Some(duration) = self.duration {
+            lines.push(duration.to_escaped());
+        }
         if let Some(summary) = self.summary {
             lines.push(summary.into_string());
         }
@@ -75,9 +239,54 @@ impl Event {
         if let Some(created) = self.created {
             lines.push(created.to_escaped());
         }
+        if let Some(location) = self.location {
+            lines.push(location.to_escaped());
+        }
+        if let Some(geo) = self.geo {
+            lines.push(geo.to_escaped());
+        }
+        if let Some(organizer) = self.organizer {
+            lines.push(organizer.to_escaped());
+        }
+        for attendee in self.attendees {
+            lines.push(attendee.to_escaped());
+        }
+        if let Some(priority) = self.priority {
+            lines.push(priority.to_escaped());
+        }
+        if let Some(sequence) = self.sequence {
+            lines.push(sequence.to_escaped());
+        }
+        if let Some(status) = self.status {
+            lines.push(status.to_escaped());
+        }
+        if let Some(transp) = self.transp {
+            lines.push(transp.to_escaped());
+        }
+        if let Some(url) = self.url {
+            lines.push(url.to_escaped());
+        }
+        if let Some(rrule) = self.rrule {
+            lines.push(rrule.to_escaped());
+        }
         for categories in self.categories {
             lines.push(categories.into_string());
         }
+        for exdate in self.exdate {
+            lines.push(exdate.to_escaped());
+        }
+        for rdate in self.rdate {
+            lines.push(rdate.to_escaped());
+        }
+        for x_prop in self.x_prop {
+            lines.push(x_prop.to_escaped());
+        }
+        for iana_prop in self.iana_prop {
+            lines.push(iana_prop.to_escaped());
+        }
+        for alarm in self.alarms {
+            lines.push(alarm.into_string());
+        }
         lines.push("END:VEVENT\r\n".to_owned());
         lines.join("")
     }
@@ -90,8 +299,24 @@ pub struct EventBuilder {
     class: Option<Classification>,
     created: Option<DateTimeCreated>,
     summary: Option<Summary>,
+    geo: Option<GeographicPosition>,
+    location: Option<Location>,
+    organizer: Option<Organizer>,
+    priority: Option<Priority>,
+    sequence: Option<SequenceNumber>,
+    status: Option<Status>,
+    transp: Option<TimeTransparency>,
+    url: Option<UniformResourceLocator>,
     dtend: Option<DateTimeEnd>,
+    duration: Option<DurationProperty>,
+    rrule: Option<RecurrenceRule>,
+    attendees: Vec<Attendee>,
     categories: Vec<Categories>,
+    exdate: Vec<ExceptionDateTimes>,
+    rdate: Vec<RecurrenceDateTimes>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+    alarms: Vec<Alarm>,
 }
 
 impl EventBuilder {
@@ -103,16 +328,62 @@ impl EventBuilder {
             class: None,
             created: None,
             summary: None,
+            geo: None,
+            location: None,
+            organizer: None,
+            priority: None,
+            sequence: None,
+            status: None,
+            transp: None,
+            url: None,
             dtend: None,
+            duration: None,
+            rrule: None,
+            attendees: Vec::new(),
             categories: Vec::new(),
+            exdate: Vec::new(),
+            rdate: Vec::new(),
+            x_prop: Vec::new(),
+            iana_prop: Vec::new(),
+            alarms: Vec::new(),
         }
     }
 
+    pub fn add_alarm(mut self, alarm: Alarm) -> Self {
+        self.alarms.push(alarm);
+        self
+    }
+
+    pub fn add_attendee(mut self, attendee: Attendee) -> Self {
+        self.attendees.push(attendee);
+        self
+    }
+
     pub fn add_categories(mut self, categories: Categories) -> Self {
         self.categories.push(categories);
         self
     }
 
+    pub fn add_exdate(mut self, exdate: ExceptionDateTimes) -> Self {
+        self.exdate.push(exdate);
+        self
+    }
+
+    pub fn add_iana_prop(mut self, iana_prop: IanaProperty) -> Self {
+        self.iana_prop.push(iana_prop);
+        self
+    }
+
+    pub fn add_rdate(mut self, rdate: RecurrenceDateTimes) -> Self {
+        self.rdate.push(rdate);
+        self
+    }
+
+    pub fn add_x_prop(mut self, x_prop: NonStandardProperty) -> Self {
+        self.x_prop.push(x_prop);
+        self
+    }
+
     pub fn build(self) -> Result<Event, EventError> {
         Event::from_builder(self)
     }
@@ -142,15 +413,65 @@ impl EventBuilder {
         self
     }
 
+    pub fn duration(mut self, duration: DurationProperty) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn geo(mut self, geo: GeographicPosition) -> Self {
+        self.geo = Some(geo);
+        self
+    }
+
+    pub fn location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    pub fn organizer(mut self, organizer: Organizer) -> Self {
+        self.organizer = Some(organizer);
+        self
+    }
+
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub fn rrule(mut self, rrule: RecurrenceRule) -> Self {
+        self.rrule = Some(rrule);
+        self
+    }
+
+    pub fn sequence(mut self, sequence: SequenceNumber) -> Self {
+        self.sequence = Some(sequence);
+        self
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
     pub fn summary(mut self, summary: Summary) -> Self {
         self.summary = Some(summary);
         self
     }
 
+    pub fn transp(mut self, transp: TimeTransparency) -> Self {
+        self.transp = Some(transp);
+        self
+    }
+
     pub fn uid(mut self, uid: UniqueIdentifier) -> Self {
         self.uid = Some(uid);
         self
     }
+
+    pub fn url(mut self, url: UniformResourceLocator) -> Self {
+        self.url = Some(url);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -191,4 +512,74 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_recurrence() -> anyhow::Result<()> {
+        use std::str::FromStr as _;
+
+        use crate::i_calendar::value_type::Recur;
+
+        let builder = || -> anyhow::Result<EventBuilder> {
+            Ok(Event::builder()
+                .uid(UniqueIdentifier::from_value(
+                    "19970901T130000Z-123403@example.com",
+                )?)
+                .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+                .dtstart(DateTimeStart::from_value("19970902T090000")?))
+        };
+        let event = builder()?
+            .rrule(RecurrenceRule::new(Recur::from_str(
+                "FREQ=WEEKLY;COUNT=4;BYDAY=TU,TH",
+            )?)?)
+            .add_exdate(ExceptionDateTimes::new(vec![DateTime::local(
+                1997, 9, 4, 9, 0, 0,
+            )?])?)
+            .add_rdate(RecurrenceDateTimes::new(vec![DateTime::local(
+                1997, 9, 5, 9, 0, 0,
+            )?])?)
+            .build()?;
+        assert_eq!(
+            event
+                .occurrences(
+                    &DateTime::local(1997, 9, 3, 0, 0, 0)?,
+                    &DateTime::local(1997, 10, 1, 0, 0, 0)?
+                )
+                .map(String::from)
+                .collect::<Vec<String>>(),
+            ["19970905T090000", "19970909T090000", "19970911T090000"]
+        );
+        assert_eq!(
+            event.into_string(),
+            [
+                "BEGIN:VEVENT\r\n",
+                "UID:19970901T130000Z-123403@example.com\r\n",
+                "DTSTAMP:19970901T130000Z\r\n",
+                "DTSTART:19970902T090000\r\n",
+                "RRULE:FREQ=WEEKLY;COUNT=4;BYDAY=TU,TH\r\n",
+                "EXDATE:19970904T090000\r\n",
+                "RDATE:19970905T090000\r\n",
+                "END:VEVENT\r\n",
+            ]
+            .join("")
+        );
+
+        // UNTIL must be a local time if DTSTART is a local time
+        assert!(
+            builder()?
+                .rrule(RecurrenceRule::new(Recur::from_str(
+                    "FREQ=DAILY;UNTIL=19971224T000000Z"
+                )?)?)
+                .build()
+                .is_err()
+        );
+        assert!(
+            builder()?
+                .rrule(RecurrenceRule::new(Recur::from_str(
+                    "FREQ=DAILY;UNTIL=19971224T000000"
+                )?)?)
+                .build()
+                .is_ok()
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,239 @@
+use crate::i_calendar::component_properties::{
+    DateTimeEnd, DateTimeStamp, DateTimeStart, FreeBusyTime, IanaProperty, NonStandardProperty,
+    UniqueIdentifier,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("free/busy")]
+pub struct FreeBusyError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("date-time end must be after date-time start")]
+    DateTimeEndBeforeDateTimeStart,
+    #[error("date-time stamp required")]
+    DateTimeStampRequired,
+    #[error("unique identifier required")]
+    UniqueIdentifierRequired,
+    #[error("UTC time format required")]
+    UtcTimeFormatRequired,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6.4>
+///
+/// freebusyc  = "BEGIN" ":" "VFREEBUSY" CRLF
+///              fbprop
+///              "END" ":" "VFREEBUSY" CRLF
+///
+/// contact, organizer, url, attendee, comment and rstatus not supported
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FreeBusy {
+    dtstamp: DateTimeStamp,
+    uid: UniqueIdentifier,
+    dtstart: Option<DateTimeStart>,
+    dtend: Option<DateTimeEnd>,
+    freebusy: Vec<FreeBusyTime>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+}
+
+impl FreeBusy {
+    pub fn builder() -> FreeBusyBuilder {
+        FreeBusyBuilder::new()
+    }
+
+    fn from_builder(builder: FreeBusyBuilder) -> Result<Self, FreeBusyError> {
+        // The date with UTC time (DTSTART, DTEND) is used in the free/busy component.
+        if builder
+            .dtstart
+            .as_ref()
+            .is_some_and(|dtstart| !dtstart.value().is_utc())
+            || builder
+                .dtend
+                .as_ref()
+                .is_some_and(|dtend| !dtend.value().is_utc())
+        {
+            return Err(ErrorInner::UtcTimeFormatRequired)?;
+        }
+        if let (Some(dtstart), Some(dtend)) = (&builder.dtstart, &builder.dtend)
+            && dtend.value() <= dtstart.value()
+        {
+            return Err(ErrorInner::DateTimeEndBeforeDateTimeStart)?;
+        }
+        Ok(Self {
+            dtstamp: builder.dtstamp.ok_or(ErrorInner::DateTimeStampRequired)?,
+            uid: builder.uid.ok_or(ErrorInner::UniqueIdentifierRequired)?,
+            dtstart: builder.dtstart,
+            dtend: builder.dtend,
+            freebusy: builder.freebusy,
+            x_prop: builder.x_prop,
+            iana_prop: builder.iana_prop,
+        })
+    }
+
+    pub fn freebusy(&self) -> &[FreeBusyTime] {
+        &self.freebusy
+    }
+
+    pub(in crate::i_calendar) fn into_string(self) -> String {
+        let mut lines = vec![];
+        lines.push("BEGIN:VFREEBUSY\r\n".to_owned());
+        lines.push(self.uid.into_string());
+        lines.push(self.dtstamp.into_string());
+        if let Some(dtstart) = self.dtstart {
+            lines.push(dtstart.into_string());
+        }
+        if let Some(dtend) = self.dtend {
+            lines.push(dtend.into_string());
+        }
+        for freebusy in self.freebusy {
+            lines.push(freebusy.to_escaped());
+        }
+        for x_prop in self.x_prop {
+            lines.push(x_prop.to_escaped());
+        }
+        for iana_prop in self.iana_prop {
+            lines.push(iana_prop.to_escaped());
+        }
+        lines.push("END:VFREEBUSY\r\n".to_owned());
+        lines.join("")
+    }
+}
+
+pub struct FreeBusyBuilder {
+    dtstamp: Option<DateTimeStamp>,
+    uid: Option<UniqueIdentifier>,
+    dtstart: Option<DateTimeStart>,
+    dtend: Option<DateTimeEnd>,
+    freebusy: Vec<FreeBusyTime>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+}
+
+impl FreeBusyBuilder {
+    fn new() -> Self {
+        Self {
+            dtstamp: None,
+            uid: None,
+            dtstart: None,
+            dtend: None,
+            freebusy: Vec::new(),
+            x_prop: Vec::new(),
+            iana_prop: Vec::new(),
+        }
+    }
+
+    pub fn add_freebusy(mut self, freebusy: FreeBusyTime) -> Self {
+        self.freebusy.push(freebusy);
+        self
+    }
+
+    pub fn add_iana_prop(mut self, iana_prop: IanaProperty) -> Self {
+        self.iana_prop.push(iana_prop);
+        self
+    }
+
+    pub fn add_x_prop(mut self, x_prop: NonStandardProperty) -> Self {
+        self.x_prop.push(x_prop);
+        self
+    }
+
+    pub fn build(self) -> Result<FreeBusy, FreeBusyError> {
+        FreeBusy::from_builder(self)
+    }
+
+    pub fn dtend(mut self, dtend: DateTimeEnd) -> Self {
+        self.dtend = Some(dtend);
+        self
+    }
+
+    pub fn dtstamp(mut self, dtstamp: DateTimeStamp) -> Self {
+        self.dtstamp = Some(dtstamp);
+        self
+    }
+
+    pub fn dtstart(mut self, dtstart: DateTimeStart) -> Self {
+        self.dtstart = Some(dtstart);
+        self
+    }
+
+    pub fn uid(mut self, uid: UniqueIdentifier) -> Self {
+        self.uid = Some(uid);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::{property_parameters::FreeBusyTimeType, value_type::Period};
+
+    use super::*;
+
+    fn builder() -> anyhow::Result<FreeBusyBuilder> {
+        Ok(FreeBusy::builder()
+            .uid(UniqueIdentifier::from_value(
+                "19970901T115957Z-76A912@example.com",
+            )?)
+            .dtstamp(DateTimeStamp::from_value("19970901T120000Z")?))
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<FreeBusy>();
+
+        assert_eq!(
+            builder()?
+                .dtstart(DateTimeStart::from_value("19980313T141711Z")?)
+                .dtend(DateTimeEnd::from_value("19980410T141711Z")?)
+                .add_freebusy(FreeBusyTime::new(
+                    None,
+                    vec![
+                        Period::from_str("19980314T233000Z/19980315T003000Z")?,
+                        Period::from_str("19980316T153000Z/19980316T163000Z")?,
+                    ]
+                )?)
+                .add_freebusy(FreeBusyTime::new(
+                    Some(FreeBusyTimeType::Free),
+                    vec![Period::from_str("19980318T030000Z/PT1H")?]
+                )?)
+                .build()?
+                .into_string(),
+            [
+                "BEGIN:VFREEBUSY\r\n",
+                "UID:19970901T115957Z-76A912@example.com\r\n",
+                "DTSTAMP:19970901T120000Z\r\n",
+                "DTSTART:19980313T141711Z\r\n",
+                "DTEND:19980410T141711Z\r\n",
+                "FREEBUSY:19980314T233000Z/19980315T003000Z,19980316T153000Z/19980316T163000Z\r\n",
+                "FREEBUSY;FBTYPE=FREE:19980318T030000Z/PT1H\r\n",
+                "END:VFREEBUSY\r\n",
+            ]
+            .join("")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation() -> anyhow::Result<()> {
+        // UTC time required
+        assert!(
+            builder()?
+                .dtstart(DateTimeStart::from_value("19980313T141711")?)
+                .build()
+                .is_err()
+        );
+
+        // dtend must be after dtstart
+        assert!(
+            builder()?
+                .dtstart(DateTimeStart::from_value("19980410T141711Z")?)
+                .dtend(DateTimeEnd::from_value("19980313T141711Z")?)
+                .build()
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use crate::i_calendar::{parser::is_valid_content_lines, property_parameters::IanaToken};
+
+#[derive(Debug, thiserror::Error)]
+#[error("iana component")]
+pub struct IanaComponentError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("content line required")]
+    ContentLineRequired,
+    #[error("invalid content lines")]
+    InvalidContentLines,
+    #[error("name required")]
+    NameRequired,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6>
+/// iana-comp  = "BEGIN" ":" iana-token CRLF
+///              1*contentline
+///              "END" ":" iana-token CRLF
+///
+/// The content lines are kept as is (unfolded and escaped).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IanaComponent {
+    name: IanaToken,
+    content_lines: Vec<String>,
+}
+
+impl IanaComponent {
+    pub fn builder() -> IanaComponentBuilder {
+        IanaComponentBuilder::new()
+    }
+
+    fn from_builder(builder: IanaComponentBuilder) -> Result<Self, IanaComponentError> {
+        let name = builder.name.ok_or(ErrorInner::NameRequired)?;
+        if builder.content_lines.is_empty() {
+            return Err(ErrorInner::ContentLineRequired)?;
+        }
+        if !is_valid_content_lines(&builder.content_lines) {
+            return Err(ErrorInner::InvalidContentLines)?;
+        }
+        Ok(Self {
+            name,
+            content_lines: builder.content_lines,
+        })
+    }
+
+    pub fn name(&self) -> &IanaToken {
+        &self.name
+    }
+
+    /// Returns the content lines (unfolded and escaped, without CRLF).
+    pub fn content_lines(&self) -> &[String] {
+        &self.content_lines
+    }
+
+    pub(in crate::i_calendar) fn into_string(self) -> String {
+        let mut lines = vec![];
+        lines.push(format!("BEGIN:{}\r\n", self.name.to_escaped()));
+        for content_line in self.content_lines {
+            lines.push(format!("{content_line}\r\n"));
+        }
+        lines.push(format!("END:{}\r\n", self.name.to_escaped()));
+        lines.join("")
+    }
+}
+
+pub struct IanaComponentBuilder {
+    name: Option<IanaToken>,
+    content_lines: Vec<String>,
+}
+
+impl IanaComponentBuilder {
+    fn new() -> Self {
+        Self {
+            name: None,
+            content_lines: Vec::new(),
+        }
+    }
+
+    /// Adds the content line (unfolded and escaped, without CRLF).
+    pub fn add_content_line(mut self, content_line: &str) -> Self {
+        self.content_lines.push(content_line.to_owned());
+        self
+    }
+
+    pub fn build(self) -> Result<IanaComponent, IanaComponentError> {
+        IanaComponent::from_builder(self)
+    }
+
+    pub fn name(mut self, name: IanaToken) -> Self {
+        self.name = Some(name);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<IanaComponent>();
+
+        let component = IanaComponent::builder()
+            .name(IanaToken::from_str("VFOO")?)
+            .add_content_line("TEXT;LANGUAGE=\"en;us\":Hello\\, world")
+            .add_content_line("BEGIN:VBAR")
+            .add_content_line("ID:1")
+            .add_content_line("END:VBAR")
+            .build()?;
+        assert_eq!(component.name(), &IanaToken::from_str("VFOO")?);
+        assert_eq!(component.content_lines().len(), 4);
+        assert_eq!(
+            component.into_string(),
+            [
+                "BEGIN:VFOO\r\n",
+                "TEXT;LANGUAGE=\"en;us\":Hello\\, world\r\n",
+                "BEGIN:VBAR\r\n",
+                "ID:1\r\n",
+                "END:VBAR\r\n",
+                "END:VFOO\r\n",
+            ]
+            .join("")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation() -> anyhow::Result<()> {
+        let builder = || -> anyhow::Result<IanaComponentBuilder> {
+            Ok(IanaComponent::builder().name(IanaToken::from_str("VFOO")?))
+        };
+        assert!(builder()?.build().is_err());
+        assert!(
+            IanaComponent::builder()
+                .add_content_line("ID:1")
+                .build()
+                .is_err()
+        );
+        assert!(builder()?.add_content_line("ID").build().is_err());
+        assert!(builder()?.add_content_line("ID:1\r\n").build().is_err());
+        assert!(builder()?.add_content_line("BEGIN:VBAR").build().is_err());
+        assert!(
+            builder()?
+                .add_content_line("BEGIN:VBAR")
+                .add_content_line("END:VBAZ")
+                .build()
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use crate::i_calendar::{
+    component_properties::{
+        Categories, Classification, DateTimeCreated, DateTimeStamp, DateTimeStart, Description,
+        ExceptionDateTimes, IanaProperty, LastModified, NonStandardProperty, RecurrenceDateTimes,
+        RecurrenceRule, Status, Summary, UniqueIdentifier,
+    },
+    property_parameters::TimeZoneIdentifier,
+    value_type::DateTime,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("journal")]
+pub struct JournalError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("date-time stamp required")]
+    DateTimeStampRequired,
+    #[error("date-time start required")]
+    DateTimeStartRequired,
+    #[error("invalid status for journal: {0}")]
+    InvalidStatus(&'static str),
+    #[error("unique identifier required")]
+    UniqueIdentifierRequired,
+    #[error("until must have the same value type as date-time start")]
+    UntilFormMismatch,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6.3>
+///
+/// journalc   = "BEGIN" ":" "VJOURNAL" CRLF
+///              jourprop
+///              "END" ":" "VJOURNAL" CRLF
+///
+/// organizer, recurid, seq, url, attach, attendee, comment, contact, related and rstatus not
+/// supported
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Journal {
+    dtstamp: DateTimeStamp,
+    uid: UniqueIdentifier,
+    class: Option<Classification>,
+    created: Option<DateTimeCreated>,
+    dtstart: Option<DateTimeStart>,
+    last_mod: Option<LastModified>,
+    status: Option<Status>,
+    summary: Option<Summary>,
+    rrule: Option<RecurrenceRule>,
+    categories: Vec<Categories>,
+    description: Vec<Description>,
+    exdate: Vec<ExceptionDateTimes>,
+    rdate: Vec<RecurrenceDateTimes>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+}
+
+impl Journal {
+    pub fn builder() -> JournalBuilder {
+        JournalBuilder::new()
+    }
+
+    fn from_builder(builder: JournalBuilder) -> Result<Self, JournalError> {
+        if let Some(rrule) = &builder.rrule {
+            let dtstart = builder
+                .dtstart
+                .as_ref()
+                .ok_or(ErrorInner::DateTimeStartRequired)?;
+            if !rrule.is_until_consistent_with(dtstart.value()) {
+                return Err(ErrorInner::UntilFormMismatch)?;
+            }
+        }
+        if let Some(status) = &builder.status
+            && !status.value().is_for_journal()
+        {
+            return Err(ErrorInner::InvalidStatus(status.value().as_str()))?;
+        }
+        Ok(Self {
+            dtstamp: builder.dtstamp.ok_or(ErrorInner::DateTimeStampRequired)?,
+            uid: builder.uid.ok_or(ErrorInner::UniqueIdentifierRequired)?,
+            class: builder.class,
+            created: builder.created,
+            dtstart: builder.dtstart,
+            last_mod: builder.last_mod,
+            status: builder.status,
+            summary: builder.summary,
+            rrule: builder.rrule,
+            categories: builder.categories,
+            description: builder.description,
+            exdate: builder.exdate,
+            rdate: builder.rdate,
+            x_prop: builder.x_prop,
+            iana_prop: builder.iana_prop,
+        })
+    }
+
+    /// Returns the time zone identifiers referenced by the date-time properties.
+    pub(in crate::i_calendar) fn time_zone_identifiers(&self) -> Vec<&TimeZoneIdentifier> {
+        self.dtstart
+            .as_ref()
+            .map(DateTimeStart::value)
+            .into_iter()
+            .chain(self.exdate.iter().flat_map(ExceptionDateTimes::values))
+            .chain(self.rdate.iter().flat_map(RecurrenceDateTimes::values))
+            .filter_map(DateTime::time_zone)
+            .collect()
+    }
+
+    pub(in crate::i_calendar) fn into_string(self) -> String {
+        let mut lines = vec![];
+        lines.push("BEGIN:VJOURNAL\r\n".to_owned());
+        lines.push(self.uid.into_string());
+        lines.push(self.dtstamp.into_string());
+        if let Some(dtstart) = self.dtstart {
+            lines.push(dtstart.into_string());
+        }
+        if let Some(summary) = self.summary {
+            lines.push(summary.into_string());
+        }
+        for description in self.description {
+            lines.push(description.to_escaped());
+        }
+        if let Some(class) = self.class {
+            lines.push(class.into_string());
+        }
+        if let Some(status) = self.status {
+            lines.push(status.to_escaped());
+        }
+        if let Some(created) = self.created {
+            lines.push(created.to_escaped());
+        }
+        if let Some(last_mod) = self.last_mod {
+            lines.push(last_mod.to_escaped());
+        }
+        if let Some(rrule) = self.rrule {
+            lines.push(rrule.to_escaped());
+        }
+        for categories in self.categories {
+            lines.push(categories.into_string());
+        }
+        for exdate in self.exdate {
+            lines.push(exdate.to_escaped());
+        }
+        for rdate in self.rdate {
+            lines.push(rdate.to_escaped());
+        }
+        for x_prop in self.x_prop {
+            lines.push(x_prop.to_escaped());
+        }
+        for iana_prop in self.iana_prop {
+            lines.push(iana_prop.to_escaped());
+        }
+        lines.push("END:VJOURNAL\r\n".to_owned());
+        lines.join("")
+    }
+}
+
+pub struct JournalBuilder {
+    dtstamp: Option<DateTimeStamp>,
+    uid: Option<UniqueIdentifier>,
+    class: Option<Classification>,
+    created: Option<DateTimeCreated>,
+    dtstart: Option<DateTimeStart>,
+    last_mod: Option<LastModified>,
+    status: Option<Status>,
+    summary: Option<Summary>,
+    rrule: Option<RecurrenceRule>,
+    categories: Vec<Categories>,
+    description: Vec<Description>,
+    exdate: Vec<ExceptionDateTimes>,
+    rdate: Vec<RecurrenceDateTimes>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+}
+
+impl JournalBuilder {
+    fn new() -> Self {
+        Self {
+            dtstamp: None,
+            uid: None,
+            class: None,
+            created: None,
+            dtstart: None,
+            last_mod: None,
+            status: None,
+            summary: None,
+            rrule: None,
+            categories: Vec::new(),
+            description: Vec::new(),
+            exdate: Vec::new(),
+            rdate: Vec::new(),
+            x_prop: Vec::new(),
+            iana_prop: Vec::new(),
+        }
+    }
+
+    pub fn add_categories(mut self, categories: Categories) -> Self {
+        self.categories.push(categories);
+        self
+    }
+
+    pub fn add_description(mut self, description: Description) -> Self {
+        self.description.push(description);
+        self
+    }
+
+    pub fn add_exdate(mut self, exdate: ExceptionDateTimes) -> Self {
+        self.exdate.push(exdate);
+        self
+    }
+
+    pub fn add_iana_prop(mut self, iana_prop: IanaProperty) -> Self {
+        self.iana_prop.push(iana_prop);
+        self
+    }
+
+    pub fn add_rdate(mut self, rdate: RecurrenceDateTimes) -> Self {
+        self.rdate.push(rdate);
+        self
+    }
+
+    pub fn add_x_prop(mut self, x_prop: NonStandardProperty) -> Self {
+        self.x_prop.push(x_prop);
+        self
+    }
+
+    pub fn build(self) -> Result<Journal, JournalError> {
+        Journal::from_builder(self)
+    }
+
+    pub fn class(mut self, class: Classification) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    pub fn created(mut self, created: DateTimeCreated) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    pub fn dtstamp(mut self, dtstamp: DateTimeStamp) -> Self {
+        self.dtstamp = Some(dtstamp);
+        self
+    }
+
+    pub fn dtstart(mut self, dtstart: DateTimeStart) -> Self {
+        self.dtstart = Some(dtstart);
+        self
+    }
+
+    pub fn last_mod(mut self, last_mod: LastModified) -> Self {
+        self.last_mod = Some(last_mod);
+        self
+    }
+
+    pub fn rrule(mut self, rrule: RecurrenceRule) -> Self {
+        self.rrule = Some(rrule);
+        self
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    pub fn uid(mut self, uid: UniqueIdentifier) -> Self {
+        self.uid = Some(uid);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::{
+        component_properties::StatusValue,
+        value_type::{Recur, Text},
+    };
+
+    use super::*;
+
+    fn builder() -> anyhow::Result<JournalBuilder> {
+        Ok(Journal::builder()
+            .uid(UniqueIdentifier::from_value(
+                "19970901T130000Z-123405@example.com",
+            )?)
+            .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?))
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Journal>();
+
+        assert_eq!(
+            builder()?
+                .dtstart(DateTimeStart::new(DateTime::local(1997, 3, 17, 0, 0, 0)?)?)
+                .summary(Summary::from_value("Staff meeting minutes")?)
+                .add_description(Description::new(Text::from_unescaped(
+                    "1. Staff meeting: Participants include Joe, Lisa, and Bob."
+                )?)?)
+                .add_description(Description::new(Text::from_unescaped(
+                    "2. Telephone Conference: ABC Corp. sales representative called."
+                )?)?)
+                .status(Status::new(StatusValue::Final)?)
+                .build()?
+                .into_string(),
+            [
+                "BEGIN:VJOURNAL\r\n",
+                "UID:19970901T130000Z-123405@example.com\r\n",
+                "DTSTAMP:19970901T130000Z\r\n",
+                "DTSTART:19970317T000000\r\n",
+                "SUMMARY:Staff meeting minutes\r\n",
+                "DESCRIPTION:1. Staff meeting: Participants include Joe\\, Lisa\\, and Bob.\r\n",
+                "DESCRIPTION:2. Telephone Conference: ABC Corp. sales representative called.\r\n",
+                "STATUS:FINAL\r\n",
+                "END:VJOURNAL\r\n",
+            ]
+            .join("")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation() -> anyhow::Result<()> {
+        // statvalue-jour
+        assert!(
+            builder()?
+                .status(Status::new(StatusValue::Confirmed)?)
+                .build()
+                .is_err()
+        );
+        assert!(
+            builder()?
+                .status(Status::new(StatusValue::Draft)?)
+                .build()
+                .is_ok()
+        );
+
+        // rrule requires dtstart
+        assert!(
+            builder()?
+                .rrule(RecurrenceRule::new(Recur::from_str("FREQ=WEEKLY")?)?)
+                .build()
+                .is_err()
+        );
+        Ok(())
+    }
+}
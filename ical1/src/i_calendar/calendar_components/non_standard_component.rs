@@ -0,0 +1,168 @@
+use crate::i_calendar::{parser::is_valid_content_lines, property_parameters::XName};
+
+#[derive(Debug, thiserror::Error)]
+#[error("non-standard component")]
+pub struct NonStandardComponentError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("content line required")]
+    ContentLineRequired,
+    #[error("invalid content lines")]
+    InvalidContentLines,
+    #[error("name required")]
+    NameRequired,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6>
+/// x-comp     = "BEGIN" ":" x-name CRLF
+///              1*contentline
+///              "END" ":" x-name CRLF
+///
+/// The content lines are kept as is (unfolded and escaped).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NonStandardComponent {
+    name: XName,
+    content_lines: Vec<String>,
+}
+
+impl NonStandardComponent {
+    pub fn builder() -> NonStandardComponentBuilder {
+        NonStandardComponentBuilder::new()
+    }
+
+    fn from_builder(
+        builder: NonStandardComponentBuilder,
+    ) -> Result<Self, NonStandardComponentError> {
+        let name = builder.name.ok_or(ErrorInner::NameRequired)?;
+        if builder.content_lines.is_empty() {
+            return Err(ErrorInner::ContentLineRequired)?;
+        }
+        if !is_valid_content_lines(&builder.content_lines) {
+            return Err(ErrorInner::InvalidContentLines)?;
+        }
+        Ok(Self {
+            name,
+            content_lines: builder.content_lines,
+        })
+    }
+
+    pub fn name(&self) -> &XName {
+        &self.name
+    }
+
+    /// Returns the content lines (unfolded and escaped, without CRLF).
+    pub fn content_lines(&self) -> &[String] {
+        &self.content_lines
+    }
+
+    pub(in crate::i_calendar) fn into_string(self) -> String {
+        let mut lines = vec![];
+        lines.push(format!("BEGIN:{}\r\n", self.name.to_escaped()));
+        for content_line in self.content_lines {
+            lines.push(format!("{content_line}\r\n"));
+        }
+        lines.push(format!("END:{}\r\n", self.name.to_escaped()));
+        lines.join("")
+    }
+}
+
+pub struct NonStandardComponentBuilder {
+    name: Option<XName>,
+    content_lines: Vec<String>,
+}
+
+impl NonStandardComponentBuilder {
+    fn new() -> Self {
+        Self {
+            name: None,
+            content_lines: Vec::new(),
+        }
+    }
+
+    /// Adds the content line (unfolded and escaped, without CRLF).
+    pub fn add_content_line(mut self, content_line: &str) -> Self {
+        self.content_lines.push(content_line.to_owned());
+        self
+    }
+
+    pub fn build(self) -> Result<NonStandardComponent, NonStandardComponentError> {
+        NonStandardComponent::from_builder(self)
+    }
+
+    pub fn name(mut self, name: XName) -> Self {
+        self.name = Some(name);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<NonStandardComponent>();
+
+        let component = NonStandardComponent::builder()
+            .name(XName::from_str("X-ABC-NOTE")?)
+            .add_content_line("X-ABC-TEXT;X-ABC-LANG=\"en;us\":Hello\\, world")
+            .add_content_line("BEGIN:X-ABC-PART")
+            .add_content_line("X-ABC-ID:1")
+            .add_content_line("END:X-ABC-PART")
+            .build()?;
+        assert_eq!(component.name(), &XName::from_str("X-ABC-NOTE")?);
+        assert_eq!(component.content_lines().len(), 4);
+        assert_eq!(
+            component.into_string(),
+            [
+                "BEGIN:X-ABC-NOTE\r\n",
+                "X-ABC-TEXT;X-ABC-LANG=\"en;us\":Hello\\, world\r\n",
+                "BEGIN:X-ABC-PART\r\n",
+                "X-ABC-ID:1\r\n",
+                "END:X-ABC-PART\r\n",
+                "END:X-ABC-NOTE\r\n",
+            ]
+            .join("")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation() -> anyhow::Result<()> {
+        let builder = || -> anyhow::Result<NonStandardComponentBuilder> {
+            Ok(NonStandardComponent::builder().name(XName::from_str("X-ABC-NOTE")?))
+        };
+        assert!(builder()?.build().is_err());
+        assert!(
+            NonStandardComponent::builder()
+                .add_content_line("X-ABC-ID:1")
+                .build()
+                .is_err()
+        );
+        assert!(builder()?.add_content_line("X-ABC-ID").build().is_err());
+        assert!(
+            builder()?
+                .add_content_line("X-ABC-ID:1\r\n")
+                .build()
+                .is_err()
+        );
+        assert!(
+            builder()?
+                .add_content_line("BEGIN:X-ABC-PART")
+                .build()
+                .is_err()
+        );
+        assert!(
+            builder()?
+                .add_content_line("BEGIN:X-ABC-PART")
+                .add_content_line("END:X-ABC-OTHER")
+                .build()
+                .is_err()
+        );
+        Ok(())
+    }
+}
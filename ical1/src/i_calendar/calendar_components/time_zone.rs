@@ -0,0 +1,533 @@
+use crate::i_calendar::{
+    component_properties::{
+        DateTimeStart, IanaProperty, LastModified, NonStandardProperty, RecurrenceDateTimes,
+        RecurrenceRule, TimeZoneName, TimeZoneOffsetFrom, TimeZoneOffsetTo,
+    },
+    property_parameters::TimeZoneIdentifier,
+    recurrence::Occurrences,
+    value_type::{DateTime, Text},
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("time zone")]
+pub struct TimeZoneError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("standard or daylight required")]
+    ObservanceRequired,
+    #[error("time zone identifier required")]
+    TimeZoneIdentifierRequired,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("observance")]
+pub struct ObservanceError(#[from] ObservanceErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ObservanceErrorInner {
+    #[error("date-time start required")]
+    DateTimeStartRequired,
+    #[error("local time format required")]
+    LocalTimeFormatRequired,
+    #[error("time zone offset from required")]
+    TimeZoneOffsetFromRequired,
+    #[error("time zone offset to required")]
+    TimeZoneOffsetToRequired,
+    #[error("until must be a date with UTC time")]
+    UntilNotUtc,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6.5>
+///
+/// timezonec  = "BEGIN" ":" "VTIMEZONE" CRLF
+///              *(
+///              ; 'tzid' is REQUIRED, but MUST NOT occur more than once.
+///              tzid /
+///              ; 'last-mod' and 'tzurl' are OPTIONAL, but MUST NOT occur more than once.
+///              last-mod / tzurl /
+///              ; One of 'standardc' or 'daylightc' MUST occur
+///              ; and each MAY occur more than once.
+///              standardc / daylightc /
+///              ; The following are OPTIONAL, and MAY occur more than once.
+///              x-prop / iana-prop
+///              )
+///              "END" ":" "VTIMEZONE" CRLF
+///
+/// tzurl not supported
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeZone {
+    tzid: TimeZoneIdentifier,
+    last_mod: Option<LastModified>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+    observances: Vec<Observance>,
+}
+
+impl TimeZone {
+    pub fn builder() -> TimeZoneBuilder {
+        TimeZoneBuilder::new()
+    }
+
+    fn from_builder(builder: TimeZoneBuilder) -> Result<Self, TimeZoneError> {
+        if builder.observances.is_empty() {
+            return Err(ErrorInner::ObservanceRequired)?;
+        }
+        Ok(Self {
+            tzid: builder.tzid.ok_or(ErrorInner::TimeZoneIdentifierRequired)?,
+            last_mod: builder.last_mod,
+            x_prop: builder.x_prop,
+            iana_prop: builder.iana_prop,
+            observances: builder.observances,
+        })
+    }
+
+    pub fn tzid(&self) -> &TimeZoneIdentifier {
+        &self.tzid
+    }
+
+    pub(in crate::i_calendar) fn into_string(self) -> String {
+        let mut lines = vec![];
+        lines.push("BEGIN:VTIMEZONE\r\n".to_owned());
+        lines.push(format!(
+            "TZID:{}\r\n",
+            Text::from_unescaped(&self.tzid.to_unescaped())
+                .expect("paramtext is valid text")
+                .into_string()
+        ));
+        if let Some(last_mod) = self.last_mod {
+            lines.push(last_mod.to_escaped());
+        }
+        for x_prop in self.x_prop {
+            lines.push(x_prop.to_escaped());
+        }
+        for iana_prop in self.iana_prop {
+            lines.push(iana_prop.to_escaped());
+        }
+        for observance in self.observances {
+            lines.push(observance.into_string());
+        }
+        lines.push("END:VTIMEZONE\r\n".to_owned());
+        lines.join("")
+    }
+}
+
+pub struct TimeZoneBuilder {
+    tzid: Option<TimeZoneIdentifier>,
+    last_mod: Option<LastModified>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+    observances: Vec<Observance>,
+}
+
+impl TimeZoneBuilder {
+    fn new() -> Self {
+        Self {
+            tzid: None,
+            last_mod: None,
+            x_prop: Vec::new(),
+            iana_prop: Vec::new(),
+            observances: Vec::new(),
+        }
+    }
+
+    pub fn add_iana_prop(mut self, iana_prop: IanaProperty) -> Self {
+        self.iana_prop.push(iana_prop);
+        self
+    }
+
+    pub fn add_observance(mut self, observance: Observance) -> Self {
+        self.observances.push(observance);
+        self
+    }
+
+    pub fn add_x_prop(mut self, x_prop: NonStandardProperty) -> Self {
+        self.x_prop.push(x_prop);
+        self
+    }
+
+    pub fn build(self) -> Result<TimeZone, TimeZoneError> {
+        TimeZone::from_builder(self)
+    }
+
+    pub fn last_mod(mut self, last_mod: LastModified) -> Self {
+        self.last_mod = Some(last_mod);
+        self
+    }
+
+    pub fn tzid(mut self, tzid: TimeZoneIdentifier) -> Self {
+        self.tzid = Some(tzid);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ObservanceKind {
+    Daylight,
+    Standard,
+}
+
+impl ObservanceKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Daylight => "DAYLIGHT",
+            Self::Standard => "STANDARD",
+        }
+    }
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6.5>
+///
+/// standardc  = "BEGIN" ":" "STANDARD" CRLF
+///              tzprop
+///              "END" ":" "STANDARD" CRLF
+///
+/// daylightc  = "BEGIN" ":" "DAYLIGHT" CRLF
+///              tzprop
+///              "END" ":" "DAYLIGHT" CRLF
+///
+/// tzprop     = *(
+///              ; The following are REQUIRED,
+///              ; but MUST NOT occur more than once.
+///              dtstart / tzoffsetto / tzoffsetfrom /
+///              ; The following is OPTIONAL,
+///              ; but SHOULD NOT occur more than once.
+///              rrule /
+///              ; The following are OPTIONAL,
+///              ; and MAY occur more than once.
+///              comment / rdate / tzname / x-prop / iana-prop
+///              )
+///
+/// comment not supported
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Observance {
+    kind: ObservanceKind,
+    dtstart: DateTimeStart,
+    tzoffsetto: TimeZoneOffsetTo,
+    tzoffsetfrom: TimeZoneOffsetFrom,
+    rrule: Option<RecurrenceRule>,
+    rdate: Vec<RecurrenceDateTimes>,
+    tzname: Vec<TimeZoneName>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+}
+
+impl Observance {
+    pub fn daylight() -> ObservanceBuilder {
+        ObservanceBuilder::new(ObservanceKind::Daylight)
+    }
+
+    pub fn standard() -> ObservanceBuilder {
+        ObservanceBuilder::new(ObservanceKind::Standard)
+    }
+
+    fn from_builder(builder: ObservanceBuilder) -> Result<Self, ObservanceError> {
+        let dtstart = builder
+            .dtstart
+            .ok_or(ObservanceErrorInner::DateTimeStartRequired)?;
+        // The "DTSTART" property MUST be specified as a date with a local time value.
+        // The "RDATE" property in "STANDARD" and "DAYLIGHT" is also a date with local time.
+        if std::iter::once(dtstart.value())
+            .chain(builder.rdate.iter().flat_map(RecurrenceDateTimes::values))
+            .any(|it| it.is_utc() || it.time_zone().is_some())
+        {
+            return Err(ObservanceErrorInner::LocalTimeFormatRequired)?;
+        }
+        // The UNTIL rule part MUST always be specified as a date with UTC time.
+        if builder
+            .rrule
+            .as_ref()
+            .and_then(|rrule| rrule.value().until())
+            .is_some_and(|until| !until.is_utc())
+        {
+            return Err(ObservanceErrorInner::UntilNotUtc)?;
+        }
+        Ok(Self {
+            kind: builder.kind,
+            dtstart,
+            tzoffsetto: builder
+                .tzoffsetto
+                .ok_or(ObservanceErrorInner::TimeZoneOffsetToRequired)?,
+            tzoffsetfrom: builder
+                .tzoffsetfrom
+                .ok_or(ObservanceErrorInner::TimeZoneOffsetFromRequired)?,
+            rrule: builder.rrule,
+            rdate: builder.rdate,
+            tzname: builder.tzname,
+            x_prop: builder.x_prop,
+            iana_prop: builder.iana_prop,
+        })
+    }
+
+    pub fn is_daylight(&self) -> bool {
+        self.kind == ObservanceKind::Daylight
+    }
+
+    /// Returns the onsets of the observance in `[start, end)`.
+    ///
+    /// The onsets are local times in the offset before the onsets ("TZOFFSETFROM").
+    pub fn onsets(&self, start: &DateTime, end: &DateTime) -> Occurrences {
+        Occurrences::new(
+            self.dtstart.value(),
+            self.rrule.as_ref().map(RecurrenceRule::value),
+            self.rdate
+                .iter()
+                .flat_map(RecurrenceDateTimes::values)
+                .collect(),
+            vec![],
+            start,
+            end,
+        )
+    }
+
+    pub(in crate::i_calendar) fn into_string(self) -> String {
+        let mut lines = vec![];
+        lines.push(format!("BEGIN:{}\r\n", self.kind.name()));
+        lines.push(self.dtstart.into_string());
+        if let Some(rrule) = self.rrule {
+            lines.push(rrule.to_escaped());
+        }
+        for rdate in self.rdate {
+            lines.push(rdate.to_escaped());
+        }
+        lines.push(self.tzoffsetfrom.to_escaped());
+        lines.push(self.tzoffsetto.to_escaped());
+        for tzname in self.tzname {
+            lines.push(tzname.to_escaped());
+        }
+        for x_prop in self.x_prop {
+            lines.push(x_prop.to_escaped());
+        }
+        for iana_prop in self.iana_prop {
+            lines.push(iana_prop.to_escaped());
+        }
+        lines.push(format!("END:{}\r\n", self.kind.name()));
+        lines.join("")
+    }
+}
+
+pub struct ObservanceBuilder {
+    kind: ObservanceKind,
+    dtstart: Option<DateTimeStart>,
+    tzoffsetto: Option<TimeZoneOffsetTo>,
+    tzoffsetfrom: Option<TimeZoneOffsetFrom>,
+    rrule: Option<RecurrenceRule>,
+    rdate: Vec<RecurrenceDateTimes>,
+    tzname: Vec<TimeZoneName>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+}
+
+impl ObservanceBuilder {
+    fn new(kind: ObservanceKind) -> Self {
+        Self {
+            kind,
+            dtstart: None,
+            tzoffsetto: None,
+            tzoffsetfrom: None,
+            rrule: None,
+            rdate: Vec::new(),
+            tzname: Vec::new(),
+            x_prop: Vec::new(),
+            iana_prop: Vec::new(),
+        }
+    }
+
+    pub fn add_iana_prop(mut self, iana_prop: IanaProperty) -> Self {
+        self.iana_prop.push(iana_prop);
+        self
+    }
+
+    pub fn add_rdate(mut self, rdate: RecurrenceDateTimes) -> Self {
+        self.rdate.push(rdate);
+        self
+    }
+
+    pub fn add_tzname(mut self, tzname: TimeZoneName) -> Self {
+        self.tzname.push(tzname);
+        self
+    }
+
+    pub fn add_x_prop(mut self, x_prop: NonStandardProperty) -> Self {
+        self.x_prop.push(x_prop);
+        self
+    }
+
+    pub fn build(self) -> Result<Observance, ObservanceError> {
+        Observance::from_builder(self)
+    }
+
+    pub fn dtstart(mut self, dtstart: DateTimeStart) -> Self {
+        self.dtstart = Some(dtstart);
+        self
+    }
+
+    pub fn rrule(mut self, rrule: RecurrenceRule) -> Self {
+        self.rrule = Some(rrule);
+        self
+    }
+
+    pub fn tzoffsetfrom(mut self, tzoffsetfrom: TimeZoneOffsetFrom) -> Self {
+        self.tzoffsetfrom = Some(tzoffsetfrom);
+        self
+    }
+
+    pub fn tzoffsetto(mut self, tzoffsetto: TimeZoneOffsetTo) -> Self {
+        self.tzoffsetto = Some(tzoffsetto);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::value_type::UtcOffset;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<TimeZone>();
+        assert_fn::<Observance>();
+
+        assert_eq!(
+            TimeZone::builder()
+                .tzid(TimeZoneIdentifier::from_unescaped("Asia/Tokyo")?)
+                .add_observance(
+                    Observance::standard()
+                        .dtstart(DateTimeStart::from_value("19700101T000000")?)
+                        .tzoffsetfrom(TimeZoneOffsetFrom::new(UtcOffset::from_str("+0900")?)?)
+                        .tzoffsetto(TimeZoneOffsetTo::new(UtcOffset::from_str("+0900")?)?)
+                        .add_tzname(TimeZoneName::new(Text::from_unescaped("JST")?)?)
+                        .build()?
+                )
+                .build()?
+                .into_string(),
+            [
+                "BEGIN:VTIMEZONE\r\n",
+                "TZID:Asia/Tokyo\r\n",
+                "BEGIN:STANDARD\r\n",
+                "DTSTART:19700101T000000\r\n",
+                "TZOFFSETFROM:+0900\r\n",
+                "TZOFFSETTO:+0900\r\n",
+                "TZNAME:JST\r\n",
+                "END:STANDARD\r\n",
+                "END:VTIMEZONE\r\n",
+            ]
+            .join("")
+        );
+
+        // tzid required
+        assert!(
+            TimeZone::builder()
+                .add_observance(
+                    Observance::daylight()
+                        .dtstart(DateTimeStart::from_value("19700101T000000")?)
+                        .tzoffsetfrom(TimeZoneOffsetFrom::new(UtcOffset::from_str("+0900")?)?)
+                        .tzoffsetto(TimeZoneOffsetTo::new(UtcOffset::from_str("+0900")?)?)
+                        .build()?
+                )
+                .build()
+                .is_err()
+        );
+
+        // standardc or daylightc required
+        assert!(
+            TimeZone::builder()
+                .tzid(TimeZoneIdentifier::from_unescaped("Asia/Tokyo")?)
+                .build()
+                .is_err()
+        );
+
+        // dtstart must be a local time
+        assert!(
+            Observance::standard()
+                .dtstart(DateTimeStart::from_value("19700101T000000Z")?)
+                .tzoffsetfrom(TimeZoneOffsetFrom::new(UtcOffset::from_str("+0900")?)?)
+                .tzoffsetto(TimeZoneOffsetTo::new(UtcOffset::from_str("+0900")?)?)
+                .build()
+                .is_err()
+        );
+
+        // tzoffsetfrom required
+        assert!(
+            Observance::standard()
+                .dtstart(DateTimeStart::from_value("19700101T000000")?)
+                .tzoffsetto(TimeZoneOffsetTo::new(UtcOffset::from_str("+0900")?)?)
+                .build()
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recurrence() -> anyhow::Result<()> {
+        use crate::i_calendar::value_type::Recur;
+
+        let daylight = || -> anyhow::Result<ObservanceBuilder> {
+            Ok(Observance::daylight()
+                .dtstart(DateTimeStart::from_value("19870405T020000")?)
+                .tzoffsetfrom(TimeZoneOffsetFrom::new(UtcOffset::from_str("-0500")?)?)
+                .tzoffsetto(TimeZoneOffsetTo::new(UtcOffset::from_str("-0400")?)?)
+                .add_tzname(TimeZoneName::new(Text::from_unescaped("EDT")?)?))
+        };
+
+        let observance = daylight()?
+            .rrule(RecurrenceRule::new(Recur::from_str(
+                "FREQ=YEARLY;BYMONTH=4;BYDAY=1SU;UNTIL=19980405T070000Z",
+            )?)?)
+            .add_rdate(RecurrenceDateTimes::new(vec![DateTime::local(
+                1999, 4, 4, 2, 0, 0,
+            )?])?)
+            .build()?;
+        assert_eq!(
+            observance.clone().into_string(),
+            [
+                "BEGIN:DAYLIGHT\r\n",
+                "DTSTART:19870405T020000\r\n",
+                "RRULE:FREQ=YEARLY;UNTIL=19980405T070000Z;BYDAY=1SU;BYMONTH=4\r\n",
+                "RDATE:19990404T020000\r\n",
+                "TZOFFSETFROM:-0500\r\n",
+                "TZOFFSETTO:-0400\r\n",
+                "TZNAME:EDT\r\n",
+                "END:DAYLIGHT\r\n",
+            ]
+            .join("")
+        );
+        assert_eq!(
+            observance
+                .onsets(
+                    &DateTime::local(1997, 1, 1, 0, 0, 0)?,
+                    &DateTime::local(2000, 1, 1, 0, 0, 0)?
+                )
+                .map(|it| it.to_escaped())
+                .collect::<Vec<String>>(),
+            ["19970406T020000", "19980405T020000", "19990404T020000"]
+        );
+
+        // until must be UTC
+        assert!(
+            daylight()?
+                .rrule(RecurrenceRule::new(Recur::from_str(
+                    "FREQ=YEARLY;BYMONTH=4;BYDAY=1SU;UNTIL=19980404T020000",
+                )?)?)
+                .build()
+                .is_err()
+        );
+
+        // rdate must be a local time
+        assert!(
+            daylight()?
+                .add_rdate(RecurrenceDateTimes::new(vec![DateTime::utc(
+                    1999, 4, 4, 7, 0, 0
+                )?])?)
+                .build()
+                .is_err()
+        );
+
+        Ok(())
+    }
+}
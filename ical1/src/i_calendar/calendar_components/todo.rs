@@ -0,0 +1,450 @@
+use crate::i_calendar::{
+    calendar_components::Alarm,
+    component_properties::{
+        Categories, Classification, DateTimeCompleted, DateTimeCreated, DateTimeDue, DateTimeStamp,
+        DateTimeStart, Description, DurationProperty, ExceptionDateTimes, IanaProperty,
+        LastModified, NonStandardProperty, PercentComplete, RecurrenceDateTimes, RecurrenceRule,
+        Status, Summary, UniqueIdentifier,
+    },
+    property_parameters::TimeZoneIdentifier,
+    value_type::DateTime,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("to-do")]
+pub struct TodoError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("date-time stamp required")]
+    DateTimeStampRequired,
+    #[error("date-time start required")]
+    DateTimeStartRequired,
+    #[error("due and duration are mutually exclusive")]
+    DueAndDuration,
+    #[error("invalid status for to-do: {0}")]
+    InvalidStatus(&'static str),
+    #[error("unique identifier required")]
+    UniqueIdentifierRequired,
+    #[error("until must have the same value type as date-time start")]
+    UntilFormMismatch,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6.2>
+///
+/// todoc      = "BEGIN" ":" "VTODO" CRLF
+///              todoprop *alarmc
+///              "END" ":" "VTODO" CRLF
+///
+/// geo, location, organizer, priority, recurid, seq, url, attach, attendee, comment, contact,
+/// rstatus, related and resources not supported
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Todo {
+    dtstamp: DateTimeStamp,
+    uid: UniqueIdentifier,
+    class: Option<Classification>,
+    completed: Option<DateTimeCompleted>,
+    created: Option<DateTimeCreated>,
+    description: Option<Description>,
+    dtstart: Option<DateTimeStart>,
+    last_mod: Option<LastModified>,
+    percent: Option<PercentComplete>,
+    status: Option<Status>,
+    summary: Option<Summary>,
+    rrule: Option<RecurrenceRule>,
+    due: Option<DateTimeDue>,
+    duration: Option<DurationProperty>,
+    categories: Vec<Categories>,
+    exdate: Vec<ExceptionDateTimes>,
+    rdate: Vec<RecurrenceDateTimes>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+    alarms: Vec<Alarm>,
+}
+
+impl Todo {
+    pub fn builder() -> TodoBuilder {
+        TodoBuilder::new()
+    }
+
+    fn from_builder(builder: TodoBuilder) -> Result<Self, TodoError> {
+        // Either 'due' or 'duration' MAY appear in a 'todoprop', but 'due' and 'duration' MUST
+        // NOT occur in the same 'todoprop'. If 'duration' appear in a 'todoprop', then 'dtstart'
+        // MUST also appear in the same 'todoprop'.
+        if builder.due.is_some() && builder.duration.is_some() {
+            return Err(ErrorInner::DueAndDuration)?;
+        }
+        if (builder.duration.is_some() || builder.rrule.is_some()) && builder.dtstart.is_none() {
+            return Err(ErrorInner::DateTimeStartRequired)?;
+        }
+        if let (Some(rrule), Some(dtstart)) = (&builder.rrule, &builder.dtstart)
+            && !rrule.is_until_consistent_with(dtstart.value())
+        {
+            return Err(ErrorInner::UntilFormMismatch)?;
+        }
+        if let Some(status) = &builder.status
+            && !status.value().is_for_todo()
+        {
+            return Err(ErrorInner::InvalidStatus(status.value().as_str()))?;
+        }
+        Ok(Self {
+            dtstamp: builder.dtstamp.ok_or(ErrorInner::DateTimeStampRequired)?,
+            uid: builder.uid.ok_or(ErrorInner::UniqueIdentifierRequired)?,
+            class: builder.class,
+            completed: builder.completed,
+            created: builder.created,
+            description: builder.description,
+            dtstart: builder.dtstart,
+            last_mod: builder.last_mod,
+            percent: builder.percent,
+            status: builder.status,
+            summary: builder.summary,
+            rrule: builder.rrule,
+            due: builder.due,
+            duration: builder.duration,
+            categories: builder.categories,
+            exdate: builder.exdate,
+            rdate: builder.rdate,
+            x_prop: builder.x_prop,
+            iana_prop: builder.iana_prop,
+            alarms: builder.alarms,
+        })
+    }
+
+    /// Returns the time zone identifiers referenced by the date-time properties.
+    pub(in crate::i_calendar) fn time_zone_identifiers(&self) -> Vec<&TimeZoneIdentifier> {
+        [
+            self.dtstart.as_ref().map(DateTimeStart::value),
+            self.due.as_ref().map(DateTimeDue::value),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.exdate.iter().flat_map(ExceptionDateTimes::values))
+        .chain(self.rdate.iter().flat_map(RecurrenceDateTimes::values))
+        .filter_map(DateTime::time_zone)
+        .collect()
+    }
+
+    pub(in crate::i_calendar) fn into_string(self) -> String {
+        let mut lines = vec![];
+        lines.push("BEGIN:VTODO\r\n".to_owned());
+        lines.push(self.uid.into_string());
+        lines.push(self.dtstamp.into_string());
+        if let Some(dtstart) = self.dtstart {
+            lines.push(dtstart.into_string());
+        }
+        if let Some(due) = self.due {
+            lines.push(due.to_escaped());
+        }
+        if let Some(duration) = self.duration {
+            lines.push(duration.to_escaped());
+        }
+        if let Some(summary) = self.summary {
+            lines.push(summary.into_string());
+        }
+        if let Some(description) = self.description {
+            lines.push(description.to_escaped());
+        }
+        if let Some(class) = self.class {
+            lines.push(class.into_string());
+        }
+        if let Some(status) = self.status {
+            lines.push(status.to_escaped());
+        }
+        if let Some(percent) = self.percent {
+            lines.push(percent.to_escaped());
+        }
+        if let Some(completed) = self.completed {
+            lines.push(completed.to_escaped());
+        }
+        if let Some(created) = self.created {
+            lines.push(created.to_escaped());
+        }
+        if let Some(last_mod) = self.last_mod {
+            lines.push(last_mod.to_escaped());
+        }
+        if let Some(rrule) = self.rrule {
+            lines.push(rrule.to_escaped());
+        }
+        for categories in self.categories {
+            lines.push(categories.into_string());
+        }
+        for exdate in self.exdate {
+            lines.push(exdate.to_escaped());
+        }
+        for rdate in self.rdate {
+            lines.push(rdate.to_escaped());
+        }
+        for x_prop in self.x_prop {
+            lines.push(x_prop.to_escaped());
+        }
+        for iana_prop in self.iana_prop {
+            lines.push(iana_prop.to_escaped());
+        }
+        for alarm in self.alarms {
+            lines.push(alarm.into_string());
+        }
+        lines.push("END:VTODO\r\n".to_owned());
+        lines.join("")
+    }
+}
+
+pub struct TodoBuilder {
+    dtstamp: Option<DateTimeStamp>,
+    uid: Option<UniqueIdentifier>,
+    class: Option<Classification>,
+    completed: Option<DateTimeCompleted>,
+    created: Option<DateTimeCreated>,
+    description: Option<Description>,
+    dtstart: Option<DateTimeStart>,
+    last_mod: Option<LastModified>,
+    percent: Option<PercentComplete>,
+    status: Option<Status>,
+    summary: Option<Summary>,
+    rrule: Option<RecurrenceRule>,
+    due: Option<DateTimeDue>,
+    duration: Option<DurationProperty>,
+    categories: Vec<Categories>,
+    exdate: Vec<ExceptionDateTimes>,
+    rdate: Vec<RecurrenceDateTimes>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
+    alarms: Vec<Alarm>,
+}
+
+impl TodoBuilder {
+    fn new() -> Self {
+        Self {
+            dtstamp: None,
+            uid: None,
+            class: None,
+            completed: None,
+            created: None,
+            description: None,
+            dtstart: None,
+            last_mod: None,
+            percent: None,
+            status: None,
+            summary: None,
+            rrule: None,
+            due: None,
+            duration: None,
+            categories: Vec::new(),
+            exdate: Vec::new(),
+            rdate: Vec::new(),
+            x_prop: Vec::new(),
+            iana_prop: Vec::new(),
+            alarms: Vec::new(),
+        }
+    }
+
+    pub fn add_alarm(mut self, alarm: Alarm) -> Self {
+        self.alarms.push(alarm);
+        self
+    }
+
+    pub fn add_categories(mut self, categories: Categories) -> Self {
+        self.categories.push(categories);
+        self
+    }
+
+    pub fn add_exdate(mut self, exdate: ExceptionDateTimes) -> Self {
+        self.exdate.push(exdate);
+        self
+    }
+
+    pub fn add_iana_prop(mut self, iana_prop: IanaProperty) -> Self {
+        self.iana_prop.push(iana_prop);
+        self
+    }
+
+    pub fn add_rdate(mut self, rdate: RecurrenceDateTimes) -> Self {
+        self.rdate.push(rdate);
+        self
+    }
+
+    pub fn add_x_prop(mut self, x_prop: NonStandardProperty) -> Self {
+        self.x_prop.push(x_prop);
+        self
+    }
+
+    pub fn build(self) -> Result<Todo, TodoError> {
+        Todo::from_builder(self)
+    }
+
+    pub fn class(mut self, class: Classification) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    pub fn completed(mut self, completed: DateTimeCompleted) -> Self {
+        self.completed = Some(completed);
+        self
+    }
+
+    pub fn created(mut self, created: DateTimeCreated) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    pub fn description(mut self, description: Description) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn dtstamp(mut self, dtstamp: DateTimeStamp) -> Self {
+        self.dtstamp = Some(dtstamp);
+        self
+    }
+
+    pub fn dtstart(mut self, dtstart: DateTimeStart) -> Self {
+        self.dtstart = Some(dtstart);
+        self
+    }
+
+    pub fn due(mut self, due: DateTimeDue) -> Self {
+        self.due = Some(due);
+        self
+    }
+
+    pub fn duration(mut self, duration: DurationProperty) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn last_mod(mut self, last_mod: LastModified) -> Self {
+        self.last_mod = Some(last_mod);
+        self
+    }
+
+    pub fn percent(mut self, percent: PercentComplete) -> Self {
+        self.percent = Some(percent);
+        self
+    }
+
+    pub fn rrule(mut self, rrule: RecurrenceRule) -> Self {
+        self.rrule = Some(rrule);
+        self
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    pub fn uid(mut self, uid: UniqueIdentifier) -> Self {
+        self.uid = Some(uid);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::{
+        component_properties::StatusValue,
+        value_type::{Duration, Text},
+    };
+
+    use super::*;
+
+    fn builder() -> anyhow::Result<TodoBuilder> {
+        Ok(Todo::builder()
+            .uid(UniqueIdentifier::from_value(
+                "20070313T123432Z-456553@example.com",
+            )?)
+            .dtstamp(DateTimeStamp::from_value("20070313T123432Z")?))
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Todo>();
+
+        assert_eq!(
+            builder()?
+                .due(DateTimeDue::new(DateTime::utc(2007, 5, 1, 0, 0, 0)?)?)
+                .summary(Summary::from_value(
+                    "Submit Quebec Income Tax Return for 2006"
+                )?)
+                .class(Classification::from_value("CONFIDENTIAL")?)
+                .status(Status::new(StatusValue::NeedsAction)?)
+                .add_categories(Categories::from_value("FAMILY,FINANCE")?)
+                .build()?
+                .into_string(),
+            [
+                "BEGIN:VTODO\r\n",
+                "UID:20070313T123432Z-456553@example.com\r\n",
+                "DTSTAMP:20070313T123432Z\r\n",
+                "DUE:20070501T000000Z\r\n",
+                "SUMMARY:Submit Quebec Income Tax Return for 2006\r\n",
+                "CLASS:CONFIDENTIAL\r\n",
+                "STATUS:NEEDS-ACTION\r\n",
+                "CATEGORIES:FAMILY,FINANCE\r\n",
+                "END:VTODO\r\n",
+            ]
+            .join("")
+        );
+
+        assert_eq!(
+            builder()?
+                .dtstart(DateTimeStart::from_value("20070514T110000Z")?)
+                .duration(DurationProperty::new(Duration::from_str("P1D")?)?)
+                .description(Description::new(Text::from_unescaped("Pay bills")?)?)
+                .percent(PercentComplete::new(50)?)
+                .status(Status::new(StatusValue::InProcess)?)
+                .build()?
+                .into_string(),
+            [
+                "BEGIN:VTODO\r\n",
+                "UID:20070313T123432Z-456553@example.com\r\n",
+                "DTSTAMP:20070313T123432Z\r\n",
+                "DTSTART:20070514T110000Z\r\n",
+                "DURATION:P1D\r\n",
+                "DESCRIPTION:Pay bills\r\n",
+                "STATUS:IN-PROCESS\r\n",
+                "PERCENT-COMPLETE:50\r\n",
+                "END:VTODO\r\n",
+            ]
+            .join("")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation() -> anyhow::Result<()> {
+        // due and duration are mutually exclusive
+        assert!(
+            builder()?
+                .dtstart(DateTimeStart::from_value("20070514T110000Z")?)
+                .due(DateTimeDue::new(DateTime::utc(2007, 5, 15, 0, 0, 0)?)?)
+                .duration(DurationProperty::new(Duration::from_str("P1D")?)?)
+                .build()
+                .is_err()
+        );
+
+        // duration requires dtstart
+        assert!(
+            builder()?
+                .duration(DurationProperty::new(Duration::from_str("P1D")?)?)
+                .build()
+                .is_err()
+        );
+
+        // statvalue-todo
+        for status in [StatusValue::Tentative, StatusValue::Draft] {
+            assert!(builder()?.status(Status::new(status)?).build().is_err());
+        }
+
+        // dtstamp and uid required
+        assert!(Todo::builder().build().is_err());
+        assert!(builder()?.build().is_ok());
+        Ok(())
+    }
+}
@@ -37,6 +37,38 @@ impl Method {
         )
     }
 
+    /// iTIP "REQUEST" method
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc5546#section-3.2.2>
+    pub fn request() -> Self {
+        Self::itip("REQUEST")
+    }
+
+    /// iTIP "REPLY" method
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc5546#section-3.2.3>
+    pub fn reply() -> Self {
+        Self::itip("REPLY")
+    }
+
+    /// iTIP "CANCEL" method
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc5546#section-3.2.5>
+    pub fn cancel() -> Self {
+        Self::itip("CANCEL")
+    }
+
+    fn itip(value: &str) -> Self {
+        Self(
+            Text::from_unescaped(value).expect("iTIP method"),
+            Vec::new(),
+        )
+    }
+
+    pub fn value(&self) -> &Text {
+        &self.0
+    }
+
     pub(in crate::i_calendar) fn to_escaped(&self) -> String {
         let mut s = String::new();
         s.push_str("METHOD");
@@ -45,7 +77,7 @@ impl Method {
             s.push_str(&p.to_escaped());
         }
         s.push(':');
-        s.push_str(&self.0.to_string());
+        s.push_str(&self.0.clone().into_string());
         s.push_str("\r\n");
         s
     }
@@ -68,6 +100,11 @@ mod tests {
             "METHOD:REQUEST\r\n"
         );
 
+        assert_eq!(Method::request().to_escaped(), "METHOD:REQUEST\r\n");
+        assert_eq!(Method::reply().to_escaped(), "METHOD:REPLY\r\n");
+        assert_eq!(Method::cancel().to_escaped(), "METHOD:CANCEL\r\n");
+        assert_eq!(Method::cancel().value(), &Text::from_unescaped("CANCEL")?);
+
         assert_eq!(
             Method::with_parameters(
                 Text::from_unescaped("REQUEST")?,
@@ -47,7 +47,7 @@ impl Version {
             s.push_str(&p.to_escaped());
         }
         s.push(':');
-        s.push_str(&self.0.to_string());
+        s.push_str(&self.0.clone().into_string());
         s.push_str("\r\n");
         s
     }
@@ -1,20 +1,57 @@
+mod action;
+mod attachment;
+mod attendee;
 mod categories;
 mod classification;
+mod date_time_completed;
 mod date_time_created;
+mod date_time_due;
 mod date_time_end;
 mod date_time_stamp;
 mod date_time_start;
 mod description;
+mod duration_property;
+mod exception_date_times;
+mod free_busy_time;
+mod geographic_position;
+mod iana_property;
 mod last_modified;
+mod location;
+mod non_standard_property;
+mod organizer;
+mod percent_complete;
+mod priority;
+mod recurrence_date_times;
+mod recurrence_rule;
+mod repeat_count;
+mod sequence_number;
+mod status;
 mod summary;
+mod time_transparency;
+mod time_zone_name;
+mod time_zone_offset_from;
+mod time_zone_offset_to;
+mod trigger;
+mod uniform_resource_locator;
 mod unique_identifier;
 
+pub use self::action::Action;
+pub use self::action::ActionError;
+pub use self::action::ActionValue;
+pub use self::attachment::Attachment;
+pub use self::attachment::AttachmentError;
+pub use self::attendee::Attendee;
+pub use self::attendee::AttendeeError;
 pub use self::categories::Categories;
 pub use self::categories::CategoriesError;
 pub use self::classification::Classification;
 pub use self::classification::ClassificationError;
+pub use self::date_time_completed::DateTimeCompleted;
+pub use self::date_time_completed::DateTimeCompletedError;
 pub use self::date_time_created::DateTimeCreated;
 pub use self::date_time_created::DateTimeCreatedError;
+pub use self::date_time_due::DateTimeDue;
+pub use self::date_time_due::DateTimeDueError;
 pub use self::date_time_end::DateTimeEnd;
 pub use self::date_time_end::DateTimeEndError;
 pub use self::date_time_stamp::DateTimeStamp;
@@ -23,9 +60,53 @@ pub use self::date_time_start::DateTimeStart;
 pub use self::date_time_start::DateTimeStartError;
 pub use self::description::Description;
 pub use self::description::DescriptionError;
+pub use self::duration_property::DurationProperty;
+pub use self::duration_property::DurationPropertyError;
+pub use self::exception_date_times::ExceptionDateTimes;
+pub use self::exception_date_times::ExceptionDateTimesError;
+pub use self::free_busy_time::FreeBusyTime;
+pub use self::free_busy_time::FreeBusyTimeError;
+pub use self::geographic_position::GeographicPosition;
+pub use self::geographic_position::GeographicPositionError;
+pub use self::iana_property::IanaProperty;
+pub use self::iana_property::IanaPropertyError;
 pub use self::last_modified::LastModified;
 pub use self::last_modified::LastModifiedError;
+pub use self::location::Location;
+pub use self::location::LocationError;
+pub use self::non_standard_property::NonStandardProperty;
+pub use self::non_standard_property::NonStandardPropertyError;
+pub use self::organizer::Organizer;
+pub use self::organizer::OrganizerError;
+pub use self::percent_complete::PercentComplete;
+pub use self::percent_complete::PercentCompleteError;
+pub use self::priority::Priority;
+pub use self::priority::PriorityError;
+pub use self::recurrence_date_times::RecurrenceDateTimes;
+pub use self::recurrence_date_times::RecurrenceDateTimesError;
+pub use self::recurrence_rule::RecurrenceRule;
+pub use self::recurrence_rule::RecurrenceRuleError;
+pub use self::repeat_count::RepeatCount;
+pub use self::repeat_count::RepeatCountError;
+pub use self::sequence_number::SequenceNumber;
+pub use self::sequence_number::SequenceNumberError;
+pub use self::status::Status;
+pub use self::status::StatusError;
+pub use self::status::StatusValue;
 pub use self::summary::Summary;
 pub use self::summary::SummaryError;
+pub use self::time_transparency::TimeTransparency;
+pub use self::time_transparency::TimeTransparencyError;
+pub use self::time_transparency::TimeTransparencyValue;
+pub use self::time_zone_name::TimeZoneName;
+pub use self::time_zone_name::TimeZoneNameError;
+pub use self::time_zone_offset_from::TimeZoneOffsetFrom;
+pub use self::time_zone_offset_from::TimeZoneOffsetFromError;
+pub use self::time_zone_offset_to::TimeZoneOffsetTo;
+pub use self::time_zone_offset_to::TimeZoneOffsetToError;
+pub use self::trigger::Trigger;
+pub use self::trigger::TriggerError;
+pub use self::uniform_resource_locator::UniformResourceLocator;
+pub use self::uniform_resource_locator::UniformResourceLocatorError;
 pub use self::unique_identifier::UniqueIdentifier;
 pub use self::unique_identifier::UniqueIdentifierError;
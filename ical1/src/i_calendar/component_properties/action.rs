@@ -0,0 +1,109 @@
+//! Action
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.6.1>
+
+use crate::i_calendar::property_parameters::OtherParam;
+
+#[derive(Debug, thiserror::Error)]
+#[error("action")]
+pub struct ActionError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("unknown action value: {0}")]
+    UnknownValue(String),
+}
+
+/// actionvalue = "AUDIO" / "DISPLAY" / "EMAIL"
+///             / iana-token / x-name
+///
+/// iana-token and x-name not supported
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum ActionValue {
+    Audio,
+    Display,
+    Email,
+}
+
+impl ActionValue {
+    pub(in crate::i_calendar) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Audio => "AUDIO",
+            Self::Display => "DISPLAY",
+            Self::Email => "EMAIL",
+        }
+    }
+}
+
+impl std::str::FromStr for ActionValue {
+    type Err = ActionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "AUDIO" => Self::Audio,
+            "DISPLAY" => Self::Display,
+            "EMAIL" => Self::Email,
+            _ => return Err(ErrorInner::UnknownValue(s.to_owned()))?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Action(ActionValue, Vec<OtherParam>);
+
+impl Action {
+    pub fn new(value: ActionValue) -> Result<Self, ActionError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: ActionValue, param: I) -> Result<Self, ActionError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> ActionValue {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("ACTION");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(self.0.as_str());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Action>();
+
+        assert_eq!(
+            Action::new(ActionValue::from_str("display")?)?.to_escaped(),
+            "ACTION:DISPLAY\r\n"
+        );
+        assert!(ActionValue::from_str("PROCEDURE").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Attachment
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.1.1>
+
+use crate::i_calendar::{property_parameters::OtherParam, value_type::Uri};
+
+#[derive(Debug, thiserror::Error)]
+#[error("attachment")]
+pub struct AttachmentError {
+    _private: (),
+}
+
+/// BINARY value type not supported
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attachment(Uri, Vec<OtherParam>);
+
+impl Attachment {
+    pub fn new(value: Uri) -> Result<Self, AttachmentError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: Uri, param: I) -> Result<Self, AttachmentError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> &Uri {
+        &self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("ATTACH");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_string());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::property_parameters::{IanaParam, IanaToken, ParamValue};
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Attachment>();
+
+        assert_eq!(
+            Attachment::with_parameters(
+                Uri::from_str("ftp://example.com/pub/sounds/bell-01.aud")?,
+                vec![IanaParam::new(
+                    IanaToken::from_unescaped("FMTTYPE")?,
+                    vec![ParamValue::from_unescaped("audio/basic")?]
+                )?],
+            )?
+            .to_escaped(),
+            "ATTACH;FMTTYPE=audio/basic:ftp://example.com/pub/sounds/bell-01.aud\r\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+//! Attendee
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.4.1>
+
+use crate::i_calendar::{
+    property_parameters::{
+        CalendarUserType, CommonName, OtherParam, ParticipationRole, ParticipationStatus,
+        RsvpExpectation,
+    },
+    value_type::CalendarUserAddress,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("attendee")]
+pub struct AttendeeError {
+    _private: (),
+}
+
+/// attparam   = *(
+///            ;
+///            ; The following are OPTIONAL,
+///            ; but MUST NOT occur more than once.
+///            ;
+///            (";" cutypeparam) / (";" memberparam) /
+///            (";" roleparam) / (";" partstatparam) /
+///            (";" rsvpparam) / (";" deltoparam) /
+///            (";" delfromparam) / (";" sentbyparam) /
+///            (";" cnparam) / (";" dirparam) /
+///            (";" languageparam) /
+///            ;
+///            ; The following is OPTIONAL,
+///            ; and MAY occur more than once.
+///            ;
+///            (";" other-param)
+///            ;
+///            )
+///
+/// memberparam, deltoparam, delfromparam, sentbyparam, dirparam and languageparam are kept as
+/// other-param
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attendee {
+    value: CalendarUserAddress,
+    cutype: Option<CalendarUserType>,
+    role: Option<ParticipationRole>,
+    partstat: Option<ParticipationStatus>,
+    rsvp: Option<RsvpExpectation>,
+    cn: Option<CommonName>,
+    param: Vec<OtherParam>,
+}
+
+impl Attendee {
+    pub fn new(value: CalendarUserAddress) -> Result<Self, AttendeeError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: CalendarUserAddress, param: I) -> Result<Self, AttendeeError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self {
+            value,
+            cutype: None,
+            role: None,
+            partstat: None,
+            rsvp: None,
+            cn: None,
+            param: param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        })
+    }
+
+    pub fn with_cn(self, cn: CommonName) -> Self {
+        Self {
+            cn: Some(cn),
+            ..self
+        }
+    }
+
+    pub fn with_cutype(self, cutype: CalendarUserType) -> Self {
+        Self {
+            cutype: Some(cutype),
+            ..self
+        }
+    }
+
+    pub fn with_partstat(self, partstat: ParticipationStatus) -> Self {
+        Self {
+            partstat: Some(partstat),
+            ..self
+        }
+    }
+
+    pub fn with_role(self, role: ParticipationRole) -> Self {
+        Self {
+            role: Some(role),
+            ..self
+        }
+    }
+
+    pub fn with_rsvp(self, rsvp: RsvpExpectation) -> Self {
+        Self {
+            rsvp: Some(rsvp),
+            ..self
+        }
+    }
+
+    pub fn value(&self) -> &CalendarUserAddress {
+        &self.value
+    }
+
+    pub fn cn(&self) -> Option<&CommonName> {
+        self.cn.as_ref()
+    }
+
+    /// Returns the CUTYPE parameter value (`INDIVIDUAL` by default).
+    pub fn cutype(&self) -> CalendarUserType {
+        self.cutype.unwrap_or_default()
+    }
+
+    /// Returns the PARTSTAT parameter value (`NEEDS-ACTION` by default).
+    pub fn partstat(&self) -> ParticipationStatus {
+        self.partstat.unwrap_or_default()
+    }
+
+    /// Returns the ROLE parameter value (`REQ-PARTICIPANT` by default).
+    pub fn role(&self) -> ParticipationRole {
+        self.role.unwrap_or_default()
+    }
+
+    /// Returns the RSVP parameter value (`FALSE` by default).
+    pub fn rsvp(&self) -> RsvpExpectation {
+        self.rsvp.unwrap_or_default()
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("ATTENDEE");
+        if let Some(cutype) = self.cutype {
+            s.push(';');
+            s.push_str(&cutype.to_escaped());
+        }
+        if let Some(role) = self.role {
+            s.push(';');
+            s.push_str(&role.to_escaped());
+        }
+        if let Some(partstat) = self.partstat {
+            s.push(';');
+            s.push_str(&partstat.to_escaped());
+        }
+        if let Some(rsvp) = self.rsvp {
+            s.push(';');
+            s.push_str(&rsvp.to_escaped());
+        }
+        if let Some(cn) = &self.cn {
+            s.push(';');
+            s.push_str(&cn.to_escaped());
+        }
+        for p in &self.param {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.value.to_escaped());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Attendee>();
+
+        let attendee = Attendee::new(CalendarUserAddress::from_str(
+            "mailto:john_doe@example.com",
+        )?)?;
+        assert_eq!(
+            attendee.to_escaped(),
+            "ATTENDEE:mailto:john_doe@example.com\r\n"
+        );
+        assert_eq!(attendee.cn(), None);
+        assert_eq!(attendee.cutype(), CalendarUserType::Individual);
+        assert_eq!(attendee.partstat(), ParticipationStatus::NeedsAction);
+        assert_eq!(attendee.role(), ParticipationRole::ReqParticipant);
+        assert!(!attendee.rsvp().value());
+
+        let attendee = Attendee::new(CalendarUserAddress::from_str("mailto:hcabot@example.com")?)?
+            .with_cn(CommonName::new("Henry Cabot")?)
+            .with_role(ParticipationRole::ReqParticipant)
+            .with_partstat(ParticipationStatus::Tentative)
+            .with_rsvp(RsvpExpectation::new(true))
+            .with_cutype(CalendarUserType::Individual);
+        assert_eq!(attendee.partstat(), ParticipationStatus::Tentative);
+        assert_eq!(
+            attendee.to_escaped(),
+            "ATTENDEE;CUTYPE=INDIVIDUAL;ROLE=REQ-PARTICIPANT;PARTSTAT=TENTATIVE;RSVP=TRUE;CN=Henry Cabot:mailto:hcabot@example.com\r\n"
+        );
+        Ok(())
+    }
+}
@@ -25,15 +25,14 @@ impl Categories {
 
     pub(in crate::i_calendar) fn from_string(s: String) -> Result<Self, CategoriesError> {
         if s.starts_with("CATEGORIES:") && s.ends_with("\r\n") {
-            Ok(s.trim_start_matches("CATEGORIES:")
-                .trim_end_matches("\r\n")
-                // FIXME: Text contains `"\\,"`
-                .split(',')
-                .map(ToOwned::to_owned)
-                .map(Text::from_string)
-                .collect::<Result<Vec<Text>, TextError>>()
-                .map(Self)
-                .map_err(ErrorInner::Text)?)
+            Ok(
+                split_text_list(s.trim_start_matches("CATEGORIES:").trim_end_matches("\r\n"))
+                    .into_iter()
+                    .map(Text::from_string)
+                    .collect::<Result<Vec<Text>, TextError>>()
+                    .map(Self)
+                    .map_err(ErrorInner::Text)?,
+            )
         } else {
             Err(ErrorInner::InvalidFormat)?
         }
@@ -51,6 +50,26 @@ impl Categories {
     }
 }
 
+fn split_text_list(s: &str) -> Vec<String> {
+    let mut list = vec![];
+    let mut t = String::new();
+    let mut iter = s.chars();
+    while let Some(c) = iter.next() {
+        match c {
+            '\\' => {
+                t.push(c);
+                if let Some(c) = iter.next() {
+                    t.push(c);
+                }
+            }
+            ',' => list.push(std::mem::take(&mut t)),
+            c => t.push(c),
+        }
+    }
+    list.push(t);
+    list
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,6 +85,10 @@ mod tests {
         let s = "CATEGORIES:MEETING\r\n".to_owned();
         assert_eq!(Categories::from_string(s.clone())?.into_string(), s);
 
+        let s = "CATEGORIES:A\\,B,C\r\n".to_owned();
+        assert_eq!(Categories::from_string(s.clone())?.0.len(), 2);
+        assert_eq!(Categories::from_string(s.clone())?.into_string(), s);
+
         let s = "CATEGORIES:MEETING".to_owned();
         assert!(Categories::from_string(s).is_err());
 
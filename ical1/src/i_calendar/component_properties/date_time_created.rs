@@ -32,7 +32,7 @@ impl DateTimeCreated {
         I: IntoIterator,
         I::Item: Into<OtherParam>,
     {
-        if !value.to_escaped().ends_with('Z') {
+        if !value.is_utc() {
             return Err(ErrorInner::UtcTimeFormatRequired)?;
         }
         Ok(Self(
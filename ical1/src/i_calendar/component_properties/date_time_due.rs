@@ -0,0 +1,65 @@
+//! Date-Time Due
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.2.3>
+
+use crate::i_calendar::value_type::DateTime;
+
+#[derive(Debug, thiserror::Error)]
+#[error("date-time due")]
+pub struct DateTimeDueError {
+    _private: (),
+}
+
+/// DATE value type not supported
+/// dueparam supports tzidparam only
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DateTimeDue(DateTime);
+
+impl DateTimeDue {
+    pub fn new(value: DateTime) -> Result<Self, DateTimeDueError> {
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> &DateTime {
+        &self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("DUE");
+        if let Some(tzid) = self.0.time_zone() {
+            s.push(';');
+            s.push_str(&tzid.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_escaped());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::i_calendar::property_parameters::TimeZoneIdentifier;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<DateTimeDue>();
+
+        assert_eq!(
+            DateTimeDue::new(DateTime::utc(1998, 4, 15, 23, 59, 59)?)?.to_escaped(),
+            "DUE:19980415T235959Z\r\n"
+        );
+
+        let tzid = TimeZoneIdentifier::from_unescaped("America/New_York")?;
+        assert_eq!(
+            DateTimeDue::new(DateTime::local(1998, 4, 15, 23, 59, 59)?.with_time_zone(tzid)?)?
+                .to_escaped(),
+            "DUE;TZID=America/New_York:19980415T235959\r\n"
+        );
+        Ok(())
+    }
+}
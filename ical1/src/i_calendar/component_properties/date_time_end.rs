@@ -1,4 +1,7 @@
-use crate::i_calendar::value_type::{DateTime, DateTimeError};
+use crate::i_calendar::{
+    property_parameters::{TimeZoneIdentifier, TimeZoneIdentifierError},
+    value_type::{DateTime, DateTimeError},
+};
 
 #[derive(Debug, thiserror::Error)]
 #[error("date-time end")]
@@ -10,36 +13,64 @@ enum ErrorInner {
     DateTime(#[from] DateTimeError),
     #[error("invalid format")]
     InvalidFormat,
+    #[error("time zone identifier")]
+    TimeZoneIdentifier(#[source] TimeZoneIdentifierError),
 }
 
 /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.2.2>
 /// DATE value type not supported
-/// dtendparam not supported
+/// dtendparam supports tzidparam only
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct DateTimeEnd(DateTime);
 
 impl DateTimeEnd {
+    pub fn new(value: DateTime) -> Result<Self, DateTimeEndError> {
+        Ok(Self(value))
+    }
+
     // TODO: what is value?
     pub fn from_value(value: &str) -> Result<Self, DateTimeEndError> {
         Self::from_string(format!("DTEND:{}\r\n", value))
     }
 
     pub(in crate::i_calendar) fn from_string(s: String) -> Result<Self, DateTimeEndError> {
-        if s.starts_with("DTEND:") && s.ends_with("\r\n") {
-            let date_time = s
-                .trim_start_matches("DTEND:")
-                .trim_end_matches("\r\n")
-                .to_owned();
-            Ok(DateTime::try_from(date_time)
-                .map(Self)
-                .map_err(ErrorInner::DateTime)?)
-        } else {
-            Err(ErrorInner::InvalidFormat)?
-        }
+        let (tzid, date_time) = s
+            .strip_prefix("DTEND")
+            .and_then(|s| s.strip_suffix("\r\n"))
+            .and_then(|s| match s.strip_prefix(";TZID=") {
+                Some(s) => s
+                    .split_once(':')
+                    .map(|(tzid, date_time)| (Some(tzid), date_time)),
+                None => s.strip_prefix(':').map(|date_time| (None, date_time)),
+            })
+            .ok_or(ErrorInner::InvalidFormat)?;
+        let date_time = DateTime::try_from(date_time.to_owned()).map_err(ErrorInner::DateTime)?;
+        Ok(Self(match tzid {
+            Some(tzid) => date_time
+                .with_time_zone(
+                    TimeZoneIdentifier::from_unescaped(tzid)
+                        .map_err(ErrorInner::TimeZoneIdentifier)?,
+                )
+                .map_err(ErrorInner::DateTime)?,
+            None => date_time,
+        }))
+    }
+
+    pub fn value(&self) -> &DateTime {
+        &self.0
     }
 
     pub(in crate::i_calendar) fn into_string(self) -> String {
-        format!("DTEND:{}\r\n", String::from(self.0))
+        let mut s = String::new();
+        s.push_str("DTEND");
+        if let Some(tzid) = self.0.time_zone() {
+            s.push(';');
+            s.push_str(&tzid.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_escaped());
+        s.push_str("\r\n");
+        s
     }
 }
 
@@ -64,6 +95,25 @@ mod tests {
             "DTEND:19960401T150000Z\r\n"
         );
 
+        let s = "DTEND;TZID=Asia/Tokyo:20250309T213000\r\n".to_owned();
+        assert_eq!(DateTimeEnd::from_string(s.clone())?.into_string(), s);
+
+        let s = "DTEND;TZID=Asia/Tokyo:20250309T213000Z\r\n".to_owned();
+        assert!(DateTimeEnd::from_string(s).is_err());
+
+        let s = "DTEND;X-PARAM=value:20250309T213000\r\n".to_owned();
+        assert!(DateTimeEnd::from_string(s).is_err());
+
+        let tzid = TimeZoneIdentifier::from_unescaped("Asia/Tokyo")?;
+        assert_eq!(
+            DateTimeEnd::new(DateTime::local(2025, 3, 9, 21, 30, 0)?.with_time_zone(tzid)?)?
+                .into_string(),
+            "DTEND;TZID=Asia/Tokyo:20250309T213000\r\n"
+        );
+        assert_eq!(
+            DateTimeEnd::new(DateTime::local(2025, 3, 9, 21, 30, 0)?)?.into_string(),
+            "DTEND:20250309T213000\r\n"
+        );
         Ok(())
     }
 }
@@ -10,6 +10,8 @@ enum ErrorInner {
     DateTime(#[source] DateTimeError),
     #[error("invalid format")]
     InvalidFormat,
+    #[error("UTC time format required")]
+    UtcTimeFormatRequired,
 }
 
 /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.7.2>
@@ -29,9 +31,11 @@ impl DateTimeStamp {
                 .trim_start_matches("DTSTAMP:")
                 .trim_end_matches("\r\n")
                 .to_owned();
-            Ok(DateTime::try_from(date_time)
-                .map(Self)
-                .map_err(ErrorInner::DateTime)?)
+            let date_time = DateTime::try_from(date_time).map_err(ErrorInner::DateTime)?;
+            if !date_time.is_utc() {
+                return Err(ErrorInner::UtcTimeFormatRequired)?;
+            }
+            Ok(Self(date_time))
         } else {
             Err(ErrorInner::InvalidFormat)?
         }
@@ -54,6 +58,9 @@ mod tests {
         let s = "DTSTAMP:19971210T080000Z\r\n".to_owned();
         assert_eq!(DateTimeStamp::from_string(s.clone())?.into_string(), s);
 
+        let s = "DTSTAMP:19971210T080000\r\n".to_owned();
+        assert!(DateTimeStamp::from_string(s).is_err());
+
         let s = "DTSTAMP:19971210T080000Z".to_owned();
         assert!(DateTimeStamp::from_string(s).is_err());
 
@@ -0,0 +1,95 @@
+//! Duration
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.2.5>
+
+use crate::i_calendar::{property_parameters::OtherParam, value_type::Duration};
+
+#[derive(Debug, thiserror::Error)]
+#[error("duration property")]
+pub struct DurationPropertyError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("positive duration required")]
+    PositiveDurationRequired,
+}
+
+/// The property is named `DurationProperty` to avoid the conflict with the DURATION value type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DurationProperty(Duration, Vec<OtherParam>);
+
+impl DurationProperty {
+    pub fn new(value: Duration) -> Result<Self, DurationPropertyError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: Duration, param: I) -> Result<Self, DurationPropertyError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        // The duration MUST be a positive duration in the VEVENT, VTODO and VFREEBUSY.
+        if value.is_negative() {
+            return Err(ErrorInner::PositiveDurationRequired)?;
+        }
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> &Duration {
+        &self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("DURATION");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_escaped());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::property_parameters::{ParamValue, XName, XParam};
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<DurationProperty>();
+
+        assert_eq!(
+            DurationProperty::new(Duration::from_str("PT1H0M0S")?)?.to_escaped(),
+            "DURATION:PT1H\r\n"
+        );
+
+        assert_eq!(
+            DurationProperty::with_parameters(
+                Duration::from_str("PT15M")?,
+                vec![XParam::new(
+                    XName::from_unescaped("X-PARAM")?,
+                    vec![ParamValue::from_unescaped("value")?]
+                )?],
+            )?
+            .to_escaped(),
+            "DURATION;X-PARAM=value:PT15M\r\n"
+        );
+
+        assert!(DurationProperty::new(Duration::from_str("-PT15M")?).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! Exception Date-Times
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.5.1>
+
+use crate::i_calendar::value_type::DateTime;
+
+#[derive(Debug, thiserror::Error)]
+#[error("exception date-times")]
+pub struct ExceptionDateTimesError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("date-time required")]
+    DateTimeRequired,
+    #[error("mixed date-time forms")]
+    MixedForms,
+}
+
+/// DATE value type not supported
+/// exdtparam supports tzidparam only
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExceptionDateTimes(Vec<DateTime>);
+
+impl ExceptionDateTimes {
+    /// All values must have the same form (e.g. the same time zone reference).
+    pub fn new(values: Vec<DateTime>) -> Result<Self, ExceptionDateTimesError> {
+        let first = values.first().ok_or(ErrorInner::DateTimeRequired)?;
+        if !values
+            .iter()
+            .all(|v| v.is_utc() == first.is_utc() && v.time_zone() == first.time_zone())
+        {
+            return Err(ErrorInner::MixedForms)?;
+        }
+        Ok(Self(values))
+    }
+
+    pub fn values(&self) -> &[DateTime] {
+        &self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("EXDATE");
+        if let Some(tzid) = self.0[0].time_zone() {
+            s.push(';');
+            s.push_str(&tzid.to_escaped());
+        }
+        s.push(':');
+        s.push_str(
+            &self
+                .0
+                .iter()
+                .map(DateTime::to_escaped)
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::i_calendar::property_parameters::TimeZoneIdentifier;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<ExceptionDateTimes>();
+
+        assert_eq!(
+            ExceptionDateTimes::new(vec![DateTime::utc(1997, 7, 14, 12, 30, 0)?])?.to_escaped(),
+            "EXDATE:19970714T123000Z\r\n"
+        );
+
+        let tzid = TimeZoneIdentifier::from_unescaped("America/New_York")?;
+        assert_eq!(
+            ExceptionDateTimes::new(vec![
+                DateTime::local(1997, 7, 14, 8, 30, 0)?.with_time_zone(tzid.clone())?,
+                DateTime::local(1997, 7, 15, 8, 30, 0)?.with_time_zone(tzid.clone())?,
+            ])?
+            .to_escaped(),
+            "EXDATE;TZID=America/New_York:19970714T083000,19970715T083000\r\n"
+        );
+
+        assert!(ExceptionDateTimes::new(vec![]).is_err());
+        assert!(
+            ExceptionDateTimes::new(vec![
+                DateTime::local(1997, 7, 14, 8, 30, 0)?.with_time_zone(tzid)?,
+                DateTime::utc(1997, 7, 15, 8, 30, 0)?,
+            ])
+            .is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! Free/Busy Time
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.2.6>
+
+use crate::i_calendar::{
+    property_parameters::{FreeBusyTimeType, OtherParam},
+    value_type::Period,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("free/busy time")]
+pub struct FreeBusyTimeError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("period required")]
+    PeriodRequired,
+    #[error("UTC time format required")]
+    UtcTimeFormatRequired,
+}
+
+/// freebusy   = "FREEBUSY" fbparam ":" fbvalue CRLF
+///
+/// fbparam    = *(
+///              ; The following is OPTIONAL,
+///              ; but MUST NOT occur more than once.
+///              (";" fbtypeparam) /
+///              ; The following is OPTIONAL,
+///              ; and MAY occur more than once.
+///              (";" other-param)
+///              )
+///
+/// fbvalue    = period *("," period)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FreeBusyTime {
+    fbtype: Option<FreeBusyTimeType>,
+    periods: Vec<Period>,
+    params: Vec<OtherParam>,
+}
+
+impl FreeBusyTime {
+    pub fn new(
+        fbtype: Option<FreeBusyTimeType>,
+        periods: Vec<Period>,
+    ) -> Result<Self, FreeBusyTimeError> {
+        Self::with_parameters(fbtype, periods, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(
+        fbtype: Option<FreeBusyTimeType>,
+        periods: Vec<Period>,
+        param: I,
+    ) -> Result<Self, FreeBusyTimeError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        if periods.is_empty() {
+            return Err(ErrorInner::PeriodRequired)?;
+        }
+        // The date and time values for free or busy time periods MUST be in the UTC time format.
+        if !periods.iter().all(|period| {
+            period.start_date_time().is_utc()
+                && period.end_date_time().is_none_or(|end| end.is_utc())
+        }) {
+            return Err(ErrorInner::UtcTimeFormatRequired)?;
+        }
+        Ok(Self {
+            fbtype,
+            periods,
+            params: param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        })
+    }
+
+    /// Returns the free/busy time type. The default value is BUSY.
+    pub fn fbtype(&self) -> FreeBusyTimeType {
+        self.fbtype.unwrap_or_default()
+    }
+
+    pub fn periods(&self) -> &[Period] {
+        &self.periods
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("FREEBUSY");
+        if let Some(fbtype) = self.fbtype {
+            s.push(';');
+            s.push_str(&fbtype.to_escaped());
+        }
+        for p in &self.params {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(
+            &self
+                .periods
+                .iter()
+                .map(Period::to_escaped)
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<FreeBusyTime>();
+
+        assert_eq!(
+            FreeBusyTime::new(
+                Some(FreeBusyTimeType::Free),
+                vec![
+                    Period::from_str("19970308T160000Z/PT3H")?,
+                    Period::from_str("19970308T200000Z/PT1H")?,
+                ]
+            )?
+            .to_escaped(),
+            "FREEBUSY;FBTYPE=FREE:19970308T160000Z/PT3H,19970308T200000Z/PT1H\r\n"
+        );
+
+        let free_busy_time = FreeBusyTime::new(
+            None,
+            vec![Period::from_str("19970308T160000Z/19970308T190000Z")?],
+        )?;
+        assert_eq!(free_busy_time.fbtype(), FreeBusyTimeType::Busy);
+        assert_eq!(
+            free_busy_time.to_escaped(),
+            "FREEBUSY:19970308T160000Z/19970308T190000Z\r\n"
+        );
+
+        assert!(FreeBusyTime::new(None, vec![]).is_err());
+        assert!(FreeBusyTime::new(None, vec![Period::from_str("19970308T160000/PT3H")?]).is_err());
+        Ok(())
+    }
+}
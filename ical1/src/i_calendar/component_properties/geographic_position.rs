@@ -0,0 +1,105 @@
+//! Geographic Position
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.1.6>
+
+use crate::i_calendar::{property_parameters::OtherParam, value_type::Float};
+
+#[derive(Debug, thiserror::Error)]
+#[error("geographic position")]
+pub struct GeographicPositionError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("latitude out of range")]
+    LatitudeOutOfRange,
+    #[error("longitude out of range")]
+    LongitudeOutOfRange,
+}
+
+/// geovalue   = float ";" float
+/// ;Latitude and Longitude components
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeographicPosition {
+    latitude: Float,
+    longitude: Float,
+    param: Vec<OtherParam>,
+}
+
+impl GeographicPosition {
+    pub fn new(latitude: Float, longitude: Float) -> Result<Self, GeographicPositionError> {
+        Self::with_parameters(latitude, longitude, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(
+        latitude: Float,
+        longitude: Float,
+        param: I,
+    ) -> Result<Self, GeographicPositionError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        if !(-90.0..=90.0).contains(&latitude.as_f64()) {
+            return Err(ErrorInner::LatitudeOutOfRange)?;
+        }
+        if !(-180.0..=180.0).contains(&longitude.as_f64()) {
+            return Err(ErrorInner::LongitudeOutOfRange)?;
+        }
+        Ok(Self {
+            latitude,
+            longitude,
+            param: param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        })
+    }
+
+    pub fn latitude(&self) -> &Float {
+        &self.latitude
+    }
+
+    pub fn longitude(&self) -> &Float {
+        &self.longitude
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("GEO");
+        for p in &self.param {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.latitude.to_escaped());
+        s.push(';');
+        s.push_str(&self.longitude.to_escaped());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<GeographicPosition>();
+
+        let geo = GeographicPosition::new(
+            Float::from_str("37.386013")?,
+            Float::from_str("-122.082932")?,
+        )?;
+        assert_eq!(geo.latitude().as_f64(), 37.386013);
+        assert_eq!(geo.longitude().as_f64(), -122.082932);
+        assert_eq!(geo.to_escaped(), "GEO:37.386013;-122.082932\r\n");
+
+        assert!(GeographicPosition::new(Float::new(90.5)?, Float::new(0.0)?).is_err());
+        assert!(GeographicPosition::new(Float::new(0.0)?, Float::new(-180.5)?).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! IANA Properties
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.8.1>
+
+use crate::i_calendar::property_parameters::{IanaToken, OtherParam};
+
+#[derive(Debug, thiserror::Error)]
+#[error("iana property")]
+pub struct IanaPropertyError {
+    _private: (),
+}
+
+/// iana-prop = iana-token *(";" icalparameter) ":" value CRLF
+///
+/// The value is kept as is (escaped), because its value type is unknown.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IanaProperty {
+    name: IanaToken,
+    param: Vec<OtherParam>,
+    value: String,
+}
+
+impl IanaProperty {
+    pub fn new(name: IanaToken, value: &str) -> Result<Self, IanaPropertyError> {
+        Self::with_parameters(name, value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(
+        name: IanaToken,
+        value: &str,
+        param: I,
+    ) -> Result<Self, IanaPropertyError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        // value = *VALUE-CHAR
+        if value.chars().any(|c| c.is_ascii_control() && c != '\t') {
+            return Err(IanaPropertyError { _private: () });
+        }
+        Ok(Self {
+            name,
+            param: param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+            value: value.to_owned(),
+        })
+    }
+
+    pub fn name(&self) -> &IanaToken {
+        &self.name
+    }
+
+    /// Returns the (escaped) value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str(&self.name.to_escaped());
+        for p in &self.param {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.value);
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::i_calendar::property_parameters::{IanaParam, ParamValue};
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<IanaProperty>();
+
+        let property = IanaProperty::new(IanaToken::from_str("NAME")?, "Company Vacation Days")?;
+        assert_eq!(property.name(), &IanaToken::from_str("NAME")?);
+        assert_eq!(property.value(), "Company Vacation Days");
+        assert_eq!(property.to_escaped(), "NAME:Company Vacation Days\r\n");
+
+        assert_eq!(
+            IanaProperty::with_parameters(
+                IanaToken::from_str("COLOR")?,
+                "turquoise",
+                [IanaParam::new(
+                    IanaToken::from_str("LANGUAGE")?,
+                    vec![ParamValue::from_unescaped("en")?]
+                )?]
+            )?
+            .to_escaped(),
+            "COLOR;LANGUAGE=en:turquoise\r\n"
+        );
+
+        assert!(IanaProperty::new(IanaToken::from_str("NAME")?, "a\u{7f}").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! Location
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.1.7>
+
+use crate::i_calendar::{
+    property_parameters::{AlternateTextRepresentation, OtherParam},
+    value_type::Text,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("location")]
+pub struct LocationError {
+    _private: (),
+}
+
+/// locparam   = *(
+///            ;
+///            ; The following are OPTIONAL,
+///            ; but MUST NOT occur more than once.
+///            ;
+///            (";" altrepparam) / (";" languageparam) /
+///            ;
+///            ; The following is OPTIONAL,
+///            ; and MAY occur more than once.
+///            ;
+///            (";" other-param)
+///            ;
+///            )
+///
+/// languageparam is kept as other-param
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Location {
+    value: Text,
+    altrep: Option<AlternateTextRepresentation>,
+    param: Vec<OtherParam>,
+}
+
+impl Location {
+    pub fn new(value: Text) -> Result<Self, LocationError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: Text, param: I) -> Result<Self, LocationError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self {
+            value,
+            altrep: None,
+            param: param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        })
+    }
+
+    pub fn with_altrep(self, altrep: AlternateTextRepresentation) -> Self {
+        Self {
+            altrep: Some(altrep),
+            ..self
+        }
+    }
+
+    pub fn value(&self) -> &Text {
+        &self.value
+    }
+
+    pub fn altrep(&self) -> Option<&AlternateTextRepresentation> {
+        self.altrep.as_ref()
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("LOCATION");
+        if let Some(altrep) = &self.altrep {
+            s.push(';');
+            s.push_str(&altrep.to_escaped());
+        }
+        for p in &self.param {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.value.clone().into_string());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::i_calendar::value_type::Uri;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Location>();
+
+        assert_eq!(
+            Location::new(Text::from_unescaped("Conference Room - F123, Bldg. 002")?)?.to_escaped(),
+            "LOCATION:Conference Room - F123\\, Bldg. 002\r\n"
+        );
+        let location = Location::new(Text::from_unescaped("Conference Room - F123, Bldg. 002")?)?
+            .with_altrep(AlternateTextRepresentation::new(Uri::from_str(
+                "http://xyzcorp.com/conf-rooms/f123.vcf",
+            )?)?);
+        assert!(location.altrep().is_some());
+        assert_eq!(
+            location.to_escaped(),
+            "LOCATION;ALTREP=\"http://xyzcorp.com/conf-rooms/f123.vcf\":Conference Room - F123\\, Bldg. 002\r\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! Non-Standard Properties
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.8.2>
+
+use crate::i_calendar::property_parameters::{OtherParam, XName};
+
+#[derive(Debug, thiserror::Error)]
+#[error("non-standard property")]
+pub struct NonStandardPropertyError {
+    _private: (),
+}
+
+/// x-prop = x-name *(";" icalparameter) ":" value CRLF
+///
+/// The value is kept as is (escaped), because its value type is unknown.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NonStandardProperty {
+    name: XName,
+    param: Vec<OtherParam>,
+    value: String,
+}
+
+impl NonStandardProperty {
+    pub fn new(name: XName, value: &str) -> Result<Self, NonStandardPropertyError> {
+        Self::with_parameters(name, value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(
+        name: XName,
+        value: &str,
+        param: I,
+    ) -> Result<Self, NonStandardPropertyError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        // value = *VALUE-CHAR
+        if value.chars().any(|c| c.is_ascii_control() && c != '\t') {
+            return Err(NonStandardPropertyError { _private: () });
+        }
+        Ok(Self {
+            name,
+            param: param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+            value: value.to_owned(),
+        })
+    }
+
+    pub fn name(&self) -> &XName {
+        &self.name
+    }
+
+    /// Returns the (escaped) value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str(&self.name.to_escaped());
+        for p in &self.param {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.value);
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::i_calendar::property_parameters::{ParamValue, XParam};
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<NonStandardProperty>();
+
+        let property = NonStandardProperty::new(XName::from_str("X-WR-CALNAME")?, "Work")?;
+        assert_eq!(property.name(), &XName::from_str("X-WR-CALNAME")?);
+        assert_eq!(property.value(), "Work");
+        assert_eq!(property.to_escaped(), "X-WR-CALNAME:Work\r\n");
+
+        assert_eq!(
+            NonStandardProperty::with_parameters(
+                XName::from_str("X-ABC-MMSUBJ")?,
+                "http://www.example.org/mysubj.au",
+                [XParam::new(
+                    XName::from_str("X-ABC-FMTTYPE")?,
+                    vec![ParamValue::from_unescaped("audio/basic")?]
+                )?]
+            )?
+            .to_escaped(),
+            "X-ABC-MMSUBJ;X-ABC-FMTTYPE=audio/basic:http://www.example.org/mysubj.au\r\n"
+        );
+
+        assert!(NonStandardProperty::new(XName::from_str("X-NAME")?, "a\r\nb").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+//! Organizer
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.4.3>
+
+use crate::i_calendar::{
+    property_parameters::{CommonName, OtherParam},
+    value_type::CalendarUserAddress,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("organizer")]
+pub struct OrganizerError {
+    _private: (),
+}
+
+/// orgparam   = *(
+///            ;
+///            ; The following are OPTIONAL,
+///            ; but MUST NOT occur more than once.
+///            ;
+///            (";" cnparam) / (";" dirparam) / (";" sentbyparam) /
+///            (";" languageparam) /
+///            ;
+///            ; The following is OPTIONAL,
+///            ; and MAY occur more than once.
+///            ;
+///            (";" other-param)
+///            ;
+///            )
+///
+/// dirparam, sentbyparam and languageparam are kept as other-param
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Organizer {
+    value: CalendarUserAddress,
+    cn: Option<CommonName>,
+    param: Vec<OtherParam>,
+}
+
+impl Organizer {
+    pub fn new(value: CalendarUserAddress) -> Result<Self, OrganizerError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: CalendarUserAddress, param: I) -> Result<Self, OrganizerError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self {
+            value,
+            cn: None,
+            param: param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        })
+    }
+
+    pub fn with_cn(self, cn: CommonName) -> Self {
+        Self {
+            cn: Some(cn),
+            ..self
+        }
+    }
+
+    pub fn value(&self) -> &CalendarUserAddress {
+        &self.value
+    }
+
+    pub fn cn(&self) -> Option<&CommonName> {
+        self.cn.as_ref()
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("ORGANIZER");
+        if let Some(cn) = &self.cn {
+            s.push(';');
+            s.push_str(&cn.to_escaped());
+        }
+        for p in &self.param {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.value.to_escaped());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Organizer>();
+
+        let organizer =
+            Organizer::new(CalendarUserAddress::from_str("mailto:jsmith@example.com")?)?
+                .with_cn(CommonName::new("John Smith")?);
+        assert_eq!(organizer.cn(), Some(&CommonName::new("John Smith")?));
+        assert_eq!(
+            organizer.to_escaped(),
+            "ORGANIZER;CN=John Smith:mailto:jsmith@example.com\r\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! Percent Complete
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.1.8>
+
+use crate::i_calendar::property_parameters::OtherParam;
+
+#[derive(Debug, thiserror::Error)]
+#[error("percent complete")]
+pub struct PercentCompleteError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("out of range")]
+    OutOfRange,
+}
+
+/// pctvalue   = integer
+///            ; Must be a positive integer between 0 and 100
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PercentComplete(u8, Vec<OtherParam>);
+
+impl PercentComplete {
+    pub fn new(value: u8) -> Result<Self, PercentCompleteError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: u8, param: I) -> Result<Self, PercentCompleteError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        if value > 100 {
+            return Err(ErrorInner::OutOfRange)?;
+        }
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("PERCENT-COMPLETE");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_string());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<PercentComplete>();
+
+        assert_eq!(
+            PercentComplete::new(39)?.to_escaped(),
+            "PERCENT-COMPLETE:39\r\n"
+        );
+        assert!(PercentComplete::new(100).is_ok());
+        assert!(PercentComplete::new(101).is_err());
+        Ok(())
+    }
+}
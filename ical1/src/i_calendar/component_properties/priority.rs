@@ -0,0 +1,72 @@
+//! Priority
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.1.9>
+
+use crate::i_calendar::property_parameters::OtherParam;
+
+#[derive(Debug, thiserror::Error)]
+#[error("priority")]
+pub struct PriorityError {
+    _private: (),
+}
+
+/// priovalue   = integer       ;Must be in the range [0..9]
+///    ; All other values are reserved for future use.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Priority(u8, Vec<OtherParam>);
+
+impl Priority {
+    pub fn new(value: u8) -> Result<Self, PriorityError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: u8, param: I) -> Result<Self, PriorityError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        if value > 9 {
+            return Err(PriorityError { _private: () });
+        }
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    /// Returns the priority (0 is undefined, 1 is the highest and 9 is the lowest).
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("PRIORITY");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_string());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Priority>();
+
+        assert_eq!(Priority::new(1)?.to_escaped(), "PRIORITY:1\r\n");
+        assert!(Priority::new(10).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+//! Recurrence Rule
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.5.3>
+
+use crate::i_calendar::{
+    property_parameters::OtherParam,
+    value_type::{DateTime, Recur},
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("recurrence rule")]
+pub struct RecurrenceRuleError {
+    _private: (),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurrenceRule(Recur, Vec<OtherParam>);
+
+impl RecurrenceRule {
+    pub fn new(value: Recur) -> Result<Self, RecurrenceRuleError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: Recur, param: I) -> Result<Self, RecurrenceRuleError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> &Recur {
+        &self.0
+    }
+
+    /// Returns whether the UNTIL rule part (if any) has the value type required by DTSTART.
+    ///
+    /// If the "DTSTART" property is specified as a date with local time, then the UNTIL rule
+    /// part MUST also be specified as a date with local time. If the "DTSTART" property is
+    /// specified as a date with UTC time or a date with local time and time zone reference,
+    /// then the UNTIL rule part MUST be specified as a date with UTC time.
+    pub(in crate::i_calendar) fn is_until_consistent_with(&self, dtstart: &DateTime) -> bool {
+        let floating = !dtstart.is_utc() && dtstart.time_zone().is_none();
+        self.0
+            .until()
+            .is_none_or(|until| until.is_utc() != floating)
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("RRULE");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_escaped());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::property_parameters::{ParamValue, XName, XParam};
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<RecurrenceRule>();
+
+        assert_eq!(
+            RecurrenceRule::new(Recur::from_str("FREQ=DAILY;COUNT=10")?)?.to_escaped(),
+            "RRULE:FREQ=DAILY;COUNT=10\r\n"
+        );
+
+        assert_eq!(
+            RecurrenceRule::with_parameters(
+                Recur::from_str("FREQ=YEARLY;BYMONTH=1;BYDAY=SU,MO")?,
+                vec![XParam::new(
+                    XName::from_unescaped("X-PARAM")?,
+                    vec![ParamValue::from_unescaped("value")?]
+                )?],
+            )?
+            .to_escaped(),
+            "RRULE;X-PARAM=value:FREQ=YEARLY;BYDAY=SU,MO;BYMONTH=1\r\n"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! Repeat Count
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.6.2>
+
+use crate::i_calendar::property_parameters::OtherParam;
+
+#[derive(Debug, thiserror::Error)]
+#[error("repeat count")]
+pub struct RepeatCountError {
+    _private: (),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepeatCount(u32, Vec<OtherParam>);
+
+impl RepeatCount {
+    pub fn new(value: u32) -> Result<Self, RepeatCountError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: u32, param: I) -> Result<Self, RepeatCountError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("REPEAT");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_string());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<RepeatCount>();
+
+        assert_eq!(RepeatCount::new(4)?.to_escaped(), "REPEAT:4\r\n");
+        Ok(())
+    }
+}
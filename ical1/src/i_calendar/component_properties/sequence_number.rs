@@ -0,0 +1,65 @@
+//! Sequence Number
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.7.4>
+
+use crate::i_calendar::property_parameters::OtherParam;
+
+#[derive(Debug, thiserror::Error)]
+#[error("sequence number")]
+pub struct SequenceNumberError {
+    _private: (),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SequenceNumber(u32, Vec<OtherParam>);
+
+impl SequenceNumber {
+    pub fn new(value: u32) -> Result<Self, SequenceNumberError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: u32, param: I) -> Result<Self, SequenceNumberError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("SEQUENCE");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_string());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<SequenceNumber>();
+
+        assert_eq!(SequenceNumber::new(2)?.to_escaped(), "SEQUENCE:2\r\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+//! Status
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.1.11>
+
+use crate::i_calendar::property_parameters::OtherParam;
+
+#[derive(Debug, thiserror::Error)]
+#[error("status")]
+pub struct StatusError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("unknown status value: {0}")]
+    UnknownValue(String),
+}
+
+/// statvalue-event = "TENTATIVE" / "CONFIRMED" / "CANCELLED"
+/// statvalue-todo  = "NEEDS-ACTION" / "COMPLETED" / "IN-PROCESS" / "CANCELLED"
+/// statvalue-jour  = "DRAFT" / "FINAL" / "CANCELLED"
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum StatusValue {
+    Tentative,
+    Confirmed,
+    Cancelled,
+    NeedsAction,
+    Completed,
+    InProcess,
+    Draft,
+    Final,
+}
+
+impl StatusValue {
+    pub(in crate::i_calendar) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tentative => "TENTATIVE",
+            Self::Confirmed => "CONFIRMED",
+            Self::Cancelled => "CANCELLED",
+            Self::NeedsAction => "NEEDS-ACTION",
+            Self::Completed => "COMPLETED",
+            Self::InProcess => "IN-PROCESS",
+            Self::Draft => "DRAFT",
+            Self::Final => "FINAL",
+        }
+    }
+
+    /// statvalue-event
+    pub(in crate::i_calendar) fn is_for_event(&self) -> bool {
+        matches!(self, Self::Tentative | Self::Confirmed | Self::Cancelled)
+    }
+
+    /// statvalue-todo
+    pub(in crate::i_calendar) fn is_for_todo(&self) -> bool {
+        matches!(
+            self,
+            Self::NeedsAction | Self::Completed | Self::InProcess | Self::Cancelled
+        )
+    }
+
+    /// statvalue-jour
+    pub(in crate::i_calendar) fn is_for_journal(&self) -> bool {
+        matches!(self, Self::Draft | Self::Final | Self::Cancelled)
+    }
+}
+
+impl std::str::FromStr for StatusValue {
+    type Err = StatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "TENTATIVE" => Self::Tentative,
+            "CONFIRMED" => Self::Confirmed,
+            "CANCELLED" => Self::Cancelled,
+            "NEEDS-ACTION" => Self::NeedsAction,
+            "COMPLETED" => Self::Completed,
+            "IN-PROCESS" => Self::InProcess,
+            "DRAFT" => Self::Draft,
+            "FINAL" => Self::Final,
+            _ => return Err(ErrorInner::UnknownValue(s.to_owned()))?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Status(StatusValue, Vec<OtherParam>);
+
+impl Status {
+    pub fn new(value: StatusValue) -> Result<Self, StatusError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: StatusValue, param: I) -> Result<Self, StatusError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> StatusValue {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("STATUS");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(self.0.as_str());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::property_parameters::{ParamValue, XName, XParam};
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Status>();
+
+        assert_eq!(
+            Status::new(StatusValue::from_str("NEEDS-ACTION")?)?.to_escaped(),
+            "STATUS:NEEDS-ACTION\r\n"
+        );
+
+        assert_eq!(
+            Status::with_parameters(
+                StatusValue::Draft,
+                vec![XParam::new(
+                    XName::from_unescaped("X-PARAM")?,
+                    vec![ParamValue::from_unescaped("value")?]
+                )?],
+            )?
+            .to_escaped(),
+            "STATUS;X-PARAM=value:DRAFT\r\n"
+        );
+
+        assert!(StatusValue::Cancelled.is_for_todo());
+        assert!(StatusValue::Cancelled.is_for_journal());
+        assert!(!StatusValue::Draft.is_for_todo());
+        assert!(StatusValue::from_str("UNKNOWN").is_err());
+        Ok(())
+    }
+}
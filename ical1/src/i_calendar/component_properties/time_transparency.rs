@@ -0,0 +1,115 @@
+//! Time Transparency
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.2.7>
+
+use crate::i_calendar::property_parameters::OtherParam;
+
+#[derive(Debug, thiserror::Error)]
+#[error("time transparency")]
+pub struct TimeTransparencyError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("unknown time transparency value: {0}")]
+    UnknownValue(String),
+}
+
+/// transvalue = "OPAQUE"
+///             ;Blocks or opaque on busy time searches.
+///             / "TRANSPARENT"
+///             ;Transparent on busy time searches.
+///    ;Default value is OPAQUE
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub enum TimeTransparencyValue {
+    #[default]
+    Opaque,
+    Transparent,
+}
+
+impl TimeTransparencyValue {
+    pub(in crate::i_calendar) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Opaque => "OPAQUE",
+            Self::Transparent => "TRANSPARENT",
+        }
+    }
+}
+
+impl std::str::FromStr for TimeTransparencyValue {
+    type Err = TimeTransparencyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "OPAQUE" => Self::Opaque,
+            "TRANSPARENT" => Self::Transparent,
+            _ => return Err(ErrorInner::UnknownValue(s.to_owned()))?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeTransparency(TimeTransparencyValue, Vec<OtherParam>);
+
+impl TimeTransparency {
+    pub fn new(value: TimeTransparencyValue) -> Result<Self, TimeTransparencyError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(
+        value: TimeTransparencyValue,
+        param: I,
+    ) -> Result<Self, TimeTransparencyError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> TimeTransparencyValue {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("TRANSP");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(self.0.as_str());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<TimeTransparency>();
+
+        assert_eq!(
+            TimeTransparencyValue::default(),
+            TimeTransparencyValue::Opaque
+        );
+        assert_eq!(
+            TimeTransparency::new(TimeTransparencyValue::from_str("transparent")?)?.to_escaped(),
+            "TRANSP:TRANSPARENT\r\n"
+        );
+        assert!(TimeTransparencyValue::from_str("CLEAR").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Time Zone Name
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.3.2>
+
+use crate::i_calendar::{
+    property_parameters::OtherParam,
+    value_type::{Text, TextError},
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("time zone name")]
+pub struct TimeZoneNameError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("text")]
+    Text(#[from] TextError),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeZoneName(Text, Vec<OtherParam>);
+
+impl TimeZoneName {
+    pub fn new(value: Text) -> Result<Self, TimeZoneNameError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: Text, param: I) -> Result<Self, TimeZoneNameError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> &Text {
+        &self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("TZNAME");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.clone().into_string());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::i_calendar::property_parameters::{ParamValue, XName, XParam};
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<TimeZoneName>();
+
+        assert_eq!(
+            TimeZoneName::new(Text::from_unescaped("EST")?)?.to_escaped(),
+            "TZNAME:EST\r\n"
+        );
+
+        assert_eq!(
+            TimeZoneName::with_parameters(
+                Text::from_unescaped("EST")?,
+                vec![XParam::new(
+                    XName::from_unescaped("X-PARAM")?,
+                    vec![ParamValue::from_unescaped("value")?]
+                )?],
+            )?
+            .to_escaped(),
+            "TZNAME;X-PARAM=value:EST\r\n"
+        );
+
+        Ok(())
+    }
+}
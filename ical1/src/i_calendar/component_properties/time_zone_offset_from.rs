@@ -0,0 +1,92 @@
+//! Time Zone Offset From
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.3.3>
+
+use crate::i_calendar::{
+    property_parameters::OtherParam,
+    value_type::{UtcOffset, UtcOffsetError},
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("time zone offset from")]
+pub struct TimeZoneOffsetFromError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("utc-offset")]
+    UtcOffset(#[from] UtcOffsetError),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeZoneOffsetFrom(UtcOffset, Vec<OtherParam>);
+
+impl TimeZoneOffsetFrom {
+    pub fn new(value: UtcOffset) -> Result<Self, TimeZoneOffsetFromError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: UtcOffset, param: I) -> Result<Self, TimeZoneOffsetFromError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> &UtcOffset {
+        &self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("TZOFFSETFROM");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_escaped());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::i_calendar::property_parameters::{ParamValue, XName, XParam};
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<TimeZoneOffsetFrom>();
+
+        assert_eq!(
+            TimeZoneOffsetFrom::new(UtcOffset::from_str("-0500")?)?.to_escaped(),
+            "TZOFFSETFROM:-0500\r\n"
+        );
+
+        assert_eq!(
+            TimeZoneOffsetFrom::with_parameters(
+                UtcOffset::from_str("-0500")?,
+                vec![XParam::new(
+                    XName::from_unescaped("X-PARAM")?,
+                    vec![ParamValue::from_unescaped("value")?]
+                )?],
+            )?
+            .to_escaped(),
+            "TZOFFSETFROM;X-PARAM=value:-0500\r\n"
+        );
+
+        Ok(())
+    }
+}
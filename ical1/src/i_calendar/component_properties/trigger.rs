@@ -0,0 +1,145 @@
+//! Trigger
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.6.3>
+
+use crate::i_calendar::{
+    property_parameters::AlarmTriggerRelationship,
+    value_type::{DateTime, Duration},
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("trigger")]
+pub struct TriggerError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("UTC time format required")]
+    UtcTimeFormatRequired,
+}
+
+/// trigger    = "TRIGGER" (trigrel / trigabs) CRLF
+///
+/// trigrel    = *(
+///              ; The following are OPTIONAL,
+///              ; but MUST NOT occur more than once.
+///              (";" "VALUE" "=" "DURATION") /
+///              (";" trigrelparam) /
+///              ; The following is OPTIONAL,
+///              ; and MAY occur more than once.
+///              (";" other-param)
+///              ) ":"  dur-value
+///
+/// trigabs    = *(
+///              ; The following is REQUIRED,
+///              ; but MUST NOT occur more than once.
+///              (";" "VALUE" "=" "DATE-TIME") /
+///              ; The following is OPTIONAL,
+///              ; and MAY occur more than once.
+///              (";" other-param)
+///              ) ":" date-time
+///
+/// other-param not supported
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trigger(TriggerInner);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum TriggerInner {
+    Relative(Duration, Option<AlarmTriggerRelationship>),
+    Absolute(DateTime),
+}
+
+impl Trigger {
+    /// trigrel (e.g. `TRIGGER;RELATED=END:-PT5M`)
+    pub fn relative(
+        duration: Duration,
+        related: Option<AlarmTriggerRelationship>,
+    ) -> Result<Self, TriggerError> {
+        Ok(Self(TriggerInner::Relative(duration, related)))
+    }
+
+    /// trigabs (e.g. `TRIGGER;VALUE=DATE-TIME:19980101T050000Z`)
+    pub fn absolute(date_time: DateTime) -> Result<Self, TriggerError> {
+        // The value type MUST be a date with UTC time.
+        if !date_time.is_utc() {
+            return Err(ErrorInner::UtcTimeFormatRequired)?;
+        }
+        Ok(Self(TriggerInner::Absolute(date_time)))
+    }
+
+    /// Returns the duration of the relative trigger.
+    pub fn duration(&self) -> Option<&Duration> {
+        match &self.0 {
+            TriggerInner::Relative(duration, _) => Some(duration),
+            TriggerInner::Absolute(_) => None,
+        }
+    }
+
+    /// Returns the relationship of the relative trigger. The default value is START.
+    pub fn related(&self) -> Option<AlarmTriggerRelationship> {
+        match &self.0 {
+            TriggerInner::Relative(_, related) => Some(related.unwrap_or_default()),
+            TriggerInner::Absolute(_) => None,
+        }
+    }
+
+    /// Returns the date-time of the absolute trigger.
+    pub fn date_time(&self) -> Option<&DateTime> {
+        match &self.0 {
+            TriggerInner::Relative(_, _) => None,
+            TriggerInner::Absolute(date_time) => Some(date_time),
+        }
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("TRIGGER");
+        match &self.0 {
+            TriggerInner::Relative(duration, related) => {
+                if let Some(related) = related {
+                    s.push(';');
+                    s.push_str(&related.to_escaped());
+                }
+                s.push(':');
+                s.push_str(&duration.to_escaped());
+            }
+            TriggerInner::Absolute(date_time) => {
+                s.push_str(";VALUE=DATE-TIME:");
+                s.push_str(&date_time.to_escaped());
+            }
+        }
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Trigger>();
+
+        assert_eq!(
+            Trigger::relative(Duration::from_str("-PT15M")?, None)?.to_escaped(),
+            "TRIGGER:-PT15M\r\n"
+        );
+        assert_eq!(
+            Trigger::relative(
+                Duration::from_str("PT5M")?,
+                Some(AlarmTriggerRelationship::End)
+            )?
+            .to_escaped(),
+            "TRIGGER;RELATED=END:PT5M\r\n"
+        );
+        assert_eq!(
+            Trigger::absolute(DateTime::utc(1998, 1, 1, 5, 0, 0)?)?.to_escaped(),
+            "TRIGGER;VALUE=DATE-TIME:19980101T050000Z\r\n"
+        );
+        assert!(Trigger::absolute(DateTime::local(1998, 1, 1, 5, 0, 0)?).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! Uniform Resource Locator
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.4.6>
+
+use crate::i_calendar::{property_parameters::OtherParam, value_type::Uri};
+
+#[derive(Debug, thiserror::Error)]
+#[error("uniform resource locator")]
+pub struct UniformResourceLocatorError {
+    _private: (),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UniformResourceLocator(Uri, Vec<OtherParam>);
+
+impl UniformResourceLocator {
+    pub fn new(value: Uri) -> Result<Self, UniformResourceLocatorError> {
+        Self::with_parameters(value, Vec::<OtherParam>::new())
+    }
+
+    pub fn with_parameters<I>(value: Uri, param: I) -> Result<Self, UniformResourceLocatorError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OtherParam>,
+    {
+        Ok(Self(
+            value,
+            param
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<OtherParam>>(),
+        ))
+    }
+
+    pub fn value(&self) -> &Uri {
+        &self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("URL");
+        for p in &self.1 {
+            s.push(';');
+            s.push_str(&p.to_escaped());
+        }
+        s.push(':');
+        s.push_str(&self.0.to_string());
+        s.push_str("\r\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<UniformResourceLocator>();
+
+        assert_eq!(
+            UniformResourceLocator::new(Uri::from_str(
+                "http://example.com/pub/calendars/jsmith/mytime.ics"
+            )?)?
+            .to_escaped(),
+            "URL:http://example.com/pub/calendars/jsmith/mytime.ics\r\n"
+        );
+        Ok(())
+    }
+}
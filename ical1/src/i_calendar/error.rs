@@ -1,25 +1,97 @@
 use crate::i_calendar::{
-    CalendarScaleError, CategoriesError, ClassificationError, DateTimeEndError, DateTimeStampError,
-    DateTimeStartError, EventError, ICalendarObjectError, ICalendarStreamError, MethodError,
-    ProductIdentifierError, SummaryError, TextError, UniqueIdentifierError, VersionError,
+    ActionError, AlarmError, AlarmTriggerRelationshipError, AlternateTextRepresentationError,
+    AttachmentError, AttendeeError, CalendarScaleError, CalendarUserAddressError,
+    CalendarUserTypeError, CategoriesError, ClassificationError, CommonNameError,
+    DateTimeCompletedError, DateTimeCreatedError, DateTimeDueError, DateTimeEndError,
+    DateTimeError, DateTimeStampError, DateTimeStartError, DescriptionError, DurationError,
+    DurationPropertyError, EventError, ExceptionDateTimesError, FloatError, FreeBusyError,
+    FreeBusyTimeError, FreeBusyTimeTypeError, GeographicPositionError, ICalendarObjectError,
+    ICalendarStreamError, IanaComponentError, IanaPropertyError, IanaTokenError, JournalError,
+    LastModifiedError, LocationError, MethodError, NonStandardComponentError,
+    NonStandardPropertyError, ObservanceError, OrganizerError, ParseError, ParticipationRoleError,
+    ParticipationStatusError, PercentCompleteError, PeriodError, PriorityError,
+    ProductIdentifierError, RecurError, RecurrenceDateTimesError, RecurrenceRuleError,
+    RepeatCountError, RsvpExpectationError, SchedulingError, SequenceNumberError, StatusError,
+    SummaryError, TextError, TimeTransparencyError, TimeZoneError, TimeZoneIdentifierError,
+    TimeZoneNameError, TimeZoneOffsetFromError, TimeZoneOffsetToError, TodoError, TriggerError,
+    UniformResourceLocatorError, UniqueIdentifierError, UriError, UtcOffsetError, VersionError,
+    XNameError,
 };
 
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
 pub enum Error {
+    Action(#[from] ActionError),
+    Alarm(#[from] AlarmError),
+    AlarmTriggerRelationship(#[from] AlarmTriggerRelationshipError),
+    AlternateTextRepresentation(#[from] AlternateTextRepresentationError),
+    Attachment(#[from] AttachmentError),
+    Attendee(#[from] AttendeeError),
     CalendarScale(#[from] CalendarScaleError),
+    CalendarUserAddress(#[from] CalendarUserAddressError),
+    CalendarUserType(#[from] CalendarUserTypeError),
     Categories(#[from] CategoriesError),
     Classification(#[from] ClassificationError),
+    CommonName(#[from] CommonNameError),
+    DateTime(#[from] DateTimeError),
+    DateTimeCompleted(#[from] DateTimeCompletedError),
+    DateTimeCreated(#[from] DateTimeCreatedError),
+    DateTimeDue(#[from] DateTimeDueError),
     DateTimeEnd(#[from] DateTimeEndError),
     DateTimeStamp(#[from] DateTimeStampError),
     DateTimeStart(#[from] DateTimeStartError),
+    Description(#[from] DescriptionError),
+    Duration(#[from] DurationError),
+    DurationProperty(#[from] DurationPropertyError),
     Event(#[from] EventError),
+    ExceptionDateTimes(#[from] ExceptionDateTimesError),
+    Float(#[from] FloatError),
+    FreeBusy(#[from] FreeBusyError),
+    FreeBusyTime(#[from] FreeBusyTimeError),
+    FreeBusyTimeType(#[from] FreeBusyTimeTypeError),
+    GeographicPosition(#[from] GeographicPositionError),
     ICalendarObject(#[from] ICalendarObjectError),
     ICalendarStream(#[from] ICalendarStreamError),
+    IanaComponent(#[from] IanaComponentError),
+    IanaProperty(#[from] IanaPropertyError),
+    IanaToken(#[from] IanaTokenError),
+    Journal(#[from] JournalError),
+    LastModified(#[from] LastModifiedError),
+    Location(#[from] LocationError),
     Method(#[from] MethodError),
+    NonStandardComponent(#[from] NonStandardComponentError),
+    NonStandardProperty(#[from] NonStandardPropertyError),
+    Observance(#[from] ObservanceError),
+    Organizer(#[from] OrganizerError),
+    Parse(#[from] ParseError),
+    ParticipationRole(#[from] ParticipationRoleError),
+    ParticipationStatus(#[from] ParticipationStatusError),
+    PercentComplete(#[from] PercentCompleteError),
+    Period(#[from] PeriodError),
+    Priority(#[from] PriorityError),
     ProductIdentifier(#[from] ProductIdentifierError),
+    Recur(#[from] RecurError),
+    RecurrenceDateTimes(#[from] RecurrenceDateTimesError),
+    RecurrenceRule(#[from] RecurrenceRuleError),
+    RepeatCount(#[from] RepeatCountError),
+    RsvpExpectation(#[from] RsvpExpectationError),
+    Scheduling(#[from] SchedulingError),
+    SequenceNumber(#[from] SequenceNumberError),
+    Status(#[from] StatusError),
     Summary(#[from] SummaryError),
     Text(#[from] TextError),
+    TimeTransparency(#[from] TimeTransparencyError),
+    TimeZone(#[from] TimeZoneError),
+    TimeZoneIdentifier(#[from] TimeZoneIdentifierError),
+    TimeZoneName(#[from] TimeZoneNameError),
+    TimeZoneOffsetFrom(#[from] TimeZoneOffsetFromError),
+    TimeZoneOffsetTo(#[from] TimeZoneOffsetToError),
+    Todo(#[from] TodoError),
+    Trigger(#[from] TriggerError),
+    UniformResourceLocator(#[from] UniformResourceLocatorError),
     UniqueIdentifier(#[from] UniqueIdentifierError),
+    Uri(#[from] UriError),
+    UtcOffset(#[from] UtcOffsetError),
     Version(#[from] VersionError),
+    XName(#[from] XNameError),
 }
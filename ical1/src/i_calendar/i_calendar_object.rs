@@ -1,5 +1,12 @@
-use crate::i_calendar::{calendar_components, calendar_properties};
+use std::collections::BTreeSet;
 
+use crate::i_calendar::{
+    calendar_components, calendar_properties,
+    component_properties::{IanaProperty, NonStandardProperty},
+    property_parameters::TimeZoneIdentifier,
+};
+
+use super::parser::{ParseError, parse};
 use super::utils::fold;
 
 mod private {
@@ -23,6 +30,7 @@ pub struct ICalendarStreamError;
 /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.4>
 /// icalstream = 1*icalobject
 /// iCalendar stream
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ICalendarStream(Vec<ICalendarObject>);
 
 impl std::fmt::Display for ICalendarStream {
@@ -33,6 +41,14 @@ impl std::fmt::Display for ICalendarStream {
     }
 }
 
+impl std::str::FromStr for ICalendarStream {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
 impl ICalendarStream {
     pub fn builder() -> ICalendarStreamBuilder {
         ICalendarStreamBuilder::new()
@@ -42,6 +58,13 @@ impl ICalendarStream {
         assert!(!i_calendar_objects.is_empty());
         Self(i_calendar_objects)
     }
+
+    /// Returns the unfolded content lines.
+    pub(in crate::i_calendar) fn to_content_lines(&self) -> String {
+        let mut s = String::new();
+        self.write_to(&mut s).expect("write to string");
+        s
+    }
 }
 
 impl WriteTo for ICalendarStream {
@@ -76,7 +99,20 @@ impl ICalendarStreamBuilder {
 
 #[derive(Debug, thiserror::Error)]
 #[error("iCalendar object")]
-pub struct ICalendarObjectError;
+pub struct ICalendarObjectError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+#[allow(clippy::enum_variant_names)]
+enum ErrorInner {
+    #[error("component required")]
+    ComponentRequired,
+    #[error("product identifier required")]
+    ProductIdentifierRequired,
+    #[error("time zone {0} required")]
+    TimeZoneRequired(String),
+    #[error("version required")]
+    VersionRequired,
+}
 
 /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.4>
 /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6>
@@ -93,6 +129,7 @@ pub struct ICalendarObjectError;
 ///              1*contentline
 ///              "END" ":" x-name CRLF
 /// iCalendar Object
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ICalendarObject {
     calprops: CalendarProperties,
     component: Vec<CalendarComponent>,
@@ -103,25 +140,58 @@ impl ICalendarObject {
         ICalendarObjectBuilder::new()
     }
 
+    /// Returns the unfolded content lines.
+    pub(in crate::i_calendar) fn to_content_lines(&self) -> String {
+        let mut s = String::new();
+        self.write_to(&mut s).expect("write to string");
+        s
+    }
+
     fn from_builder(builder: ICalendarObjectBuilder) -> Result<Self, ICalendarObjectError> {
         if builder.component.is_empty() {
-            return Err(ICalendarObjectError);
+            return Err(ErrorInner::ComponentRequired)?;
         }
-        match (builder.prodid, builder.version) {
-            (Some(prodid), Some(version)) => Ok(Self {
-                calprops: CalendarProperties {
-                    prodid,
-                    version,
-                    calscale: builder.calscale,
-                    method: builder.method,
-                },
-                component: builder.component,
-            }),
-            _ => Err(ICalendarObjectError),
+        // An individual "VTIMEZONE" calendar component MUST be specified for each unique
+        // "TZID" parameter value specified in the iCalendar object.
+        if let Some(tzid) = missing_time_zone_identifiers(&builder.component)
+            .into_iter()
+            .next()
+        {
+            return Err(ErrorInner::TimeZoneRequired(tzid.to_unescaped()))?;
         }
+        Ok(Self {
+            calprops: CalendarProperties {
+                prodid: builder
+                    .prodid
+                    .ok_or(ErrorInner::ProductIdentifierRequired)?,
+                version: builder.version.ok_or(ErrorInner::VersionRequired)?,
+                calscale: builder.calscale,
+                method: builder.method,
+                x_prop: builder.x_prop,
+                iana_prop: builder.iana_prop,
+            },
+            component: builder.component,
+        })
     }
 }
 
+/// Returns the time zone identifiers that are referenced but not defined by "VTIMEZONE".
+fn missing_time_zone_identifiers(components: &[CalendarComponent]) -> BTreeSet<TimeZoneIdentifier> {
+    let defined = components
+        .iter()
+        .filter_map(|component| match &component.0 {
+            CalendarComponentInner::TimeZone(time_zone) => Some(time_zone.tzid()),
+            _ => None,
+        })
+        .collect::<BTreeSet<&TimeZoneIdentifier>>();
+    components
+        .iter()
+        .flat_map(CalendarComponent::time_zone_identifiers)
+        .filter(|tzid| !defined.contains(tzid))
+        .cloned()
+        .collect::<BTreeSet<TimeZoneIdentifier>>()
+}
+
 impl WriteTo for ICalendarObject {
     fn write_to<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
         w.write_str("BEGIN:VCALENDAR\r\n")?;
@@ -140,8 +210,8 @@ pub struct ICalendarObjectBuilder {
     version: Option<calendar_properties::Version>,
     calscale: Option<calendar_properties::CalendarScale>,
     method: Option<calendar_properties::Method>,
-    // x_prop: Vec<NonStandardProperty>,
-    // iana_prop: Vec<IanaProperty>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
 
     // component
     component: Vec<CalendarComponent>,
@@ -154,6 +224,8 @@ impl ICalendarObjectBuilder {
             version: None,
             calscale: None,
             method: None,
+            x_prop: Vec::new(),
+            iana_prop: Vec::new(),
             component: Vec::new(),
         }
     }
@@ -167,6 +239,34 @@ impl ICalendarObjectBuilder {
         self
     }
 
+    pub fn add_iana_prop(mut self, iana_prop: IanaProperty) -> Self {
+        self.iana_prop.push(iana_prop);
+        self
+    }
+
+    /// Adds "VTIMEZONE" components for the time zones referenced by the components added so far.
+    ///
+    /// `f` returns the definition of the time zone. Time zones that are already added or that
+    /// `f` returns `None` are skipped.
+    pub fn add_time_zones<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&TimeZoneIdentifier) -> Option<calendar_components::TimeZone>,
+    {
+        let time_zones = missing_time_zone_identifiers(&self.component)
+            .iter()
+            .filter_map(&mut f)
+            .map(private::IntoCalendarComponentSealed::into_calendar_component)
+            .collect::<Vec<CalendarComponent>>();
+        // "VTIMEZONE" components are placed before the components referencing them.
+        self.component.splice(0..0, time_zones);
+        self
+    }
+
+    pub fn add_x_prop(mut self, x_prop: NonStandardProperty) -> Self {
+        self.x_prop.push(x_prop);
+        self
+    }
+
     pub fn calscale(mut self, calscale: calendar_properties::CalendarScale) -> Self {
         self.calscale = Some(calscale);
         self
@@ -207,13 +307,14 @@ impl ICalendarObjectBuilder {
 ///            x-prop / iana-prop
 ///            ;
 ///            )
+#[derive(Clone, Debug, Eq, PartialEq)]
 struct CalendarProperties {
     prodid: calendar_properties::ProductIdentifier,
     version: calendar_properties::Version,
     calscale: Option<calendar_properties::CalendarScale>,
     method: Option<calendar_properties::Method>,
-    // x_prop: Vec<NonStandardProperty>,
-    // iana_prop: Vec<IanaProperty>,
+    x_prop: Vec<NonStandardProperty>,
+    iana_prop: Vec<IanaProperty>,
 }
 
 impl WriteTo for CalendarProperties {
@@ -226,29 +327,16 @@ impl WriteTo for CalendarProperties {
         if let Some(method) = &self.method {
             w.write_str(method.to_escaped().as_str())?;
         }
+        for x_prop in &self.x_prop {
+            w.write_str(x_prop.to_escaped().as_str())?;
+        }
+        for iana_prop in &self.iana_prop {
+            w.write_str(iana_prop.to_escaped().as_str())?;
+        }
         Ok(())
     }
 }
 
-// enum CalendarProperty {
-//     /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.8.1>
-//     /// iana-prop
-//     /// 3.8. Component Properties
-//     /// 3.8.8. Miscellaneous Component Properties
-//     /// 3.8.8.1. IANA Properties
-//     IanaProperties(
-//         // TODO
-//     ),
-//     /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.8.2>
-//     /// x-prop
-//     /// 3.8. Component Properties
-//     /// 3.8.8. Miscellaneous Component Properties
-//     /// 3.8.8.2. Non-Standard Properties
-//     NonStandardProperties(
-//         // TODO
-//     ),
-// }
-
 /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.6>
 /// component  = 1*(eventc / todoc / journalc / freebusyc /
 ///              timezonec / iana-comp / x-comp)
@@ -258,44 +346,30 @@ impl WriteTo for CalendarProperties {
 /// x-comp     = "BEGIN" ":" x-name CRLF
 ///              1*contentline
 ///              "END" ":" x-name CRLF
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CalendarComponent(CalendarComponentInner);
 
+#[derive(Clone, Debug, Eq, PartialEq)]
 enum CalendarComponentInner {
     /// Event Component
     /// eventc
-    Event(calendar_components::Event),
-    // /// To-Do Component
-    // /// todoc
-    // Todo(
-    //     // TODO
-    // ),
-    // /// Journal Component
-    // /// journalc
-    // Journal(
-    //     // TODO
-    // ),
-    // /// Free/Busy Component
-    // /// freebusyc
-    // Freebusy(
-    //     // TODO
-    // ),
-    // /// Time Zone Component
-    // /// timezonec
-    // Timezone(
-    //     // TODO
-    // ),
-    // /// iana-comp  = "BEGIN" ":" iana-token CRLF
-    // ///              1*contentline
-    // ///              "END" ":" iana-token CRLF
-    // IanaComp(
-    //     // TODO
-    // ),
-    // /// x-comp     = "BEGIN" ":" x-name CRLF
-    // ///              1*contentline
-    // ///              "END" ":" x-name CRLF
-    // XComp(
-    //     // TODO
-    // ),
+    Event(Box<calendar_components::Event>),
+    /// Time Zone Component
+    /// timezonec
+    TimeZone(calendar_components::TimeZone),
+    /// To-Do Component
+    /// todoc
+    Todo(Box<calendar_components::Todo>),
+    /// Journal Component
+    /// journalc
+    Journal(Box<calendar_components::Journal>),
+    /// Free/Busy Component
+    /// freebusyc
+    FreeBusy(calendar_components::FreeBusy),
+    /// iana-comp
+    IanaComp(calendar_components::IanaComponent),
+    /// x-comp
+    XComp(calendar_components::NonStandardComponent),
 }
 
 impl private::IntoCalendarComponentSealed for CalendarComponent {
@@ -308,17 +382,98 @@ impl IntoCalendarComponent for CalendarComponent {}
 
 impl private::IntoCalendarComponentSealed for calendar_components::Event {
     fn into_calendar_component(self) -> CalendarComponent {
-        CalendarComponent(CalendarComponentInner::Event(self))
+        CalendarComponent(CalendarComponentInner::Event(Box::new(self)))
     }
 }
 
 impl IntoCalendarComponent for calendar_components::Event {}
 
+impl private::IntoCalendarComponentSealed for calendar_components::TimeZone {
+    fn into_calendar_component(self) -> CalendarComponent {
+        CalendarComponent(CalendarComponentInner::TimeZone(self))
+    }
+}
+
+impl IntoCalendarComponent for calendar_components::TimeZone {}
+
+impl private::IntoCalendarComponentSealed for calendar_components::Todo {
+    fn into_calendar_component(self) -> CalendarComponent {
+        CalendarComponent(CalendarComponentInner::Todo(Box::new(self)))
+    }
+}
+
+impl IntoCalendarComponent for calendar_components::Todo {}
+
+impl private::IntoCalendarComponentSealed for calendar_components::Journal {
+    fn into_calendar_component(self) -> CalendarComponent {
+        CalendarComponent(CalendarComponentInner::Journal(Box::new(self)))
+    }
+}
+
+impl IntoCalendarComponent for calendar_components::Journal {}
+
+impl private::IntoCalendarComponentSealed for calendar_components::FreeBusy {
+    fn into_calendar_component(self) -> CalendarComponent {
+        CalendarComponent(CalendarComponentInner::FreeBusy(self))
+    }
+}
+
+impl IntoCalendarComponent for calendar_components::FreeBusy {}
+
+impl private::IntoCalendarComponentSealed for calendar_components::IanaComponent {
+    fn into_calendar_component(self) -> CalendarComponent {
+        CalendarComponent(CalendarComponentInner::IanaComp(self))
+    }
+}
+
+impl IntoCalendarComponent for calendar_components::IanaComponent {}
+
+impl private::IntoCalendarComponentSealed for calendar_components::NonStandardComponent {
+    fn into_calendar_component(self) -> CalendarComponent {
+        CalendarComponent(CalendarComponentInner::XComp(self))
+    }
+}
+
+impl IntoCalendarComponent for calendar_components::NonStandardComponent {}
+
+impl CalendarComponent {
+    fn time_zone_identifiers(&self) -> Vec<&TimeZoneIdentifier> {
+        match &self.0 {
+            CalendarComponentInner::Event(event) => event.time_zone_identifiers(),
+            CalendarComponentInner::TimeZone(_) => vec![],
+            CalendarComponentInner::Todo(todo) => todo.time_zone_identifiers(),
+            CalendarComponentInner::Journal(journal) => journal.time_zone_identifiers(),
+            // The free/busy component uses UTC times only.
+            CalendarComponentInner::FreeBusy(_) => vec![],
+            // The content lines are opaque.
+            CalendarComponentInner::IanaComp(_) | CalendarComponentInner::XComp(_) => vec![],
+        }
+    }
+}
+
 impl WriteTo for CalendarComponent {
     fn write_to<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
         match &self.0 {
             CalendarComponentInner::Event(event) => {
-                w.write_str(event.clone().into_string().as_str())?
+                w.write_str((**event).clone().into_string().as_str())?
+            }
+            CalendarComponentInner::TimeZone(time_zone) => {
+                w.write_str(time_zone.clone().into_string().as_str())?
+            }
+            CalendarComponentInner::Todo(todo) => {
+                w.write_str((**todo).clone().into_string().as_str())?
+            }
+            CalendarComponentInner::Journal(journal) => {
+                w.write_str((**journal).clone().into_string().as_str())?
+            }
+            CalendarComponentInner::FreeBusy(free_busy) => {
+                w.write_str(free_busy.clone().into_string().as_str())?
+            }
+            CalendarComponentInner::IanaComp(iana_comp) => {
+                w.write_str(iana_comp.clone().into_string().as_str())?
+            }
+            CalendarComponentInner::XComp(x_comp) => {
+                w.write_str(x_comp.clone().into_string().as_str())?
             }
         }
         Ok(())
@@ -0,0 +1,363 @@
+//! jCal: The JSON Format for iCalendar
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc7265>
+
+use serde_json::{Map, Number};
+
+use crate::i_calendar::{
+    calendar_components::Event,
+    i_calendar_object::{ICalendarObject, ICalendarStream},
+    parser::{self, ParseError},
+    structured::{Component, Property, StructuredError, Value},
+};
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("invalid component")]
+    InvalidComponent,
+    #[error("invalid property")]
+    InvalidProperty,
+    #[error("invalid value for {0}")]
+    InvalidValue(String),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Structured(#[from] StructuredError),
+}
+
+/// Returns the jCal of the (unfolded) content lines.
+fn to_json(content_lines: &str) -> Result<Vec<serde_json::Value>, ErrorInner> {
+    Ok(Component::from_content_lines(content_lines)?
+        .iter()
+        .map(component_to_json)
+        .collect())
+}
+
+/// component = [name, [properties...], [components...]]
+fn component_to_json(component: &Component) -> serde_json::Value {
+    serde_json::Value::Array(vec![
+        serde_json::Value::String(component.name.clone()),
+        serde_json::Value::Array(component.properties.iter().map(property_to_json).collect()),
+        serde_json::Value::Array(component.components.iter().map(component_to_json).collect()),
+    ])
+}
+
+/// property = [name, {parameters}, type, value...]
+fn property_to_json(property: &Property) -> serde_json::Value {
+    let params = property
+        .params
+        .iter()
+        .map(|(name, values)| {
+            let value = match values.as_slice() {
+                [value] => serde_json::Value::String(value.clone()),
+                _ => serde_json::Value::Array(
+                    values
+                        .iter()
+                        .cloned()
+                        .map(serde_json::Value::String)
+                        .collect(),
+                ),
+            };
+            (name.clone(), value)
+        })
+        .collect::<Map<String, serde_json::Value>>();
+    let mut array = vec![
+        serde_json::Value::String(property.name.clone()),
+        serde_json::Value::Object(params),
+        serde_json::Value::String(property.value_type.clone()),
+    ];
+    array.extend(property.values.iter().map(value_to_json));
+    serde_json::Value::Array(array)
+}
+
+fn value_to_json(value: &Value) -> serde_json::Value {
+    fn float(s: &str) -> serde_json::Value {
+        s.parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map_or_else(|| serde_json::Value::String(s.to_owned()), Into::into)
+    }
+
+    fn recur_part(name: &str, value: &str) -> serde_json::Value {
+        match name {
+            "count" | "interval" | "bysecond" | "byminute" | "byhour" | "bymonthday"
+            | "byyearday" | "byweekno" | "bymonth" | "bysetpos" => value
+                .parse::<i64>()
+                .map_or_else(|_| serde_json::Value::String(value.to_owned()), Into::into),
+            _ => serde_json::Value::String(value.to_owned()),
+        }
+    }
+
+    match value {
+        Value::Boolean(b) => serde_json::Value::Bool(*b),
+        Value::Float(f) => float(f),
+        Value::Geo(latitude, longitude) => {
+            serde_json::Value::Array(vec![float(latitude), float(longitude)])
+        }
+        Value::Integer(i) => serde_json::Value::from(*i),
+        Value::Period(start, end) => serde_json::Value::String(format!("{start}/{end}")),
+        Value::Recur(parts) => serde_json::Value::Object(
+            parts
+                .iter()
+                .map(|(name, values)| {
+                    let value = match values.as_slice() {
+                        [value] => recur_part(name, value),
+                        _ => serde_json::Value::Array(
+                            values.iter().map(|v| recur_part(name, v)).collect(),
+                        ),
+                    };
+                    (name.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Text(s) => serde_json::Value::String(s.clone()),
+    }
+}
+
+/// Returns the unfolded content lines of the jCal component.
+fn from_json(value: &serde_json::Value) -> Result<String, ErrorInner> {
+    Ok(component_from_json(value)?.to_content_lines()?)
+}
+
+fn component_from_json(value: &serde_json::Value) -> Result<Component, ErrorInner> {
+    let [
+        serde_json::Value::String(name),
+        serde_json::Value::Array(properties),
+        serde_json::Value::Array(components),
+    ] = value.as_array().map(Vec::as_slice).unwrap_or_default()
+    else {
+        return Err(ErrorInner::InvalidComponent);
+    };
+    Ok(Component {
+        name: name.to_ascii_lowercase(),
+        properties: properties
+            .iter()
+            .map(property_from_json)
+            .collect::<Result<Vec<Property>, ErrorInner>>()?,
+        components: components
+            .iter()
+            .map(component_from_json)
+            .collect::<Result<Vec<Component>, ErrorInner>>()?,
+    })
+}
+
+fn property_from_json(value: &serde_json::Value) -> Result<Property, ErrorInner> {
+    let [
+        serde_json::Value::String(name),
+        serde_json::Value::Object(params),
+        serde_json::Value::String(value_type),
+        values @ ..,
+    ] = value.as_array().map(Vec::as_slice).unwrap_or_default()
+    else {
+        return Err(ErrorInner::InvalidProperty);
+    };
+    let name = name.to_ascii_lowercase();
+    let value_type = value_type.to_ascii_lowercase();
+    let invalid = || ErrorInner::InvalidValue(name.clone());
+    if values.is_empty() {
+        return Err(invalid());
+    }
+    let params = params
+        .iter()
+        .map(|(param_name, value)| {
+            let values = match value {
+                serde_json::Value::String(s) => vec![s.clone()],
+                serde_json::Value::Array(values) => values
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_owned))
+                    .collect::<Option<Vec<String>>>()
+                    .ok_or_else(invalid)?,
+                _ => return Err(invalid()),
+            };
+            Ok((param_name.to_ascii_lowercase(), values))
+        })
+        .collect::<Result<Vec<(String, Vec<String>)>, ErrorInner>>()?;
+    let values = values
+        .iter()
+        .map(|value| value_from_json(&value_type, value).ok_or_else(invalid))
+        .collect::<Result<Vec<Value>, ErrorInner>>()?;
+    Ok(Property {
+        name,
+        params,
+        value_type,
+        values,
+    })
+}
+
+fn value_from_json(value_type: &str, value: &serde_json::Value) -> Option<Value> {
+    fn recur_part(value: &serde_json::Value) -> Option<String> {
+        match value {
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            serde_json::Value::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    Some(match (value_type, value) {
+        (_, serde_json::Value::Bool(b)) => Value::Boolean(*b),
+        ("float", serde_json::Value::Number(n)) => Value::Float(n.to_string()),
+        (_, serde_json::Value::Number(n)) => Value::Integer(n.as_i64()?),
+        ("float", serde_json::Value::Array(a)) => match a.as_slice() {
+            [
+                serde_json::Value::Number(latitude),
+                serde_json::Value::Number(longitude),
+            ] => Value::Geo(latitude.to_string(), longitude.to_string()),
+            _ => return None,
+        },
+        ("period", serde_json::Value::String(s)) => {
+            let (start, end) = s.split_once('/')?;
+            Value::Period(start.to_owned(), end.to_owned())
+        }
+        ("recur", serde_json::Value::Object(parts)) => Value::Recur(
+            parts
+                .iter()
+                .map(|(name, value)| {
+                    let values = match value {
+                        serde_json::Value::Array(values) => values
+                            .iter()
+                            .map(recur_part)
+                            .collect::<Option<Vec<String>>>()?,
+                        value => vec![recur_part(value)?],
+                    };
+                    Some((name.to_ascii_lowercase(), values))
+                })
+                .collect::<Option<Vec<(String, Vec<String>)>>>()?,
+        ),
+        (_, serde_json::Value::String(s)) => Value::Text(s.clone()),
+        _ => return None,
+    })
+}
+
+/// Serializes the stream as an array of the jCal "vcalendar" components.
+impl serde::Serialize for ICalendarStream {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        to_json(&self.to_content_lines())
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// Deserializes an array of the jCal "vcalendar" components or a single jCal "vcalendar"
+/// component.
+impl<'de> serde::Deserialize<'de> for ICalendarStream {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let components = match value.as_array().and_then(|a| a.first()) {
+            Some(serde_json::Value::Array(_)) => value.as_array().cloned().unwrap_or_default(),
+            _ => vec![value],
+        };
+        let s = components
+            .iter()
+            .map(from_json)
+            .collect::<Result<String, ErrorInner>>()
+            .map_err(serde::de::Error::custom)?;
+        parser::parse(&s)
+            .map_err(ErrorInner::Parse)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes the object as the jCal "vcalendar" component.
+impl serde::Serialize for ICalendarObject {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        single(to_json(&self.to_content_lines()))
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ICalendarObject {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let s = from_json(&value).map_err(serde::de::Error::custom)?;
+        parser::parse_object(&s)
+            .map_err(ErrorInner::Parse)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes the event as the jCal "vevent" component.
+impl serde::Serialize for Event {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        single(to_json(&self.clone().into_string()))
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Event {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let s = from_json(&value).map_err(serde::de::Error::custom)?;
+        parser::parse_event(&s)
+            .map_err(ErrorInner::Parse)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+fn single(
+    values: Result<Vec<serde_json::Value>, ErrorInner>,
+) -> Result<serde_json::Value, ErrorInner> {
+    match <[serde_json::Value; 1]>::try_from(values?) {
+        Ok([value]) => Ok(value),
+        Err(_) => Err(ErrorInner::InvalidComponent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value() {
+        for (value_type, value, json) in [
+            ("boolean", Value::Boolean(true), serde_json::json!(true)),
+            (
+                "float",
+                Value::Float("1.5".to_owned()),
+                serde_json::json!(1.5),
+            ),
+            (
+                "float",
+                Value::Geo("37.386013".to_owned(), "-122.082932".to_owned()),
+                serde_json::json!([37.386013, -122.082932]),
+            ),
+            ("integer", Value::Integer(-1), serde_json::json!(-1)),
+            (
+                "period",
+                Value::Period("1997-03-08T16:00:00Z".to_owned(), "PT8H30M".to_owned()),
+                serde_json::json!("1997-03-08T16:00:00Z/PT8H30M"),
+            ),
+            (
+                "recur",
+                Value::Recur(vec![
+                    (
+                        "byday".to_owned(),
+                        vec!["1SU".to_owned(), "-1SU".to_owned()],
+                    ),
+                    ("count".to_owned(), vec!["5".to_owned()]),
+                    ("freq".to_owned(), vec!["YEARLY".to_owned()]),
+                ]),
+                serde_json::json!({"byday": ["1SU", "-1SU"], "count": 5, "freq": "YEARLY"}),
+            ),
+            ("text", Value::Text("a".to_owned()), serde_json::json!("a")),
+        ] {
+            assert_eq!(value_to_json(&value), json);
+            assert_eq!(value_from_json(value_type, &json), Some(value));
+        }
+        assert_eq!(value_from_json("integer", &serde_json::json!(1.5)), None);
+        assert_eq!(
+            value_from_json("recur", &serde_json::json!({"a": {}})),
+            None
+        );
+    }
+
+    #[test]
+    fn test_property() {
+        assert!(property_from_json(&serde_json::json!(["uid", {}, "text", "1"])).is_ok());
+        assert!(property_from_json(&serde_json::json!(["uid", {}, "text"])).is_err());
+        assert!(property_from_json(&serde_json::json!(["uid", [], "text", "1"])).is_err());
+        assert!(property_from_json(&serde_json::json!(["uid", {"a": 1}, "text", "1"])).is_err());
+        assert!(component_from_json(&serde_json::json!(["vevent", []])).is_err());
+    }
+}
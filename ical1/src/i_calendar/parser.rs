@@ -0,0 +1,1929 @@
+//! Parser
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.1>
+
+use std::{collections::BTreeSet, str::FromStr as _};
+
+use crate::i_calendar::{
+    calendar_components::{
+        Alarm, Event, FreeBusy, IanaComponent, Journal, NonStandardComponent, Observance,
+        ObservanceBuilder, TimeZone, Todo,
+    },
+    calendar_properties::{CalendarScale, Method, ProductIdentifier, Version},
+    component_properties::{
+        Action, ActionValue, Attachment, Attendee, Categories, Classification, DateTimeCompleted,
+        DateTimeCreated, DateTimeDue, DateTimeEnd, DateTimeStamp, DateTimeStart, Description,
+        DurationProperty, ExceptionDateTimes, FreeBusyTime, GeographicPosition, IanaProperty,
+        LastModified, Location, NonStandardProperty, Organizer, PercentComplete, Priority,
+        RecurrenceDateTimes, RecurrenceRule, RepeatCount, SequenceNumber, Status, StatusValue,
+        Summary, TimeTransparency, TimeTransparencyValue, TimeZoneName, TimeZoneOffsetFrom,
+        TimeZoneOffsetTo, Trigger, UniformResourceLocator, UniqueIdentifier,
+    },
+    i_calendar_object::{ICalendarObject, ICalendarStream},
+    property_parameters::{
+        AlarmTriggerRelationship, AlternateTextRepresentation, CalendarUserType, CommonName,
+        FreeBusyTimeType, IanaParam, IanaToken, OtherParam, ParamValue, ParamValueError,
+        ParticipationRole, ParticipationStatus, RsvpExpectation, TimeZoneIdentifier, XName, XParam,
+    },
+    utils::unfold,
+    value_type::{
+        CalendarUserAddress, DateTime, Duration, Float, Period, Recur, Text, Uri, UtcOffset,
+    },
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("parse error at line {line}")]
+pub struct ParseError {
+    line: usize,
+    #[source]
+    inner: ErrorInner,
+}
+
+impl ParseError {
+    fn new(line: usize, inner: ErrorInner) -> Self {
+        Self { line, inner }
+    }
+
+    /// Returns the (1-based) line number where the error occurred.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("duplicate property {0}")]
+    DuplicateProperty(String),
+    #[error("invalid content line")]
+    InvalidContentLine,
+    #[error("invalid line ending")]
+    InvalidLineEnding,
+    #[error("invalid property {0}")]
+    InvalidProperty(String, #[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("unexpected content line {0}")]
+    UnexpectedContentLine(String),
+    #[error("unexpected end of input")]
+    UnexpectedEndOfInput,
+    #[error("unknown component {0}")]
+    UnknownComponent(String),
+    #[error("DATE values unsupported")]
+    UnsupportedDateValue,
+    #[error("unsupported parameter {0}")]
+    UnsupportedParameter(String),
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.1>
+/// contentline   = name *(";" param ) ":" value CRLF
+/// name          = iana-token / x-name
+/// param         = param-name "=" param-value *("," param-value)
+/// param-name    = iana-token / x-name
+/// value         = *VALUE-CHAR
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(in crate::i_calendar) struct ContentLine {
+    name: String,
+    params: Vec<Param>,
+    value: String,
+    // as is (e.g. for "iana-comp" and "x-comp")
+    unfolded: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Param {
+    name: String,
+    // unquoted
+    values: Vec<String>,
+}
+
+impl ContentLine {
+    fn from_unfolded(s: &str) -> Result<Self, ErrorInner> {
+        fn is_name_char(c: char) -> bool {
+            c.is_ascii_alphanumeric() || c == '-'
+        }
+
+        let name_len = s.find(|c| !is_name_char(c)).unwrap_or(s.len());
+        if name_len == 0 {
+            return Err(ErrorInner::InvalidContentLine);
+        }
+        let name = s[..name_len].to_owned();
+        let mut rest = &s[name_len..];
+
+        let mut params = vec![];
+        while let Some(r) = rest.strip_prefix(';') {
+            let param_name_len = r.find(|c| !is_name_char(c)).unwrap_or(r.len());
+            if param_name_len == 0 {
+                return Err(ErrorInner::InvalidContentLine);
+            }
+            let param_name = r[..param_name_len].to_owned();
+            rest = r[param_name_len..]
+                .strip_prefix('=')
+                .ok_or(ErrorInner::InvalidContentLine)?;
+
+            let mut values = vec![];
+            loop {
+                if let Some(r) = rest.strip_prefix('"') {
+                    let end = r.find('"').ok_or(ErrorInner::InvalidContentLine)?;
+                    values.push(r[..end].to_owned());
+                    rest = &r[end + 1..];
+                } else {
+                    let end = rest
+                        .find([';', ':', ',', '"'])
+                        .ok_or(ErrorInner::InvalidContentLine)?;
+                    values.push(rest[..end].to_owned());
+                    rest = &rest[end..];
+                }
+                match rest.strip_prefix(',') {
+                    Some(r) => rest = r,
+                    None => break,
+                }
+            }
+            params.push(Param {
+                name: param_name,
+                values,
+            });
+        }
+
+        let value = rest
+            .strip_prefix(':')
+            .ok_or(ErrorInner::InvalidContentLine)?
+            .to_owned();
+        if value.chars().any(|c| c.is_ascii_control() && c != '\t') {
+            return Err(ErrorInner::InvalidContentLine);
+        }
+
+        Ok(Self {
+            name,
+            params,
+            value,
+            unfolded: s.to_owned(),
+        })
+    }
+
+    pub(in crate::i_calendar) fn name(&self) -> String {
+        self.name.to_ascii_uppercase()
+    }
+
+    /// Returns the parameter names (as is) and the (unquoted) parameter values.
+    pub(in crate::i_calendar) fn params(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.params
+            .iter()
+            .map(|param| (param.name.as_str(), param.values.as_slice()))
+    }
+
+    pub(in crate::i_calendar) fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn invalid<E>(&self, e: E) -> ErrorInner
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        ErrorInner::InvalidProperty(self.name(), Box::new(e))
+    }
+
+    fn no_params(&self) -> Result<(), ErrorInner> {
+        match self.params.first() {
+            Some(param) => Err(ErrorInner::UnsupportedParameter(param.name.clone())),
+            None => Ok(()),
+        }
+    }
+
+    fn other_params(&self) -> Result<Vec<OtherParam>, ErrorInner> {
+        fn param_values(values: &[String]) -> Result<Vec<ParamValue>, ParamValueError> {
+            values
+                .iter()
+                .map(|v| ParamValue::from_unescaped(v))
+                .collect::<Result<Vec<ParamValue>, ParamValueError>>()
+        }
+
+        let mut other_params = vec![];
+        for param in &self.params {
+            let values = param_values(&param.values).map_err(|e| self.invalid(e))?;
+            let other_param = if param.name.to_ascii_uppercase().starts_with("X-") {
+                let name = XName::from_unescaped(&param.name).map_err(|e| self.invalid(e))?;
+                OtherParam::from(XParam::new(name, values).map_err(|e| self.invalid(e))?)
+            } else {
+                let name = IanaToken::from_unescaped(&param.name).map_err(|e| self.invalid(e))?;
+                OtherParam::from(IanaParam::new(name, values).map_err(|e| self.invalid(e))?)
+            };
+            other_params.push(other_param);
+        }
+        Ok(other_params)
+    }
+
+    /// Returns the content line without parameters (e.g. `"SUMMARY:value\r\n"`).
+    fn to_string_without_params(&self) -> Result<String, ErrorInner> {
+        self.no_params()?;
+        Ok(format!("{}:{}\r\n", self.name(), self.value))
+    }
+
+    /// Returns the ATTENDEE property with the CUTYPE, ROLE, PARTSTAT, RSVP and CN parameters (if
+    /// any).
+    fn attendee(&self) -> Result<Attendee, ErrorInner> {
+        let mut cutype = None;
+        let mut role = None;
+        let mut partstat = None;
+        let mut rsvp = None;
+        let mut cn = None;
+        let mut params = vec![];
+        for param in &self.params {
+            match (
+                param.name.to_ascii_uppercase().as_str(),
+                param.values.as_slice(),
+            ) {
+                ("CUTYPE", [value]) if cutype.is_none() => {
+                    cutype = Some(CalendarUserType::from_str(value).map_err(|e| self.invalid(e))?);
+                }
+                ("ROLE", [value]) if role.is_none() => {
+                    role = Some(ParticipationRole::from_str(value).map_err(|e| self.invalid(e))?);
+                }
+                ("PARTSTAT", [value]) if partstat.is_none() => {
+                    partstat =
+                        Some(ParticipationStatus::from_str(value).map_err(|e| self.invalid(e))?);
+                }
+                ("RSVP", [value]) if rsvp.is_none() => {
+                    rsvp = Some(RsvpExpectation::from_str(value).map_err(|e| self.invalid(e))?);
+                }
+                ("CN", [value]) if cn.is_none() => {
+                    cn = Some(CommonName::new(value).map_err(|e| self.invalid(e))?);
+                }
+                ("CUTYPE" | "ROLE" | "PARTSTAT" | "RSVP" | "CN", _) => {
+                    return Err(ErrorInner::UnsupportedParameter(param.name.clone()));
+                }
+                _ => params.push(param.clone()),
+            }
+        }
+        let other_params = Self {
+            params,
+            ..self.clone()
+        }
+        .other_params()?;
+        let mut attendee = Attendee::with_parameters(
+            CalendarUserAddress::from_str(&self.value).map_err(|e| self.invalid(e))?,
+            other_params,
+        )
+        .map_err(|e| self.invalid(e))?;
+        if let Some(cutype) = cutype {
+            attendee = attendee.with_cutype(cutype);
+        }
+        if let Some(role) = role {
+            attendee = attendee.with_role(role);
+        }
+        if let Some(partstat) = partstat {
+            attendee = attendee.with_partstat(partstat);
+        }
+        if let Some(rsvp) = rsvp {
+            attendee = attendee.with_rsvp(rsvp);
+        }
+        if let Some(cn) = cn {
+            attendee = attendee.with_cn(cn);
+        }
+        Ok(attendee)
+    }
+
+    /// Returns the DATE-TIME value with the TZID parameter (if any).
+    ///
+    /// The DATE value ("VALUE=DATE") is not supported.
+    fn date_time(&self) -> Result<DateTime, ErrorInner> {
+        let mut tzid = None;
+        for param in &self.params {
+            match (
+                param.name.to_ascii_uppercase().as_str(),
+                param.values.as_slice(),
+            ) {
+                ("TZID", [value]) if tzid.is_none() => {
+                    tzid = Some(
+                        TimeZoneIdentifier::from_unescaped(value).map_err(|e| self.invalid(e))?,
+                    );
+                }
+                ("VALUE", [value]) if value.eq_ignore_ascii_case("DATE-TIME") => {}
+                ("VALUE", [value]) if value.eq_ignore_ascii_case("DATE") => {
+                    return Err(ErrorInner::UnsupportedDateValue);
+                }
+                _ => return Err(ErrorInner::UnsupportedParameter(param.name.clone())),
+            }
+        }
+        let date_time = DateTime::try_from(self.value.clone()).map_err(|e| self.invalid(e))?;
+        match tzid {
+            Some(tzid) => date_time.with_time_zone(tzid).map_err(|e| self.invalid(e)),
+            None => Ok(date_time),
+        }
+    }
+
+    /// Returns the comma-separated DATE-TIME values with the TZID parameter (if any).
+    fn date_times(&self) -> Result<Vec<DateTime>, ErrorInner> {
+        self.value
+            .split(',')
+            .map(|value| {
+                Self {
+                    value: value.to_owned(),
+                    ..self.clone()
+                }
+                .date_time()
+            })
+            .collect()
+    }
+
+    /// Returns the FREEBUSY property with the FBTYPE parameter (if any).
+    fn free_busy_time(&self) -> Result<FreeBusyTime, ErrorInner> {
+        let mut fbtype = None;
+        let mut params = vec![];
+        for param in &self.params {
+            match (
+                param.name.to_ascii_uppercase().as_str(),
+                param.values.as_slice(),
+            ) {
+                ("FBTYPE", [value]) if fbtype.is_none() => {
+                    fbtype = Some(FreeBusyTimeType::from_str(value).map_err(|e| self.invalid(e))?);
+                }
+                ("FBTYPE", _) => return Err(ErrorInner::UnsupportedParameter(param.name.clone())),
+                _ => params.push(param.clone()),
+            }
+        }
+        let periods = self
+            .value
+            .split(',')
+            .map(Period::from_str)
+            .collect::<Result<Vec<Period>, _>>()
+            .map_err(|e| self.invalid(e))?;
+        let other_params = Self {
+            params,
+            ..self.clone()
+        }
+        .other_params()?;
+        FreeBusyTime::with_parameters(fbtype, periods, other_params).map_err(|e| self.invalid(e))
+    }
+
+    /// Returns the TRIGGER property with the VALUE and RELATED parameters (if any).
+    fn trigger(&self) -> Result<Trigger, ErrorInner> {
+        let mut value_type = None;
+        let mut related = None;
+        for param in &self.params {
+            match (
+                param.name.to_ascii_uppercase().as_str(),
+                param.values.as_slice(),
+            ) {
+                ("VALUE", [value]) if value_type.is_none() => {
+                    value_type = Some(value.to_ascii_uppercase());
+                }
+                ("RELATED", [value]) if related.is_none() => {
+                    related = Some(
+                        AlarmTriggerRelationship::from_str(value).map_err(|e| self.invalid(e))?,
+                    );
+                }
+                _ => return Err(ErrorInner::UnsupportedParameter(param.name.clone())),
+            }
+        }
+        match value_type.as_deref() {
+            None | Some("DURATION") => Trigger::relative(
+                Duration::from_str(&self.value).map_err(|e| self.invalid(e))?,
+                related,
+            ),
+            Some("DATE-TIME") if related.is_none() => Trigger::absolute(
+                DateTime::try_from(self.value.clone()).map_err(|e| self.invalid(e))?,
+            ),
+            _ => return Err(ErrorInner::UnsupportedParameter("VALUE".to_owned())),
+        }
+        .map_err(|e| self.invalid(e))
+    }
+
+    /// Returns the LOCATION property with the ALTREP parameter (if any).
+    fn location(&self) -> Result<Location, ErrorInner> {
+        let mut altrep = None;
+        let mut params = vec![];
+        for param in &self.params {
+            match (
+                param.name.to_ascii_uppercase().as_str(),
+                param.values.as_slice(),
+            ) {
+                ("ALTREP", [value]) if altrep.is_none() => {
+                    altrep = Some(
+                        AlternateTextRepresentation::new(
+                            Uri::from_str(value).map_err(|e| self.invalid(e))?,
+                        )
+                        .map_err(|e| self.invalid(e))?,
+                    );
+                }
+                ("ALTREP", _) => return Err(ErrorInner::UnsupportedParameter(param.name.clone())),
+                _ => params.push(param.clone()),
+            }
+        }
+        let other_params = Self {
+            params,
+            ..self.clone()
+        }
+        .other_params()?;
+        let location =
+            Location::with_parameters(self.text()?, other_params).map_err(|e| self.invalid(e))?;
+        Ok(match altrep {
+            Some(altrep) => location.with_altrep(altrep),
+            None => location,
+        })
+    }
+
+    /// Returns the ORGANIZER property with the CN parameter (if any).
+    fn organizer(&self) -> Result<Organizer, ErrorInner> {
+        let mut cn = None;
+        let mut params = vec![];
+        for param in &self.params {
+            match (
+                param.name.to_ascii_uppercase().as_str(),
+                param.values.as_slice(),
+            ) {
+                ("CN", [value]) if cn.is_none() => {
+                    cn = Some(CommonName::new(value).map_err(|e| self.invalid(e))?);
+                }
+                ("CN", _) => return Err(ErrorInner::UnsupportedParameter(param.name.clone())),
+                _ => params.push(param.clone()),
+            }
+        }
+        let other_params = Self {
+            params,
+            ..self.clone()
+        }
+        .other_params()?;
+        let organizer = Organizer::with_parameters(
+            CalendarUserAddress::from_str(&self.value).map_err(|e| self.invalid(e))?,
+            other_params,
+        )
+        .map_err(|e| self.invalid(e))?;
+        Ok(match cn {
+            Some(cn) => organizer.with_cn(cn),
+            None => organizer,
+        })
+    }
+
+    fn iana_property(&self) -> Result<IanaProperty, ErrorInner> {
+        IanaProperty::with_parameters(
+            IanaToken::from_unescaped(&self.name()).map_err(|e| self.invalid(e))?,
+            &self.value,
+            self.other_params()?,
+        )
+        .map_err(|e| self.invalid(e))
+    }
+
+    fn non_standard_property(&self) -> Result<NonStandardProperty, ErrorInner> {
+        NonStandardProperty::with_parameters(
+            XName::from_unescaped(&self.name()).map_err(|e| self.invalid(e))?,
+            &self.value,
+            self.other_params()?,
+        )
+        .map_err(|e| self.invalid(e))
+    }
+
+    fn utc_offset(&self) -> Result<UtcOffset, ErrorInner> {
+        UtcOffset::from_str(&self.value).map_err(|e| self.invalid(e))
+    }
+
+    fn text(&self) -> Result<Text, ErrorInner> {
+        Text::from_string(self.value.clone()).map_err(|e| self.invalid(e))
+    }
+}
+
+/// Returns whether the (unfolded) content lines are valid and nested "BEGIN" and "END" are
+/// balanced.
+pub(in crate::i_calendar) fn is_valid_content_lines(content_lines: &[String]) -> bool {
+    let mut nested = vec![];
+    for s in content_lines {
+        if s.contains(|c: char| c.is_ascii_control() && c != '\t') {
+            return false;
+        }
+        let Ok(content_line) = ContentLine::from_unfolded(s) else {
+            return false;
+        };
+        match content_line.name().as_str() {
+            "BEGIN" => nested.push(content_line.value.to_ascii_uppercase()),
+            "END" if nested.pop() != Some(content_line.value.to_ascii_uppercase()) => {
+                return false;
+            }
+            _ => {}
+        }
+    }
+    nested.is_empty()
+}
+
+pub(in crate::i_calendar) fn parse(s: &str) -> Result<ICalendarStream, ParseError> {
+    Parser::new(s)?.parse_stream()
+}
+
+/// Parses a single "VCALENDAR" component.
+pub(in crate::i_calendar) fn parse_object(s: &str) -> Result<ICalendarObject, ParseError> {
+    let mut parser = Parser::new(s)?;
+    let i_calendar_object = parser.parse_object()?;
+    parser.expect_end_of_input()?;
+    Ok(i_calendar_object)
+}
+
+/// Parses a single "VEVENT" component.
+pub(in crate::i_calendar) fn parse_event(s: &str) -> Result<Event, ParseError> {
+    let mut parser = Parser::new(s)?;
+    parser.expect_begin("VEVENT")?;
+    let event = parser.parse_event()?;
+    parser.expect_end_of_input()?;
+    Ok(event)
+}
+
+/// Returns the content lines without interpreting the components and properties.
+pub(in crate::i_calendar) fn content_lines(s: &str) -> Result<Vec<ContentLine>, ParseError> {
+    Ok(Parser::new(s)?
+        .lines
+        .map(|(_, content_line)| content_line)
+        .collect())
+}
+
+struct Parser {
+    lines: std::iter::Peekable<std::vec::IntoIter<(usize, ContentLine)>>,
+    last_line: usize,
+}
+
+impl Parser {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        let mut line = 1;
+        let mut iter = s.chars().peekable();
+        while let Some(c) = iter.next() {
+            if c == '\r' {
+                if iter.next_if_eq(&'\n').is_none() {
+                    return Err(ParseError::new(line, ErrorInner::InvalidLineEnding));
+                }
+                line += 1;
+            } else if c == '\n' {
+                return Err(ParseError::new(line, ErrorInner::InvalidLineEnding));
+            }
+        }
+        if !s.ends_with("\r\n") {
+            return Err(ParseError::new(line, ErrorInner::InvalidLineEnding));
+        }
+
+        // the physical line number where each unfolded content line starts
+        let lines = s
+            .split("\r\n")
+            .enumerate()
+            .filter(|(_, l)| !(l.starts_with(' ') || l.starts_with('\t')))
+            .map(|(index, _)| index + 1);
+        let unfolded = unfold(s.chars());
+        let mut content_lines = vec![];
+        for (line, l) in lines.zip(
+            unfolded
+                .strip_suffix("\r\n")
+                .unwrap_or_default()
+                .split("\r\n"),
+        ) {
+            let content_line =
+                ContentLine::from_unfolded(l).map_err(|inner| ParseError::new(line, inner))?;
+            content_lines.push((line, content_line));
+        }
+
+        Ok(Self {
+            lines: content_lines.into_iter().peekable(),
+            last_line: 0,
+        })
+    }
+
+    fn next(&mut self) -> Result<(usize, ContentLine), ParseError> {
+        match self.lines.next() {
+            Some((line, content_line)) => {
+                self.last_line = line;
+                Ok((line, content_line))
+            }
+            None => Err(ParseError::new(
+                self.last_line,
+                ErrorInner::UnexpectedEndOfInput,
+            )),
+        }
+    }
+
+    fn expect_begin(&mut self, component_name: &str) -> Result<(), ParseError> {
+        let (line, content_line) = self.next()?;
+        if content_line.name() == "BEGIN"
+            && content_line.params.is_empty()
+            && content_line.value.eq_ignore_ascii_case(component_name)
+        {
+            Ok(())
+        } else {
+            Err(ParseError::new(
+                line,
+                ErrorInner::UnexpectedContentLine(content_line.name()),
+            ))
+        }
+    }
+
+    fn expect_end_of_input(&mut self) -> Result<(), ParseError> {
+        match self.lines.next() {
+            Some((line, content_line)) => Err(ParseError::new(
+                line,
+                ErrorInner::UnexpectedContentLine(content_line.name()),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the next content line in the component or `None` at the end of the component.
+    fn next_in(
+        &mut self,
+        component_name: &str,
+    ) -> Result<Option<(usize, ContentLine)>, ParseError> {
+        let (line, content_line) = self.next()?;
+        if content_line.name() == "END" {
+            if !content_line.params.is_empty()
+                || !content_line.value.eq_ignore_ascii_case(component_name)
+            {
+                return Err(ParseError::new(
+                    line,
+                    ErrorInner::UnexpectedContentLine(content_line.name()),
+                ));
+            }
+            return Ok(None);
+        }
+        Ok(Some((line, content_line)))
+    }
+
+    /// Returns the error at the end of the component.
+    fn invalid_component<E>(&self, component_name: &str, e: E) -> ParseError
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        ParseError::new(
+            self.last_line,
+            ErrorInner::InvalidProperty(component_name.to_owned(), Box::new(e)),
+        )
+    }
+
+    fn parse_stream(mut self) -> Result<ICalendarStream, ParseError> {
+        let mut builder = ICalendarStream::builder();
+        loop {
+            builder = builder.add_object(self.parse_object()?);
+            if self.lines.peek().is_none() {
+                break;
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component("VCALENDAR", e))
+    }
+
+    fn parse_object(&mut self) -> Result<ICalendarObject, ParseError> {
+        self.expect_begin("VCALENDAR")?;
+
+        let mut once = BTreeSet::new();
+        let mut builder = ICalendarObject::builder();
+        while let Some((line, content_line)) = self.next_in("VCALENDAR")? {
+            let f = |inner| ParseError::new(line, inner);
+            let name = content_line.name();
+            match name.as_str() {
+                "BEGIN" => {
+                    content_line.no_params().map_err(f)?;
+                    match content_line.value.to_ascii_uppercase().as_str() {
+                        "VEVENT" => {
+                            builder = builder.add_component(self.parse_event()?);
+                        }
+                        "VFREEBUSY" => {
+                            builder = builder.add_component(self.parse_free_busy()?);
+                        }
+                        "VJOURNAL" => {
+                            builder = builder.add_component(self.parse_journal()?);
+                        }
+                        "VTIMEZONE" => {
+                            builder = builder.add_component(self.parse_time_zone()?);
+                        }
+                        "VTODO" => {
+                            builder = builder.add_component(self.parse_todo()?);
+                        }
+                        name @ ("VALARM" | "STANDARD" | "DAYLIGHT") => {
+                            return Err(f(ErrorInner::UnknownComponent(name.to_owned())));
+                        }
+                        name if name.starts_with("X-") => {
+                            let name = XName::from_unescaped(name)
+                                .map_err(|e| f(content_line.invalid(e)))?;
+                            builder =
+                                builder.add_component(self.parse_non_standard_component(name)?);
+                        }
+                        name => {
+                            let name = IanaToken::from_unescaped(name)
+                                .map_err(|e| f(content_line.invalid(e)))?;
+                            builder = builder.add_component(self.parse_iana_component(name)?);
+                        }
+                    }
+                }
+                "CALSCALE" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.calscale(
+                        CalendarScale::with_parameters(
+                            content_line.text().map_err(f)?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "METHOD" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.method(Method::with_parameters(
+                        content_line.text().map_err(f)?,
+                        content_line.other_params().map_err(f)?,
+                    ));
+                }
+                "PRODID" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.prodid(
+                        ProductIdentifier::with_parameters(
+                            content_line.text().map_err(f)?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "VERSION" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.version(
+                        Version::with_parameters(
+                            content_line.text().map_err(f)?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                name if name.starts_with("X-") => {
+                    builder = builder.add_x_prop(content_line.non_standard_property().map_err(f)?);
+                }
+                _ => {
+                    builder = builder.add_iana_prop(content_line.iana_property().map_err(f)?);
+                }
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component("VCALENDAR", e))
+    }
+
+    /// Returns the content lines of "iana-comp" or "x-comp" as is.
+    fn parse_content_lines(&mut self, component_name: &str) -> Result<Vec<String>, ParseError> {
+        let mut nested = vec![];
+        let mut content_lines = vec![];
+        loop {
+            let (line, content_line) = if nested.is_empty() {
+                match self.next_in(component_name)? {
+                    Some(next) => next,
+                    None => break,
+                }
+            } else {
+                self.next()?
+            };
+            match content_line.name().as_str() {
+                "BEGIN" => nested.push(content_line.value.to_ascii_uppercase()),
+                "END" if nested.pop() != Some(content_line.value.to_ascii_uppercase()) => {
+                    return Err(ParseError::new(
+                        line,
+                        ErrorInner::UnexpectedContentLine(content_line.name()),
+                    ));
+                }
+                _ => {}
+            }
+            content_lines.push(content_line.unfolded);
+        }
+        Ok(content_lines)
+    }
+
+    fn parse_iana_component(&mut self, name: IanaToken) -> Result<IanaComponent, ParseError> {
+        let component_name = name.to_unescaped();
+        self.parse_content_lines(&component_name)?
+            .iter()
+            .fold(
+                IanaComponent::builder().name(name),
+                |builder, content_line| builder.add_content_line(content_line),
+            )
+            .build()
+            .map_err(|e| self.invalid_component(&component_name, e))
+    }
+
+    fn parse_non_standard_component(
+        &mut self,
+        name: XName,
+    ) -> Result<NonStandardComponent, ParseError> {
+        let component_name = name.to_unescaped();
+        self.parse_content_lines(&component_name)?
+            .iter()
+            .fold(
+                NonStandardComponent::builder().name(name),
+                |builder, content_line| builder.add_content_line(content_line),
+            )
+            .build()
+            .map_err(|e| self.invalid_component(&component_name, e))
+    }
+
+    fn parse_alarm(&mut self) -> Result<Alarm, ParseError> {
+        let mut once = BTreeSet::new();
+        let mut builder = Alarm::builder();
+        while let Some((line, content_line)) = self.next_in("VALARM")? {
+            let f = |inner| ParseError::new(line, inner);
+            let name = content_line.name();
+            match name.as_str() {
+                "BEGIN" => {
+                    return Err(f(ErrorInner::UnknownComponent(
+                        content_line.value.to_ascii_uppercase(),
+                    )));
+                }
+                "ACTION" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.action(
+                        Action::with_parameters(
+                            ActionValue::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "ATTACH" => {
+                    builder = builder.add_attach(
+                        Attachment::with_parameters(
+                            Uri::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "ATTENDEE" => {
+                    builder = builder.add_attendee(content_line.attendee().map_err(f)?);
+                }
+                "DESCRIPTION" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    content_line.no_params().map_err(f)?;
+                    builder = builder.description(
+                        Description::new(content_line.text().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DURATION" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.duration(
+                        DurationProperty::with_parameters(
+                            Duration::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "REPEAT" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.repeat(
+                        RepeatCount::with_parameters(
+                            content_line
+                                .value
+                                .parse::<u32>()
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "SUMMARY" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.summary(
+                        Summary::from_string(content_line.to_string_without_params().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "TRIGGER" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.trigger(content_line.trigger().map_err(f)?);
+                }
+                name if name.starts_with("X-") => {
+                    builder = builder.add_x_prop(content_line.non_standard_property().map_err(f)?);
+                }
+                _ => {
+                    builder = builder.add_iana_prop(content_line.iana_property().map_err(f)?);
+                }
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component("VALARM", e))
+    }
+
+    fn parse_event(&mut self) -> Result<Event, ParseError> {
+        let mut once = BTreeSet::new();
+        let mut builder = Event::builder();
+        while let Some((line, content_line)) = self.next_in("VEVENT")? {
+            let f = |inner| ParseError::new(line, inner);
+            let name = content_line.name();
+            match name.as_str() {
+                "BEGIN" => {
+                    content_line.no_params().map_err(f)?;
+                    match content_line.value.to_ascii_uppercase().as_str() {
+                        "VALARM" => builder = builder.add_alarm(self.parse_alarm()?),
+                        name => return Err(f(ErrorInner::UnknownComponent(name.to_owned()))),
+                    }
+                }
+                "ATTENDEE" => {
+                    builder = builder.add_attendee(content_line.attendee().map_err(f)?);
+                }
+                "CATEGORIES" => {
+                    builder = builder.add_categories(
+                        Categories::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "CLASS" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.class(
+                        Classification::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "CREATED" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.created(
+                        DateTimeCreated::with_parameters(
+                            DateTime::try_from(content_line.value.clone())
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTEND" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtend(
+                        DateTimeEnd::new(content_line.date_time().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTSTAMP" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstamp(
+                        DateTimeStamp::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTSTART" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstart(
+                        DateTimeStart::new(content_line.date_time().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DURATION" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.duration(
+                        DurationProperty::with_parameters(
+                            Duration::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "EXDATE" => {
+                    builder = builder.add_exdate(
+                        ExceptionDateTimes::new(content_line.date_times().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "GEO" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    let (latitude, longitude) = content_line
+                        .value
+                        .split_once(';')
+                        .ok_or_else(|| f(ErrorInner::InvalidContentLine))?;
+                    builder = builder.geo(
+                        GeographicPosition::with_parameters(
+                            Float::from_str(latitude).map_err(|e| f(content_line.invalid(e)))?,
+                            Float::from_str(longitude).map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "LOCATION" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.location(content_line.location().map_err(f)?);
+                }
+                "ORGANIZER" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.organizer(content_line.organizer().map_err(f)?);
+                }
+                "PRIORITY" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.priority(
+                        Priority::with_parameters(
+                            content_line
+                                .value
+                                .parse::<u8>()
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "RDATE" => {
+                    builder = builder.add_rdate(
+                        RecurrenceDateTimes::new(content_line.date_times().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "RRULE" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.rrule(
+                        RecurrenceRule::with_parameters(
+                            Recur::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "SEQUENCE" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.sequence(
+                        SequenceNumber::with_parameters(
+                            content_line
+                                .value
+                                .parse::<u32>()
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "STATUS" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.status(
+                        Status::with_parameters(
+                            StatusValue::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "SUMMARY" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.summary(
+                        Summary::from_string(content_line.to_string_without_params().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "TRANSP" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.transp(
+                        TimeTransparency::with_parameters(
+                            TimeTransparencyValue::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "UID" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.uid(
+                        UniqueIdentifier::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "URL" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.url(
+                        UniformResourceLocator::with_parameters(
+                            Uri::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                name if name.starts_with("X-") => {
+                    builder = builder.add_x_prop(content_line.non_standard_property().map_err(f)?);
+                }
+                _ => {
+                    builder = builder.add_iana_prop(content_line.iana_property().map_err(f)?);
+                }
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component("VEVENT", e))
+    }
+
+    fn parse_free_busy(&mut self) -> Result<FreeBusy, ParseError> {
+        let mut once = BTreeSet::new();
+        let mut builder = FreeBusy::builder();
+        while let Some((line, content_line)) = self.next_in("VFREEBUSY")? {
+            let f = |inner| ParseError::new(line, inner);
+            let name = content_line.name();
+            match name.as_str() {
+                "BEGIN" => {
+                    return Err(f(ErrorInner::UnknownComponent(
+                        content_line.value.to_ascii_uppercase(),
+                    )));
+                }
+                "DTEND" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtend(
+                        DateTimeEnd::new(content_line.date_time().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTSTAMP" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstamp(
+                        DateTimeStamp::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTSTART" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstart(
+                        DateTimeStart::new(content_line.date_time().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "FREEBUSY" => {
+                    builder = builder.add_freebusy(content_line.free_busy_time().map_err(f)?);
+                }
+                "UID" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.uid(
+                        UniqueIdentifier::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                name if name.starts_with("X-") => {
+                    builder = builder.add_x_prop(content_line.non_standard_property().map_err(f)?);
+                }
+                _ => {
+                    builder = builder.add_iana_prop(content_line.iana_property().map_err(f)?);
+                }
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component("VFREEBUSY", e))
+    }
+
+    fn parse_journal(&mut self) -> Result<Journal, ParseError> {
+        let mut once = BTreeSet::new();
+        let mut builder = Journal::builder();
+        while let Some((line, content_line)) = self.next_in("VJOURNAL")? {
+            let f = |inner| ParseError::new(line, inner);
+            let name = content_line.name();
+            match name.as_str() {
+                "BEGIN" => {
+                    return Err(f(ErrorInner::UnknownComponent(
+                        content_line.value.to_ascii_uppercase(),
+                    )));
+                }
+                "CATEGORIES" => {
+                    builder = builder.add_categories(
+                        Categories::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "CLASS" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.class(
+                        Classification::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "CREATED" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.created(
+                        DateTimeCreated::with_parameters(
+                            DateTime::try_from(content_line.value.clone())
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DESCRIPTION" => {
+                    content_line.no_params().map_err(f)?;
+                    builder = builder.add_description(
+                        Description::new(content_line.text().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTSTAMP" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstamp(
+                        DateTimeStamp::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTSTART" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstart(
+                        DateTimeStart::new(content_line.date_time().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "EXDATE" => {
+                    builder = builder.add_exdate(
+                        ExceptionDateTimes::new(content_line.date_times().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "LAST-MODIFIED" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.last_mod(
+                        LastModified::with_parameters(
+                            DateTime::try_from(content_line.value.clone())
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "RDATE" => {
+                    builder = builder.add_rdate(
+                        RecurrenceDateTimes::new(content_line.date_times().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "RRULE" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.rrule(
+                        RecurrenceRule::with_parameters(
+                            Recur::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "STATUS" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.status(
+                        Status::with_parameters(
+                            StatusValue::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "SUMMARY" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.summary(
+                        Summary::from_string(content_line.to_string_without_params().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "UID" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.uid(
+                        UniqueIdentifier::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                name if name.starts_with("X-") => {
+                    builder = builder.add_x_prop(content_line.non_standard_property().map_err(f)?);
+                }
+                _ => {
+                    builder = builder.add_iana_prop(content_line.iana_property().map_err(f)?);
+                }
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component("VJOURNAL", e))
+    }
+
+    fn parse_time_zone(&mut self) -> Result<TimeZone, ParseError> {
+        let mut once = BTreeSet::new();
+        let mut builder = TimeZone::builder();
+        while let Some((line, content_line)) = self.next_in("VTIMEZONE")? {
+            let f = |inner| ParseError::new(line, inner);
+            let name = content_line.name();
+            match name.as_str() {
+                "BEGIN" => {
+                    content_line.no_params().map_err(f)?;
+                    let observance = match content_line.value.to_ascii_uppercase().as_str() {
+                        "DAYLIGHT" => self.parse_observance("DAYLIGHT", Observance::daylight())?,
+                        "STANDARD" => self.parse_observance("STANDARD", Observance::standard())?,
+                        name => {
+                            return Err(f(ErrorInner::UnknownComponent(name.to_owned())));
+                        }
+                    };
+                    builder = builder.add_observance(observance);
+                }
+                "LAST-MODIFIED" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.last_mod(
+                        LastModified::with_parameters(
+                            DateTime::try_from(content_line.value.clone())
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "TZID" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    content_line.no_params().map_err(f)?;
+                    builder = builder.tzid(
+                        TimeZoneIdentifier::from_unescaped(
+                            &content_line.text().map_err(f)?.to_unescaped(),
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                name if name.starts_with("X-") => {
+                    builder = builder.add_x_prop(content_line.non_standard_property().map_err(f)?);
+                }
+                _ => {
+                    builder = builder.add_iana_prop(content_line.iana_property().map_err(f)?);
+                }
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component("VTIMEZONE", e))
+    }
+
+    fn parse_observance(
+        &mut self,
+        component_name: &str,
+        mut builder: ObservanceBuilder,
+    ) -> Result<Observance, ParseError> {
+        let mut once = BTreeSet::new();
+        while let Some((line, content_line)) = self.next_in(component_name)? {
+            let f = |inner| ParseError::new(line, inner);
+            let name = content_line.name();
+            match name.as_str() {
+                "BEGIN" => {
+                    return Err(f(ErrorInner::UnknownComponent(
+                        content_line.value.to_ascii_uppercase(),
+                    )));
+                }
+                "DTSTART" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstart(
+                        DateTimeStart::new(content_line.date_time().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "RDATE" => {
+                    builder = builder.add_rdate(
+                        RecurrenceDateTimes::new(content_line.date_times().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "RRULE" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.rrule(
+                        RecurrenceRule::with_parameters(
+                            Recur::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "TZNAME" => {
+                    builder = builder.add_tzname(
+                        TimeZoneName::with_parameters(
+                            content_line.text().map_err(f)?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "TZOFFSETFROM" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.tzoffsetfrom(
+                        TimeZoneOffsetFrom::with_parameters(
+                            content_line.utc_offset().map_err(f)?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "TZOFFSETTO" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.tzoffsetto(
+                        TimeZoneOffsetTo::with_parameters(
+                            content_line.utc_offset().map_err(f)?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                name if name.starts_with("X-") => {
+                    builder = builder.add_x_prop(content_line.non_standard_property().map_err(f)?);
+                }
+                _ => {
+                    builder = builder.add_iana_prop(content_line.iana_property().map_err(f)?);
+                }
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component(component_name, e))
+    }
+
+    fn parse_todo(&mut self) -> Result<Todo, ParseError> {
+        let mut once = BTreeSet::new();
+        let mut builder = Todo::builder();
+        while let Some((line, content_line)) = self.next_in("VTODO")? {
+            let f = |inner| ParseError::new(line, inner);
+            let name = content_line.name();
+            match name.as_str() {
+                "BEGIN" => {
+                    content_line.no_params().map_err(f)?;
+                    match content_line.value.to_ascii_uppercase().as_str() {
+                        "VALARM" => builder = builder.add_alarm(self.parse_alarm()?),
+                        name => return Err(f(ErrorInner::UnknownComponent(name.to_owned()))),
+                    }
+                }
+                "CATEGORIES" => {
+                    builder = builder.add_categories(
+                        Categories::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "CLASS" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.class(
+                        Classification::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "COMPLETED" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.completed(
+                        DateTimeCompleted::with_parameters(
+                            DateTime::try_from(content_line.value.clone())
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "CREATED" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.created(
+                        DateTimeCreated::with_parameters(
+                            DateTime::try_from(content_line.value.clone())
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DESCRIPTION" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    content_line.no_params().map_err(f)?;
+                    builder = builder.description(
+                        Description::new(content_line.text().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTSTAMP" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstamp(
+                        DateTimeStamp::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DTSTART" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.dtstart(
+                        DateTimeStart::new(content_line.date_time().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DUE" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.due(
+                        DateTimeDue::new(content_line.date_time().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "DURATION" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.duration(
+                        DurationProperty::with_parameters(
+                            Duration::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "EXDATE" => {
+                    builder = builder.add_exdate(
+                        ExceptionDateTimes::new(content_line.date_times().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "LAST-MODIFIED" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.last_mod(
+                        LastModified::with_parameters(
+                            DateTime::try_from(content_line.value.clone())
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "PERCENT-COMPLETE" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.percent(
+                        PercentComplete::with_parameters(
+                            content_line
+                                .value
+                                .parse::<u8>()
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "RDATE" => {
+                    builder = builder.add_rdate(
+                        RecurrenceDateTimes::new(content_line.date_times().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "RRULE" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.rrule(
+                        RecurrenceRule::with_parameters(
+                            Recur::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "STATUS" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.status(
+                        Status::with_parameters(
+                            StatusValue::from_str(&content_line.value)
+                                .map_err(|e| f(content_line.invalid(e)))?,
+                            content_line.other_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "SUMMARY" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.summary(
+                        Summary::from_string(content_line.to_string_without_params().map_err(f)?)
+                            .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                "UID" => {
+                    check_once(&mut once, &content_line).map_err(f)?;
+                    builder = builder.uid(
+                        UniqueIdentifier::from_string(
+                            content_line.to_string_without_params().map_err(f)?,
+                        )
+                        .map_err(|e| f(content_line.invalid(e)))?,
+                    );
+                }
+                name if name.starts_with("X-") => {
+                    builder = builder.add_x_prop(content_line.non_standard_property().map_err(f)?);
+                }
+                _ => {
+                    builder = builder.add_iana_prop(content_line.iana_property().map_err(f)?);
+                }
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| self.invalid_component("VTODO", e))
+    }
+}
+
+/// Checks that the property does not occur more than once.
+fn check_once(once: &mut BTreeSet<String>, content_line: &ContentLine) -> Result<(), ErrorInner> {
+    if !once.insert(content_line.name()) {
+        return Err(ErrorInner::DuplicateProperty(content_line.name()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_line() -> anyhow::Result<()> {
+        let content_line = ContentLine::from_unfolded("SUMMARY:Annual Employee Review")?;
+        assert_eq!(content_line.name(), "SUMMARY");
+        assert!(content_line.params.is_empty());
+        assert_eq!(content_line.value, "Annual Employee Review");
+
+        let content_line = ContentLine::from_unfolded(
+            "ATTENDEE;RSVP=TRUE;DELEGATED-TO=\"mailto:a@example.com\",\"mailto:b@example.com\":mailto:c@example.com",
+        )?;
+        assert_eq!(content_line.name(), "ATTENDEE");
+        assert_eq!(
+            content_line.params,
+            vec![
+                Param {
+                    name: "RSVP".to_owned(),
+                    values: vec!["TRUE".to_owned()],
+                },
+                Param {
+                    name: "DELEGATED-TO".to_owned(),
+                    values: vec![
+                        "mailto:a@example.com".to_owned(),
+                        "mailto:b@example.com".to_owned()
+                    ],
+                },
+            ]
+        );
+        assert_eq!(content_line.value, "mailto:c@example.com");
+
+        assert!(ContentLine::from_unfolded(":value").is_err());
+        assert!(ContentLine::from_unfolded("NAME").is_err());
+        assert!(ContentLine::from_unfolded("NAME;PARAM:value").is_err());
+        assert!(ContentLine::from_unfolded("NAME;PARAM=\"value:value").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let s = [
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+            "VERSION:2.0\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:19970901T130000Z-123401@example.com\r\n",
+            "DTSTAMP:19970901T130000Z\r\n",
+            "DTSTART:19970903T163000Z\r\n",
+            "SUMMARY:Annual \r\n",
+            " Employee Review\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ]
+        .join("");
+        let i_calendar_stream = parse(&s)?;
+        assert_eq!(
+            i_calendar_stream.to_string(),
+            s.replace("Annual \r\n Employee", "Annual Employee")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_alarm_x_prop_and_iana_prop() -> anyhow::Result<()> {
+        assert_round_trip(&[
+            "BEGIN:VEVENT\r\n",
+            "UID:19970901T130000Z-123401@example.com\r\n",
+            "DTSTAMP:19970901T130000Z\r\n",
+            "DTSTART:19970903T163000Z\r\n",
+            "BEGIN:VALARM\r\n",
+            "ACTION:DISPLAY\r\n",
+            "TRIGGER:-PT30M\r\n",
+            "DESCRIPTION:Breakfast meeting\r\n",
+            "X-WR-ALARMUID:5A1C1B4B-1C5E-4C3B-9C1E-3F2B8D7E6A01\r\n",
+            "ACKNOWLEDGED:19970903T160000Z\r\n",
+            "END:VALARM\r\n",
+            "END:VEVENT\r\n",
+        ])
+    }
+
+    #[test]
+    fn test_parse_free_busy_x_prop_and_iana_prop() -> anyhow::Result<()> {
+        assert_round_trip(&[
+            "BEGIN:VFREEBUSY\r\n",
+            "UID:19970901T115957Z-76A912@example.com\r\n",
+            "DTSTAMP:19970901T120000Z\r\n",
+            "X-ABC-SOURCE:calendar\r\n",
+            "NEW-PROP:value\r\n",
+            "END:VFREEBUSY\r\n",
+        ])
+    }
+
+    #[test]
+    fn test_parse_journal_x_prop_and_iana_prop() -> anyhow::Result<()> {
+        assert_round_trip(&[
+            "BEGIN:VJOURNAL\r\n",
+            "UID:19970901T130000Z-123405@example.com\r\n",
+            "DTSTAMP:19970901T130000Z\r\n",
+            "X-ABC-MOOD:calm\r\n",
+            "NEW-PROP:value\r\n",
+            "END:VJOURNAL\r\n",
+        ])
+    }
+
+    #[test]
+    fn test_parse_time_zone_x_prop_and_iana_prop() -> anyhow::Result<()> {
+        assert_round_trip(&[
+            "BEGIN:VTIMEZONE\r\n",
+            "TZID:America/New_York\r\n",
+            "X-LIC-LOCATION:America/New_York\r\n",
+            "TZUNTIL:20300101T000000Z\r\n",
+            "BEGIN:STANDARD\r\n",
+            "DTSTART:20071104T020000\r\n",
+            "TZOFFSETFROM:-0400\r\n",
+            "TZOFFSETTO:-0500\r\n",
+            "TZNAME:EST\r\n",
+            "X-ABC-NOTE:standard\r\n",
+            "NEW-PROP:value\r\n",
+            "END:STANDARD\r\n",
+            "END:VTIMEZONE\r\n",
+        ])
+    }
+
+    #[test]
+    fn test_parse_todo_x_prop_and_iana_prop() -> anyhow::Result<()> {
+        assert_round_trip(&[
+            "BEGIN:VTODO\r\n",
+            "UID:20070313T123432Z-456553@example.com\r\n",
+            "DTSTAMP:20070313T123432Z\r\n",
+            "X-APPLE-SORT-ORDER:1\r\n",
+            "NEW-PROP:value\r\n",
+            "END:VTODO\r\n",
+        ])
+    }
+
+    #[test]
+    fn test_parse_case_insensitive() -> anyhow::Result<()> {
+        let i_calendar_stream = parse(
+            &[
+                "begin:VCALENDAR\r\n",
+                "prodid:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "Version:2.0\r\n",
+                "Begin:vevent\r\n",
+                "uid:19970901T130000Z-123401@example.com\r\n",
+                "DTSTAMP:19970901T130000Z\r\n",
+                "dtstart:19970903T163000Z\r\n",
+                "begin:valarm\r\n",
+                "action:AUDIO\r\n",
+                "trigger:-PT30M\r\n",
+                "end:VALARM\r\n",
+                "END:vEvent\r\n",
+                "begin:x-abc-note\r\n",
+                "begin:x-abc-part\r\n",
+                "END:X-ABC-PART\r\n",
+                "end:X-abc-note\r\n",
+                "END:VCALENDAR\r\n",
+            ]
+            .join(""),
+        )?;
+        assert_eq!(
+            i_calendar_stream.to_string(),
+            [
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "BEGIN:VEVENT\r\n",
+                "UID:19970901T130000Z-123401@example.com\r\n",
+                "DTSTAMP:19970901T130000Z\r\n",
+                "DTSTART:19970903T163000Z\r\n",
+                "BEGIN:VALARM\r\n",
+                "ACTION:AUDIO\r\n",
+                "TRIGGER:-PT30M\r\n",
+                "END:VALARM\r\n",
+                "END:VEVENT\r\n",
+                "BEGIN:X-ABC-NOTE\r\n",
+                "begin:x-abc-part\r\n",
+                "END:X-ABC-PART\r\n",
+                "END:X-ABC-NOTE\r\n",
+                "END:VCALENDAR\r\n",
+            ]
+            .join("")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_date_value() -> anyhow::Result<()> {
+        let s = |dtstart: &str| {
+            [
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "BEGIN:VEVENT\r\n",
+                "UID:19970901T130000Z-123401@example.com\r\n",
+                "DTSTAMP:19970901T130000Z\r\n",
+                dtstart,
+                "END:VEVENT\r\n",
+                "END:VCALENDAR\r\n",
+            ]
+            .join("")
+        };
+
+        let e = parse(&s("DTSTART;VALUE=DATE:19970903\r\n")).unwrap_err();
+        assert_eq!(e.line(), 7);
+        assert!(matches!(e.inner, ErrorInner::UnsupportedDateValue));
+        assert_eq!(
+            std::error::Error::source(&e).map(ToString::to_string),
+            Some("DATE values unsupported".to_owned())
+        );
+
+        // the explicit default value type
+        assert!(parse(&s("DTSTART;VALUE=DATE-TIME:19970903T163000Z\r\n")).is_ok());
+        Ok(())
+    }
+
+    fn assert_round_trip(component: &[&str]) -> anyhow::Result<()> {
+        let s = [
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+            "VERSION:2.0\r\n",
+        ]
+        .into_iter()
+        .chain(component.iter().copied())
+        .chain(["END:VCALENDAR\r\n"])
+        .collect::<String>();
+        assert_eq!(parse(&s)?.to_string(), s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        fn line(s: &[&str]) -> usize {
+            parse(&s.join("")).unwrap_err().line()
+        }
+
+        // LF without CR
+        assert_eq!(line(&["BEGIN:VCALENDAR\r\n", "VERSION:2.0\n"]), 2);
+        // unknown component
+        assert_eq!(
+            line(&[
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "BEGIN:VTODO\r\n",
+                "BEGIN:VUNKNOWN\r\n",
+            ]),
+            5
+        );
+        // duplicate property
+        assert_eq!(
+            line(&["BEGIN:VCALENDAR\r\n", "VERSION:2.0\r\n", "VERSION:2.0\r\n",]),
+            3
+        );
+        // invalid property value
+        assert_eq!(
+            line(&[
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "BEGIN:VEVENT\r\n",
+                "DTSTAMP:invalid\r\n",
+            ]),
+            5
+        );
+        // missing required property
+        assert_eq!(
+            line(&[
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "BEGIN:VEVENT\r\n",
+                "DTSTAMP:19970901T130000Z\r\n",
+                "END:VEVENT\r\n",
+            ]),
+            6
+        );
+        // unexpected end of input
+        assert_eq!(line(&["BEGIN:VCALENDAR\r\n", "VERSION:2.0\r\n"]), 2);
+        // folded line
+        assert_eq!(
+            line(&[
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML\r\n",
+                "  My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "VERSION:2.0\r\n",
+            ]),
+            5
+        );
+    }
+}
@@ -1,11 +1,29 @@
+mod alarm_trigger_relationship;
 mod alternate_text_representation;
+mod calendar_user_type;
+mod common_name;
+mod free_busy_time_type;
 mod iana_param;
 mod iana_token;
 mod other_param;
 mod param_value;
+mod participation_role;
+mod participation_status;
+mod rsvp_expectation;
+mod time_zone_identifier;
 mod x_name;
 mod x_param;
 
+pub use self::alarm_trigger_relationship::AlarmTriggerRelationship;
+pub use self::alarm_trigger_relationship::AlarmTriggerRelationshipError;
+pub use self::alternate_text_representation::AlternateTextRepresentation;
+pub use self::alternate_text_representation::AlternateTextRepresentationError;
+pub use self::calendar_user_type::CalendarUserType;
+pub use self::calendar_user_type::CalendarUserTypeError;
+pub use self::common_name::CommonName;
+pub use self::common_name::CommonNameError;
+pub use self::free_busy_time_type::FreeBusyTimeType;
+pub use self::free_busy_time_type::FreeBusyTimeTypeError;
 pub use self::iana_param::IanaParam;
 pub use self::iana_param::IanaParamError;
 pub use self::iana_token::IanaToken;
@@ -13,6 +31,14 @@ pub use self::iana_token::IanaTokenError;
 pub(in crate::i_calendar) use self::other_param::OtherParam;
 pub use self::param_value::ParamValue;
 pub use self::param_value::ParamValueError;
+pub use self::participation_role::ParticipationRole;
+pub use self::participation_role::ParticipationRoleError;
+pub use self::participation_status::ParticipationStatus;
+pub use self::participation_status::ParticipationStatusError;
+pub use self::rsvp_expectation::RsvpExpectation;
+pub use self::rsvp_expectation::RsvpExpectationError;
+pub use self::time_zone_identifier::TimeZoneIdentifier;
+pub use self::time_zone_identifier::TimeZoneIdentifierError;
 pub use self::x_name::XName;
 pub use self::x_name::XNameError;
 pub use self::x_param::XParam;
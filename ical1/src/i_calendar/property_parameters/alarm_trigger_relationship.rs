@@ -0,0 +1,67 @@
+//! Alarm Trigger Relationship
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.14>
+
+#[derive(Debug, thiserror::Error)]
+#[error("alarm trigger relationship")]
+pub struct AlarmTriggerRelationshipError {
+    _private: (),
+}
+
+/// trigrelparam       = "RELATED" "="
+///                      ("START"       ; Trigger off of start
+///                     / "END")        ; Trigger off of end
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub enum AlarmTriggerRelationship {
+    #[default]
+    Start,
+    End,
+}
+
+impl AlarmTriggerRelationship {
+    pub(in crate::i_calendar) fn to_escaped(self) -> String {
+        let mut s = String::new();
+        s.push_str("RELATED=");
+        s.push_str(match self {
+            Self::Start => "START",
+            Self::End => "END",
+        });
+        s
+    }
+}
+
+impl std::str::FromStr for AlarmTriggerRelationship {
+    type Err = AlarmTriggerRelationshipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "START" => Self::Start,
+            "END" => Self::End,
+            _ => return Err(AlarmTriggerRelationshipError { _private: () }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Copy + Default + Eq + PartialEq>() {}
+        assert_fn::<AlarmTriggerRelationship>();
+
+        assert_eq!(
+            AlarmTriggerRelationship::default(),
+            AlarmTriggerRelationship::Start
+        );
+        assert_eq!(
+            AlarmTriggerRelationship::from_str("end")?.to_escaped(),
+            "RELATED=END"
+        );
+        assert!(AlarmTriggerRelationship::from_str("MIDDLE").is_err());
+        Ok(())
+    }
+}
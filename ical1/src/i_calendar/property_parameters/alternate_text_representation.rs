@@ -2,7 +2,7 @@
 //!
 //! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.1>
 
-use crate::i_calendar::value_type::{Uri, UriError};
+use crate::i_calendar::value_type::Uri;
 
 #[derive(Debug, thiserror::Error)]
 #[error("alternate text representation")]
@@ -18,6 +18,10 @@ impl AlternateTextRepresentation {
         Ok(Self(value))
     }
 
+    pub fn value(&self) -> &Uri {
+        &self.0
+    }
+
     pub(in crate::i_calendar) fn to_escaped(&self) -> String {
         let mut s = String::new();
         s.push_str("ALTREP=\"");
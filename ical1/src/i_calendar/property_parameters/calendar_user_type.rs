@@ -0,0 +1,81 @@
+//! Calendar User Type
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.3>
+
+#[derive(Debug, thiserror::Error)]
+#[error("calendar user type")]
+pub struct CalendarUserTypeError {
+    _private: (),
+}
+
+/// cutypeparam        = "CUTYPE" "="
+///                    ("INDIVIDUAL"   ; An individual
+///                   / "GROUP"        ; A group of individuals
+///                   / "RESOURCE"     ; A physical resource
+///                   / "ROOM"         ; A room resource
+///                   / "UNKNOWN"      ; Otherwise not known
+///                   / x-name         ; Experimental type
+///                   / iana-token)    ; Other IANA-registered
+///                                    ; type
+///
+/// x-name and iana-token not supported
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub enum CalendarUserType {
+    #[default]
+    Individual,
+    Group,
+    Resource,
+    Room,
+    Unknown,
+}
+
+impl CalendarUserType {
+    pub(in crate::i_calendar) fn to_escaped(self) -> String {
+        let mut s = String::new();
+        s.push_str("CUTYPE=");
+        s.push_str(match self {
+            Self::Individual => "INDIVIDUAL",
+            Self::Group => "GROUP",
+            Self::Resource => "RESOURCE",
+            Self::Room => "ROOM",
+            Self::Unknown => "UNKNOWN",
+        });
+        s
+    }
+}
+
+impl std::str::FromStr for CalendarUserType {
+    type Err = CalendarUserTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "INDIVIDUAL" => Self::Individual,
+            "GROUP" => Self::Group,
+            "RESOURCE" => Self::Resource,
+            "ROOM" => Self::Room,
+            "UNKNOWN" => Self::Unknown,
+            _ => return Err(CalendarUserTypeError { _private: () }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Copy + Default + Eq + PartialEq>() {}
+        assert_fn::<CalendarUserType>();
+
+        assert_eq!(CalendarUserType::default(), CalendarUserType::Individual);
+        assert_eq!(
+            CalendarUserType::from_str("room")?.to_escaped(),
+            "CUTYPE=ROOM"
+        );
+        assert!(CalendarUserType::from_str("X-UNKNOWN").is_err());
+        Ok(())
+    }
+}
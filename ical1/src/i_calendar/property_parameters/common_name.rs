@@ -0,0 +1,54 @@
+//! Common Name
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.2>
+
+use crate::i_calendar::property_parameters::ParamValue;
+
+#[derive(Debug, thiserror::Error)]
+#[error("common name")]
+pub struct CommonNameError {
+    _private: (),
+}
+
+/// cnparam    = "CN" "=" param-value
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommonName(ParamValue);
+
+impl CommonName {
+    pub fn new(value: &str) -> Result<Self, CommonNameError> {
+        ParamValue::from_unescaped(value)
+            .map(Self)
+            .map_err(|_| CommonNameError { _private: () })
+    }
+
+    pub fn value(&self) -> String {
+        self.0.to_unescaped()
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("CN=");
+        s.push_str(&self.0.to_escaped());
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<CommonName>();
+
+        assert_eq!(CommonName::new("John Smith")?.to_escaped(), "CN=John Smith");
+        assert_eq!(
+            CommonName::new("Doe, John")?.to_escaped(),
+            "CN=\"Doe, John\""
+        );
+        assert_eq!(CommonName::new("Doe, John")?.value(), "Doe, John");
+        assert!(CommonName::new("\"John\"").is_err());
+        Ok(())
+    }
+}
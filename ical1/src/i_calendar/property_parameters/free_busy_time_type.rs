@@ -0,0 +1,73 @@
+//! Free/Busy Time Type
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.9>
+
+#[derive(Debug, thiserror::Error)]
+#[error("free/busy time type")]
+pub struct FreeBusyTimeTypeError {
+    _private: (),
+}
+
+/// fbtypeparam = "FBTYPE" "=" ("FREE" / "BUSY"
+///               / "BUSY-UNAVAILABLE" / "BUSY-TENTATIVE"
+///               / x-name
+///               / iana-token)
+///
+/// x-name and iana-token not supported
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub enum FreeBusyTimeType {
+    Free,
+    #[default]
+    Busy,
+    BusyUnavailable,
+    BusyTentative,
+}
+
+impl FreeBusyTimeType {
+    pub(in crate::i_calendar) fn to_escaped(self) -> String {
+        let mut s = String::new();
+        s.push_str("FBTYPE=");
+        s.push_str(match self {
+            Self::Free => "FREE",
+            Self::Busy => "BUSY",
+            Self::BusyUnavailable => "BUSY-UNAVAILABLE",
+            Self::BusyTentative => "BUSY-TENTATIVE",
+        });
+        s
+    }
+}
+
+impl std::str::FromStr for FreeBusyTimeType {
+    type Err = FreeBusyTimeTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "FREE" => Self::Free,
+            "BUSY" => Self::Busy,
+            "BUSY-UNAVAILABLE" => Self::BusyUnavailable,
+            "BUSY-TENTATIVE" => Self::BusyTentative,
+            _ => return Err(FreeBusyTimeTypeError { _private: () }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Copy + Default + Eq + PartialEq>() {}
+        assert_fn::<FreeBusyTimeType>();
+
+        assert_eq!(FreeBusyTimeType::default(), FreeBusyTimeType::Busy);
+        assert_eq!(
+            FreeBusyTimeType::from_str("busy-unavailable")?.to_escaped(),
+            "FBTYPE=BUSY-UNAVAILABLE"
+        );
+        assert!(FreeBusyTimeType::from_str("X-UNKNOWN").is_err());
+        Ok(())
+    }
+}
@@ -26,6 +26,14 @@ impl IanaToken {
     }
 }
 
+impl std::str::FromStr for IanaToken {
+    type Err = IanaTokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_unescaped(s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,7 +46,7 @@ impl ParamValue {
         }
     }
 
-    fn to_unescaped(&self) -> String {
+    pub(in crate::i_calendar) fn to_unescaped(&self) -> String {
         self.s.clone()
     }
 }
@@ -0,0 +1,84 @@
+//! Participation Role
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.16>
+
+#[derive(Debug, thiserror::Error)]
+#[error("participation role")]
+pub struct ParticipationRoleError {
+    _private: (),
+}
+
+/// roleparam  = "ROLE" "="
+///             ("CHAIR"             ; Indicates chair of the
+///                                  ; calendar entity
+///            / "REQ-PARTICIPANT"   ; Indicates a participant whose
+///                                  ; participation is required
+///            / "OPT-PARTICIPANT"   ; Indicates a participant whose
+///                                  ; participation is optional
+///            / "NON-PARTICIPANT"   ; Indicates a participant who
+///                                  ; is copied for information
+///                                  ; purposes only
+///            / x-name              ; Experimental role
+///            / iana-token)         ; Other IANA role
+///
+/// x-name and iana-token not supported
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub enum ParticipationRole {
+    Chair,
+    #[default]
+    ReqParticipant,
+    OptParticipant,
+    NonParticipant,
+}
+
+impl ParticipationRole {
+    pub(in crate::i_calendar) fn to_escaped(self) -> String {
+        let mut s = String::new();
+        s.push_str("ROLE=");
+        s.push_str(match self {
+            Self::Chair => "CHAIR",
+            Self::ReqParticipant => "REQ-PARTICIPANT",
+            Self::OptParticipant => "OPT-PARTICIPANT",
+            Self::NonParticipant => "NON-PARTICIPANT",
+        });
+        s
+    }
+}
+
+impl std::str::FromStr for ParticipationRole {
+    type Err = ParticipationRoleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "CHAIR" => Self::Chair,
+            "REQ-PARTICIPANT" => Self::ReqParticipant,
+            "OPT-PARTICIPANT" => Self::OptParticipant,
+            "NON-PARTICIPANT" => Self::NonParticipant,
+            _ => return Err(ParticipationRoleError { _private: () }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Copy + Default + Eq + PartialEq>() {}
+        assert_fn::<ParticipationRole>();
+
+        assert_eq!(
+            ParticipationRole::default(),
+            ParticipationRole::ReqParticipant
+        );
+        assert_eq!(
+            ParticipationRole::from_str("opt-participant")?.to_escaped(),
+            "ROLE=OPT-PARTICIPANT"
+        );
+        assert!(ParticipationRole::from_str("X-UNKNOWN").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+//! Participation Status
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.12>
+
+#[derive(Debug, thiserror::Error)]
+#[error("participation status")]
+pub struct ParticipationStatusError {
+    _private: (),
+}
+
+/// partstatparam    = "PARTSTAT" "="
+///                   (partstat-event
+///                  / partstat-todo
+///                  / partstat-jour)
+///
+/// partstat-event   = ("NEEDS-ACTION"    ; Event needs action
+///                  / "ACCEPTED"         ; Event accepted
+///                  / "DECLINED"         ; Event declined
+///                  / "TENTATIVE"        ; Event tentatively
+///                                       ; accepted
+///                  / "DELEGATED"        ; Event delegated
+///                  / x-name             ; Experimental status
+///                  / iana-token)        ; Other IANA-registered
+///                                       ; status
+///
+/// partstat-todo    = ("NEEDS-ACTION"    ; To-do needs action
+///                  / "ACCEPTED"         ; To-do accepted
+///                  / "DECLINED"         ; To-do declined
+///                  / "TENTATIVE"        ; To-do tentatively
+///                                       ; accepted
+///                  / "DELEGATED"        ; To-do delegated
+///                  / "COMPLETED"        ; To-do completed
+///                                       ; COMPLETED property has
+///                                       ; DATE-TIME completed
+///                  / "IN-PROCESS"       ; To-do in process of
+///                                       ; being completed
+///                  / x-name             ; Experimental status
+///                  / iana-token)        ; Other IANA-registered
+///                                       ; status
+///
+/// x-name and iana-token not supported
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub enum ParticipationStatus {
+    #[default]
+    NeedsAction,
+    Accepted,
+    Declined,
+    Tentative,
+    Delegated,
+    Completed,
+    InProcess,
+}
+
+impl ParticipationStatus {
+    /// partstat-event
+    pub(in crate::i_calendar) fn is_for_event(&self) -> bool {
+        !matches!(self, Self::Completed | Self::InProcess)
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(self) -> String {
+        let mut s = String::new();
+        s.push_str("PARTSTAT=");
+        s.push_str(match self {
+            Self::NeedsAction => "NEEDS-ACTION",
+            Self::Accepted => "ACCEPTED",
+            Self::Declined => "DECLINED",
+            Self::Tentative => "TENTATIVE",
+            Self::Delegated => "DELEGATED",
+            Self::Completed => "COMPLETED",
+            Self::InProcess => "IN-PROCESS",
+        });
+        s
+    }
+}
+
+impl std::str::FromStr for ParticipationStatus {
+    type Err = ParticipationStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "NEEDS-ACTION" => Self::NeedsAction,
+            "ACCEPTED" => Self::Accepted,
+            "DECLINED" => Self::Declined,
+            "TENTATIVE" => Self::Tentative,
+            "DELEGATED" => Self::Delegated,
+            "COMPLETED" => Self::Completed,
+            "IN-PROCESS" => Self::InProcess,
+            _ => return Err(ParticipationStatusError { _private: () }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Copy + Default + Eq + PartialEq>() {}
+        assert_fn::<ParticipationStatus>();
+
+        assert_eq!(
+            ParticipationStatus::default(),
+            ParticipationStatus::NeedsAction
+        );
+        assert_eq!(
+            ParticipationStatus::from_str("accepted")?.to_escaped(),
+            "PARTSTAT=ACCEPTED"
+        );
+        assert!(ParticipationStatus::Tentative.is_for_event());
+        assert!(!ParticipationStatus::InProcess.is_for_event());
+        assert!(ParticipationStatus::from_str("X-UNKNOWN").is_err());
+        Ok(())
+    }
+}
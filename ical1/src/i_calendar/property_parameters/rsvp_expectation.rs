@@ -0,0 +1,62 @@
+//! RSVP Expectation
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.17>
+
+#[derive(Debug, thiserror::Error)]
+#[error("rsvp expectation")]
+pub struct RsvpExpectationError {
+    _private: (),
+}
+
+/// rsvpparam = "RSVP" "=" ("TRUE" / "FALSE")
+/// ; Default is FALSE
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct RsvpExpectation(bool);
+
+impl RsvpExpectation {
+    pub fn new(value: bool) -> Self {
+        Self(value)
+    }
+
+    pub fn value(self) -> bool {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(self) -> String {
+        let mut s = String::new();
+        s.push_str("RSVP=");
+        s.push_str(if self.0 { "TRUE" } else { "FALSE" });
+        s
+    }
+}
+
+impl std::str::FromStr for RsvpExpectation {
+    type Err = RsvpExpectationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "TRUE" => Self(true),
+            "FALSE" => Self(false),
+            _ => return Err(RsvpExpectationError { _private: () }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Copy + Default + Eq + PartialEq>() {}
+        assert_fn::<RsvpExpectation>();
+
+        assert!(!RsvpExpectation::default().value());
+        assert_eq!(RsvpExpectation::from_str("true")?.to_escaped(), "RSVP=TRUE");
+        assert_eq!(RsvpExpectation::new(false).to_escaped(), "RSVP=FALSE");
+        assert!(RsvpExpectation::from_str("YES").is_err());
+        Ok(())
+    }
+}
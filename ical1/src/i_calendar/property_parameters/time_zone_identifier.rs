@@ -0,0 +1,81 @@
+//! Time Zone Identifier
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.2.19>
+
+#[derive(Debug, thiserror::Error)]
+#[error("time zone identifier")]
+pub struct TimeZoneIdentifierError {
+    _private: (),
+}
+
+/// tzidparam  = "TZID" "=" [tzidprefix] paramtext
+/// tzidprefix = "/"
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct TimeZoneIdentifier(String);
+
+impl TimeZoneIdentifier {
+    pub fn from_unescaped(s: &str) -> Result<Self, TimeZoneIdentifierError> {
+        // paramtext = *SAFE-CHAR
+        if s.is_empty()
+            || s.chars()
+                .any(|c| (c.is_ascii_control() && c != '\t') || matches!(c, '"' | ';' | ':' | ','))
+        {
+            return Err(TimeZoneIdentifierError { _private: () });
+        }
+        Ok(Self(s.to_owned()))
+    }
+
+    /// Returns the tzid (e.g. `"America/New_York"`).
+    pub fn to_unescaped(&self) -> String {
+        self.0.clone()
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str("TZID=");
+        s.push_str(&self.0);
+        s
+    }
+}
+
+impl std::fmt::Display for TimeZoneIdentifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for TimeZoneIdentifier {
+    type Err = TimeZoneIdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_unescaped(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + Ord + PartialEq + PartialOrd>() {}
+        assert_fn::<TimeZoneIdentifier>();
+
+        let s = "America/New_York";
+        assert_eq!(TimeZoneIdentifier::from_unescaped(s)?.to_unescaped(), s);
+        assert_eq!(
+            TimeZoneIdentifier::from_unescaped(s)?.to_escaped(),
+            "TZID=America/New_York"
+        );
+
+        let s = "/example.org/America/New_York";
+        assert_eq!(
+            TimeZoneIdentifier::from_unescaped(s)?.to_escaped(),
+            "TZID=/example.org/America/New_York"
+        );
+
+        assert!(TimeZoneIdentifier::from_unescaped("").is_err());
+        assert!(TimeZoneIdentifier::from_unescaped("America:New_York").is_err());
+        Ok(())
+    }
+}
@@ -29,6 +29,14 @@ impl XName {
     }
 }
 
+impl std::str::FromStr for XName {
+    type Err = XNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_unescaped(s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,851 @@
+//! Recurrence set expansion
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.5>
+//!
+//! The date-times are compared as wall-clock times. UTC offsets of the time zones are not applied.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::i_calendar::value_type::{DateTime, Frequency, Recur, Weekday, WeekdayNum};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Seconds since 1970-01-01T00:00:00 (wall-clock)
+type Timestamp = i64;
+
+/// An iterator over the occurrences of a recurrence set within a window.
+///
+/// recurrence set = DTSTART + RRULE + RDATE - EXDATE
+pub struct Occurrences {
+    template: DateTime,
+    rule: Option<RuleIter>,
+    rule_next: Option<Timestamp>,
+    rdates: VecDeque<Timestamp>,
+    exdates: BTreeSet<Timestamp>,
+    start: Timestamp,
+    end: Timestamp,
+    last: Option<Timestamp>,
+}
+
+impl Occurrences {
+    /// Returns the occurrences in `[start, end)`.
+    pub(in crate::i_calendar) fn new(
+        dtstart: &DateTime,
+        rrule: Option<&Recur>,
+        rdates: Vec<&DateTime>,
+        exdates: Vec<&DateTime>,
+        start: &DateTime,
+        end: &DateTime,
+    ) -> Self {
+        let dtstart_ts = timestamp(dtstart);
+        let mut rdates = rdates
+            .into_iter()
+            .map(timestamp)
+            .collect::<Vec<Timestamp>>();
+        rdates.sort();
+        Self {
+            template: dtstart.clone(),
+            rule: Some(match rrule {
+                Some(recur) => RuleIter::new(dtstart_ts, recur),
+                // The "DTSTART" property value always counts as the first occurrence.
+                None => RuleIter::single(dtstart_ts),
+            }),
+            rule_next: None,
+            rdates: VecDeque::from(rdates),
+            exdates: exdates.into_iter().map(timestamp).collect(),
+            start: timestamp(start),
+            end: timestamp(end),
+            last: None,
+        }
+    }
+
+    fn next_timestamp(&mut self) -> Option<Timestamp> {
+        if self.rule_next.is_none() {
+            self.rule_next = self.rule.as_mut().and_then(Iterator::next);
+            if self.rule_next.is_none() {
+                self.rule = None;
+            }
+        }
+        match (self.rule_next, self.rdates.front().copied()) {
+            (Some(r), Some(d)) if d < r => self.rdates.pop_front(),
+            (Some(_), _) => self.rule_next.take(),
+            (None, _) => self.rdates.pop_front(),
+        }
+    }
+}
+
+impl Iterator for Occurrences {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let ts = self.next_timestamp()?;
+            if ts >= self.end {
+                self.rule = None;
+                self.rdates.clear();
+                return None;
+            }
+            if ts < self.start || self.exdates.contains(&ts) || self.last == Some(ts) {
+                continue;
+            }
+            self.last = Some(ts);
+            return from_timestamp(ts, &self.template);
+        }
+    }
+}
+
+/// An iterator over the date-times defined by DTSTART and RRULE.
+struct RuleIter {
+    dtstart: Timestamp,
+    freq: Frequency,
+    interval: i64,
+    until: Option<Timestamp>,
+    count: Option<u32>,
+    bysecond: Vec<u8>,
+    byminute: Vec<u8>,
+    byhour: Vec<u8>,
+    byday: Vec<WeekdayNum>,
+    bymonthday: Vec<i8>,
+    byyearday: Vec<i16>,
+    byweekno: Vec<i8>,
+    bymonth: Vec<u8>,
+    bysetpos: Vec<i16>,
+    wkst: Weekday,
+    period: i64,
+    buffer: VecDeque<Timestamp>,
+    emitted: u32,
+    done: bool,
+}
+
+impl RuleIter {
+    fn new(dtstart: Timestamp, recur: &Recur) -> Self {
+        let (_, m, d) = civil_from_days(dtstart.div_euclid(SECONDS_PER_DAY));
+        let has_day_rules = !recur.byweekno.is_empty()
+            || !recur.byyearday.is_empty()
+            || !recur.bymonthday.is_empty()
+            || !recur.byday.is_empty();
+        let mut bymonth = recur.bymonth.clone();
+        let mut bymonthday = recur.bymonthday.clone();
+        let mut byday = recur.byday.clone();
+        // The missing rule parts are derived from "DTSTART".
+        if !has_day_rules {
+            match recur.freq {
+                Frequency::Yearly => {
+                    if bymonth.is_empty() {
+                        bymonth = vec![m];
+                    }
+                    bymonthday = vec![d as i8];
+                }
+                Frequency::Monthly => bymonthday = vec![d as i8],
+                Frequency::Weekly => {
+                    byday = vec![
+                        WeekdayNum::new(None, weekday(dtstart.div_euclid(SECONDS_PER_DAY)))
+                            .expect("valid weekdaynum"),
+                    ]
+                }
+                _ => {}
+            }
+        }
+        Self {
+            dtstart,
+            freq: recur.freq,
+            interval: i64::from(recur.interval()),
+            until: recur.until.as_ref().map(timestamp),
+            count: recur.count,
+            bysecond: recur.bysecond.clone(),
+            byminute: recur.byminute.clone(),
+            byhour: recur.byhour.clone(),
+            byday,
+            bymonthday,
+            byyearday: recur.byyearday.clone(),
+            byweekno: recur.byweekno.clone(),
+            bymonth,
+            bysetpos: recur.bysetpos.clone(),
+            wkst: recur.wkst(),
+            period: 0,
+            // The "DTSTART" property value always counts as the first occurrence.
+            buffer: VecDeque::from([dtstart]),
+            emitted: 0,
+            done: false,
+        }
+    }
+
+    fn single(dtstart: Timestamp) -> Self {
+        let mut iter = Self::new(
+            dtstart,
+            &"FREQ=DAILY;COUNT=1".parse::<Recur>().expect("valid recur"),
+        );
+        iter.done = true;
+        iter
+    }
+
+    /// Fills the buffer with the date-times of the next period.
+    fn fill(&mut self) {
+        while self.buffer.is_empty() && !self.done {
+            let (days, times) = match self.period_days_and_times() {
+                Some(days_and_times) => days_and_times,
+                None => {
+                    self.done = true;
+                    return;
+                }
+            };
+            let mut candidates = days
+                .iter()
+                .filter(|day| self.matches_day(**day))
+                .flat_map(|day| times.iter().map(move |time| day * SECONDS_PER_DAY + time))
+                .collect::<Vec<Timestamp>>();
+            candidates.sort();
+            candidates.dedup();
+            if !self.bysetpos.is_empty() {
+                let len = candidates.len() as i64;
+                let positions = self
+                    .bysetpos
+                    .iter()
+                    .map(|pos| i64::from(*pos))
+                    .filter_map(|pos| {
+                        let index = if pos > 0 { pos - 1 } else { len + pos };
+                        (0..len).contains(&index).then_some(index as usize)
+                    })
+                    .collect::<BTreeSet<usize>>();
+                candidates = positions.into_iter().map(|i| candidates[i]).collect();
+            }
+            self.buffer
+                .extend(candidates.into_iter().filter(|ts| *ts > self.dtstart));
+        }
+    }
+
+    /// Returns the days and the times (seconds of the day) of the current period and advances
+    /// the period, or `None` if the period is out of range.
+    fn period_days_and_times(&mut self) -> Option<(Vec<i64>, Vec<i64>)> {
+        let dtstart_day = self.dtstart.div_euclid(SECONDS_PER_DAY);
+        let dtstart_time = self.dtstart.rem_euclid(SECONDS_PER_DAY);
+        let (y0, m0, _) = civil_from_days(dtstart_day);
+        let k = self.period;
+        self.period += 1;
+
+        let default_times = || {
+            let list = |values: &[u8], default: i64| {
+                if values.is_empty() {
+                    vec![default]
+                } else {
+                    values.iter().map(|v| i64::from(*v)).collect::<Vec<i64>>()
+                }
+            };
+            let mut times = vec![];
+            for h in list(&self.byhour, dtstart_time / 3600) {
+                for mi in list(&self.byminute, dtstart_time / 60 % 60) {
+                    for s in list(&self.bysecond, dtstart_time % 60) {
+                        times.push(h * 3600 + mi * 60 + s);
+                    }
+                }
+            }
+            times
+        };
+
+        let days_and_times = match self.freq {
+            Frequency::Yearly => {
+                let y = y0 + k * self.interval;
+                let days = if self.byweekno.is_empty() {
+                    days_from_civil(y, 1, 1)..days_from_civil(y + 1, 1, 1)
+                } else {
+                    week1_start(y, self.wkst)..week1_start(y + 1, self.wkst)
+                };
+                (days.collect(), default_times())
+            }
+            Frequency::Monthly => {
+                let month_index = y0 * 12 + i64::from(m0) - 1 + k * self.interval;
+                let (y, m) = (
+                    month_index.div_euclid(12),
+                    (month_index.rem_euclid(12) + 1) as u8,
+                );
+                let first = days_from_civil(y, m, 1);
+                (
+                    (first..first + i64::from(days_in_month(y, m))).collect(),
+                    default_times(),
+                )
+            }
+            Frequency::Weekly => {
+                let offset = (i64::from(weekday(dtstart_day).number_from_sunday())
+                    - i64::from(self.wkst.number_from_sunday()))
+                .rem_euclid(7);
+                let first = dtstart_day - offset + 7 * k * self.interval;
+                ((first..first + 7).collect(), default_times())
+            }
+            Frequency::Daily => (vec![dtstart_day + k * self.interval], default_times()),
+            Frequency::Hourly | Frequency::Minutely | Frequency::Secondly => {
+                let unit = match self.freq {
+                    Frequency::Hourly => 3600,
+                    Frequency::Minutely => 60,
+                    _ => 1,
+                };
+                let step = unit * self.interval;
+                let ts = self.dtstart + k * step;
+                let (day, time) = (
+                    ts.div_euclid(SECONDS_PER_DAY),
+                    ts.rem_euclid(SECONDS_PER_DAY),
+                );
+                if civil_from_days(day).0 > 9999 {
+                    return None;
+                }
+                let skip_to = |next: Timestamp| (next - self.dtstart + step - 1) / step;
+                if !self.matches_day(day) {
+                    // skip to the next day
+                    self.period = self.period.max(skip_to((day + 1) * SECONDS_PER_DAY));
+                    (vec![], vec![])
+                } else if !self.byhour.is_empty() && !self.byhour.contains(&((time / 3600) as u8)) {
+                    // skip to the next hour
+                    self.period = self.period.max(skip_to(ts - ts.rem_euclid(3600) + 3600));
+                    (vec![], vec![])
+                } else {
+                    let hour = time / 3600;
+                    let minutes = if self.freq == Frequency::Hourly && !self.byminute.is_empty() {
+                        self.byminute.iter().map(|v| i64::from(*v)).collect()
+                    } else {
+                        vec![time / 60 % 60]
+                    };
+                    let seconds = if self.freq != Frequency::Secondly && !self.bysecond.is_empty() {
+                        self.bysecond.iter().map(|v| i64::from(*v)).collect()
+                    } else {
+                        vec![time % 60]
+                    };
+                    let mut times = vec![];
+                    for mi in minutes {
+                        if self.freq != Frequency::Hourly
+                            && !self.byminute.is_empty()
+                            && !self.byminute.contains(&(mi as u8))
+                        {
+                            continue;
+                        }
+                        for s in &seconds {
+                            if self.freq == Frequency::Secondly
+                                && !self.bysecond.is_empty()
+                                && !self.bysecond.contains(&(*s as u8))
+                            {
+                                continue;
+                            }
+                            times.push(hour * 3600 + mi * 60 + s);
+                        }
+                    }
+                    (vec![day], times)
+                }
+            }
+        };
+        if let Some(day) = days_and_times.0.first()
+            && civil_from_days(*day).0 > 9999
+        {
+            return None;
+        }
+        Some(days_and_times)
+    }
+
+    fn matches_day(&self, day: i64) -> bool {
+        let (y, m, d) = civil_from_days(day);
+        let yday = day - days_from_civil(y, 1, 1) + 1;
+        let days_in_year = days_from_civil(y + 1, 1, 1) - days_from_civil(y, 1, 1);
+        let dim = i64::from(days_in_month(y, m));
+        let d = i64::from(d);
+
+        if !self.bymonth.is_empty() && !self.bymonth.contains(&m) {
+            return false;
+        }
+        if !self.byweekno.is_empty() {
+            let (week_year, weekno) = week_number(day, self.wkst);
+            let weeks =
+                (week1_start(week_year + 1, self.wkst) - week1_start(week_year, self.wkst)) / 7;
+            if !self.byweekno.iter().any(|w| {
+                let w = i64::from(*w);
+                (if w > 0 { w } else { weeks + w + 1 }) == weekno
+            }) {
+                return false;
+            }
+        }
+        if !self.byyearday.is_empty()
+            && !self.byyearday.iter().any(|n| {
+                let n = i64::from(*n);
+                (if n > 0 { n } else { days_in_year + n + 1 }) == yday
+            })
+        {
+            return false;
+        }
+        if !self.bymonthday.is_empty()
+            && !self.bymonthday.iter().any(|n| {
+                let n = i64::from(*n);
+                (if n > 0 { n } else { dim + n + 1 }) == d
+            })
+        {
+            return false;
+        }
+        if !self.byday.is_empty() {
+            // The numeric value in a BYDAY rule part indicates the nth occurrence within the
+            // MONTHLY or YEARLY "RRULE" (within the month if BYMONTH is specified).
+            let in_month = self.freq == Frequency::Monthly || !self.bymonth.is_empty();
+            let (nth, nth_from_end) = if in_month {
+                ((d - 1) / 7 + 1, (dim - d) / 7 + 1)
+            } else {
+                ((yday - 1) / 7 + 1, (days_in_year - yday) / 7 + 1)
+            };
+            let wd = weekday(day);
+            if !self.byday.iter().any(|weekdaynum| {
+                weekdaynum.weekday() == wd
+                    && match weekdaynum.ordwk() {
+                        None => true,
+                        Some(n) if n > 0 => i64::from(n) == nth,
+                        Some(n) => i64::from(-n) == nth_from_end,
+                    }
+            }) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Iterator for RuleIter {
+    type Item = Timestamp;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count.is_some_and(|count| self.emitted >= count) {
+            return None;
+        }
+        if self.buffer.is_empty() {
+            self.fill();
+        }
+        let ts = self.buffer.pop_front()?;
+        if self.until.is_some_and(|until| ts > until) {
+            self.buffer.clear();
+            self.done = true;
+            return None;
+        }
+        self.emitted += 1;
+        Some(ts)
+    }
+}
+
+fn timestamp(date_time: &DateTime) -> Timestamp {
+    let days = days_from_civil(
+        i64::from(date_time.year()),
+        date_time.month(),
+        date_time.day(),
+    );
+    days * SECONDS_PER_DAY
+        + i64::from(date_time.hour()) * 3600
+        + i64::from(date_time.minute()) * 60
+        + i64::from(date_time.second())
+}
+
+fn from_timestamp(ts: Timestamp, template: &DateTime) -> Option<DateTime> {
+    let (y, m, d) = civil_from_days(ts.div_euclid(SECONDS_PER_DAY));
+    let time = ts.rem_euclid(SECONDS_PER_DAY);
+    template
+        .with_same_form(
+            u16::try_from(y).ok()?,
+            m,
+            d,
+            (time / 3600) as u8,
+            (time / 60 % 60) as u8,
+            (time % 60) as u8,
+        )
+        .ok()
+}
+
+/// <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(y: i64, m: u8, d: u8) -> i64 {
+    let (m, d) = (i64::from(m), i64::from(d));
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(z: i64) -> (i64, u8, u8) {
+    let z = z + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let m = (if mp < 10 { mp + 3 } else { mp - 9 }) as u8;
+    (
+        if m <= 2 {
+            era * 400 + yoe + 1
+        } else {
+            era * 400 + yoe
+        },
+        m,
+        d,
+    )
+}
+
+fn days_in_month(y: i64, m: u8) -> u8 {
+    let next = if m == 12 {
+        days_from_civil(y + 1, 1, 1)
+    } else {
+        days_from_civil(y, m + 1, 1)
+    };
+    (next - days_from_civil(y, m, 1)) as u8
+}
+
+fn weekday(day: i64) -> Weekday {
+    // 1970-01-01 is Thursday
+    match (day + 4).rem_euclid(7) {
+        0 => Weekday::Sunday,
+        1 => Weekday::Monday,
+        2 => Weekday::Tuesday,
+        3 => Weekday::Wednesday,
+        4 => Weekday::Thursday,
+        5 => Weekday::Friday,
+        _ => Weekday::Saturday,
+    }
+}
+
+/// Returns the first day of the week 1 of the year.
+///
+/// Week number one of the calendar year is the first week that contains at least four (4) days in
+/// that calendar year.
+fn week1_start(y: i64, wkst: Weekday) -> i64 {
+    let jan4 = days_from_civil(y, 1, 4);
+    let offset = (i64::from(weekday(jan4).number_from_sunday())
+        - i64::from(wkst.number_from_sunday()))
+    .rem_euclid(7);
+    jan4 - offset
+}
+
+/// Returns the year and the week number of the day.
+fn week_number(day: i64, wkst: Weekday) -> (i64, i64) {
+    let (y, _, _) = civil_from_days(day);
+    let year = [y + 1, y, y - 1]
+        .into_iter()
+        .find(|y| week1_start(*y, wkst) <= day)
+        .expect("week1 of the previous year starts before the day");
+    (year, (day - week1_start(year, wkst)) / 7 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    fn expand(dtstart: &str, rrule: &str, start: &str, end: &str) -> anyhow::Result<Vec<String>> {
+        Ok(Occurrences::new(
+            &DateTime::try_from(dtstart.to_owned())?,
+            Some(&Recur::from_str(rrule)?),
+            vec![],
+            vec![],
+            &DateTime::try_from(start.to_owned())?,
+            &DateTime::try_from(end.to_owned())?,
+        )
+        .map(String::from)
+        .collect())
+    }
+
+    #[test]
+    fn test_date_algorithms() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(days_from_civil(2000, 2, 29)), (2000, 2, 29));
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2100, 2), 28);
+        assert_eq!(weekday(days_from_civil(1997, 9, 2)), Weekday::Tuesday);
+        // 1997-12-29 is in the week 1 of 1998 (WKST=MO)
+        assert_eq!(
+            week_number(days_from_civil(1997, 12, 29), Weekday::Monday),
+            (1998, 1)
+        );
+        assert_eq!(
+            week_number(days_from_civil(2021, 1, 1), Weekday::Monday),
+            (2020, 53)
+        );
+    }
+
+    /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.5.3>
+    #[test]
+    fn test_rfc5545_examples() -> anyhow::Result<()> {
+        // Daily for 10 occurrences
+        assert_eq!(
+            expand(
+                "19970902T090000",
+                "FREQ=DAILY;COUNT=10",
+                "19970101T000000",
+                "19980101T000000"
+            )?,
+            (2..=11)
+                .map(|d| format!("199709{:02}T090000", d))
+                .collect::<Vec<String>>()
+        );
+
+        // Every other day - forever
+        assert_eq!(
+            expand(
+                "19970902T090000",
+                "FREQ=DAILY;INTERVAL=2",
+                "19970901T000000",
+                "19970910T000000"
+            )?,
+            [
+                "19970902T090000",
+                "19970904T090000",
+                "19970906T090000",
+                "19970908T090000"
+            ]
+        );
+
+        // Weekly on Tuesday and Thursday for five weeks
+        assert_eq!(
+            expand(
+                "19970902T090000",
+                "FREQ=WEEKLY;COUNT=10;WKST=SU;BYDAY=TU,TH",
+                "19970101T000000",
+                "19980101T000000"
+            )?,
+            [
+                "19970902T090000",
+                "19970904T090000",
+                "19970909T090000",
+                "19970911T090000",
+                "19970916T090000",
+                "19970918T090000",
+                "19970923T090000",
+                "19970925T090000",
+                "19970930T090000",
+                "19971002T090000",
+            ]
+        );
+
+        // Every other week on Monday, Wednesday, and Friday until December 24, 1997
+        assert_eq!(
+            expand(
+                "19970901T090000",
+                "FREQ=WEEKLY;INTERVAL=2;UNTIL=19971224T000000Z;WKST=SU;BYDAY=MO,WE,FR",
+                "19970101T000000",
+                "19980101T000000"
+            )?
+            .len(),
+            25
+        );
+
+        // Monthly on the first Friday for 10 occurrences
+        assert_eq!(
+            expand(
+                "19970905T090000",
+                "FREQ=MONTHLY;COUNT=10;BYDAY=1FR",
+                "19970101T000000",
+                "19990101T000000"
+            )?,
+            [
+                "19970905T090000",
+                "19971003T090000",
+                "19971107T090000",
+                "19971205T090000",
+                "19980102T090000",
+                "19980206T090000",
+                "19980306T090000",
+                "19980403T090000",
+                "19980501T090000",
+                "19980605T090000",
+            ]
+        );
+
+        // Every other month on the first and last Sunday of the month for 10 occurrences
+        assert_eq!(
+            expand(
+                "19970907T090000",
+                "FREQ=MONTHLY;INTERVAL=2;COUNT=10;BYDAY=1SU,-1SU",
+                "19970101T000000",
+                "19990101T000000"
+            )?,
+            [
+                "19970907T090000",
+                "19970928T090000",
+                "19971102T090000",
+                "19971130T090000",
+                "19980104T090000",
+                "19980125T090000",
+                "19980301T090000",
+                "19980329T090000",
+                "19980503T090000",
+                "19980531T090000",
+            ]
+        );
+
+        // Monthly on the third-to-the-last day of the month, forever
+        assert_eq!(
+            expand(
+                "19970928T090000",
+                "FREQ=MONTHLY;BYMONTHDAY=-3",
+                "19970101T000000",
+                "19980301T000000"
+            )?,
+            [
+                "19970928T090000",
+                "19971029T090000",
+                "19971128T090000",
+                "19971229T090000",
+                "19980129T090000",
+                "19980226T090000",
+            ]
+        );
+
+        // Every 3rd year on the 1st, 100th, and 200th day for 10 occurrences
+        assert_eq!(
+            expand(
+                "19970101T090000",
+                "FREQ=YEARLY;INTERVAL=3;COUNT=10;BYYEARDAY=1,100,200",
+                "19970101T000000",
+                "20100101T000000"
+            )?,
+            [
+                "19970101T090000",
+                "19970410T090000",
+                "19970719T090000",
+                "20000101T090000",
+                "20000409T090000",
+                "20000718T090000",
+                "20030101T090000",
+                "20030410T090000",
+                "20030719T090000",
+                "20060101T090000",
+            ]
+        );
+
+        // Monday of week number 20 (where the default start of the week is Monday), forever
+        assert_eq!(
+            expand(
+                "19970512T090000",
+                "FREQ=YEARLY;BYWEEKNO=20;BYDAY=MO",
+                "19970101T000000",
+                "20000101T000000"
+            )?,
+            ["19970512T090000", "19980511T090000", "19990517T090000"]
+        );
+
+        // Every Friday the 13th, forever (DTSTART is excluded as it is not a Friday the 13th)
+        assert_eq!(
+            expand(
+                "19970902T090000",
+                "FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13",
+                "19970903T000000",
+                "20000101T000000"
+            )?,
+            [
+                "19980213T090000",
+                "19980313T090000",
+                "19981113T090000",
+                "19990813T090000",
+            ]
+        );
+
+        // The third instance into the month of one of Tuesday, Wednesday, or Thursday, for the
+        // next 3 months
+        assert_eq!(
+            expand(
+                "19970904T090000",
+                "FREQ=MONTHLY;COUNT=3;BYDAY=TU,WE,TH;BYSETPOS=3",
+                "19970101T000000",
+                "19980101T000000"
+            )?,
+            ["19970904T090000", "19971007T090000", "19971106T090000"]
+        );
+
+        // The second-to-last weekday of the month
+        assert_eq!(
+            expand(
+                "19970929T090000",
+                "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-2",
+                "19970101T000000",
+                "19980101T000000"
+            )?,
+            [
+                "19970929T090000",
+                "19971030T090000",
+                "19971127T090000",
+                "19971230T090000",
+            ]
+        );
+
+        // Every 20 minutes from 9:00 AM to 4:40 PM every day
+        let occurrences = expand(
+            "19970902T090000",
+            "FREQ=MINUTELY;INTERVAL=20;BYHOUR=9,10,11,12,13,14,15,16",
+            "19970902T000000",
+            "19970904T000000",
+        )?;
+        assert_eq!(occurrences.len(), 48);
+        assert_eq!(occurrences[23], "19970902T164000");
+        assert_eq!(occurrences[24], "19970903T090000");
+
+        // Every 3 hours from 9:00 AM to 5:00 PM on a specific day
+        assert_eq!(
+            expand(
+                "19970902T090000Z",
+                "FREQ=HOURLY;INTERVAL=3;UNTIL=19970902T170000Z",
+                "19970101T000000Z",
+                "19980101T000000Z"
+            )?,
+            ["19970902T090000Z", "19970902T120000Z", "19970902T150000Z"]
+        );
+
+        // An example where an invalid date (i.e., February 30) is ignored.
+        assert_eq!(
+            expand(
+                "20070115T090000",
+                "FREQ=MONTHLY;BYMONTHDAY=15,30;COUNT=5",
+                "20070101T000000",
+                "20080101T000000"
+            )?,
+            [
+                "20070115T090000",
+                "20070130T090000",
+                "20070215T090000",
+                "20070315T090000",
+                "20070330T090000",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rdate_and_exdate() -> anyhow::Result<()> {
+        let date_time = |s: &str| DateTime::try_from(s.to_owned());
+        let rdate = date_time("19970903T120000")?;
+        let exdate = date_time("19970904T090000")?;
+        assert_eq!(
+            Occurrences::new(
+                &date_time("19970902T090000")?,
+                Some(&Recur::from_str("FREQ=DAILY;COUNT=4")?),
+                vec![&rdate],
+                vec![&exdate],
+                &date_time("19970101T000000")?,
+                &date_time("19980101T000000")?,
+            )
+            .map(String::from)
+            .collect::<Vec<String>>(),
+            [
+                "19970902T090000",
+                "19970903T090000",
+                "19970903T120000",
+                "19970905T090000",
+            ]
+        );
+
+        // without RRULE
+        assert_eq!(
+            Occurrences::new(
+                &date_time("19970902T090000")?,
+                None,
+                vec![&rdate],
+                vec![],
+                &date_time("19970101T000000")?,
+                &date_time("19980101T000000")?,
+            )
+            .map(String::from)
+            .collect::<Vec<String>>(),
+            ["19970902T090000", "19970903T120000"]
+        );
+        Ok(())
+    }
+}
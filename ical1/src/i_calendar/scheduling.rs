@@ -0,0 +1,296 @@
+//! iCalendar Transport-Independent Interoperability Protocol (iTIP)
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5546>
+
+use crate::i_calendar::{
+    calendar_components::{Event, TimeZone},
+    calendar_properties::{Method, ProductIdentifier, Version},
+    component_properties::{DateTimeStamp, SequenceNumber},
+    i_calendar_object::{ICalendarObject, ICalendarObjectError},
+    property_parameters::{ParticipationStatus, TimeZoneIdentifier},
+    value_type::{CalendarUserAddress, Text},
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("scheduling")]
+pub struct SchedulingError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("attendee required")]
+    AttendeeRequired,
+    #[error("attendee not found: {0}")]
+    AttendeeNotFound(String),
+    #[error("iCalendar object")]
+    ICalendarObject(#[from] ICalendarObjectError),
+    #[error("invalid participation status for event")]
+    InvalidParticipationStatus,
+    #[error("organizer required")]
+    OrganizerRequired,
+    #[error("sequence number overflow")]
+    SequenceNumberOverflow,
+    #[error("summary required")]
+    SummaryRequired,
+}
+
+impl ICalendarObject {
+    /// Returns the iTIP "REQUEST" for the event.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc5546#section-3.2.2>
+    ///
+    /// The event MUST have "ORGANIZER", "SUMMARY" and at least one "ATTENDEE". `time_zones`
+    /// returns the definition of the time zones referenced by the event (see
+    /// [`ICalendarObjectBuilder::add_time_zones`](crate::ICalendarObjectBuilder::add_time_zones)).
+    pub fn request<F>(
+        prodid: ProductIdentifier,
+        event: Event,
+        time_zones: F,
+    ) -> Result<Self, SchedulingError>
+    where
+        F: FnMut(&TimeZoneIdentifier) -> Option<TimeZone>,
+    {
+        check_organizer_and_attendees(&event)?;
+        if event.summary().is_none() {
+            return Err(ErrorInner::SummaryRequired)?;
+        }
+        itip(prodid, Method::request(), event, time_zones)
+    }
+
+    /// Returns the iTIP "REPLY" of the attendee to the requested event.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc5546#section-3.2.3>
+    ///
+    /// The reply contains the attendee only with the updated "PARTSTAT" parameter. The
+    /// "SEQUENCE" property is kept as is.
+    pub fn reply<F>(
+        prodid: ProductIdentifier,
+        event: Event,
+        attendee: &CalendarUserAddress,
+        partstat: ParticipationStatus,
+        dtstamp: DateTimeStamp,
+        time_zones: F,
+    ) -> Result<Self, SchedulingError>
+    where
+        F: FnMut(&TimeZoneIdentifier) -> Option<TimeZone>,
+    {
+        check_organizer_and_attendees(&event)?;
+        if !partstat.is_for_event() {
+            return Err(ErrorInner::InvalidParticipationStatus)?;
+        }
+        let replying = event
+            .attendees()
+            .iter()
+            .find(|a| a.value() == attendee)
+            .ok_or_else(|| ErrorInner::AttendeeNotFound(attendee.value().to_string()))?
+            .clone()
+            .with_partstat(partstat);
+        let event = event.with_only_attendee(replying, dtstamp);
+        itip(prodid, Method::reply(), event, time_zones)
+    }
+
+    /// Returns the iTIP "CANCEL" of the entire event.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc5546#section-3.2.5>
+    ///
+    /// The "SEQUENCE" property is incremented and the "STATUS" property is set to "CANCELLED".
+    pub fn cancel<F>(
+        prodid: ProductIdentifier,
+        event: Event,
+        dtstamp: DateTimeStamp,
+        time_zones: F,
+    ) -> Result<Self, SchedulingError>
+    where
+        F: FnMut(&TimeZoneIdentifier) -> Option<TimeZone>,
+    {
+        check_organizer_and_attendees(&event)?;
+        let sequence = event
+            .sequence()
+            .map_or(0, SequenceNumber::value)
+            .checked_add(1)
+            .ok_or(ErrorInner::SequenceNumberOverflow)?;
+        let sequence = SequenceNumber::new(sequence).expect("sequence number");
+        let event = event.with_cancellation(sequence, dtstamp);
+        itip(prodid, Method::cancel(), event, time_zones)
+    }
+}
+
+/// "ORGANIZER" and at least one "ATTENDEE" are required by all methods for "VEVENT".
+fn check_organizer_and_attendees(event: &Event) -> Result<(), SchedulingError> {
+    if event.organizer().is_none() {
+        return Err(ErrorInner::OrganizerRequired.into());
+    }
+    if event.attendees().is_empty() {
+        return Err(ErrorInner::AttendeeRequired.into());
+    }
+    Ok(())
+}
+
+fn itip<F>(
+    prodid: ProductIdentifier,
+    method: Method,
+    event: Event,
+    time_zones: F,
+) -> Result<ICalendarObject, SchedulingError>
+where
+    F: FnMut(&TimeZoneIdentifier) -> Option<TimeZone>,
+{
+    Ok(ICalendarObject::builder()
+        .prodid(prodid)
+        .version(Version::new(Text::from_unescaped("2.0").expect("version")).expect("version"))
+        .method(method)
+        .add_component(event)
+        .add_time_zones(time_zones)
+        .build()
+        .map_err(ErrorInner::ICalendarObject)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::i_calendar::{
+        component_properties::{Attendee, DateTimeStart, Organizer, Summary, UniqueIdentifier},
+        i_calendar_object::ICalendarStream,
+        property_parameters::RsvpExpectation,
+    };
+
+    fn event(organizer: bool, summary: bool, attendees: bool) -> anyhow::Result<Event> {
+        let mut builder = Event::builder()
+            .uid(UniqueIdentifier::from_value("123456789@example.com")?)
+            .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+            .dtstart(DateTimeStart::from_value("19970903T163000Z")?);
+        if organizer {
+            builder = builder.organizer(Organizer::new(CalendarUserAddress::from_str(
+                "mailto:a@example.com",
+            )?)?);
+        }
+        if summary {
+            builder = builder.summary(Summary::from_value("Review Accounts")?);
+        }
+        if attendees {
+            builder = builder
+                .add_attendee(
+                    Attendee::new(CalendarUserAddress::from_str("mailto:b@example.com")?)?
+                        .with_rsvp(RsvpExpectation::new(true)),
+                )
+                .add_attendee(Attendee::new(CalendarUserAddress::from_str(
+                    "mailto:c@example.com",
+                )?)?);
+        }
+        Ok(builder.build()?)
+    }
+
+    fn prodid() -> anyhow::Result<ProductIdentifier> {
+        Ok(ProductIdentifier::new(Text::from_unescaped(
+            "-//Example/ExampleCalendarClient//EN",
+        )?)?)
+    }
+
+    fn to_string(object: ICalendarObject) -> anyhow::Result<String> {
+        Ok(ICalendarStream::builder()
+            .add_object(object)
+            .build()?
+            .to_string())
+    }
+
+    #[test]
+    fn test_request() -> anyhow::Result<()> {
+        let s = to_string(ICalendarObject::request(
+            prodid()?,
+            event(true, true, true)?,
+            |_| None,
+        )?)?;
+        assert!(s.contains("METHOD:REQUEST\r\n"));
+        assert!(s.contains("ATTENDEE;RSVP=TRUE:mailto:b@example.com\r\n"));
+
+        // "ORGANIZER", "SUMMARY" and "ATTENDEE" are required
+        assert!(ICalendarObject::request(prodid()?, event(false, true, true)?, |_| None).is_err());
+        assert!(ICalendarObject::request(prodid()?, event(true, false, true)?, |_| None).is_err());
+        assert!(ICalendarObject::request(prodid()?, event(true, true, false)?, |_| None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_reply() -> anyhow::Result<()> {
+        let attendee = CalendarUserAddress::from_str("mailto:b@example.com")?;
+        let dtstamp = DateTimeStamp::from_value("19970902T120000Z")?;
+        let s = to_string(ICalendarObject::reply(
+            prodid()?,
+            event(true, true, true)?,
+            &attendee,
+            ParticipationStatus::Accepted,
+            dtstamp.clone(),
+            |_| None,
+        )?)?;
+        assert!(s.contains("METHOD:REPLY\r\n"));
+        assert!(s.contains("DTSTAMP:19970902T120000Z\r\n"));
+        assert!(s.contains("ATTENDEE;PARTSTAT=ACCEPTED;RSVP=TRUE:mailto:b@example.com\r\n"));
+        assert!(!s.contains("mailto:c@example.com"));
+
+        // unknown attendee
+        assert!(
+            ICalendarObject::reply(
+                prodid()?,
+                event(true, true, true)?,
+                &CalendarUserAddress::from_str("mailto:d@example.com")?,
+                ParticipationStatus::Accepted,
+                dtstamp.clone(),
+                |_| None,
+            )
+            .is_err()
+        );
+        // partstat-todo
+        assert!(
+            ICalendarObject::reply(
+                prodid()?,
+                event(true, true, true)?,
+                &attendee,
+                ParticipationStatus::Completed,
+                dtstamp,
+                |_| None,
+            )
+            .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cancel() -> anyhow::Result<()> {
+        let dtstamp = DateTimeStamp::from_value("19970902T120000Z")?;
+        let s = to_string(ICalendarObject::cancel(
+            prodid()?,
+            event(true, true, true)?,
+            dtstamp.clone(),
+            |_| None,
+        )?)?;
+        assert!(s.contains("METHOD:CANCEL\r\n"));
+        assert!(s.contains("SEQUENCE:1\r\n"));
+        assert!(s.contains("STATUS:CANCELLED\r\n"));
+
+        let s = to_string(ICalendarObject::cancel(
+            prodid()?,
+            event(true, true, true)?.with_cancellation(SequenceNumber::new(1)?, dtstamp.clone()),
+            dtstamp.clone(),
+            |_| None,
+        )?)?;
+        assert!(s.contains("SEQUENCE:2\r\n"));
+
+        assert!(
+            ICalendarObject::cancel(
+                prodid()?,
+                event(true, true, true)?
+                    .with_cancellation(SequenceNumber::new(u32::MAX)?, dtstamp.clone()),
+                dtstamp.clone(),
+                |_| None,
+            )
+            .is_err()
+        );
+        // "ATTENDEE" is required
+        assert!(
+            ICalendarObject::cancel(prodid()?, event(true, true, false)?, dtstamp, |_| None)
+                .is_err()
+        );
+        Ok(())
+    }
+}
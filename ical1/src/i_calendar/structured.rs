@@ -0,0 +1,514 @@
+//! Structured representation of the content lines
+//!
+//! The components, properties, parameters and typed values shared by jCal
+//! (<https://datatracker.ietf.org/doc/html/rfc7265>) and xCal
+//! (<https://datatracker.ietf.org/doc/html/rfc6321>).
+
+use crate::i_calendar::{
+    parser::{ContentLine, ParseError, content_lines},
+    value_type::{Text, TextError},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub(in crate::i_calendar) enum StructuredError {
+    #[error("content line")]
+    ContentLine(#[from] ParseError),
+    #[error("invalid value for {0}")]
+    InvalidValue(String),
+    #[error("text")]
+    Text(#[from] TextError),
+    #[error("unbalanced component {0}")]
+    UnbalancedComponent(String),
+}
+
+/// The component with the lowercase name (e.g. `"vevent"`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(in crate::i_calendar) struct Component {
+    pub(in crate::i_calendar) name: String,
+    pub(in crate::i_calendar) properties: Vec<Property>,
+    pub(in crate::i_calendar) components: Vec<Component>,
+}
+
+/// The property with the lowercase name, the lowercase parameter names and the lowercase value
+/// type (e.g. `"date-time"`).
+///
+/// The "VALUE" parameter is represented by the value type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(in crate::i_calendar) struct Property {
+    pub(in crate::i_calendar) name: String,
+    pub(in crate::i_calendar) params: Vec<(String, Vec<String>)>,
+    pub(in crate::i_calendar) value_type: String,
+    pub(in crate::i_calendar) values: Vec<Value>,
+}
+
+/// The property value in the jCal and xCal forms (e.g. `"2006-01-02T12:00:00Z"` for
+/// `"20060102T120000Z"`, unescaped text).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(in crate::i_calendar) enum Value {
+    Boolean(bool),
+    Float(String),
+    // latitude, longitude
+    Geo(String, String),
+    Integer(i64),
+    // start, end or duration
+    Period(String, String),
+    // rule parts with the lowercase names in order
+    Recur(Vec<(String, Vec<String>)>),
+    Text(String),
+}
+
+impl Component {
+    /// Returns the components of the (folded or unfolded) content lines.
+    pub(in crate::i_calendar) fn from_content_lines(
+        s: &str,
+    ) -> Result<Vec<Component>, StructuredError> {
+        let content_lines = content_lines(s)?;
+        let mut stack = vec![Component {
+            name: String::new(),
+            properties: vec![],
+            components: vec![],
+        }];
+        for content_line in content_lines {
+            match content_line.name().as_str() {
+                "BEGIN" => stack.push(Component {
+                    name: content_line.value().to_ascii_lowercase(),
+                    properties: vec![],
+                    components: vec![],
+                }),
+                "END" => {
+                    let name = content_line.value().to_ascii_lowercase();
+                    let component = stack
+                        .pop()
+                        .filter(|component| component.name == name && !stack.is_empty())
+                        .ok_or_else(|| StructuredError::UnbalancedComponent(name.clone()))?;
+                    stack
+                        .last_mut()
+                        .ok_or(StructuredError::UnbalancedComponent(name))?
+                        .components
+                        .push(component);
+                }
+                _ => {
+                    if stack.len() == 1 {
+                        return Err(StructuredError::UnbalancedComponent(content_line.name()));
+                    }
+                    let property = Property::from_content_line(&content_line);
+                    if let Some(component) = stack.last_mut() {
+                        component.properties.push(property);
+                    }
+                }
+            }
+        }
+        match (stack.pop(), stack.is_empty()) {
+            (Some(root), true) => Ok(root.components),
+            (Some(component), false) => Err(StructuredError::UnbalancedComponent(component.name)),
+            (None, _) => unreachable!(),
+        }
+    }
+
+    /// Returns the unfolded content lines.
+    pub(in crate::i_calendar) fn to_content_lines(&self) -> Result<String, StructuredError> {
+        let name = self.name.to_ascii_uppercase();
+        let mut s = String::new();
+        s.push_str(&format!("BEGIN:{name}\r\n"));
+        for property in &self.properties {
+            s.push_str(&property.to_content_line()?);
+        }
+        for component in &self.components {
+            s.push_str(&component.to_content_lines()?);
+        }
+        s.push_str(&format!("END:{name}\r\n"));
+        Ok(s)
+    }
+}
+
+impl Property {
+    /// Returns the property with the typed values. The value that does not match the value type
+    /// is kept as is with the "unknown" value type.
+    fn from_content_line(content_line: &ContentLine) -> Self {
+        let name = content_line.name();
+        let mut value_type = default_value_type(&name).to_owned();
+        let mut params = vec![];
+        for (param_name, values) in content_line.params() {
+            match values {
+                [value] if param_name.eq_ignore_ascii_case("VALUE") => {
+                    value_type = value.to_ascii_lowercase();
+                }
+                _ => params.push((param_name.to_ascii_lowercase(), values.to_vec())),
+            }
+        }
+        let values = split_values(&name, &value_type, content_line.value())
+            .into_iter()
+            .map(|v| Value::from_escaped(&name, &value_type, v))
+            .collect::<Option<Vec<Value>>>();
+        match values {
+            Some(values) => Self {
+                name: name.to_ascii_lowercase(),
+                params,
+                value_type,
+                values,
+            },
+            None => Self {
+                name: name.to_ascii_lowercase(),
+                params,
+                value_type: "unknown".to_owned(),
+                values: vec![Value::Text(content_line.value().to_owned())],
+            },
+        }
+    }
+
+    /// Returns the unfolded content line.
+    fn to_content_line(&self) -> Result<String, StructuredError> {
+        let name = self.name.to_ascii_uppercase();
+        let mut s = String::new();
+        s.push_str(&name);
+        for (param_name, values) in &self.params {
+            s.push(';');
+            s.push_str(&param_name.to_ascii_uppercase());
+            s.push('=');
+            s.push_str(
+                &values
+                    .iter()
+                    .map(|v| {
+                        if v.contains([';', ':', ',']) {
+                            format!("\"{v}\"")
+                        } else {
+                            v.clone()
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+        }
+        if self.value_type != default_value_type(&name) && self.value_type != "unknown" {
+            s.push_str(";VALUE=");
+            s.push_str(&self.value_type.to_ascii_uppercase());
+        }
+        s.push(':');
+        s.push_str(
+            &self
+                .values
+                .iter()
+                .map(|v| v.to_escaped(&name, &self.value_type))
+                .collect::<Result<Vec<String>, StructuredError>>()?
+                .join(","),
+        );
+        s.push_str("\r\n");
+        Ok(s)
+    }
+}
+
+impl Value {
+    fn from_escaped(name: &str, value_type: &str, s: &str) -> Option<Self> {
+        Some(match value_type {
+            "boolean" => match s.to_ascii_uppercase().as_str() {
+                "TRUE" => Self::Boolean(true),
+                "FALSE" => Self::Boolean(false),
+                _ => return None,
+            },
+            "date" => Self::Text(format_date(s)?),
+            "date-time" => Self::Text(format_date_time(s)?),
+            "float" if name == "GEO" => {
+                let (latitude, longitude) = s.split_once(';')?;
+                Self::Geo(latitude.to_owned(), longitude.to_owned())
+            }
+            "float" => Self::Float(s.to_owned()),
+            "integer" => Self::Integer(s.parse().ok()?),
+            "period" => {
+                let (start, end) = s.split_once('/')?;
+                let end = if is_duration(end) {
+                    end.to_owned()
+                } else {
+                    format_date_time(end)?
+                };
+                Self::Period(format_date_time(start)?, end)
+            }
+            "recur" => {
+                let mut parts = vec![];
+                for part in s.split(';') {
+                    let (part_name, values) = part.split_once('=')?;
+                    let part_name = part_name.to_ascii_lowercase();
+                    let values = match part_name.as_str() {
+                        "until" => vec![format_date_time(values).or_else(|| format_date(values))?],
+                        _ => values.split(',').map(str::to_owned).collect(),
+                    };
+                    parts.push((part_name, values));
+                }
+                Self::Recur(parts)
+            }
+            "text" => Self::Text(Text::from_string(s.to_owned()).ok()?.to_unescaped()),
+            "time" => Self::Text(format_time(s)?),
+            "utc-offset" => Self::Text(format_utc_offset(s)?),
+            // binary, cal-address, duration, uri, unknown
+            _ => Self::Text(s.to_owned()),
+        })
+    }
+
+    fn to_escaped(&self, name: &str, value_type: &str) -> Result<String, StructuredError> {
+        let s = match self {
+            Self::Boolean(b) => if *b { "TRUE" } else { "FALSE" }.to_owned(),
+            Self::Float(f) => f.clone(),
+            Self::Geo(latitude, longitude) => format!("{latitude};{longitude}"),
+            Self::Integer(i) => i.to_string(),
+            Self::Period(start, end) => {
+                let end = if is_duration(end) {
+                    end.clone()
+                } else {
+                    unformat(end)
+                };
+                format!("{}/{}", unformat(start), end)
+            }
+            Self::Recur(parts) => {
+                // "FREQ" rule part first
+                let mut parts = parts.iter().collect::<Vec<&(String, Vec<String>)>>();
+                parts.sort_by_key(|(part_name, _)| part_name != "freq");
+                parts
+                    .into_iter()
+                    .map(|(part_name, values)| {
+                        let values = match part_name.as_str() {
+                            "until" => values.iter().map(|v| unformat(v)).collect(),
+                            _ => values.clone(),
+                        };
+                        format!("{}={}", part_name.to_ascii_uppercase(), values.join(","))
+                    })
+                    .collect::<Vec<String>>()
+                    .join(";")
+            }
+            Self::Text(s) => match value_type {
+                "date" | "date-time" | "time" | "utc-offset" => unformat(s),
+                "text" => Text::from_unescaped(s)?.into_string(),
+                _ => s.clone(),
+            },
+        };
+        if s.contains(|c: char| c.is_ascii_control() && c != '\t') {
+            return Err(StructuredError::InvalidValue(name.to_owned()));
+        }
+        Ok(s)
+    }
+}
+
+/// Returns the default value type of the property.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc7265#section-3.4.1.1>
+pub(in crate::i_calendar) fn default_value_type(name: &str) -> &'static str {
+    match name {
+        "CALSCALE" | "METHOD" | "PRODID" | "VERSION" | "CATEGORIES" | "CLASS" | "COMMENT"
+        | "DESCRIPTION" | "LOCATION" | "RESOURCES" | "STATUS" | "SUMMARY" | "TRANSP" | "TZID"
+        | "TZNAME" | "CONTACT" | "RELATED-TO" | "UID" | "ACTION" | "REQUEST-STATUS" => "text",
+        "COMPLETED" | "DTEND" | "DUE" | "DTSTART" | "RECURRENCE-ID" | "EXDATE" | "RDATE"
+        | "CREATED" | "DTSTAMP" | "LAST-MODIFIED" => "date-time",
+        "DURATION" | "TRIGGER" => "duration",
+        "FREEBUSY" => "period",
+        "GEO" => "float",
+        "PERCENT-COMPLETE" | "PRIORITY" | "REPEAT" | "SEQUENCE" => "integer",
+        "RRULE" => "recur",
+        "TZOFFSETFROM" | "TZOFFSETTO" => "utc-offset",
+        "ATTACH" | "TZURL" | "URL" => "uri",
+        "ATTENDEE" | "ORGANIZER" => "cal-address",
+        _ => "unknown",
+    }
+}
+
+/// Splits the multiple values separated by (unescaped) COMMA characters.
+fn split_values<'a>(name: &str, value_type: &str, s: &'a str) -> Vec<&'a str> {
+    if !matches!(
+        name,
+        "CATEGORIES" | "RESOURCES" | "EXDATE" | "RDATE" | "FREEBUSY"
+    ) {
+        return vec![s];
+    }
+    if value_type != "text" {
+        return s.split(',').collect();
+    }
+    let mut values = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                values.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    values.push(&s[start..]);
+    values
+}
+
+pub(in crate::i_calendar) fn is_duration(s: &str) -> bool {
+    s.trim_start_matches(['+', '-']).starts_with('P')
+}
+
+fn is_digits(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// `"20060102"` -> `"2006-01-02"`
+fn format_date(s: &str) -> Option<String> {
+    is_digits(s, 8).then(|| format!("{}-{}-{}", &s[0..4], &s[4..6], &s[6..8]))
+}
+
+/// `"120000Z"` -> `"12:00:00Z"`
+fn format_time(s: &str) -> Option<String> {
+    let (time, utc) = match s.strip_suffix('Z') {
+        Some(time) => (time, "Z"),
+        None => (s, ""),
+    };
+    is_digits(time, 6).then(|| format!("{}:{}:{}{utc}", &time[0..2], &time[2..4], &time[4..6]))
+}
+
+/// `"20060102T120000Z"` -> `"2006-01-02T12:00:00Z"`
+fn format_date_time(s: &str) -> Option<String> {
+    let (date, time) = s.split_once('T')?;
+    Some(format!("{}T{}", format_date(date)?, format_time(time)?))
+}
+
+/// `"-0500"` -> `"-05:00"`
+fn format_utc_offset(s: &str) -> Option<String> {
+    let (sign, offset) = s.split_at_checked(1)?;
+    if !matches!(sign, "+" | "-") {
+        return None;
+    }
+    match offset.len() {
+        4 if is_digits(offset, 4) => Some(format!("{sign}{}:{}", &offset[0..2], &offset[2..4])),
+        6 if is_digits(offset, 6) => Some(format!(
+            "{sign}{}:{}:{}",
+            &offset[0..2],
+            &offset[2..4],
+            &offset[4..6]
+        )),
+        _ => None,
+    }
+}
+
+/// `"2006-01-02T12:00:00Z"` -> `"20060102T120000Z"`
+fn unformat(s: &str) -> String {
+    let (sign, rest) = match s.strip_prefix(['+', '-']) {
+        Some(rest) => (&s[..1], rest),
+        None => ("", s),
+    };
+    format!("{sign}{}", rest.replace(['-', ':'], ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_content_lines() -> anyhow::Result<()> {
+        let components = Component::from_content_lines(
+            &[
+                "BEGIN:VEVENT\r\n",
+                "DTSTART;TZID=US/Eastern:20060102T120000\r\n",
+                "CATEGORIES:a\\,b,c\r\n",
+                "GEO:37.386013;-122.082932\r\n",
+                "RRULE:COUNT=5;FREQ=DAILY\r\n",
+                "X-NUMBER:01\r\n",
+                "SEQUENCE:x\r\n",
+                "BEGIN:VALARM\r\n",
+                "TRIGGER;VALUE=DATE-TIME:19970317T133000Z\r\n",
+                "END:VALARM\r\n",
+                "END:VEVENT\r\n",
+            ]
+            .join(""),
+        )?;
+        assert_eq!(
+            components,
+            vec![Component {
+                name: "vevent".to_owned(),
+                properties: vec![
+                    Property {
+                        name: "dtstart".to_owned(),
+                        params: vec![("tzid".to_owned(), vec!["US/Eastern".to_owned()])],
+                        value_type: "date-time".to_owned(),
+                        values: vec![Value::Text("2006-01-02T12:00:00".to_owned())],
+                    },
+                    Property {
+                        name: "categories".to_owned(),
+                        params: vec![],
+                        value_type: "text".to_owned(),
+                        values: vec![Value::Text("a,b".to_owned()), Value::Text("c".to_owned())],
+                    },
+                    Property {
+                        name: "geo".to_owned(),
+                        params: vec![],
+                        value_type: "float".to_owned(),
+                        values: vec![Value::Geo("37.386013".to_owned(), "-122.082932".to_owned())],
+                    },
+                    Property {
+                        name: "rrule".to_owned(),
+                        params: vec![],
+                        value_type: "recur".to_owned(),
+                        values: vec![Value::Recur(vec![
+                            ("count".to_owned(), vec!["5".to_owned()]),
+                            ("freq".to_owned(), vec!["DAILY".to_owned()]),
+                        ])],
+                    },
+                    Property {
+                        name: "x-number".to_owned(),
+                        params: vec![],
+                        value_type: "unknown".to_owned(),
+                        values: vec![Value::Text("01".to_owned())],
+                    },
+                    // invalid value kept as is
+                    Property {
+                        name: "sequence".to_owned(),
+                        params: vec![],
+                        value_type: "unknown".to_owned(),
+                        values: vec![Value::Text("x".to_owned())],
+                    },
+                ],
+                components: vec![Component {
+                    name: "valarm".to_owned(),
+                    properties: vec![Property {
+                        name: "trigger".to_owned(),
+                        params: vec![],
+                        value_type: "date-time".to_owned(),
+                        values: vec![Value::Text("1997-03-17T13:30:00Z".to_owned())],
+                    }],
+                    components: vec![],
+                }],
+            }]
+        );
+        assert_eq!(
+            components[0].to_content_lines()?,
+            [
+                "BEGIN:VEVENT\r\n",
+                "DTSTART;TZID=US/Eastern:20060102T120000\r\n",
+                "CATEGORIES:a\\,b,c\r\n",
+                "GEO:37.386013;-122.082932\r\n",
+                "RRULE:FREQ=DAILY;COUNT=5\r\n",
+                "X-NUMBER:01\r\n",
+                "SEQUENCE:x\r\n",
+                "BEGIN:VALARM\r\n",
+                "TRIGGER;VALUE=DATE-TIME:19970317T133000Z\r\n",
+                "END:VALARM\r\n",
+                "END:VEVENT\r\n",
+            ]
+            .join("")
+        );
+
+        assert!(Component::from_content_lines("BEGIN:VEVENT\r\n").is_err());
+        assert!(Component::from_content_lines("END:VEVENT\r\n").is_err());
+        assert!(Component::from_content_lines("UID:1\r\n").is_err());
+        assert!(Component::from_content_lines("BEGIN:VEVENT\r\nEND:VTODO\r\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format_date("20060102").as_deref(), Some("2006-01-02"));
+        assert_eq!(
+            format_date_time("20060102T120000Z").as_deref(),
+            Some("2006-01-02T12:00:00Z")
+        );
+        assert_eq!(format_date_time("20060102").as_deref(), None);
+        assert_eq!(format_utc_offset("-0500").as_deref(), Some("-05:00"));
+        assert_eq!(format_utc_offset("+013045").as_deref(), Some("+01:30:45"));
+        assert_eq!(format_utc_offset("0500").as_deref(), None);
+        assert_eq!(unformat("2006-01-02T12:00:00Z"), "20060102T120000Z");
+        assert_eq!(unformat("-05:00"), "-0500");
+    }
+}
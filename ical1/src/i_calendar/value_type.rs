@@ -1,13 +1,34 @@
 //! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3>
 //! Property Value Data Types
 
+mod calendar_user_address;
 mod date_time;
+mod duration;
+mod float;
+mod period;
+mod recur;
 mod text;
 mod uri;
+mod utc_offset;
 
+pub use self::calendar_user_address::CalendarUserAddress;
+pub use self::calendar_user_address::CalendarUserAddressError;
 pub use self::date_time::DateTime;
 pub use self::date_time::DateTimeError;
+pub use self::duration::Duration;
+pub use self::duration::DurationError;
+pub use self::float::Float;
+pub use self::float::FloatError;
+pub use self::period::Period;
+pub use self::period::PeriodError;
+pub use self::recur::Frequency;
+pub use self::recur::Recur;
+pub use self::recur::RecurError;
+pub use self::recur::Weekday;
+pub use self::recur::WeekdayNum;
 pub use self::text::Text;
 pub use self::text::TextError;
 pub use self::uri::Uri;
 pub use self::uri::UriError;
+pub use self::utc_offset::UtcOffset;
+pub use self::utc_offset::UtcOffsetError;
@@ -0,0 +1,59 @@
+//! Calendar User Address
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.3>
+
+use crate::i_calendar::value_type::{Uri, UriError};
+
+#[derive(Debug, thiserror::Error)]
+#[error("calendar user address")]
+pub struct CalendarUserAddressError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("uri")]
+    Uri(#[from] UriError),
+}
+
+/// cal-address        = uri
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalendarUserAddress(Uri);
+
+impl CalendarUserAddress {
+    pub fn new(value: Uri) -> Result<Self, CalendarUserAddressError> {
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> &Uri {
+        &self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl std::str::FromStr for CalendarUserAddress {
+    type Err = CalendarUserAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(Uri::from_str(s).map_err(ErrorInner::Uri)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<CalendarUserAddress>();
+
+        let s = "mailto:jane_doe@example.com";
+        assert_eq!(CalendarUserAddress::from_str(s)?.to_escaped(), s);
+        assert!(CalendarUserAddress::from_str("jane_doe@example.com").is_err());
+        Ok(())
+    }
+}
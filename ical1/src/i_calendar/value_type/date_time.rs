@@ -1,42 +1,223 @@
+use crate::i_calendar::property_parameters::TimeZoneIdentifier;
+
 #[derive(Debug, thiserror::Error)]
 #[error("date-time")]
-pub struct DateTimeError {
-    // TODO: improve error message
-    _private: (),
+pub struct DateTimeError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("invalid date")]
+    InvalidDate,
+    #[error("invalid format")]
+    InvalidFormat,
+    #[error("invalid time")]
+    InvalidTime,
+    #[error("time zone reference with UTC time")]
+    TimeZoneReferenceWithUtcTime,
 }
 
 /// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.5>
-/// FORM #1 and FORM #3 are not supported
-/// YYYYMMDDTHHMMSSZ
+///
+/// date-time  = date "T" time ;As specified in the DATE and TIME
+///                            ;value definitions
+///
+/// FORM #1: DATE WITH LOCAL TIME (e.g. `19980118T230000`)
+/// FORM #2: DATE WITH UTC TIME (e.g. `19980119T070000Z`)
+/// FORM #3: DATE WITH LOCAL TIME AND TIME ZONE REFERENCE
+///          (e.g. `TZID=America/New_York:19980119T020000`)
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
-pub struct DateTime(String);
+pub struct DateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    form: DateTimeForm,
+}
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum DateTimeForm {
+    /// FORM #1
+    Local,
+    /// FORM #2
+    Utc,
+    /// FORM #3
+    LocalWithTimeZoneReference(TimeZoneIdentifier),
+}
 
 impl DateTime {
+    /// FORM #1: DATE WITH LOCAL TIME
+    pub fn local(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Self, DateTimeError> {
+        Self::new(year, month, day, hour, minute, second, DateTimeForm::Local)
+    }
+
+    /// FORM #2: DATE WITH UTC TIME
+    pub fn utc(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Self, DateTimeError> {
+        Self::new(year, month, day, hour, minute, second, DateTimeForm::Utc)
+    }
+
+    /// FORM #3: DATE WITH LOCAL TIME AND TIME ZONE REFERENCE
+    pub fn with_time_zone(self, tzid: TimeZoneIdentifier) -> Result<Self, DateTimeError> {
+        if self.is_utc() {
+            return Err(ErrorInner::TimeZoneReferenceWithUtcTime)?;
+        }
+        Ok(Self {
+            form: DateTimeForm::LocalWithTimeZoneReference(tzid),
+            ..self
+        })
+    }
+
+    fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        form: DateTimeForm,
+    ) -> Result<Self, DateTimeError> {
+        if year > 9999
+            || !(1..=12).contains(&month)
+            || !(1..=days_in_month(year, month)).contains(&day)
+        {
+            return Err(ErrorInner::InvalidDate)?;
+        }
+        // The "60" value for seconds is used to account for positive "leap" seconds.
+        if hour > 23 || minute > 59 || second > 60 {
+            return Err(ErrorInner::InvalidTime)?;
+        }
+        Ok(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            form,
+        })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    pub fn is_utc(&self) -> bool {
+        matches!(self.form, DateTimeForm::Utc)
+    }
+
+    pub fn time_zone(&self) -> Option<&TimeZoneIdentifier> {
+        match &self.form {
+            DateTimeForm::LocalWithTimeZoneReference(tzid) => Some(tzid),
+            DateTimeForm::Local | DateTimeForm::Utc => None,
+        }
+    }
+
+    /// Returns the date-time with the same form (e.g. the same time zone reference).
+    pub(in crate::i_calendar) fn with_same_form(
+        &self,
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Self, DateTimeError> {
+        Self::new(year, month, day, hour, minute, second, self.form.clone())
+    }
+
+    /// Returns the value without the time zone reference (e.g. `"19980119T020000"`).
     pub(in crate::i_calendar) fn to_escaped(&self) -> String {
-        self.0.clone()
+        format!(
+            "{:04}{:02}{:02}T{:02}{:02}{:02}{}",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            if self.is_utc() { "Z" } else { "" }
+        )
     }
 }
 
 impl TryFrom<String> for DateTime {
     type Error = DateTimeError;
 
+    /// Parses FORM #1 or FORM #2. FORM #3 is created by [`DateTime::with_time_zone`].
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        if value
-            .chars()
-            .all(|c| c.is_ascii_digit() || c == 'T' || c == 'Z')
-            && value.ends_with('Z')
+        let (s, form) = match value.strip_suffix('Z') {
+            Some(s) => (s, DateTimeForm::Utc),
+            None => (value.as_str(), DateTimeForm::Local),
+        };
+        if s.len() != 15
+            || s.as_bytes()[8] != b'T'
+            || !s.char_indices().all(|(i, c)| i == 8 || c.is_ascii_digit())
         {
-            // TODO: improve validation
-            Ok(Self(value))
-        } else {
-            Err(DateTimeError { _private: () })
+            return Err(ErrorInner::InvalidFormat)?;
         }
+        let n = |r: std::ops::Range<usize>| s[r].parse::<u16>().expect("digits");
+        Self::new(
+            n(0..4),
+            n(4..6) as u8,
+            n(6..8) as u8,
+            n(9..11) as u8,
+            n(11..13) as u8,
+            n(13..15) as u8,
+            form,
+        )
     }
 }
 
 impl From<DateTime> for String {
     fn from(value: DateTime) -> Self {
-        value.0
+        value.to_escaped()
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400) => {
+            29
+        }
+        2 => 28,
+        _ => 0,
     }
 }
 
@@ -52,14 +233,70 @@ mod tests {
         // UTC time
         let s = "19970714T173000Z".to_owned();
         assert_eq!(String::from(DateTime::try_from(s.clone())?), s);
+        assert!(DateTime::try_from(s.clone())?.is_utc());
+        assert_eq!(
+            DateTime::try_from(s)?,
+            DateTime::utc(1997, 7, 14, 17, 30, 0)?
+        );
 
         // Local time
         let s = "19970714T133000".to_owned();
-        assert!(DateTime::try_from(s).is_err());
+        assert_eq!(String::from(DateTime::try_from(s.clone())?), s);
+        assert!(!DateTime::try_from(s.clone())?.is_utc());
+        assert_eq!(DateTime::try_from(s)?.time_zone(), None);
 
         // Local time with time zone reference
         let s = "TZID=America/New_York:19970714T133000".to_owned();
         assert!(DateTime::try_from(s).is_err());
+        let tzid = TimeZoneIdentifier::from_unescaped("America/New_York")?;
+        let date_time = DateTime::local(1997, 7, 14, 13, 30, 0)?.with_time_zone(tzid.clone())?;
+        assert_eq!(date_time.time_zone(), Some(&tzid));
+        assert_eq!(String::from(date_time), "19970714T133000");
+        assert!(
+            DateTime::utc(1997, 7, 14, 13, 30, 0)?
+                .with_time_zone(tzid)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation() -> anyhow::Result<()> {
+        let date_time = DateTime::try_from("20240229T235960Z".to_owned())?;
+        assert_eq!(
+            (
+                date_time.year(),
+                date_time.month(),
+                date_time.day(),
+                date_time.hour(),
+                date_time.minute(),
+                date_time.second()
+            ),
+            (2024, 2, 29, 23, 59, 60)
+        );
+        assert!(DateTime::try_from("20000229T000000".to_owned()).is_ok());
+
+        for s in [
+            "",
+            "19970714",
+            "19970714 133000",
+            "19970714T1330",
+            "19970714T133000z",
+            "1997-07-14T13:30:00",
+            "19970714T13300０",
+            "19970014T133000",
+            "19971314T133000",
+            "19970700T133000",
+            "19970732T133000",
+            "19970631T133000",
+            "19000229T133000",
+            "20230229T133000",
+            "19970714T243000",
+            "19970714T136000",
+            "19970714T133061",
+        ] {
+            assert!(DateTime::try_from(s.to_owned()).is_err(), "{s}");
+        }
         Ok(())
     }
 }
@@ -0,0 +1,234 @@
+#[derive(Debug, thiserror::Error)]
+#[error("duration")]
+pub struct DurationError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("invalid format")]
+    InvalidFormat,
+    #[error("out of range")]
+    OutOfRange,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.6>
+///
+/// dur-value  = (["+"] / "-") "P" (dur-date / dur-time / dur-week)
+///
+/// dur-date   = dur-day [dur-time]
+/// dur-time   = "T" (dur-hour / dur-minute / dur-second)
+/// dur-week   = 1*DIGIT "W"
+/// dur-hour   = 1*DIGIT "H" [dur-minute]
+/// dur-minute = 1*DIGIT "M" [dur-second]
+/// dur-second = 1*DIGIT "S"
+/// dur-day    = 1*DIGIT "D"
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Duration {
+    negative: bool,
+    weeks: u32,
+    days: u32,
+    hours: u32,
+    minutes: u32,
+    seconds: u32,
+}
+
+impl Duration {
+    /// dur-date / dur-time (e.g. `P15DT5H0M20S`)
+    pub fn new(days: u32, hours: u32, minutes: u32, seconds: u32) -> Result<Self, DurationError> {
+        let duration = Self {
+            negative: false,
+            weeks: 0,
+            days,
+            hours,
+            minutes,
+            seconds,
+        };
+        duration.checked_seconds().ok_or(ErrorInner::OutOfRange)?;
+        Ok(duration)
+    }
+
+    /// dur-week (e.g. `P7W`)
+    pub fn weeks(weeks: u32) -> Result<Self, DurationError> {
+        let duration = Self {
+            negative: false,
+            weeks,
+            days: 0,
+            hours: 0,
+            minutes: 0,
+            seconds: 0,
+        };
+        duration.checked_seconds().ok_or(ErrorInner::OutOfRange)?;
+        Ok(duration)
+    }
+
+    /// Returns the negative duration (e.g. `-PT15M`).
+    pub fn negate(self) -> Self {
+        Self {
+            negative: !self.negative,
+            ..self
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the duration in seconds.
+    ///
+    /// A day is counted as 24 hours (the nominal duration is not taken into account).
+    pub fn as_seconds(&self) -> i64 {
+        self.checked_seconds().expect("checked by the constructor")
+    }
+
+    fn checked_seconds(&self) -> Option<i64> {
+        let seconds = i64::from(self.weeks)
+            .checked_mul(7 * 24 * 60 * 60)?
+            .checked_add(i64::from(self.days).checked_mul(24 * 60 * 60)?)?
+            .checked_add(i64::from(self.hours) * 60 * 60)?
+            .checked_add(i64::from(self.minutes) * 60)?
+            .checked_add(i64::from(self.seconds))?;
+        Some(if self.negative { -seconds } else { seconds })
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        if self.negative {
+            s.push('-');
+        }
+        s.push('P');
+        if self.weeks > 0 {
+            s.push_str(&format!("{}W", self.weeks));
+            return s;
+        }
+        if self.days > 0 {
+            s.push_str(&format!("{}D", self.days));
+        }
+        let time = [(self.hours, 'H'), (self.minutes, 'M'), (self.seconds, 'S')];
+        let first = time.iter().position(|(n, _)| *n > 0);
+        let last = time.iter().rposition(|(n, _)| *n > 0);
+        match (first, last) {
+            (Some(first), Some(last)) => {
+                s.push('T');
+                for (n, designator) in &time[first..=last] {
+                    s.push_str(&format!("{n}{designator}"));
+                }
+            }
+            _ if self.days == 0 => s.push_str("T0S"),
+            _ => {}
+        }
+        s
+    }
+}
+
+impl std::str::FromStr for Duration {
+    type Err = DurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Splits "15D" into [(15, 'D')].
+        fn components(s: &str) -> Result<Vec<(u32, char)>, DurationError> {
+            let mut components = vec![];
+            let mut digits = String::new();
+            for c in s.chars() {
+                if c.is_ascii_digit() {
+                    digits.push(c);
+                } else {
+                    if digits.is_empty() {
+                        return Err(ErrorInner::InvalidFormat.into());
+                    }
+                    let n = digits.parse::<u32>().map_err(|_| ErrorInner::OutOfRange)?;
+                    components.push((n, c));
+                    digits.clear();
+                }
+            }
+            if !digits.is_empty() {
+                return Err(ErrorInner::InvalidFormat.into());
+            }
+            Ok(components)
+        }
+
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let s = s.strip_prefix('P').ok_or(ErrorInner::InvalidFormat)?;
+        let (date, time) = match s.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (s, None),
+        };
+
+        let duration = match (components(date)?.as_slice(), time) {
+            ([(weeks, 'W')], None) => Self::weeks(*weeks)?,
+            ([], None) => return Err(ErrorInner::InvalidFormat)?,
+            (date, time) => {
+                let days = match date {
+                    [] => 0,
+                    [(days, 'D')] => *days,
+                    _ => return Err(ErrorInner::InvalidFormat)?,
+                };
+                let (hours, minutes, seconds) = match time.map(components).transpose()?.as_deref() {
+                    None => (0, 0, 0),
+                    Some([(h, 'H')]) => (*h, 0, 0),
+                    Some([(h, 'H'), (m, 'M')]) => (*h, *m, 0),
+                    Some([(h, 'H'), (m, 'M'), (s, 'S')]) => (*h, *m, *s),
+                    Some([(m, 'M')]) => (0, *m, 0),
+                    Some([(m, 'M'), (s, 'S')]) => (0, *m, *s),
+                    Some([(s, 'S')]) => (0, 0, *s),
+                    Some(_) => return Err(ErrorInner::InvalidFormat)?,
+                };
+                Self::new(days, hours, minutes, seconds)?
+            }
+        };
+        Ok(if negative {
+            duration.negate()
+        } else {
+            duration
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Duration>();
+
+        for s in [
+            "P15DT5H0M20S",
+            "P7W",
+            "PT1H",
+            "PT15M",
+            "-PT15M",
+            "PT1H0M0S",
+            "P1D",
+            "PT0S",
+        ] {
+            let canonical = Duration::from_str(s)?.to_escaped();
+            assert_eq!(Duration::from_str(&canonical)?, Duration::from_str(s)?);
+        }
+        assert_eq!(
+            Duration::from_str("P15DT5H0M20S")?.to_escaped(),
+            "P15DT5H0M20S"
+        );
+        assert_eq!(Duration::from_str("+PT1H0M0S")?.to_escaped(), "PT1H");
+        assert_eq!(Duration::from_str("-PT15M")?.to_escaped(), "-PT15M");
+        assert_eq!(Duration::from_str("-PT15M")?.as_seconds(), -15 * 60);
+        assert_eq!(
+            Duration::from_str("P7W")?.as_seconds(),
+            7 * 7 * 24 * 60 * 60
+        );
+        assert_eq!(Duration::new(0, 1, 0, 5)?.to_escaped(), "PT1H0M5S");
+        assert_eq!(Duration::new(0, 0, 0, 0)?.to_escaped(), "PT0S");
+
+        for s in [
+            "", "P", "PT", "15M", "P1H", "PT1D", "P1W1D", "PT1S1M", "PT1H1S", "P1DT", "P-1D",
+            "PTM", "P1D2",
+        ] {
+            assert!(Duration::from_str(s).is_err(), "{s}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! Float
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.7>
+
+#[derive(Debug, thiserror::Error)]
+#[error("float")]
+pub struct FloatError {
+    _private: (),
+}
+
+/// float      = (["+"] / "-") 1*DIGIT ["." 1*DIGIT]
+///
+/// The value is kept as is to preserve its precision.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Float(String);
+
+impl Float {
+    pub fn new(value: f64) -> Result<Self, FloatError> {
+        if !value.is_finite() {
+            return Err(FloatError { _private: () });
+        }
+        // `Display` for `f64` never uses the exponent notation.
+        Ok(Self(value.to_string()))
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.0.parse::<f64>().expect("float")
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl std::str::FromStr for Float {
+    type Err = FloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+        let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if !is_digits(integer) || fraction.is_some_and(|f| !is_digits(f)) {
+            return Err(FloatError { _private: () });
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Float>();
+
+        for s in ["1000000.0000001", "1.333", "-2.5", "+2", "0.0"] {
+            assert_eq!(Float::from_str(s)?.to_escaped(), s);
+        }
+        assert_eq!(Float::from_str("-2.5")?.as_f64(), -2.5);
+        assert_eq!(Float::new(37.386013)?.to_escaped(), "37.386013");
+        assert_eq!(Float::new(-122.0)?.to_escaped(), "-122");
+        assert_eq!(Float::new(1e-7)?.to_escaped(), "0.0000001");
+
+        for s in ["", "-", "1.", ".5", "1e3", "1,5", "NaN"] {
+            assert!(Float::from_str(s).is_err(), "{s}");
+        }
+        assert!(Float::new(f64::NAN).is_err());
+        assert!(Float::new(f64::INFINITY).is_err());
+        Ok(())
+    }
+}
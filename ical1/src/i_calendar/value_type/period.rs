@@ -0,0 +1,158 @@
+use crate::i_calendar::value_type::{DateTime, DateTimeError, Duration, DurationError};
+
+#[derive(Debug, thiserror::Error)]
+#[error("period")]
+pub struct PeriodError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("date-time")]
+    DateTime(#[source] DateTimeError),
+    #[error("duration")]
+    Duration(#[source] DurationError),
+    #[error("end must be after start")]
+    EndBeforeStart,
+    #[error("invalid format")]
+    InvalidFormat,
+    #[error("mixed date-time forms")]
+    MixedForms,
+    #[error("positive duration required")]
+    PositiveDurationRequired,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.9>
+///
+/// period     = period-explicit / period-start
+///
+/// period-explicit = date-time "/" date-time
+/// period-start = date-time "/" dur-value
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Period {
+    start: DateTime,
+    end: PeriodEnd,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum PeriodEnd {
+    DateTime(DateTime),
+    Duration(Duration),
+}
+
+impl Period {
+    /// period-explicit (e.g. `19970101T180000Z/19970102T070000Z`)
+    pub fn explicit(start: DateTime, end: DateTime) -> Result<Self, PeriodError> {
+        if start.is_utc() != end.is_utc() || start.time_zone() != end.time_zone() {
+            return Err(ErrorInner::MixedForms)?;
+        }
+        if end <= start {
+            return Err(ErrorInner::EndBeforeStart)?;
+        }
+        Ok(Self {
+            start,
+            end: PeriodEnd::DateTime(end),
+        })
+    }
+
+    /// period-start (e.g. `19970101T180000Z/PT5H30M`)
+    pub fn start(start: DateTime, duration: Duration) -> Result<Self, PeriodError> {
+        if duration.as_seconds() <= 0 {
+            return Err(ErrorInner::PositiveDurationRequired)?;
+        }
+        Ok(Self {
+            start,
+            end: PeriodEnd::Duration(duration),
+        })
+    }
+
+    pub fn start_date_time(&self) -> &DateTime {
+        &self.start
+    }
+
+    /// Returns the end of the period-explicit, or `None` for the period-start.
+    pub fn end_date_time(&self) -> Option<&DateTime> {
+        match &self.end {
+            PeriodEnd::DateTime(end) => Some(end),
+            PeriodEnd::Duration(_) => None,
+        }
+    }
+
+    /// Returns the duration of the period-start, or `None` for the period-explicit.
+    pub fn duration(&self) -> Option<&Duration> {
+        match &self.end {
+            PeriodEnd::DateTime(_) => None,
+            PeriodEnd::Duration(duration) => Some(duration),
+        }
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let mut s = String::new();
+        s.push_str(&self.start.to_escaped());
+        s.push('/');
+        s.push_str(&match &self.end {
+            PeriodEnd::DateTime(end) => end.to_escaped(),
+            PeriodEnd::Duration(duration) => duration.to_escaped(),
+        });
+        s
+    }
+}
+
+impl std::str::FromStr for Period {
+    type Err = PeriodError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('/').ok_or(ErrorInner::InvalidFormat)?;
+        let start = DateTime::try_from(start.to_owned()).map_err(ErrorInner::DateTime)?;
+        if end.starts_with(['+', '-', 'P']) {
+            Self::start(
+                start,
+                Duration::from_str(end).map_err(ErrorInner::Duration)?,
+            )
+        } else {
+            Self::explicit(
+                start,
+                DateTime::try_from(end.to_owned()).map_err(ErrorInner::DateTime)?,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Period>();
+
+        for s in [
+            "19970101T180000Z/19970102T070000Z",
+            "19970101T180000Z/PT5H30M",
+        ] {
+            assert_eq!(Period::from_str(s)?.to_escaped(), s);
+        }
+        let period = Period::from_str("19970101T180000Z/PT5H30M")?;
+        assert_eq!(
+            period.start_date_time(),
+            &DateTime::utc(1997, 1, 1, 18, 0, 0)?
+        );
+        assert_eq!(period.duration(), Some(&Duration::new(0, 5, 30, 0)?));
+        assert_eq!(period.end_date_time(), None);
+
+        for s in [
+            "",
+            "19970101T180000Z",
+            "19970101T180000Z/",
+            "19970101T180000Z/19970101T180000Z",
+            "19970102T070000Z/19970101T180000Z",
+            "19970101T180000Z/19970102T070000",
+            "19970101T180000Z/-PT5H",
+            "19970101T180000Z/PT0S",
+        ] {
+            assert!(Period::from_str(s).is_err(), "{s}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,514 @@
+use crate::i_calendar::value_type::DateTime;
+
+#[derive(Debug, thiserror::Error)]
+#[error("recur")]
+pub struct RecurError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("{0} is not allowed with FREQ={1}")]
+    Conflict(&'static str, &'static str),
+    #[error("duplicate rule part {0}")]
+    DuplicateRulePart(String),
+    #[error("FREQ required")]
+    FrequencyRequired,
+    #[error("invalid rule part {0}")]
+    InvalidRulePart(String),
+    #[error("invalid value {1} for {0}")]
+    InvalidValue(&'static str, String),
+    #[error("BYSETPOS requires another BYxxx rule part")]
+    SetPositionWithoutOtherRulePart,
+    #[error("UNTIL and COUNT MUST NOT occur in the same recur")]
+    UntilAndCount,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.10>
+///
+/// freq        = "SECONDLY" / "MINUTELY" / "HOURLY" / "DAILY"
+///             / "WEEKLY" / "MONTHLY" / "YEARLY"
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Frequency {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Secondly => "SECONDLY",
+            Self::Minutely => "MINUTELY",
+            Self::Hourly => "HOURLY",
+            Self::Daily => "DAILY",
+            Self::Weekly => "WEEKLY",
+            Self::Monthly => "MONTHLY",
+            Self::Yearly => "YEARLY",
+        }
+    }
+}
+
+impl std::str::FromStr for Frequency {
+    type Err = RecurError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "SECONDLY" => Self::Secondly,
+            "MINUTELY" => Self::Minutely,
+            "HOURLY" => Self::Hourly,
+            "DAILY" => Self::Daily,
+            "WEEKLY" => Self::Weekly,
+            "MONTHLY" => Self::Monthly,
+            "YEARLY" => Self::Yearly,
+            _ => return Err(ErrorInner::InvalidValue("FREQ", s.to_owned()))?,
+        })
+    }
+}
+
+/// weekday     = "SU" / "MO" / "TU" / "WE" / "TH" / "FR" / "SA"
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Weekday {
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+impl Weekday {
+    /// Returns the number of days from Sunday (`0..=6`).
+    pub(in crate::i_calendar) fn number_from_sunday(&self) -> u8 {
+        *self as u8
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sunday => "SU",
+            Self::Monday => "MO",
+            Self::Tuesday => "TU",
+            Self::Wednesday => "WE",
+            Self::Thursday => "TH",
+            Self::Friday => "FR",
+            Self::Saturday => "SA",
+        }
+    }
+}
+
+impl std::str::FromStr for Weekday {
+    type Err = RecurError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "SU" => Self::Sunday,
+            "MO" => Self::Monday,
+            "TU" => Self::Tuesday,
+            "WE" => Self::Wednesday,
+            "TH" => Self::Thursday,
+            "FR" => Self::Friday,
+            "SA" => Self::Saturday,
+            _ => return Err(ErrorInner::InvalidValue("weekday", s.to_owned()))?,
+        })
+    }
+}
+
+/// weekdaynum  = [[plus / minus] ordwk] weekday
+/// ordwk       = 1*2DIGIT       ;1 to 53
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct WeekdayNum {
+    ordwk: Option<i8>,
+    weekday: Weekday,
+}
+
+impl WeekdayNum {
+    pub fn new(ordwk: Option<i8>, weekday: Weekday) -> Result<Self, RecurError> {
+        if let Some(ordwk) = ordwk
+            && !(1..=53).contains(&ordwk.unsigned_abs())
+        {
+            return Err(ErrorInner::InvalidValue("ordwk", ordwk.to_string()))?;
+        }
+        Ok(Self { ordwk, weekday })
+    }
+
+    pub fn ordwk(&self) -> Option<i8> {
+        self.ordwk
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        self.weekday
+    }
+}
+
+impl std::str::FromStr for WeekdayNum {
+    type Err = RecurError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ordwk, weekday) = s
+            .split_at_checked(s.len().saturating_sub(2))
+            .ok_or_else(|| ErrorInner::InvalidValue("weekdaynum", s.to_owned()))?;
+        let ordwk = if ordwk.is_empty() {
+            None
+        } else {
+            Some(parse_int::<i8>("ordwk", ordwk)?)
+        };
+        Self::new(ordwk, weekday.parse()?)
+    }
+}
+
+impl std::fmt::Display for WeekdayNum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(ordwk) = self.ordwk {
+            write!(f, "{}", ordwk)?;
+        }
+        f.write_str(self.weekday.as_str())
+    }
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.10>
+///
+/// recur           = recur-rule-part *( ";" recur-rule-part )
+///                 ;
+///                 ; The rule parts are not ordered in any
+///                 ; particular sequence.
+///                 ;
+///                 ; The FREQ rule part is REQUIRED,
+///                 ; but MUST NOT occur more than once.
+///                 ;
+///                 ; The UNTIL or COUNT rule parts are OPTIONAL,
+///                 ; but they MUST NOT occur in the same 'recur'.
+///                 ;
+///                 ; The other rule parts are OPTIONAL,
+///                 ; but MUST NOT occur more than once.
+///
+/// recur-rule-part = ( "FREQ" "=" freq )
+///                 / ( "UNTIL" "=" enddate )
+///                 / ( "COUNT" "=" 1*DIGIT )
+///                 / ( "INTERVAL" "=" 1*DIGIT )
+///                 / ( "BYSECOND" "=" byseclist )
+///                 / ( "BYMINUTE" "=" byminlist )
+///                 / ( "BYHOUR" "=" byhrlist )
+///                 / ( "BYDAY" "=" bywdaylist )
+///                 / ( "BYMONTHDAY" "=" bymodaylist )
+///                 / ( "BYYEARDAY" "=" byyrdaylist )
+///                 / ( "BYWEEKNO" "=" bywknolist )
+///                 / ( "BYMONTH" "=" bymolist )
+///                 / ( "BYSETPOS" "=" bysplist )
+///                 / ( "WKST" "=" weekday )
+///
+/// enddate (UNTIL) supports DATE-TIME only
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Recur {
+    pub(in crate::i_calendar) freq: Frequency,
+    pub(in crate::i_calendar) until: Option<DateTime>,
+    pub(in crate::i_calendar) count: Option<u32>,
+    pub(in crate::i_calendar) interval: Option<u32>,
+    pub(in crate::i_calendar) bysecond: Vec<u8>,
+    pub(in crate::i_calendar) byminute: Vec<u8>,
+    pub(in crate::i_calendar) byhour: Vec<u8>,
+    pub(in crate::i_calendar) byday: Vec<WeekdayNum>,
+    pub(in crate::i_calendar) bymonthday: Vec<i8>,
+    pub(in crate::i_calendar) byyearday: Vec<i16>,
+    pub(in crate::i_calendar) byweekno: Vec<i8>,
+    pub(in crate::i_calendar) bymonth: Vec<u8>,
+    pub(in crate::i_calendar) bysetpos: Vec<i16>,
+    pub(in crate::i_calendar) wkst: Option<Weekday>,
+}
+
+impl Recur {
+    pub fn freq(&self) -> Frequency {
+        self.freq
+    }
+
+    pub fn until(&self) -> Option<&DateTime> {
+        self.until.as_ref()
+    }
+
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Returns the INTERVAL (default: `1`).
+    pub fn interval(&self) -> u32 {
+        self.interval.unwrap_or(1)
+    }
+
+    /// Returns the WKST (default: `MO`).
+    pub fn wkst(&self) -> Weekday {
+        self.wkst.unwrap_or(Weekday::Monday)
+    }
+
+    fn validate(self) -> Result<Self, RecurError> {
+        let freq = self.freq.as_str();
+        if self.until.is_some() && self.count.is_some() {
+            return Err(ErrorInner::UntilAndCount)?;
+        }
+        if !self.bysetpos.is_empty()
+            && self.bysecond.is_empty()
+            && self.byminute.is_empty()
+            && self.byhour.is_empty()
+            && self.byday.is_empty()
+            && self.bymonthday.is_empty()
+            && self.byyearday.is_empty()
+            && self.byweekno.is_empty()
+            && self.bymonth.is_empty()
+        {
+            return Err(ErrorInner::SetPositionWithoutOtherRulePart)?;
+        }
+        // The BYMONTHDAY rule part MUST NOT be specified when the FREQ rule part is set to WEEKLY.
+        if !self.bymonthday.is_empty() && self.freq == Frequency::Weekly {
+            return Err(ErrorInner::Conflict("BYMONTHDAY", freq))?;
+        }
+        // The BYYEARDAY rule part MUST NOT be specified when the FREQ rule part is set to DAILY,
+        // WEEKLY, or MONTHLY.
+        if !self.byyearday.is_empty()
+            && matches!(
+                self.freq,
+                Frequency::Daily | Frequency::Weekly | Frequency::Monthly
+            )
+        {
+            return Err(ErrorInner::Conflict("BYYEARDAY", freq))?;
+        }
+        // The BYWEEKNO rule part MUST NOT be used when the FREQ rule part is set to anything
+        // other than YEARLY.
+        if !self.byweekno.is_empty() && self.freq != Frequency::Yearly {
+            return Err(ErrorInner::Conflict("BYWEEKNO", freq))?;
+        }
+        // The BYDAY rule part MUST NOT be specified with a numeric value when the FREQ rule part
+        // is not set to MONTHLY or YEARLY. Furthermore, the BYDAY rule part MUST NOT be specified
+        // with a numeric value with the FREQ rule part set to YEARLY when the BYWEEKNO rule part
+        // is specified.
+        if self.byday.iter().any(|d| d.ordwk.is_some())
+            && (!matches!(self.freq, Frequency::Monthly | Frequency::Yearly)
+                || !self.byweekno.is_empty())
+        {
+            return Err(ErrorInner::Conflict("BYDAY with ordwk", freq))?;
+        }
+        Ok(self)
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        fn join<T: ToString>(values: &[T]) -> String {
+            values
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(",")
+        }
+
+        let mut parts = vec![format!("FREQ={}", self.freq.as_str())];
+        if let Some(until) = &self.until {
+            parts.push(format!("UNTIL={}", until.to_escaped()));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        if let Some(interval) = self.interval {
+            parts.push(format!("INTERVAL={}", interval));
+        }
+        for (name, values) in [
+            ("BYSECOND", join(&self.bysecond)),
+            ("BYMINUTE", join(&self.byminute)),
+            ("BYHOUR", join(&self.byhour)),
+            ("BYDAY", join(&self.byday)),
+            ("BYMONTHDAY", join(&self.bymonthday)),
+            ("BYYEARDAY", join(&self.byyearday)),
+            ("BYWEEKNO", join(&self.byweekno)),
+            ("BYMONTH", join(&self.bymonth)),
+            ("BYSETPOS", join(&self.bysetpos)),
+        ] {
+            if !values.is_empty() {
+                parts.push(format!("{}={}", name, values));
+            }
+        }
+        if let Some(wkst) = self.wkst {
+            parts.push(format!("WKST={}", wkst.as_str()));
+        }
+        parts.join(";")
+    }
+}
+
+impl std::str::FromStr for Recur {
+    type Err = RecurError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn list<T, F>(values: &str, f: F) -> Result<Vec<T>, RecurError>
+        where
+            F: Fn(&str) -> Result<T, RecurError>,
+        {
+            values
+                .split(',')
+                .map(f)
+                .collect::<Result<Vec<T>, RecurError>>()
+        }
+
+        fn signed(name: &'static str, value: &str, max: u16) -> Result<i16, RecurError> {
+            let n = parse_int::<i16>(name, value)?;
+            if !(1..=max).contains(&n.unsigned_abs()) {
+                return Err(ErrorInner::InvalidValue(name, value.to_owned()).into());
+            }
+            Ok(n)
+        }
+
+        fn unsigned(
+            name: &'static str,
+            value: &str,
+            range: std::ops::RangeInclusive<u8>,
+        ) -> Result<u8, RecurError> {
+            let n = parse_int::<u8>(name, value)?;
+            if !range.contains(&n) {
+                return Err(ErrorInner::InvalidValue(name, value.to_owned()).into());
+            }
+            Ok(n)
+        }
+
+        let mut freq = None;
+        let mut recur = Recur {
+            freq: Frequency::Yearly,
+            until: None,
+            count: None,
+            interval: None,
+            bysecond: vec![],
+            byminute: vec![],
+            byhour: vec![],
+            byday: vec![],
+            bymonthday: vec![],
+            byyearday: vec![],
+            byweekno: vec![],
+            bymonth: vec![],
+            bysetpos: vec![],
+            wkst: None,
+        };
+        let mut names = std::collections::BTreeSet::new();
+        for part in s.split(';') {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| ErrorInner::InvalidRulePart(part.to_owned()))?;
+            if !names.insert(name) {
+                return Err(ErrorInner::DuplicateRulePart(name.to_owned()))?;
+            }
+            match name {
+                "FREQ" => freq = Some(value.parse::<Frequency>()?),
+                "UNTIL" => {
+                    recur.until = Some(
+                        DateTime::try_from(value.to_owned())
+                            .map_err(|_| ErrorInner::InvalidValue("UNTIL", value.to_owned()))?,
+                    )
+                }
+                "COUNT" => recur.count = Some(parse_int::<u32>("COUNT", value)?),
+                "INTERVAL" => {
+                    let interval = parse_int::<u32>("INTERVAL", value)?;
+                    if interval == 0 {
+                        return Err(ErrorInner::InvalidValue("INTERVAL", value.to_owned()))?;
+                    }
+                    recur.interval = Some(interval);
+                }
+                // The "60" value for seconds is used to account for positive "leap" seconds.
+                "BYSECOND" => recur.bysecond = list(value, |v| unsigned("BYSECOND", v, 0..=60))?,
+                "BYMINUTE" => recur.byminute = list(value, |v| unsigned("BYMINUTE", v, 0..=59))?,
+                "BYHOUR" => recur.byhour = list(value, |v| unsigned("BYHOUR", v, 0..=23))?,
+                "BYDAY" => recur.byday = list(value, str::parse::<WeekdayNum>)?,
+                "BYMONTHDAY" => {
+                    recur.bymonthday =
+                        list(value, |v| signed("BYMONTHDAY", v, 31).map(|n| n as i8))?
+                }
+                "BYYEARDAY" => recur.byyearday = list(value, |v| signed("BYYEARDAY", v, 366))?,
+                "BYWEEKNO" => {
+                    recur.byweekno = list(value, |v| signed("BYWEEKNO", v, 53).map(|n| n as i8))?
+                }
+                "BYMONTH" => recur.bymonth = list(value, |v| unsigned("BYMONTH", v, 1..=12))?,
+                "BYSETPOS" => recur.bysetpos = list(value, |v| signed("BYSETPOS", v, 366))?,
+                "WKST" => recur.wkst = Some(value.parse::<Weekday>()?),
+                _ => return Err(ErrorInner::InvalidRulePart(part.to_owned()))?,
+            }
+        }
+        recur.freq = freq.ok_or(ErrorInner::FrequencyRequired)?;
+        recur.validate()
+    }
+}
+
+fn parse_int<T: std::str::FromStr>(name: &'static str, value: &str) -> Result<T, RecurError> {
+    // 1*DIGIT (with an optional sign)
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ErrorInner::InvalidValue(name, value.to_owned()).into());
+    }
+    value
+        .strip_prefix('+')
+        .unwrap_or(value)
+        .parse::<T>()
+        .map_err(|_| ErrorInner::InvalidValue(name, value.to_owned()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + PartialEq>() {}
+        assert_fn::<Recur>();
+
+        for s in [
+            "FREQ=DAILY;COUNT=10",
+            "FREQ=DAILY;UNTIL=19971224T000000Z",
+            "FREQ=DAILY;INTERVAL=2",
+            "FREQ=WEEKLY;UNTIL=19971007T000000Z;BYDAY=TU,TH;WKST=SU",
+            "FREQ=MONTHLY;COUNT=10;BYDAY=1FR",
+            "FREQ=MONTHLY;COUNT=10;INTERVAL=2;BYDAY=1SU,-1SU",
+            "FREQ=MONTHLY;BYMONTHDAY=-3",
+            "FREQ=YEARLY;COUNT=10;INTERVAL=3;BYYEARDAY=1,100,200",
+            "FREQ=YEARLY;BYDAY=MO;BYWEEKNO=20",
+            "FREQ=YEARLY;BYDAY=TH;BYMONTH=3",
+            "FREQ=MONTHLY;COUNT=3;BYDAY=TU,WE,TH;BYSETPOS=3",
+            "FREQ=MINUTELY;INTERVAL=20;BYHOUR=9,10,11,12,13,14,15,16",
+            "FREQ=HOURLY;UNTIL=19970902T170000Z;INTERVAL=3",
+            "FREQ=SECONDLY;BYSECOND=0,60",
+        ] {
+            assert_eq!(Recur::from_str(s)?.to_escaped(), s);
+        }
+
+        // The rule parts are not ordered in any particular sequence.
+        let recur = Recur::from_str("BYDAY=-1SU;FREQ=YEARLY;BYMONTH=10")?;
+        assert_eq!(recur.freq(), Frequency::Yearly);
+        assert_eq!(recur.interval(), 1);
+        assert_eq!(recur.wkst(), Weekday::Monday);
+        assert_eq!(recur.to_escaped(), "FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10");
+        assert_eq!(
+            WeekdayNum::from_str("+2MO")?,
+            WeekdayNum::new(Some(2), Weekday::Monday)?
+        );
+
+        for s in [
+            "",
+            "COUNT=10",
+            "FREQ=FORTNIGHTLY",
+            "FREQ=DAILY;FREQ=DAILY",
+            "FREQ=DAILY;COUNT=10;UNTIL=19971224T000000Z",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;COUNT=-1",
+            "FREQ=DAILY;BYHOUR=24",
+            "FREQ=DAILY;BYMONTH=0",
+            "FREQ=MONTHLY;BYMONTHDAY=0",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=MONTHLY;BYDAY=XX",
+            "FREQ=MONTHLY;BYSETPOS=1",
+            "FREQ=WEEKLY;BYMONTHDAY=1",
+            "FREQ=MONTHLY;BYYEARDAY=1",
+            "FREQ=MONTHLY;BYWEEKNO=1",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=YEARLY;BYWEEKNO=1;BYDAY=1MO",
+            "FREQ=DAILY;UNTIL=19971224",
+            "FREQ=DAILY;X-NAME=1",
+        ] {
+            assert!(Recur::from_str(s).is_err(), "{s}");
+        }
+        Ok(())
+    }
+}
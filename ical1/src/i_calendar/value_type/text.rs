@@ -21,6 +21,12 @@ pub struct Text(String);
 
 impl Text {
     pub(in crate::i_calendar) fn from_string(s: String) -> Result<Self, TextError> {
+        let mut iter = s.chars();
+        while let Some(c) = iter.next() {
+            if c == '\\' && !matches!(iter.next(), Some('\\' | ';' | ',' | 'n' | 'N')) {
+                return Err(TextError { _private: () });
+            }
+        }
         Ok(Self(s))
     }
 
@@ -135,6 +141,8 @@ mod tests {
             Text::from_string(escaped.to_owned())?.into_string(),
             escaped
         );
+        assert!(Text::from_string("\\".to_owned()).is_err());
+        assert!(Text::from_string("\\x".to_owned()).is_err());
         Ok(())
     }
 }
@@ -0,0 +1,105 @@
+#[derive(Debug, thiserror::Error)]
+#[error("utc-offset")]
+pub struct UtcOffsetError(#[from] ErrorInner);
+
+#[derive(Debug, thiserror::Error)]
+enum ErrorInner {
+    #[error("invalid format")]
+    InvalidFormat,
+    #[error("negative zero")]
+    NegativeZero,
+    #[error("out of range")]
+    OutOfRange,
+}
+
+/// <https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.14>
+///
+/// utc-offset = time-numzone
+/// time-numzone = ("+" / "-") time-hour time-minute [time-second]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct UtcOffset(i32);
+
+impl UtcOffset {
+    /// Creates a UTC offset from seconds east of UTC (e.g. `32400` for `+0900`).
+    pub fn from_seconds(seconds: i32) -> Result<Self, UtcOffsetError> {
+        if seconds.unsigned_abs() >= 24 * 60 * 60 {
+            return Err(ErrorInner::OutOfRange)?;
+        }
+        Ok(Self(seconds))
+    }
+
+    /// Returns seconds east of UTC.
+    pub fn as_seconds(&self) -> i32 {
+        self.0
+    }
+
+    pub(in crate::i_calendar) fn to_escaped(&self) -> String {
+        let sign = if self.0 < 0 { '-' } else { '+' };
+        let abs = self.0.unsigned_abs();
+        let (hour, minute, second) = (abs / 3600, abs / 60 % 60, abs % 60);
+        if second == 0 {
+            format!("{sign}{hour:02}{minute:02}")
+        } else {
+            format!("{sign}{hour:02}{minute:02}{second:02}")
+        }
+    }
+}
+
+impl std::str::FromStr for UtcOffset {
+    type Err = UtcOffsetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (sign, digits) = match s.split_at_checked(1) {
+            Some(("+", digits)) => (1, digits),
+            Some(("-", digits)) => (-1, digits),
+            _ => return Err(ErrorInner::InvalidFormat)?,
+        };
+        if !(digits.len() == 4 || digits.len() == 6) || !digits.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(ErrorInner::InvalidFormat)?;
+        }
+        let n = |r: std::ops::Range<usize>| digits[r].parse::<i32>().expect("digits");
+        let (hour, minute, second) = (
+            n(0..2),
+            n(2..4),
+            if digits.len() == 6 { n(4..6) } else { 0 },
+        );
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(ErrorInner::OutOfRange)?;
+        }
+        let seconds = hour * 3600 + minute * 60 + second;
+        // The value of "-0000", "-000000" is not allowed.
+        if sign < 0 && seconds == 0 {
+            return Err(ErrorInner::NegativeZero)?;
+        }
+        Self::from_seconds(sign * seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        fn assert_fn<T: Clone + Eq + Ord + PartialEq + PartialOrd>() {}
+        assert_fn::<UtcOffset>();
+
+        for s in ["-0500", "+0100", "+0000", "+0530", "-033045"] {
+            assert_eq!(UtcOffset::from_str(s)?.to_escaped(), s);
+        }
+        assert_eq!(UtcOffset::from_str("+0900")?.as_seconds(), 9 * 60 * 60);
+        assert_eq!(UtcOffset::from_str("+090000")?.to_escaped(), "+0900");
+        assert_eq!(UtcOffset::from_seconds(-18000)?.to_escaped(), "-0500");
+
+        for s in [
+            "", "0900", "+900", "+09000", "+2400", "+0960", "-0000", "-000000",
+        ] {
+            assert!(UtcOffset::from_str(s).is_err(), "{s}");
+        }
+        assert!(UtcOffset::from_seconds(24 * 60 * 60).is_err());
+        Ok(())
+    }
+}
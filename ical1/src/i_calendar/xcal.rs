@@ -0,0 +1,231 @@
+//! xCal: The XML Format for iCalendar
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc6321>
+
+use crate::i_calendar::{
+    calendar_components::Event,
+    i_calendar_object::{ICalendarObject, ICalendarStream},
+    structured::{Component, Property, Value, is_duration},
+};
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n";
+
+const NAMESPACE: &str = "urn:ietf:params:xml:ns:icalendar-2.0";
+
+impl ICalendarStream {
+    /// Returns the xCal document with the "icalendar" element containing the "vcalendar"
+    /// elements.
+    pub fn to_xcal(&self) -> String {
+        document(&self.to_content_lines())
+    }
+}
+
+impl ICalendarObject {
+    /// Returns the xCal document with the "icalendar" element containing the "vcalendar"
+    /// element.
+    pub fn to_xcal(&self) -> String {
+        document(&self.to_content_lines())
+    }
+}
+
+impl Event {
+    /// Returns the xCal "vevent" element (without the XML declaration and the namespace).
+    pub fn to_xcal(&self) -> String {
+        let mut s = String::new();
+        for component in components(&self.clone().into_string()) {
+            write_component(&mut s, &component);
+        }
+        s
+    }
+}
+
+fn components(content_lines: &str) -> Vec<Component> {
+    // the content lines are serialized by this crate
+    Component::from_content_lines(content_lines).expect("valid content lines")
+}
+
+fn document(content_lines: &str) -> String {
+    let mut s = String::new();
+    s.push_str(XML_DECLARATION);
+    s.push_str(&format!("<icalendar xmlns=\"{NAMESPACE}\">"));
+    for component in components(content_lines) {
+        write_component(&mut s, &component);
+    }
+    s.push_str("</icalendar>\n");
+    s
+}
+
+fn write_component(s: &mut String, component: &Component) {
+    start(s, &component.name);
+    if !component.properties.is_empty() {
+        start(s, "properties");
+        for property in &component.properties {
+            write_property(s, property);
+        }
+        end(s, "properties");
+    }
+    if !component.components.is_empty() {
+        start(s, "components");
+        for component in &component.components {
+            write_component(s, component);
+        }
+        end(s, "components");
+    }
+    end(s, &component.name);
+}
+
+fn write_property(s: &mut String, property: &Property) {
+    start(s, &property.name);
+    if !property.params.is_empty() {
+        start(s, "parameters");
+        for (name, values) in &property.params {
+            start(s, name);
+            let value_type = param_value_type(name);
+            for value in values {
+                match value_type {
+                    "boolean" => element(s, value_type, &value.to_ascii_lowercase()),
+                    _ => element(s, value_type, value),
+                }
+            }
+            end(s, name);
+        }
+        end(s, "parameters");
+    }
+    for value in &property.values {
+        write_value(s, &property.value_type, value);
+    }
+    end(s, &property.name);
+}
+
+fn write_value(s: &mut String, value_type: &str, value: &Value) {
+    match value {
+        Value::Boolean(b) => element(s, "boolean", if *b { "true" } else { "false" }),
+        Value::Float(f) => element(s, "float", f),
+        Value::Geo(latitude, longitude) => {
+            element(s, "latitude", latitude);
+            element(s, "longitude", longitude);
+        }
+        Value::Integer(i) => element(s, "integer", &i.to_string()),
+        Value::Period(start_value, end_value) => {
+            start(s, "period");
+            element(s, "start", start_value);
+            if is_duration(end_value) {
+                element(s, "duration", end_value);
+            } else {
+                element(s, "end", end_value);
+            }
+            end(s, "period");
+        }
+        Value::Recur(parts) => {
+            start(s, "recur");
+            // "freq" element first
+            let mut parts = parts.iter().collect::<Vec<&(String, Vec<String>)>>();
+            parts.sort_by_key(|(name, _)| name != "freq");
+            for (name, values) in parts {
+                for value in values {
+                    element(s, name, value);
+                }
+            }
+            end(s, "recur");
+        }
+        Value::Text(text) => element(s, value_type, text),
+    }
+}
+
+/// Returns the value type of the parameter.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc6321#section-3.5>
+fn param_value_type(name: &str) -> &'static str {
+    match name {
+        "altrep" | "dir" => "uri",
+        "delegated-from" | "delegated-to" | "member" | "sent-by" => "cal-address",
+        "rsvp" => "boolean",
+        _ => "text",
+    }
+}
+
+fn start(s: &mut String, name: &str) {
+    s.push('<');
+    s.push_str(name);
+    s.push('>');
+}
+
+fn end(s: &mut String, name: &str) {
+    s.push_str("</");
+    s.push_str(name);
+    s.push('>');
+}
+
+fn element(s: &mut String, name: &str, text: &str) {
+    start(s, name);
+    for c in text.chars() {
+        match c {
+            '&' => s.push_str("&amp;"),
+            '<' => s.push_str("&lt;"),
+            '>' => s.push_str("&gt;"),
+            c => s.push(c),
+        }
+    }
+    end(s, name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_property() {
+        let mut s = String::new();
+        write_property(
+            &mut s,
+            &Property {
+                name: "attendee".to_owned(),
+                params: vec![
+                    ("rsvp".to_owned(), vec!["TRUE".to_owned()]),
+                    ("cn".to_owned(), vec!["A & B".to_owned()]),
+                ],
+                value_type: "cal-address".to_owned(),
+                values: vec![Value::Text("mailto:a@example.com".to_owned())],
+            },
+        );
+        assert_eq!(
+            s,
+            [
+                "<attendee><parameters>",
+                "<rsvp><boolean>true</boolean></rsvp>",
+                "<cn><text>A &amp; B</text></cn>",
+                "</parameters>",
+                "<cal-address>mailto:a@example.com</cal-address>",
+                "</attendee>",
+            ]
+            .join("")
+        );
+
+        let mut s = String::new();
+        write_property(
+            &mut s,
+            &Property {
+                name: "freebusy".to_owned(),
+                params: vec![],
+                value_type: "period".to_owned(),
+                values: vec![
+                    Value::Period("1997-03-08T16:00:00Z".to_owned(), "PT8H30M".to_owned()),
+                    Value::Period(
+                        "1997-03-08T16:00:00Z".to_owned(),
+                        "1997-03-08T17:00:00Z".to_owned(),
+                    ),
+                ],
+            },
+        );
+        assert_eq!(
+            s,
+            [
+                "<freebusy>",
+                "<period><start>1997-03-08T16:00:00Z</start><duration>PT8H30M</duration></period>",
+                "<period><start>1997-03-08T16:00:00Z</start><end>1997-03-08T17:00:00Z</end></period>",
+                "</freebusy>",
+            ]
+            .join("")
+        );
+    }
+}
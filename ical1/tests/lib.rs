@@ -99,3 +99,1146 @@ fn test_i_calendar_stream_minimum() -> Result<(), ical1::Error> {
     );
     Ok(())
 }
+
+#[test]
+fn test_i_calendar_stream_parse() -> Result<(), ical1::Error> {
+    use ical1::{
+        CalendarScale, Categories, Classification, DateTimeEnd, DateTimeStamp, DateTimeStart,
+        Event, ICalendarObject, ICalendarStream, Method, ProductIdentifier, Summary, Text,
+        UniqueIdentifier, Version,
+    };
+    use std::str::FromStr as _;
+
+    let i_calendar_stream = ICalendarStream::builder()
+        .add_object(
+            ICalendarObject::builder()
+                .prodid(ProductIdentifier::new(Text::from_unescaped(
+                    "-//ABC Corporation//NONSGML My Product//EN",
+                )?)?)
+                .version(Version::new(Text::from_str("2.0")?)?)
+                .calscale(CalendarScale::new(Text::from_str("GREGORIAN")?)?)
+                .method(Method::new(Text::from_str("PUBLISH")?)?)
+                .add_component(
+                    Event::builder()
+                        .uid(UniqueIdentifier::from_value(
+                            "19970901T130000Z-123401@example.com",
+                        )?)
+                        .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+                        .dtstart(DateTimeStart::from_value("19970903T163000Z")?)
+                        .dtend(DateTimeEnd::from_value("19970903T190000Z")?)
+                        .summary(Summary::from_value(
+                            "Annual Employee Review\\, with a summary long enough to be folded",
+                        )?)
+                        .class(Classification::from_value("PRIVATE")?)
+                        .add_categories(Categories::from_value("BUSINESS,HUMAN RESOURCES")?)
+                        .build()?,
+                )
+                .build()?,
+        )
+        .build()?;
+    assert_eq!(
+        ICalendarStream::from_str(&i_calendar_stream.to_string())?,
+        i_calendar_stream
+    );
+    assert_eq!(
+        i_calendar_stream.to_string().parse::<ICalendarStream>()?,
+        i_calendar_stream
+    );
+    Ok(())
+}
+
+#[test]
+fn test_i_calendar_stream_time_zone_reference() -> Result<(), ical1::Error> {
+    use ical1::{
+        DateTime, DateTimeEnd, DateTimeStamp, DateTimeStart, Event, ICalendarObject,
+        ICalendarStream, Observance, ProductIdentifier, Text, TimeZone, TimeZoneIdentifier,
+        TimeZoneName, TimeZoneOffsetFrom, TimeZoneOffsetTo, UniqueIdentifier, UtcOffset, Version,
+    };
+    use std::str::FromStr as _;
+
+    fn time_zone(tzid: &TimeZoneIdentifier) -> Option<TimeZone> {
+        let build = || -> Result<TimeZone, ical1::Error> {
+            Ok(TimeZone::builder()
+                .tzid(tzid.clone())
+                .add_observance(
+                    Observance::standard()
+                        .dtstart(DateTimeStart::from_value("19700101T000000")?)
+                        .tzoffsetfrom(TimeZoneOffsetFrom::new(UtcOffset::from_str("+0900")?)?)
+                        .tzoffsetto(TimeZoneOffsetTo::new(UtcOffset::from_str("+0900")?)?)
+                        .add_tzname(TimeZoneName::new(Text::from_unescaped("JST")?)?)
+                        .build()?,
+                )
+                .build()?)
+        };
+        (tzid.to_unescaped() == "Asia/Tokyo").then(|| build().unwrap())
+    }
+
+    let tzid = TimeZoneIdentifier::from_unescaped("Asia/Tokyo")?;
+    let event = Event::builder()
+        .uid(UniqueIdentifier::from_value(
+            "c2b4ad47-168c-4a3b-81e7-e06bf5fd3091",
+        )?)
+        .dtstamp(DateTimeStamp::from_value("20250309T120000Z")?)
+        .dtstart(DateTimeStart::new(
+            DateTime::local(2025, 3, 9, 21, 30, 0)?.with_time_zone(tzid.clone())?,
+        )?)
+        .dtend(DateTimeEnd::new(
+            DateTime::local(2025, 3, 9, 22, 0, 0)?.with_time_zone(tzid.clone())?,
+        )?)
+        .build()?;
+
+    // VTIMEZONE required
+    assert!(
+        ICalendarObject::builder()
+            .prodid(ProductIdentifier::new(Text::from_unescaped(
+                "-//ABC Corporation//NONSGML My Product//EN",
+            )?)?)
+            .version(Version::new(Text::from_unescaped("2.0")?)?)
+            .add_component(event.clone())
+            .build()
+            .is_err()
+    );
+
+    let i_calendar_stream = ICalendarStream::builder()
+        .add_object(
+            ICalendarObject::builder()
+                .prodid(ProductIdentifier::new(Text::from_unescaped(
+                    "-//ABC Corporation//NONSGML My Product//EN",
+                )?)?)
+                .version(Version::new(Text::from_unescaped("2.0")?)?)
+                .add_component(event)
+                .add_time_zones(time_zone)
+                .build()?,
+        )
+        .build()?;
+    assert_eq!(
+        i_calendar_stream.to_string(),
+        [
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+            "VERSION:2.0\r\n",
+            "BEGIN:VTIMEZONE\r\n",
+            "TZID:Asia/Tokyo\r\n",
+            "BEGIN:STANDARD\r\n",
+            "DTSTART:19700101T000000\r\n",
+            "TZOFFSETFROM:+0900\r\n",
+            "TZOFFSETTO:+0900\r\n",
+            "TZNAME:JST\r\n",
+            "END:STANDARD\r\n",
+            "END:VTIMEZONE\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:c2b4ad47-168c-4a3b-81e7-e06bf5fd3091\r\n",
+            "DTSTAMP:20250309T120000Z\r\n",
+            "DTSTART;TZID=Asia/Tokyo:20250309T213000\r\n",
+            "DTEND;TZID=Asia/Tokyo:20250309T220000\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n"
+        ]
+        .join("")
+    );
+    assert_eq!(
+        ICalendarStream::from_str(&i_calendar_stream.to_string())?,
+        i_calendar_stream
+    );
+    Ok(())
+}
+
+#[test]
+fn test_i_calendar_stream_recurrence() -> Result<(), ical1::Error> {
+    use ical1::{
+        DateTime, DateTimeStamp, DateTimeStart, Event, ExceptionDateTimes, ICalendarObject,
+        ICalendarStream, ProductIdentifier, Recur, RecurrenceDateTimes, RecurrenceRule, Text,
+        UniqueIdentifier, Version,
+    };
+    use std::str::FromStr as _;
+
+    let event = Event::builder()
+        .uid(UniqueIdentifier::from_value(
+            "19970901T130000Z-123404@example.com",
+        )?)
+        .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+        .dtstart(DateTimeStart::from_value("19970905T090000Z")?)
+        .rrule(RecurrenceRule::new(Recur::from_str(
+            "FREQ=MONTHLY;UNTIL=19971231T000000Z;BYDAY=1FR",
+        )?)?)
+        .build()?;
+    assert_eq!(
+        event
+            .occurrences(
+                &DateTime::utc(1997, 1, 1, 0, 0, 0)?,
+                &DateTime::utc(1998, 1, 1, 0, 0, 0)?
+            )
+            .map(String::from)
+            .collect::<Vec<String>>(),
+        [
+            "19970905T090000Z",
+            "19971003T090000Z",
+            "19971107T090000Z",
+            "19971205T090000Z"
+        ]
+    );
+
+    let s = [
+        "BEGIN:VCALENDAR\r\n",
+        "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+        "VERSION:2.0\r\n",
+        "BEGIN:VEVENT\r\n",
+        "UID:19970901T130000Z-123404@example.com\r\n",
+        "DTSTAMP:19970901T130000Z\r\n",
+        "DTSTART:19970905T090000Z\r\n",
+        "RRULE:FREQ=MONTHLY;UNTIL=19971231T000000Z;BYDAY=1FR\r\n",
+        "EXDATE:19971003T090000Z\r\n",
+        "RDATE:19971010T090000Z,19971017T090000Z\r\n",
+        "END:VEVENT\r\n",
+        "END:VCALENDAR\r\n",
+    ]
+    .join("");
+    let i_calendar_stream = ICalendarStream::from_str(&s)?;
+    assert_eq!(i_calendar_stream.to_string(), s);
+    assert_eq!(
+        i_calendar_stream,
+        ICalendarStream::builder()
+            .add_object(
+                ICalendarObject::builder()
+                    .prodid(ProductIdentifier::new(Text::from_unescaped(
+                        "-//ABC Corporation//NONSGML My Product//EN",
+                    )?)?)
+                    .version(Version::new(Text::from_str("2.0")?)?)
+                    .add_component(
+                        Event::builder()
+                            .uid(UniqueIdentifier::from_value(
+                                "19970901T130000Z-123404@example.com",
+                            )?)
+                            .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+                            .dtstart(DateTimeStart::from_value("19970905T090000Z")?)
+                            .rrule(RecurrenceRule::new(Recur::from_str(
+                                "FREQ=MONTHLY;UNTIL=19971231T000000Z;BYDAY=1FR",
+                            )?)?)
+                            .add_exdate(ExceptionDateTimes::new(vec![DateTime::utc(
+                                1997, 10, 3, 9, 0, 0
+                            )?])?)
+                            .add_rdate(RecurrenceDateTimes::new(vec![
+                                DateTime::utc(1997, 10, 10, 9, 0, 0)?,
+                                DateTime::utc(1997, 10, 17, 9, 0, 0)?,
+                            ])?)
+                            .build()?,
+                    )
+                    .build()?,
+            )
+            .build()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_i_calendar_stream_todo_journal_free_busy() -> Result<(), ical1::Error> {
+    use ical1::{
+        DateTime, DateTimeDue, DateTimeStamp, Description, Duration, FreeBusy, FreeBusyTime,
+        FreeBusyTimeType, ICalendarObject, ICalendarStream, Journal, Period, ProductIdentifier,
+        Status, StatusValue, Summary, Text, Todo, UniqueIdentifier, Version,
+    };
+    use std::str::FromStr as _;
+
+    let i_calendar_stream = ICalendarStream::builder()
+        .add_object(
+            ICalendarObject::builder()
+                .prodid(ProductIdentifier::new(Text::from_unescaped(
+                    "-//ABC Corporation//NONSGML My Product//EN",
+                )?)?)
+                .version(Version::new(Text::from_str("2.0")?)?)
+                .add_component(
+                    Todo::builder()
+                        .uid(UniqueIdentifier::from_value(
+                            "20070313T123432Z-456553@example.com",
+                        )?)
+                        .dtstamp(DateTimeStamp::from_value("20070313T123432Z")?)
+                        .due(DateTimeDue::new(DateTime::utc(2007, 5, 1, 0, 0, 0)?)?)
+                        .summary(Summary::from_value(
+                            "Submit Quebec Income Tax Return for 2006",
+                        )?)
+                        .status(Status::new(StatusValue::NeedsAction)?)
+                        .build()?,
+                )
+                .add_component(
+                    Journal::builder()
+                        .uid(UniqueIdentifier::from_value(
+                            "19970901T130000Z-123405@example.com",
+                        )?)
+                        .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+                        .summary(Summary::from_value("Staff meeting minutes")?)
+                        .add_description(Description::new(Text::from_unescaped(
+                            "1. Staff meeting: Participants include Joe, Lisa, and Bob.",
+                        )?)?)
+                        .status(Status::new(StatusValue::Draft)?)
+                        .build()?,
+                )
+                .add_component(
+                    FreeBusy::builder()
+                        .uid(UniqueIdentifier::from_value(
+                            "19970901T115957Z-76A912@example.com",
+                        )?)
+                        .dtstamp(DateTimeStamp::from_value("19970901T120000Z")?)
+                        .add_freebusy(FreeBusyTime::new(
+                            Some(FreeBusyTimeType::BusyTentative),
+                            vec![
+                                Period::from_str("19980314T233000Z/19980315T003000Z")?,
+                                Period::start(
+                                    DateTime::utc(1998, 3, 16, 15, 30, 0)?,
+                                    Duration::new(0, 1, 0, 0)?,
+                                )?,
+                            ],
+                        )?)
+                        .build()?,
+                )
+                .build()?,
+        )
+        .build()?;
+    assert_eq!(
+        i_calendar_stream.to_string(),
+        [
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+            "VERSION:2.0\r\n",
+            "BEGIN:VTODO\r\n",
+            "UID:20070313T123432Z-456553@example.com\r\n",
+            "DTSTAMP:20070313T123432Z\r\n",
+            "DUE:20070501T000000Z\r\n",
+            "SUMMARY:Submit Quebec Income Tax Return for 2006\r\n",
+            "STATUS:NEEDS-ACTION\r\n",
+            "END:VTODO\r\n",
+            "BEGIN:VJOURNAL\r\n",
+            "UID:19970901T130000Z-123405@example.com\r\n",
+            "DTSTAMP:19970901T130000Z\r\n",
+            "SUMMARY:Staff meeting minutes\r\n",
+            "DESCRIPTION:1. Staff meeting: Participants include Joe\\, Lisa\\, and Bob.\r\n",
+            "STATUS:DRAFT\r\n",
+            "END:VJOURNAL\r\n",
+            "BEGIN:VFREEBUSY\r\n",
+            "UID:19970901T115957Z-76A912@example.com\r\n",
+            "DTSTAMP:19970901T120000Z\r\n",
+            "FREEBUSY;FBTYPE=BUSY-TENTATIVE:19980314T233000Z/19980315T003000Z,19980316T1\r\n",
+            " 53000Z/PT1H\r\n",
+            "END:VFREEBUSY\r\n",
+            "END:VCALENDAR\r\n",
+        ]
+        .join("")
+    );
+    assert_eq!(
+        ICalendarStream::from_str(&i_calendar_stream.to_string())?,
+        i_calendar_stream
+    );
+
+    // DUE and DURATION are mutually exclusive
+    assert!(
+        ICalendarStream::from_str(
+            &[
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "BEGIN:VTODO\r\n",
+                "UID:20070313T123432Z-456553@example.com\r\n",
+                "DTSTAMP:20070313T123432Z\r\n",
+                "DTSTART:20070313T123432Z\r\n",
+                "DUE:20070501T000000Z\r\n",
+                "DURATION:P1D\r\n",
+                "END:VTODO\r\n",
+                "END:VCALENDAR\r\n",
+            ]
+            .join("")
+        )
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_i_calendar_stream_alarm() -> Result<(), ical1::Error> {
+    use ical1::{
+        Action, ActionValue, Alarm, AlarmTriggerRelationship, Attendee, CalendarUserAddress,
+        DateTime, DateTimeStamp, DateTimeStart, Description, Duration, DurationProperty, Event,
+        ICalendarObject, ICalendarStream, ProductIdentifier, RepeatCount, Summary, Text, Trigger,
+        UniqueIdentifier, Uri, Version,
+    };
+    use std::str::FromStr as _;
+
+    let i_calendar_stream = ICalendarStream::builder()
+        .add_object(
+            ICalendarObject::builder()
+                .prodid(ProductIdentifier::new(Text::from_unescaped(
+                    "-//ABC Corporation//NONSGML My Product//EN",
+                )?)?)
+                .version(Version::new(Text::from_str("2.0")?)?)
+                .add_component(
+                    Event::builder()
+                        .uid(UniqueIdentifier::from_value(
+                            "19970901T130000Z-123401@example.com",
+                        )?)
+                        .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+                        .dtstart(DateTimeStart::from_value("19970903T163000Z")?)
+                        .summary(Summary::from_value("Annual Employee Review")?)
+                        .add_alarm(
+                            Alarm::builder()
+                                .action(Action::new(ActionValue::Display)?)
+                                .trigger(Trigger::relative(Duration::from_str("-PT30M")?, None)?)
+                                .duration(DurationProperty::new(Duration::new(0, 0, 15, 0)?)?)
+                                .repeat(RepeatCount::new(2)?)
+                                .description(Description::new(Text::from_unescaped(
+                                    "Breakfast meeting with executive team at 8:30 AM EST.",
+                                )?)?)
+                                .build()?,
+                        )
+                        .add_alarm(
+                            Alarm::builder()
+                                .action(Action::new(ActionValue::Email)?)
+                                .trigger(Trigger::relative(
+                                    Duration::from_str("-P2D")?,
+                                    Some(AlarmTriggerRelationship::End),
+                                )?)
+                                .summary(Summary::from_value(
+                                    "*** REMINDER: SEND AGENDA FOR WEEKLY STAFF MEETING ***",
+                                )?)
+                                .description(Description::new(Text::from_unescaped(
+                                    "A draft agenda needs to be sent out to the attendees.",
+                                )?)?)
+                                .add_attendee(Attendee::new(CalendarUserAddress::new(
+                                    Uri::from_str("mailto:john_doe@example.com")?,
+                                )?)?)
+                                .build()?,
+                        )
+                        .build()?,
+                )
+                .build()?,
+        )
+        .build()?;
+    let s = i_calendar_stream.to_string();
+    assert!(
+        s.contains(
+            &[
+                "BEGIN:VALARM\r\n",
+                "ACTION:DISPLAY\r\n",
+                "TRIGGER:-PT30M\r\n",
+                "DURATION:PT15M\r\n",
+                "REPEAT:2\r\n",
+                "DESCRIPTION:Breakfast meeting with executive team at 8:30 AM EST.\r\n",
+                "END:VALARM\r\n",
+                "BEGIN:VALARM\r\n",
+                "ACTION:EMAIL\r\n",
+                "TRIGGER;RELATED=END:-P2D\r\n",
+            ]
+            .join("")
+        )
+    );
+    assert!(s.contains("ATTENDEE:mailto:john_doe@example.com\r\n"));
+    assert_eq!(ICalendarStream::from_str(&s)?, i_calendar_stream);
+
+    // absolute triggers must be UTC
+    assert!(Trigger::absolute(DateTime::local(1997, 3, 17, 13, 30, 0)?).is_err());
+
+    // DURATION and REPEAT must occur together
+    assert!(
+        Alarm::builder()
+            .action(Action::new(ActionValue::Audio)?)
+            .trigger(Trigger::absolute(DateTime::utc(1997, 3, 17, 13, 30, 0)?)?)
+            .repeat(RepeatCount::new(4)?)
+            .build()
+            .is_err()
+    );
+
+    // DISPLAY requires DESCRIPTION
+    assert!(
+        ICalendarStream::from_str(
+            &[
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "BEGIN:VEVENT\r\n",
+                "UID:19970901T130000Z-123401@example.com\r\n",
+                "DTSTAMP:19970901T130000Z\r\n",
+                "DTSTART:19970903T163000Z\r\n",
+                "BEGIN:VALARM\r\n",
+                "ACTION:DISPLAY\r\n",
+                "TRIGGER:-PT30M\r\n",
+                "END:VALARM\r\n",
+                "END:VEVENT\r\n",
+                "END:VCALENDAR\r\n",
+            ]
+            .join("")
+        )
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_i_calendar_stream_non_standard() -> Result<(), ical1::Error> {
+    use ical1::{
+        DateTimeStamp, DateTimeStart, Event, ICalendarObject, ICalendarStream, IanaComponent,
+        IanaProperty, IanaToken, NonStandardComponent, NonStandardProperty, ProductIdentifier,
+        Text, UniqueIdentifier, Version, XName,
+    };
+    use std::str::FromStr as _;
+
+    let s = [
+        "BEGIN:VCALENDAR\r\n",
+        "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+        "VERSION:2.0\r\n",
+        "X-WR-CALNAME:Work\r\n",
+        "X-WR-TIMEZONE:Europe/Berlin\r\n",
+        "COLOR:turquoise\r\n",
+        "BEGIN:VEVENT\r\n",
+        "UID:19970901T130000Z-123401@example.com\r\n",
+        "DTSTAMP:19970901T130000Z\r\n",
+        "DTSTART:19970903T163000Z\r\n",
+        "X-MICROSOFT-CDO-BUSYSTATUS:OOF\r\n",
+        "X-ABC-MMSUBJ;VALUE=URI;FMTTYPE=audio/basic:http://www.example.org/mysubj.au\r\n",
+        "CONFERENCE;VALUE=URI;FEATURE=PHONE:tel:+1-412-555-0123\\,,,654321\r\n",
+        "END:VEVENT\r\n",
+        "BEGIN:X-ABC-NOTE\r\n",
+        "X-ABC-TEXT;X-ABC-LANG=\"en;us\":Hello\\, world\r\n",
+        "BEGIN:X-ABC-PART\r\n",
+        "X-ABC-ID:1\r\n",
+        "END:X-ABC-PART\r\n",
+        "END:X-ABC-NOTE\r\n",
+        "BEGIN:VAVAILABILITY\r\n",
+        "UID:20061005T133225Z-00001-availability@example.com\r\n",
+        "DTSTAMP:20061005T133225Z\r\n",
+        "END:VAVAILABILITY\r\n",
+        "END:VCALENDAR\r\n",
+    ]
+    .join("");
+    let i_calendar_stream = ICalendarStream::from_str(&s)?;
+    assert_eq!(i_calendar_stream.to_string(), s);
+
+    let built = ICalendarStream::builder()
+        .add_object(
+            ICalendarObject::builder()
+                .prodid(ProductIdentifier::new(Text::from_unescaped(
+                    "-//ABC Corporation//NONSGML My Product//EN",
+                )?)?)
+                .version(Version::new(Text::from_str("2.0")?)?)
+                .add_x_prop(NonStandardProperty::new(
+                    XName::from_str("X-WR-CALNAME")?,
+                    "Work",
+                )?)
+                .add_x_prop(NonStandardProperty::new(
+                    XName::from_str("X-WR-TIMEZONE")?,
+                    "Europe/Berlin",
+                )?)
+                .add_iana_prop(IanaProperty::new(
+                    IanaToken::from_str("COLOR")?,
+                    "turquoise",
+                )?)
+                .add_component(
+                    Event::builder()
+                        .uid(UniqueIdentifier::from_value(
+                            "19970901T130000Z-123401@example.com",
+                        )?)
+                        .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+                        .dtstart(DateTimeStart::from_value("19970903T163000Z")?)
+                        .add_x_prop(NonStandardProperty::new(
+                            XName::from_str("X-MICROSOFT-CDO-BUSYSTATUS")?,
+                            "OOF",
+                        )?)
+                        .build()?,
+                )
+                .add_component(
+                    NonStandardComponent::builder()
+                        .name(XName::from_str("X-ABC-NOTE")?)
+                        .add_content_line("X-ABC-ID:1")
+                        .build()?,
+                )
+                .add_component(
+                    IanaComponent::builder()
+                        .name(IanaToken::from_str("VAVAILABILITY")?)
+                        .add_content_line("UID:20061005T133225Z-00001-availability@example.com")
+                        .build()?,
+                )
+                .build()?,
+        )
+        .build()?;
+    assert_eq!(ICalendarStream::from_str(&built.to_string())?, built);
+    assert!(
+        built.to_string().contains(
+            &[
+                "X-WR-CALNAME:Work\r\n",
+                "X-WR-TIMEZONE:Europe/Berlin\r\n",
+                "COLOR:turquoise\r\n",
+            ]
+            .join("")
+        )
+    );
+
+    // unbalanced nested component
+    assert!(
+        ICalendarStream::from_str(
+            &[
+                "BEGIN:VCALENDAR\r\n",
+                "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+                "VERSION:2.0\r\n",
+                "BEGIN:X-ABC-NOTE\r\n",
+                "BEGIN:X-ABC-PART\r\n",
+                "END:X-ABC-NOTE\r\n",
+                "END:VCALENDAR\r\n",
+            ]
+            .join("")
+        )
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_i_calendar_stream_people_and_location() -> Result<(), ical1::Error> {
+    use ical1::{
+        AlternateTextRepresentation, Attendee, CalendarUserAddress, CalendarUserType, CommonName,
+        DateTimeEnd, DateTimeStamp, DateTimeStart, Duration, DurationProperty, Event, Float,
+        GeographicPosition, ICalendarObject, ICalendarStream, Location, Organizer,
+        ParticipationRole, ParticipationStatus, Priority, ProductIdentifier, RsvpExpectation,
+        SequenceNumber, Status, StatusValue, Summary, Text, TimeTransparency,
+        TimeTransparencyValue, UniformResourceLocator, UniqueIdentifier, Uri, Version,
+    };
+    use std::str::FromStr as _;
+
+    let builder = || -> Result<_, ical1::Error> {
+        Ok(Event::builder()
+            .uid(UniqueIdentifier::from_value(
+                "19970901T130000Z-123401@example.com",
+            )?)
+            .dtstamp(DateTimeStamp::from_value("19970901T130000Z")?)
+            .dtstart(DateTimeStart::from_value("19970903T163000Z")?))
+    };
+    let i_calendar_stream = ICalendarStream::builder()
+        .add_object(
+            ICalendarObject::builder()
+                .prodid(ProductIdentifier::new(Text::from_unescaped(
+                    "-//ABC Corporation//NONSGML My Product//EN",
+                )?)?)
+                .version(Version::new(Text::from_str("2.0")?)?)
+                .add_component(
+                    builder()?
+                        .duration(DurationProperty::new(Duration::new(0, 1, 0, 0)?)?)
+                        .summary(Summary::from_value("Annual Employee Review")?)
+                        .location(
+                            Location::new(Text::from_unescaped(
+                                "Conference Room - F123, Bldg. 002",
+                            )?)?
+                            .with_altrep(
+                                AlternateTextRepresentation::new(Uri::from_str(
+                                    "http://xyzcorp.com/conf-rooms/f123.vcf",
+                                )?)?,
+                            ),
+                        )
+                        .geo(GeographicPosition::new(
+                            Float::from_str("37.386013")?,
+                            Float::from_str("-122.082932")?,
+                        )?)
+                        .organizer(
+                            Organizer::new(CalendarUserAddress::from_str(
+                                "mailto:jsmith@example.com",
+                            )?)?
+                            .with_cn(CommonName::new("John Smith")?),
+                        )
+                        .add_attendee(
+                            Attendee::new(CalendarUserAddress::from_str(
+                                "mailto:hcabot@example.com",
+                            )?)?
+                            .with_role(ParticipationRole::ReqParticipant)
+                            .with_partstat(ParticipationStatus::Tentative)
+                            .with_cn(CommonName::new("Henry Cabot")?),
+                        )
+                        .add_attendee(
+                            Attendee::new(CalendarUserAddress::from_str(
+                                "mailto:conf-f123@example.com",
+                            )?)?
+                            .with_cutype(CalendarUserType::Room)
+                            .with_rsvp(RsvpExpectation::new(true)),
+                        )
+                        .priority(Priority::new(1)?)
+                        .sequence(SequenceNumber::new(2)?)
+                        .status(Status::new(StatusValue::Confirmed)?)
+                        .transp(TimeTransparency::new(TimeTransparencyValue::Transparent)?)
+                        .url(UniformResourceLocator::new(Uri::from_str(
+                            "http://example.com/pub/calendars/jsmith/mytime.ics",
+                        )?)?)
+                        .build()?,
+                )
+                .build()?,
+        )
+        .build()?;
+    assert_eq!(
+        i_calendar_stream.to_string(),
+        [
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//ABC Corporation//NONSGML My Product//EN\r\n",
+            "VERSION:2.0\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:19970901T130000Z-123401@example.com\r\n",
+            "DTSTAMP:19970901T130000Z\r\n",
+            "DTSTART:19970903T163000Z\r\n",
+            "DURATION:PT1H\r\n",
+            "SUMMARY:Annual Employee Review\r\n",
+            "LOCATION;ALTREP=\"http://xyzcorp.com/conf-rooms/f123.vcf\":Conference Room - \r\n",
+            " F123\\, Bldg. 002\r\n",
+            "GEO:37.386013;-122.082932\r\n",
+            "ORGANIZER;CN=John Smith:mailto:jsmith@example.com\r\n",
+            "ATTENDEE;ROLE=REQ-PARTICIPANT;PARTSTAT=TENTATIVE;CN=Henry Cabot:mailto:hcab\r\n",
+            " ot@example.com\r\n",
+            "ATTENDEE;CUTYPE=ROOM;RSVP=TRUE:mailto:conf-f123@example.com\r\n",
+            "PRIORITY:1\r\n",
+            "SEQUENCE:2\r\n",
+            "STATUS:CONFIRMED\r\n",
+            "TRANSP:TRANSPARENT\r\n",
+            "URL:http://example.com/pub/calendars/jsmith/mytime.ics\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ]
+        .join("")
+    );
+    assert_eq!(
+        ICalendarStream::from_str(&i_calendar_stream.to_string())?,
+        i_calendar_stream
+    );
+
+    // DTEND and DURATION are mutually exclusive
+    assert!(
+        builder()?
+            .dtend(DateTimeEnd::from_value("19970903T190000Z")?)
+            .duration(DurationProperty::new(Duration::new(0, 1, 0, 0)?)?)
+            .build()
+            .is_err()
+    );
+    // STATUS and PARTSTAT must be values for events
+    assert!(
+        builder()?
+            .status(Status::new(StatusValue::NeedsAction)?)
+            .build()
+            .is_err()
+    );
+    assert!(
+        builder()?
+            .add_attendee(
+                Attendee::new(CalendarUserAddress::from_str("mailto:hcabot@example.com")?)?
+                    .with_partstat(ParticipationStatus::InProcess)
+            )
+            .build()
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_i_calendar_stream_itip() -> Result<(), ical1::Error> {
+    use ical1::{
+        Attendee, CalendarUserAddress, CommonName, DateTimeStamp, DateTimeStart, Event,
+        ICalendarObject, ICalendarStream, Organizer, ParticipationRole, ParticipationStatus,
+        ProductIdentifier, RsvpExpectation, Summary, Text, UniqueIdentifier,
+    };
+    use std::str::FromStr as _;
+
+    // <https://datatracker.ietf.org/doc/html/rfc5546#section-4.2.1>
+    let prodid = || -> Result<_, ical1::Error> {
+        Ok(ProductIdentifier::new(Text::from_unescaped(
+            "-//Example/ExampleCalendarClient//EN",
+        )?)?)
+    };
+    let event = Event::builder()
+        .uid(UniqueIdentifier::from_value("123456789@example.com")?)
+        .dtstamp(DateTimeStamp::from_value("19970611T190000Z")?)
+        .dtstart(DateTimeStart::from_value("19970701T200000Z")?)
+        .summary(Summary::from_value("Conference")?)
+        .organizer(Organizer::new(CalendarUserAddress::from_str(
+            "mailto:a@example.com",
+        )?)?)
+        .add_attendee(
+            Attendee::new(CalendarUserAddress::from_str("mailto:a@example.com")?)?
+                .with_role(ParticipationRole::Chair)
+                .with_partstat(ParticipationStatus::Accepted),
+        )
+        .add_attendee(
+            Attendee::new(CalendarUserAddress::from_str("mailto:b@example.com")?)?
+                .with_rsvp(RsvpExpectation::new(true))
+                .with_cn(CommonName::new("B")?),
+        )
+        .build()?;
+
+    let request = ICalendarObject::request(prodid()?, event.clone(), |_| None)?;
+    assert_eq!(
+        ICalendarStream::builder()
+            .add_object(request)
+            .build()?
+            .to_string(),
+        [
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//Example/ExampleCalendarClient//EN\r\n",
+            "VERSION:2.0\r\n",
+            "METHOD:REQUEST\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:123456789@example.com\r\n",
+            "DTSTAMP:19970611T190000Z\r\n",
+            "DTSTART:19970701T200000Z\r\n",
+            "SUMMARY:Conference\r\n",
+            "ORGANIZER:mailto:a@example.com\r\n",
+            "ATTENDEE;ROLE=CHAIR;PARTSTAT=ACCEPTED:mailto:a@example.com\r\n",
+            "ATTENDEE;RSVP=TRUE;CN=B:mailto:b@example.com\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ]
+        .join("")
+    );
+
+    let reply = ICalendarObject::reply(
+        prodid()?,
+        event.clone(),
+        &CalendarUserAddress::from_str("mailto:b@example.com")?,
+        ParticipationStatus::Accepted,
+        DateTimeStamp::from_value("19970612T190000Z")?,
+        |_| None,
+    )?;
+    assert_eq!(
+        ICalendarStream::builder()
+            .add_object(reply)
+            .build()?
+            .to_string(),
+        [
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//Example/ExampleCalendarClient//EN\r\n",
+            "VERSION:2.0\r\n",
+            "METHOD:REPLY\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:123456789@example.com\r\n",
+            "DTSTAMP:19970612T190000Z\r\n",
+            "DTSTART:19970701T200000Z\r\n",
+            "SUMMARY:Conference\r\n",
+            "ORGANIZER:mailto:a@example.com\r\n",
+            "ATTENDEE;PARTSTAT=ACCEPTED;RSVP=TRUE;CN=B:mailto:b@example.com\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ]
+        .join("")
+    );
+
+    let cancel = ICalendarObject::cancel(
+        prodid()?,
+        event,
+        DateTimeStamp::from_value("19970613T190000Z")?,
+        |_| None,
+    )?;
+    assert_eq!(
+        ICalendarStream::builder()
+            .add_object(cancel)
+            .build()?
+            .to_string(),
+        [
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//Example/ExampleCalendarClient//EN\r\n",
+            "VERSION:2.0\r\n",
+            "METHOD:CANCEL\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:123456789@example.com\r\n",
+            "DTSTAMP:19970613T190000Z\r\n",
+            "DTSTART:19970701T200000Z\r\n",
+            "SUMMARY:Conference\r\n",
+            "ORGANIZER:mailto:a@example.com\r\n",
+            "ATTENDEE;ROLE=CHAIR;PARTSTAT=ACCEPTED:mailto:a@example.com\r\n",
+            "ATTENDEE;RSVP=TRUE;CN=B:mailto:b@example.com\r\n",
+            "SEQUENCE:1\r\n",
+            "STATUS:CANCELLED\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ]
+        .join("")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_i_calendar_stream_jcal_and_xcal() -> anyhow::Result<()> {
+    use ical1::{Event, ICalendarObject, ICalendarStream};
+    use std::str::FromStr as _;
+
+    // <https://datatracker.ietf.org/doc/html/rfc7265#appendix-B.2> with "DTSTAMP" and without
+    // the unsupported "RDATE" with "PERIOD" and "RECURRENCE-ID"
+    let i_calendar_stream = ICalendarStream::from_str(
+        &[
+            "BEGIN:VCALENDAR\r\n",
+            "PRODID:-//Example Corp.//Example Client//EN\r\n",
+            "VERSION:2.0\r\n",
+            "BEGIN:VTIMEZONE\r\n",
+            "LAST-MODIFIED:20040110T032845Z\r\n",
+            "TZID:US/Eastern\r\n",
+            "BEGIN:DAYLIGHT\r\n",
+            "DTSTART:20000404T020000\r\n",
+            "RRULE:FREQ=YEARLY;BYDAY=1SU;BYMONTH=4\r\n",
+            "TZNAME:EDT\r\n",
+            "TZOFFSETFROM:-0500\r\n",
+            "TZOFFSETTO:-0400\r\n",
+            "END:DAYLIGHT\r\n",
+            "BEGIN:STANDARD\r\n",
+            "DTSTART:20001026T020000\r\n",
+            "RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10\r\n",
+            "TZNAME:EST\r\n",
+            "TZOFFSETFROM:-0400\r\n",
+            "TZOFFSETTO:-0500\r\n",
+            "END:STANDARD\r\n",
+            "END:VTIMEZONE\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTAMP:20060206T001121Z\r\n",
+            "DTSTART;TZID=US/Eastern:20060102T120000\r\n",
+            "DURATION:PT1H\r\n",
+            "RRULE:FREQ=DAILY;COUNT=5\r\n",
+            "SUMMARY:Event #2\r\n",
+            "DESCRIPTION:We are having a meeting all this week at 12 pm fo\r\n",
+            " r one hour\\, with an additional meeting on the first day 2 h\r\n",
+            " ours long.\\nPlease bring your own lunch for the 12 pm meetin\r\n",
+            " gs.\r\n",
+            "UID:00959BC664CA650E933C892C@example.com\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ]
+        .join(""),
+    )?;
+
+    // jCal in the order of the RFC example
+    let jcal = serde_json::json!(
+        ["vcalendar",
+          [
+            ["prodid", {}, "text", "-//Example Corp.//Example Client//EN"],
+            ["version", {}, "text", "2.0"]
+          ],
+          [
+            ["vtimezone",
+              [
+                ["last-modified", {}, "date-time", "2004-01-10T03:28:45Z"],
+                ["tzid", {}, "text", "US/Eastern"]
+              ],
+              [
+                ["daylight",
+                  [
+                    ["dtstart", {}, "date-time", "2000-04-04T02:00:00"],
+                    ["rrule", {}, "recur", { "freq": "YEARLY", "byday": "1SU", "bymonth": 4 }],
+                    ["tzname", {}, "text", "EDT"],
+                    ["tzoffsetfrom", {}, "utc-offset", "-05:00"],
+                    ["tzoffsetto", {}, "utc-offset", "-04:00"]
+                  ],
+                  []
+                ],
+                ["standard",
+                  [
+                    ["dtstart", {}, "date-time", "2000-10-26T02:00:00"],
+                    ["rrule", {}, "recur", { "freq": "YEARLY", "byday": "-1SU", "bymonth": 10 }],
+                    ["tzname", {}, "text", "EST"],
+                    ["tzoffsetfrom", {}, "utc-offset", "-04:00"],
+                    ["tzoffsetto", {}, "utc-offset", "-05:00"]
+                  ],
+                  []
+                ]
+              ]
+            ],
+            ["vevent",
+              [
+                ["dtstamp", {}, "date-time", "2006-02-06T00:11:21Z"],
+                ["dtstart", { "tzid": "US/Eastern" }, "date-time", "2006-01-02T12:00:00"],
+                ["duration", {}, "duration", "PT1H"],
+                ["rrule", {}, "recur", { "freq": "DAILY", "count": 5 } ],
+                ["summary", {}, "text", "Event #2"],
+                ["description", {}, "text", "We are having a meeting all this week at 12 pm for one hour, with an additional meeting on the first day 2 hours long.\nPlease bring your own lunch for the 12 pm meetings."],
+                ["uid", {}, "text", "00959BC664CA650E933C892C@example.com"]
+              ],
+              []
+            ]
+          ]
+        ]
+    );
+    assert_eq!(
+        serde_json::from_value::<ICalendarStream>(jcal.clone())?,
+        i_calendar_stream
+    );
+    assert_eq!(
+        serde_json::to_value(&i_calendar_stream)?,
+        serde_json::json!([
+          ["vcalendar",
+            [
+              ["prodid", {}, "text", "-//Example Corp.//Example Client//EN"],
+              ["version", {}, "text", "2.0"]
+            ],
+            [
+              ["vtimezone",
+                [
+                  ["tzid", {}, "text", "US/Eastern"],
+                  ["last-modified", {}, "date-time", "2004-01-10T03:28:45Z"]
+                ],
+                [
+                  ["daylight",
+                    [
+                      ["dtstart", {}, "date-time", "2000-04-04T02:00:00"],
+                      ["rrule", {}, "recur", { "freq": "YEARLY", "byday": "1SU", "bymonth": 4 }],
+                      ["tzoffsetfrom", {}, "utc-offset", "-05:00"],
+                      ["tzoffsetto", {}, "utc-offset", "-04:00"],
+                      ["tzname", {}, "text", "EDT"]
+                    ],
+                    []
+                  ],
+                  ["standard",
+                    [
+                      ["dtstart", {}, "date-time", "2000-10-26T02:00:00"],
+                      ["rrule", {}, "recur", { "freq": "YEARLY", "byday": "-1SU", "bymonth": 10 }],
+                      ["tzoffsetfrom", {}, "utc-offset", "-04:00"],
+                      ["tzoffsetto", {}, "utc-offset", "-05:00"],
+                      ["tzname", {}, "text", "EST"]
+                    ],
+                    []
+                  ]
+                ]
+              ],
+              ["vevent",
+                [
+                  ["uid", {}, "text", "00959BC664CA650E933C892C@example.com"],
+                  ["dtstamp", {}, "date-time", "2006-02-06T00:11:21Z"],
+                  ["dtstart", { "tzid": "US/Eastern" }, "date-time", "2006-01-02T12:00:00"],
+                  ["duration", {}, "duration", "PT1H"],
+                  ["summary", {}, "text", "Event #2"],
+                  ["rrule", {}, "recur", { "freq": "DAILY", "count": 5 } ],
+                  ["description", {}, "text", "We are having a meeting all this week at 12 pm for one hour, with an additional meeting on the first day 2 hours long.\nPlease bring your own lunch for the 12 pm meetings."]
+                ],
+                []
+              ]
+            ]
+          ]
+        ])
+    );
+    // round trip
+    assert_eq!(
+        serde_json::from_str::<ICalendarStream>(&serde_json::to_string(&i_calendar_stream)?)?,
+        i_calendar_stream
+    );
+    assert_eq!(
+        serde_json::from_str::<ICalendarObject>(&serde_json::to_string(&jcal)?)?.to_xcal(),
+        i_calendar_stream.to_xcal()
+    );
+
+    // <https://datatracker.ietf.org/doc/html/rfc6321#appendix-B.2> in the same order as the
+    // content lines and without whitespace
+    assert_eq!(
+        i_calendar_stream.to_xcal(),
+        [
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+            "<icalendar xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">",
+            "<vcalendar>",
+            "<properties>",
+            "<prodid><text>-//Example Corp.//Example Client//EN</text></prodid>",
+            "<version><text>2.0</text></version>",
+            "</properties>",
+            "<components>",
+            "<vtimezone>",
+            "<properties>",
+            "<tzid><text>US/Eastern</text></tzid>",
+            "<last-modified><date-time>2004-01-10T03:28:45Z</date-time></last-modified>",
+            "</properties>",
+            "<components>",
+            "<daylight>",
+            "<properties>",
+            "<dtstart><date-time>2000-04-04T02:00:00</date-time></dtstart>",
+            "<rrule><recur><freq>YEARLY</freq><byday>1SU</byday><bymonth>4</bymonth></recur></rrule>",
+            "<tzoffsetfrom><utc-offset>-05:00</utc-offset></tzoffsetfrom>",
+            "<tzoffsetto><utc-offset>-04:00</utc-offset></tzoffsetto>",
+            "<tzname><text>EDT</text></tzname>",
+            "</properties>",
+            "</daylight>",
+            "<standard>",
+            "<properties>",
+            "<dtstart><date-time>2000-10-26T02:00:00</date-time></dtstart>",
+            "<rrule><recur><freq>YEARLY</freq><byday>-1SU</byday><bymonth>10</bymonth></recur></rrule>",
+            "<tzoffsetfrom><utc-offset>-04:00</utc-offset></tzoffsetfrom>",
+            "<tzoffsetto><utc-offset>-05:00</utc-offset></tzoffsetto>",
+            "<tzname><text>EST</text></tzname>",
+            "</properties>",
+            "</standard>",
+            "</components>",
+            "</vtimezone>",
+            "<vevent>",
+            "<properties>",
+            "<uid><text>00959BC664CA650E933C892C@example.com</text></uid>",
+            "<dtstamp><date-time>2006-02-06T00:11:21Z</date-time></dtstamp>",
+            "<dtstart>",
+            "<parameters><tzid><text>US/Eastern</text></tzid></parameters>",
+            "<date-time>2006-01-02T12:00:00</date-time>",
+            "</dtstart>",
+            "<duration><duration>PT1H</duration></duration>",
+            "<summary><text>Event #2</text></summary>",
+            "<rrule><recur><freq>DAILY</freq><count>5</count></recur></rrule>",
+            "<description><text>We are having a meeting all this week at 12 pm for one hour, ",
+            "with an additional meeting on the first day 2 hours long.\n",
+            "Please bring your own lunch for the 12 pm meetings.</text></description>",
+            "</properties>",
+            "</vevent>",
+            "</components>",
+            "</vcalendar>",
+            "</icalendar>\n",
+        ]
+        .join("")
+    );
+
+    // <https://datatracker.ietf.org/doc/html/rfc7265#appendix-B.1> with "DATE-TIME" value
+    let jcal = serde_json::json!(
+        ["vevent",
+          [
+            ["dtstamp", {}, "date-time", "2008-02-05T19:12:24Z"],
+            ["dtstart", {}, "date-time", "2008-10-06T09:00:00Z"],
+            ["summary", {}, "text", "Planning meeting"],
+            ["uid", {}, "text", "4088E990AD89CB3DBB484909"],
+            ["geo", {}, "float", [37.386013, -122.082932]],
+            ["categories", {}, "text", "MEETING", "a,b"],
+            ["x-custom", { "x-param": ["a", "b;c"] }, "unknown", "value\\;"]
+          ],
+          []
+        ]
+    );
+    let event = serde_json::from_value::<Event>(jcal)?;
+    assert_eq!(
+        serde_json::from_value::<Event>(serde_json::to_value(&event)?)?,
+        event
+    );
+    assert_eq!(
+        serde_json::to_value(&event)?,
+        serde_json::json!(
+            ["vevent",
+              [
+                ["uid", {}, "text", "4088E990AD89CB3DBB484909"],
+                ["dtstamp", {}, "date-time", "2008-02-05T19:12:24Z"],
+                ["dtstart", {}, "date-time", "2008-10-06T09:00:00Z"],
+                ["summary", {}, "text", "Planning meeting"],
+                ["geo", {}, "float", [37.386013, -122.082932]],
+                ["categories", {}, "text", "MEETING", "a,b"],
+                ["x-custom", { "x-param": ["a", "b;c"] }, "unknown", "value\\;"]
+              ],
+              []
+            ]
+        )
+    );
+    assert_eq!(
+        event.to_xcal(),
+        [
+            "<vevent>",
+            "<properties>",
+            "<uid><text>4088E990AD89CB3DBB484909</text></uid>",
+            "<dtstamp><date-time>2008-02-05T19:12:24Z</date-time></dtstamp>",
+            "<dtstart><date-time>2008-10-06T09:00:00Z</date-time></dtstart>",
+            "<summary><text>Planning meeting</text></summary>",
+            "<geo><latitude>37.386013</latitude><longitude>-122.082932</longitude></geo>",
+            "<categories><text>MEETING</text><text>a,b</text></categories>",
+            "<x-custom>",
+            "<parameters><x-param><text>a</text><text>b;c</text></x-param></parameters>",
+            "<unknown>value\\;</unknown>",
+            "</x-custom>",
+            "</properties>",
+            "</vevent>",
+        ]
+        .join("")
+    );
+
+    assert!(serde_json::from_value::<Event>(serde_json::json!(["vevent", []])).is_err());
+    assert!(
+        serde_json::from_value::<ICalendarObject>(serde_json::json!(["vevent", [], []])).is_err()
+    );
+    Ok(())
+}